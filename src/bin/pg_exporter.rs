@@ -9,6 +9,7 @@ async fn main() -> Result<()> {
 
     match action {
         Action::Run { .. } => actions::run::handle(action).await?,
+        Action::Healthcheck { .. } => actions::healthcheck::handle(action).await?,
     }
 
     Ok(())
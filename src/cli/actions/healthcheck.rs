@@ -0,0 +1,157 @@
+use crate::cli::actions::Action;
+use anyhow::{Context, Result, anyhow};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use url::Url;
+
+/// Handle the healthcheck action
+///
+/// # Errors
+///
+/// Returns an error if the endpoint is unreachable, times out, or answers with a non-2xx status
+pub async fn handle(action: Action) -> Result<()> {
+    match action {
+        Action::Healthcheck { url, timeout } => {
+            let status = tokio::time::timeout(timeout, fetch_status(&url))
+                .await
+                .map_err(|_| anyhow!("health check against {url} timed out after {timeout:?}"))??;
+
+            if !(200..300).contains(&status) {
+                return Err(anyhow!("health check against {url} returned HTTP {status}"));
+            }
+        }
+        Action::Run { .. } => return Err(anyhow!("healthcheck handler received a run action")),
+    }
+
+    Ok(())
+}
+
+/// Send a plain HTTP/1.1 GET and return the status code of the response.
+async fn fetch_status(url: &str) -> Result<u16> {
+    let parsed = Url::parse(url).with_context(|| format!("invalid health check URL '{url}'"))?;
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| anyhow!("health check URL '{url}' has no host"))?;
+    let port = parsed.port_or_known_default().unwrap_or(80);
+
+    let path = match parsed.query() {
+        Some(query) => format!("{}?{query}", parsed.path()),
+        None => parsed.path().to_string(),
+    };
+
+    // host_str() keeps the brackets around IPv6 literals, which is what Host expects
+    // but not what the resolver expects.
+    let addr_host = host.trim_start_matches('[').trim_end_matches(']');
+
+    let mut stream = TcpStream::connect((addr_host, port))
+        .await
+        .with_context(|| format!("failed to connect to {host}:{port}"))?;
+
+    let request = build_request(&path, host, port);
+    stream.write_all(request.as_bytes()).await?;
+
+    let mut status_line = String::new();
+    BufReader::new(stream).read_line(&mut status_line).await?;
+
+    parse_status_line(&status_line)
+}
+
+fn build_request(path: &str, host: &str, port: u16) -> String {
+    format!(
+        "GET {path} HTTP/1.1\r\n\
+         Host: {host}:{port}\r\n\
+         User-Agent: pg_exporter-healthcheck/{}\r\n\
+         Accept: */*\r\n\
+         Connection: close\r\n\r\n",
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+fn parse_status_line(line: &str) -> Result<u16> {
+    let mut parts = line.split_whitespace();
+
+    match (parts.next(), parts.next()) {
+        (Some(version), Some(code)) if version.starts_with("HTTP/") => code
+            .parse::<u16>()
+            .map_err(|_| anyhow!("invalid HTTP status line: {:?}", line.trim_end())),
+        _ => Err(anyhow!("invalid HTTP status line: {:?}", line.trim_end())),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    async fn serve_once(response: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            if let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        format!("http://{addr}/health")
+    }
+
+    fn action(url: String) -> Action {
+        Action::Healthcheck {
+            url,
+            timeout: Duration::from_secs(2),
+        }
+    }
+
+    #[test]
+    fn test_parse_status_line() {
+        assert_eq!(parse_status_line("HTTP/1.1 200 OK\r\n").unwrap(), 200);
+        assert_eq!(
+            parse_status_line("HTTP/1.1 503 Service Unavailable\r\n").unwrap(),
+            503
+        );
+        assert!(parse_status_line("").is_err());
+        assert!(parse_status_line("garbage\r\n").is_err());
+        assert!(parse_status_line("HTTP/1.1 abc\r\n").is_err());
+    }
+
+    #[test]
+    fn test_build_request() {
+        let request = build_request("/health", "127.0.0.1", 9432);
+        assert!(request.starts_with("GET /health HTTP/1.1\r\n"));
+        assert!(request.contains("Host: 127.0.0.1:9432\r\n"));
+        assert!(request.contains("Connection: close\r\n"));
+        assert!(request.ends_with("\r\n\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_healthy() {
+        let url = serve_once("HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").await;
+        assert!(handle(action(url)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_handle_unhealthy() {
+        let url = serve_once("HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n").await;
+        assert!(handle(action(url)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_handle_unreachable() {
+        // Bind and drop to get a port that is very likely closed.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        assert!(
+            handle(action(format!("http://{addr}/health")))
+                .await
+                .is_err()
+        );
+    }
+}
@@ -1,7 +1,9 @@
+pub mod healthcheck;
 pub mod run;
 
 use crate::collectors::config::CollectorConfig;
 use secrecy::SecretString;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub enum Action {
@@ -9,6 +11,11 @@ pub enum Action {
         port: u16,
         listen: Option<String>,
         dsn: SecretString,
-        collector_config: CollectorConfig,
+        // Boxed so the rarely used `Healthcheck` variant stays small.
+        collector_config: Box<CollectorConfig>,
+    },
+    Healthcheck {
+        url: String,
+        timeout: Duration,
     },
 }
@@ -1,6 +1,6 @@
 use crate::cli::actions::Action;
 use crate::exporter::new;
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 
 /// Handle the create action
 ///
@@ -15,7 +15,10 @@ pub async fn handle(action: Action) -> Result<()> {
             dsn,
             collector_config,
         } => {
-            new(port, listen, dsn, collector_config).await?;
+            new(port, listen, dsn, *collector_config).await?;
+        }
+        Action::Healthcheck { .. } => {
+            return Err(anyhow!("run handler received a healthcheck action"));
         }
     }
 
@@ -37,7 +40,9 @@ mod tests {
             port: 9999,
             listen: None,
             dsn: SecretString::new("invalid-dsn".into()),
-            collector_config: CollectorConfig::new(25).with_enabled(&["default".to_string()]),
+            collector_config: Box::new(
+                CollectorConfig::new(25).with_enabled(&["default".to_string()]),
+            ),
         };
 
         let result = handle(action).await;
@@ -52,8 +57,10 @@ mod tests {
             port: 9432,
             listen: Some("127.0.0.1".to_string()),
             dsn: SecretString::new("postgresql://user@host/db".into()),
-            collector_config: CollectorConfig::new(25)
-                .with_enabled(&["default".to_string(), "vacuum".to_string()]),
+            collector_config: Box::new(
+                CollectorConfig::new(25)
+                    .with_enabled(&["default".to_string(), "vacuum".to_string()]),
+            ),
         };
 
         match action {
@@ -68,6 +75,7 @@ mod tests {
                 assert!(collector_config.is_enabled("default"));
                 assert!(collector_config.is_enabled("vacuum"));
             }
+            Action::Healthcheck { .. } => unreachable!("expected Action::Run"),
         }
     }
 
@@ -78,7 +86,7 @@ mod tests {
             port: 8080,
             listen: None,
             dsn: SecretString::new("postgresql://localhost/db".into()),
-            collector_config: CollectorConfig::new(25),
+            collector_config: Box::new(CollectorConfig::new(25)),
         };
 
         match action {
@@ -90,6 +98,7 @@ mod tests {
                     "Should allow empty collectors list"
                 );
             }
+            Action::Healthcheck { .. } => unreachable!("expected Action::Run"),
         }
     }
 }
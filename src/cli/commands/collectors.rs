@@ -1,5 +1,8 @@
-use crate::collectors::{COLLECTOR_NAMES, Collector, all_factories};
+use crate::collectors::{
+    COLLECTOR_NAMES, Collector, all_factories, config::SCRAPE_ONLY_COLLECTORS,
+};
 use clap::{Arg, Command};
+use std::time::Duration;
 
 pub fn add_collectors_args(mut cmd: Command) -> Command {
     let factories = all_factories();
@@ -41,8 +44,126 @@ pub fn add_collectors_args(mut cmd: Command) -> Command {
                     .action(clap::ArgAction::SetTrue)
                     .overrides_with(enable_flag),
             );
+
+        if !SCRAPE_ONLY_COLLECTORS.contains(&name) {
+            cmd = cmd.arg(collector_interval_arg(name));
+        }
+    }
+    cmd.arg(collection_interval_arg())
+        .arg(collection_jitter_arg())
+}
+
+/// `--collector.<name>.interval`, moving that collector to a background schedule
+fn collector_interval_arg(name: &str) -> Arg {
+    let interval_flag: &'static str =
+        Box::leak(format!("collector.{name}.interval").into_boxed_str());
+    let interval_env: &'static str = Box::leak(
+        format!("PG_EXPORTER_COLLECTOR_{}_INTERVAL", name.to_uppercase()).into_boxed_str(),
+    );
+    let interval_help: &'static str = Box::leak(
+        format!("Run the {name} collector in the background on this interval").into_boxed_str(),
+    );
+    let interval_long_help: &'static str = Box::leak(
+        format!(
+            "Run the {name} collector in the background on this interval instead of on every scrape.\n\n\
+             /metrics serves the values from the collector's most recent run. Collectors without \
+             an interval keep running on each scrape.\n\n\
+             Examples:\n  \
+               --collector.{name}.interval 30s\n  \
+               --collector.{name}.interval 5m\n  \
+               {interval_env}=1h"
+        )
+        .into_boxed_str(),
+    );
+
+    Arg::new(interval_flag)
+        .long(interval_flag)
+        .help(interval_help)
+        .long_help(interval_long_help)
+        .env(interval_env)
+        .value_name("DURATION")
+        .value_parser(parse_collector_interval)
+}
+
+fn collection_interval_arg() -> Arg {
+    Arg::new("collection-interval")
+        .long("collection-interval")
+        .help("Run every collector in the background on this interval and serve cached results")
+        .long_help(
+            "Run every enabled collector in the background on this interval instead of on \
+             every scrape; /metrics only serves the values of the most recent runs.\n\n\
+             This decouples database load from the number of scrapers, e.g. an HA Prometheus \
+             pair. A --collector.<name>.interval still overrides it for that collector.\n\n\
+             Examples:\n  \
+               --collection-interval 30s\n  \
+               --collection-interval 1m --collection-jitter 15s\n  \
+               PG_EXPORTER_COLLECTION_INTERVAL=1m",
+        )
+        .env("PG_EXPORTER_COLLECTION_INTERVAL")
+        .value_name("DURATION")
+        .value_parser(parse_collector_interval)
+}
+
+fn collection_jitter_arg() -> Arg {
+    Arg::new("collection-jitter")
+        .long("collection-jitter")
+        .help("Delay each background collection by a random amount up to this duration")
+        .long_help(
+            "Delay the first background run of every scheduled collector, and every run after \
+             it, by a random amount between 0 and this duration.\n\n\
+             Exporters started together (a fleet rollout, a cluster restart) otherwise query \
+             their databases at the same moment on every interval; jitter spreads that load \
+             over time. Applies to --collection-interval and --collector.<name>.interval; keep \
+             it well below the interval. Default 0 (no jitter).\n\n\
+             Examples:\n  \
+               --collection-jitter 10s\n  \
+               PG_EXPORTER_COLLECTION_JITTER=500ms",
+        )
+        .env("PG_EXPORTER_COLLECTION_JITTER")
+        .value_name("DURATION")
+        .default_value("0")
+        .value_parser(parse_collection_jitter)
+}
+
+/// Parse a collection jitter: `0` or an interval such as `500ms`, `10s` or `1m`.
+fn parse_collection_jitter(value: &str) -> Result<Duration, String> {
+    if value.trim() == "0" {
+        Ok(Duration::ZERO)
+    } else {
+        parse_collector_interval(value)
     }
-    cmd
+}
+
+/// Parse a collector interval such as `500ms`, `30s`, `5m` or `1h`.
+fn parse_collector_interval(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid interval '{value}': expected e.g. 30s, 5m or 1h"))?;
+    if amount == 0 {
+        return Err(format!(
+            "invalid interval '{value}': must be greater than zero"
+        ));
+    }
+
+    let interval = match unit {
+        "ms" => Duration::from_millis(amount),
+        "s" => Duration::from_secs(amount),
+        "m" => Duration::from_secs(amount.saturating_mul(60)),
+        "h" => Duration::from_secs(amount.saturating_mul(3600)),
+        _ => {
+            return Err(format!(
+                "invalid interval unit in '{value}': use ms, s, m or h"
+            ));
+        }
+    };
+
+    Ok(interval)
 }
 
 #[cfg(test)]
@@ -169,6 +290,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_collector_interval() {
+        assert_eq!(
+            parse_collector_interval("500ms"),
+            Ok(Duration::from_millis(500))
+        );
+        assert_eq!(parse_collector_interval("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_collector_interval("5m"), Ok(Duration::from_mins(5)));
+        assert_eq!(parse_collector_interval("1h"), Ok(Duration::from_hours(1)));
+        assert!(parse_collector_interval("0s").is_err());
+        assert!(parse_collector_interval("5").is_err());
+        assert!(parse_collector_interval("5d").is_err());
+        assert!(parse_collector_interval("m").is_err());
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_collector_interval_flag() {
+        let cmd = commands::new();
+        let matches = cmd
+            .try_get_matches_from(vec!["pg_exporter", "--collector.statements.interval", "5m"])
+            .unwrap();
+
+        assert_eq!(
+            matches
+                .get_one::<Duration>("collector.statements.interval")
+                .copied(),
+            Some(Duration::from_mins(5))
+        );
+        assert!(
+            matches
+                .get_one::<Duration>("collector.default.interval")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_parse_collection_jitter() {
+        assert_eq!(parse_collection_jitter("0"), Ok(Duration::ZERO));
+        assert_eq!(parse_collection_jitter("15s"), Ok(Duration::from_secs(15)));
+        assert!(parse_collection_jitter("-1s").is_err());
+        assert!(parse_collection_jitter("15").is_err());
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_collection_interval_and_jitter_flags() {
+        let matches = commands::new()
+            .try_get_matches_from(vec!["pg_exporter"])
+            .unwrap();
+        assert!(matches.get_one::<Duration>("collection-interval").is_none());
+        assert_eq!(
+            matches.get_one::<Duration>("collection-jitter").copied(),
+            Some(Duration::ZERO)
+        );
+
+        let matches = commands::new()
+            .try_get_matches_from(vec![
+                "pg_exporter",
+                "--collection-interval",
+                "1m",
+                "--collection-jitter",
+                "15s",
+            ])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<Duration>("collection-interval").copied(),
+            Some(Duration::from_mins(1))
+        );
+        assert_eq!(
+            matches.get_one::<Duration>("collection-jitter").copied(),
+            Some(Duration::from_secs(15))
+        );
+    }
+
     #[test]
     fn test_collector_flags_help_text() {
         let mut cmd = commands::new();
@@ -0,0 +1,116 @@
+use clap::{Arg, Command};
+use std::num::NonZeroU64;
+
+/// Default time budget for the whole health request (connect, send, response).
+pub const DEFAULT_HEALTHCHECK_TIMEOUT_MS: u64 = 5_000;
+
+pub fn subcommand() -> Command {
+    Command::new("healthcheck")
+        .about("Query the local exporter's /health endpoint and exit 0 (healthy) or 1")
+        .long_about(
+            "Query the local exporter's /health endpoint and exit 0 when it answers with a 2xx \
+             status, or 1 otherwise (unreachable, timeout, or an unhealthy database).\n\n\
+             Intended for container HEALTHCHECK directives, so images do not need curl or wget. \
+             The target defaults to http://127.0.0.1:<port>/health, where <port> and the listen \
+             address come from --port/--listen (or PG_EXPORTER_PORT/PG_EXPORTER_LISTEN), so the \
+             exporter's own environment is enough.\n\n\
+             Examples:\n\
+               HEALTHCHECK CMD [\"/usr/local/bin/pg_exporter\", \"healthcheck\"]\n\
+               pg_exporter healthcheck --port 9187\n\
+               pg_exporter healthcheck --url http://127.0.0.1:9432/health --timeout-ms 2000",
+        )
+        .arg(url_arg())
+        .arg(timeout_arg())
+}
+
+fn url_arg() -> Arg {
+    Arg::new("healthcheck.url")
+        .long("url")
+        .help("Health endpoint to query (default: http://127.0.0.1:<port>/health)")
+        .long_help(
+            "Health endpoint to query. Only plain http:// URLs are supported.\n\n\
+             Examples:\n\
+               --url http://127.0.0.1:9432/health\n\
+               --url http://[::1]:9432/health\n\
+               PG_EXPORTER_HEALTHCHECK_URL=http://127.0.0.1:9432/health",
+        )
+        .env("PG_EXPORTER_HEALTHCHECK_URL")
+        .value_name("URL")
+        .value_parser(parse_healthcheck_url)
+}
+
+fn timeout_arg() -> Arg {
+    Arg::new("healthcheck.timeout-ms")
+        .long("timeout-ms")
+        .help("Timeout in milliseconds for the whole health request")
+        .long_help(
+            "Timeout in milliseconds for connecting, sending the request and reading the status \
+             line. Keep it below the container HEALTHCHECK --timeout.\n\n\
+             Examples:\n\
+               --timeout-ms 2000\n\
+               PG_EXPORTER_HEALTHCHECK_TIMEOUT_MS=2000",
+        )
+        .env("PG_EXPORTER_HEALTHCHECK_TIMEOUT_MS")
+        .default_value("5000")
+        .value_name("MS")
+        .value_parser(clap::value_parser!(NonZeroU64))
+}
+
+fn parse_healthcheck_url(value: &str) -> Result<String, String> {
+    let url = value.trim();
+    if url.is_empty() {
+        return Err("value must not be empty".to_string());
+    }
+    if !url.starts_with("http://") {
+        return Err("only http:// URLs are supported".to_string());
+    }
+
+    Ok(url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command() -> Command {
+        Command::new("pg_exporter").subcommand(subcommand())
+    }
+
+    #[test]
+    fn test_healthcheck_defaults() {
+        temp_env::with_vars(
+            [
+                ("PG_EXPORTER_HEALTHCHECK_URL", None::<&str>),
+                ("PG_EXPORTER_HEALTHCHECK_TIMEOUT_MS", None::<&str>),
+            ],
+            || {
+                let matches = command().get_matches_from(vec!["pg_exporter", "healthcheck"]);
+                let sub = matches.subcommand_matches("healthcheck");
+
+                assert!(sub.is_some_and(|m| m.get_one::<String>("healthcheck.url").is_none()));
+                assert_eq!(
+                    sub.and_then(|m| m.get_one::<NonZeroU64>("healthcheck.timeout-ms"))
+                        .map(|v| v.get()),
+                    Some(DEFAULT_HEALTHCHECK_TIMEOUT_MS)
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_healthcheck_url_requires_http() {
+        for value in ["https://127.0.0.1:9432/health", "127.0.0.1:9432", "  "] {
+            let result =
+                command().try_get_matches_from(vec!["pg_exporter", "healthcheck", "--url", value]);
+            assert!(result.is_err(), "{value:?} should be rejected");
+        }
+
+        let result = command().try_get_matches_from(vec![
+            "pg_exporter",
+            "healthcheck",
+            "--url",
+            "http://127.0.0.1:9432/health",
+        ]);
+        assert!(result.is_ok());
+    }
+}
@@ -1,10 +1,17 @@
 use clap::{
     Arg, ArgAction, ColorChoice, Command,
     builder::styling::{AnsiColor, Effects, Styles},
+    value_parser,
 };
+use std::path::PathBuf;
 
 mod collectors;
+mod healthcheck;
 mod options;
+mod telemetry;
+mod web;
+
+pub use healthcheck::DEFAULT_HEALTHCHECK_TIMEOUT_MS;
 
 pub mod built_info {
     #![allow(clippy::doc_markdown)]
@@ -36,6 +43,7 @@ pub fn new() -> Command {
                 .help("Port to listen on")
                 .default_value("9432")
                 .env("PG_EXPORTER_PORT")
+                .global(true)
                 .value_parser(clap::value_parser!(u16)),
         )
         .arg(
@@ -58,6 +66,7 @@ pub fn new() -> Command {
                      IPv4 connections through IPv4-mapped IPv6 addresses on dual-stack systems.",
                 )
                 .env("PG_EXPORTER_LISTEN")
+                .global(true)
                 .value_name("IP"),
         )
         .arg(
@@ -90,27 +99,118 @@ pub fn new() -> Command {
                 .env("PG_EXPORTER_DSN")
                 .value_name("DSN"),
         )
-        .arg(
-            Arg::new("exclude-databases")
-                .long("exclude-databases")
-                .help("Comma-separated list of databases to exclude (exact/case-sensitive)")
-                .env("PG_EXPORTER_EXCLUDE_DATABASES")
-                .value_name("template0,template1,...")
-                .value_delimiter(',') // split CLI and env values by comma
-                .action(ArgAction::Append), // allow repeated flags if desired
-        )
+        .arg(exclude_databases_arg())
+        .arg(exclude_users_arg())
+        .arg(exclude_schemas_arg())
+        .arg(exclude_tables_arg())
+        .arg(exclusions_file_arg())
         .arg(
             Arg::new("verbose")
                 .short('v')
                 .long("verbose")
                 .help("Increase verbosity, -vv for debug")
                 .action(ArgAction::Count),
-        );
+        )
+        .subcommand(healthcheck::subcommand());
 
+    let cmd = telemetry::add_telemetry_args(cmd);
+    let cmd = web::add_web_args(cmd);
     let cmd = collectors::add_collectors_args(cmd);
     options::add_collector_option_args(cmd)
 }
 
+fn exclude_databases_arg() -> Arg {
+    Arg::new("exclude-databases")
+        .long("exclude-databases")
+        .help("Comma-separated list of databases to exclude (exact/case-sensitive)")
+        .env("PG_EXPORTER_EXCLUDE_DATABASES")
+        .value_name("template0,template1,...")
+        .value_delimiter(',') // split CLI and env values by comma
+        .action(ArgAction::Append) // allow repeated flags if desired
+}
+
+fn exclude_users_arg() -> Arg {
+    Arg::new("exclude-users")
+        .long("exclude-users")
+        .help(
+            "Comma-separated list of roles to exclude from activity metrics (exact/case-sensitive)",
+        )
+        .long_help(
+            "Comma-separated list of roles whose sessions are ignored by --collector.activity \
+             (exact/case-sensitive match on pg_stat_activity.usename).\n\n\
+             Use it to keep the exporter's own role, replication roles and backup tools out of \
+             connection and query counts so they reflect application usage.\n\n\
+             Examples:\n\
+               --exclude-users postgres_exporter,replicator\n\
+               PG_EXPORTER_EXCLUDE_USERS=postgres_exporter,pgbackrest",
+        )
+        .env("PG_EXPORTER_EXCLUDE_USERS")
+        .value_name("postgres_exporter,replicator,...")
+        .value_delimiter(',')
+        .action(ArgAction::Append)
+}
+
+fn exclude_schemas_arg() -> Arg {
+    Arg::new("exclude-schemas")
+        .long("exclude-schemas")
+        .help("Comma-separated list of schemas whose table, index and sequence series are hidden")
+        .long_help(
+            "Comma-separated list of schemas whose per-relation series are hidden (exact/case-sensitive \
+             match on the schemaname label).\n\n\
+             Series with a schemaname label (tables, indexes, sequences) in these schemas are dropped \
+             from /metrics. Collectors still query them; use it to keep tenant, audit or scratch \
+             schemas from inflating cardinality.\n\n\
+             Examples:\n\
+               --exclude-schemas audit,scratch\n\
+               PG_EXPORTER_EXCLUDE_SCHEMAS=audit",
+        )
+        .env("PG_EXPORTER_EXCLUDE_SCHEMAS")
+        .value_name("audit,scratch,...")
+        .value_delimiter(',')
+        .action(ArgAction::Append)
+}
+
+fn exclude_tables_arg() -> Arg {
+    Arg::new("exclude-tables")
+        .long("exclude-tables")
+        .help("Comma-separated list of tables (schema.table, or table in any schema) whose series are hidden")
+        .long_help(
+            "Comma-separated list of relations whose series are hidden: schema.table, or a bare \
+             table name to match it in any schema (exact/case-sensitive match on the schemaname \
+             and relname or sequencename labels).\n\n\
+             Examples:\n\
+               --exclude-tables public.events,audit_log\n\
+               PG_EXPORTER_EXCLUDE_TABLES=public.events",
+        )
+        .env("PG_EXPORTER_EXCLUDE_TABLES")
+        .value_name("schema.table,table,...")
+        .value_delimiter(',')
+        .action(ArgAction::Append)
+}
+
+fn exclusions_file_arg() -> Arg {
+    Arg::new("exclusions-file")
+        .long("exclusions-file")
+        .help("TOML file with databases, schemas, tables and users exclusion lists")
+        .long_help(
+            "TOML file with exclusion lists, for fleets whose lists outgrow command-line flags.\n\n\
+             Every key is optional and holds an array of names:\n\n\
+               databases = [\"template0\", \"template1\"]\n\
+               schemas = [\"audit\"]\n\
+               tables = [\"public.events\"]\n\
+               users = [\"replicator\"]\n\n\
+             Each list is merged with its flag (--exclude-databases, --exclude-schemas, \
+             --exclude-tables, --exclude-users): names from either source are excluded. Unknown \
+             keys and unreadable files stop startup.\n\n\
+             Examples:\n\
+               --exclusions-file /etc/pg_exporter/exclusions.toml\n\
+               PG_EXPORTER_EXCLUSIONS_FILE=/etc/pg_exporter/exclusions.toml",
+        )
+        .env("PG_EXPORTER_EXCLUSIONS_FILE")
+        .value_name("FILE")
+        .value_parser(value_parser!(PathBuf))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,6 +296,59 @@ mod tests {
         });
     }
 
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_check_exclude_users() {
+        temp_env::with_var(
+            "PG_EXPORTER_EXCLUDE_USERS",
+            Some("backup,replicator"),
+            || {
+                let matches = new().get_matches_from(vec!["pg_exporter"]);
+                let excludes: Vec<String> = matches
+                    .get_many::<String>("exclude-users")
+                    .unwrap()
+                    .map(std::string::ToString::to_string)
+                    .collect();
+                assert_eq!(excludes, vec!["backup", "replicator"]);
+
+                let matches = new().get_matches_from(vec![
+                    "pg_exporter",
+                    "--exclude-users",
+                    "postgres_exporter",
+                ]);
+                let excludes: Vec<String> = matches
+                    .get_many::<String>("exclude-users")
+                    .unwrap()
+                    .map(std::string::ToString::to_string)
+                    .collect();
+                assert_eq!(excludes, vec!["postgres_exporter"]);
+            },
+        );
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_check_exclude_schemas_tables_and_file() {
+        let matches = new().get_matches_from(vec![
+            "pg_exporter",
+            "--exclude-schemas",
+            "audit,scratch",
+            "--exclude-tables",
+            "public.events",
+            "--exclusions-file",
+            "/etc/pg_exporter/exclusions.toml",
+        ]);
+
+        let schemas: Vec<&String> = matches.get_many("exclude-schemas").unwrap().collect();
+        assert_eq!(schemas, vec!["audit", "scratch"]);
+        let tables: Vec<&String> = matches.get_many("exclude-tables").unwrap().collect();
+        assert_eq!(tables, vec!["public.events"]);
+        assert_eq!(
+            matches.get_one::<PathBuf>("exclusions-file"),
+            Some(&PathBuf::from("/etc/pg_exporter/exclusions.toml"))
+        );
+    }
+
     #[test]
     fn test_verbose_flag_single() {
         let command = new();
@@ -1,27 +1,16 @@
-use clap::{Arg, Command, value_parser};
-use std::num::{NonZeroU64, NonZeroUsize};
+use clap::{Arg, ArgAction, Command, value_parser};
+use regex::Regex;
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    path::PathBuf,
+};
 
-use crate::collectors::MAX_DB_QUERY_CONCURRENCY_LIMIT;
+use crate::collectors::{COLLECTOR_NAMES, MAX_DB_QUERY_CONCURRENCY_LIMIT, util::ANONYMIZED_LABELS};
 
 pub fn add_collector_option_args(cmd: Command) -> Command {
-    cmd.arg(
-        Arg::new("statements.top-n")
-            .long("statements.top-n")
-            .help("Number of pg_stat_statements rows to expose")
-            .long_help(
-                "Number of pg_stat_statements rows to expose.\n\n\
-                 This limits the exporter-side top-N query set ordered by total execution time.\n\
-                 Lower values reduce cardinality and scrape cost; higher values provide more query coverage.\n\n\
-                 Examples:\n\
-                   --statements.top-n 10\n\
-                   --statements.top-n 25\n\
-                   PG_EXPORTER_STATEMENTS_TOP_N=50",
-            )
-            .env("PG_EXPORTER_STATEMENTS_TOP_N")
-            .default_value("25")
-            .value_name("N")
-            .value_parser(value_parser!(NonZeroUsize)),
-    )
+    cmd.arg(statements_top_n_arg())
+    .arg(statements_normalize_arg())
+    .arg(statements_min_mean_time_arg())
     .arg(max_db_concurrency_arg())
     .arg(connect_timeout_arg())
     .arg(
@@ -82,6 +71,486 @@ pub fn add_collector_option_args(cmd: Command) -> Command {
             .value_parser(value_parser!(NonZeroU64)),
     )
     .arg(sequences_min_ratio_arg())
+    .arg(stat_seconds_ago_arg())
+    .arg(stat_include_system_arg())
+    .arg(activity_query_age_buckets_arg())
+    .arg(activity_stuck_threshold_arg())
+    .arg(activity_slow_query_threshold_arg())
+    .arg(activity_by_application_arg())
+    .arg(activity_by_application_limit_arg())
+    .arg(fdw_probe_arg())
+    .arg(tls_cert_pem_arg())
+    .arg(tls_cert_pem_file_arg())
+    .arg(search_path_arg())
+    .arg(fail_fast_on_exhaustion_arg())
+    .arg(fail_scrape_on_db_down_arg())
+    .arg(sequential_collection_arg())
+    .arg(preflight_arg())
+    .arg(serial_collectors_arg())
+    .arg(log_scrape_summary_arg())
+    .arg(null_datname_label_arg())
+    .arg(anonymize_labels_arg())
+    .arg(anonymize_salt_arg())
+    .arg(metric_allowlist_arg())
+    .arg(metric_denylist_arg())
+    .arg(timestamp_unit_arg())
+    .arg(counter_reset_detection_arg())
+    .arg(circuit_breaker_threshold_arg())
+    .arg(circuit_breaker_cooldown_arg())
+}
+
+fn statements_top_n_arg() -> Arg {
+    Arg::new("statements.top-n")
+        .long("statements.top-n")
+        .help("Number of pg_stat_statements rows to expose")
+        .long_help(
+            "Number of pg_stat_statements rows to expose.\n\n\
+             This limits the exporter-side top-N query set ordered by total execution time.\n\
+             Lower values reduce cardinality and scrape cost; higher values provide more query coverage.\n\n\
+             Examples:\n\
+               --statements.top-n 10\n\
+               --statements.top-n 25\n\
+               PG_EXPORTER_STATEMENTS_TOP_N=50",
+        )
+        .env("PG_EXPORTER_STATEMENTS_TOP_N")
+        .default_value("25")
+        .value_name("N")
+        .value_parser(value_parser!(NonZeroUsize))
+}
+
+fn statements_normalize_arg() -> Arg {
+    Arg::new("statements.normalize")
+        .long("statements.normalize")
+        .help("Replace literal numbers and strings in pg_stat_statements query text with '?'")
+        .long_help(
+            "Replace literal numbers and quoted strings in the exported pg_stat_statements \
+             query_short label with '?' before truncation.\n\n\
+             PostgreSQL 14+ already normalizes pg_stat_statements query text server-side. On \
+             older servers some statement types keep their literal constants, which can leak \
+             PII into Prometheus labels; enable this there.\n\n\
+             Examples:\n\
+               --statements.normalize\n\
+               PG_EXPORTER_STATEMENTS_NORMALIZE=true",
+        )
+        .env("PG_EXPORTER_STATEMENTS_NORMALIZE")
+        .action(ArgAction::SetTrue)
+}
+
+fn statements_min_mean_time_arg() -> Arg {
+    Arg::new("statements.min-mean-time-ms")
+        .long("statements.min-mean-time-ms")
+        .help("Only expose pg_stat_statements rows whose mean execution time is at least this many milliseconds (0 disables)")
+        .long_help(
+            "Only expose pg_stat_statements rows whose mean execution time is at least this many \
+             milliseconds.\n\n\
+             The filter is applied in SQL before the --statements.top-n ranking, so fast but \
+             frequent statements no longer take top-N slots from slower ones, and a database \
+             without slow statements exports none. 0 (the default) keeps every statement.\n\n\
+             Examples:\n\
+               --statements.min-mean-time-ms 100\n\
+               --statements.min-mean-time-ms 2.5 --statements.top-n 50\n\
+               PG_EXPORTER_STATEMENTS_MIN_MEAN_TIME_MS=250",
+        )
+        .env("PG_EXPORTER_STATEMENTS_MIN_MEAN_TIME_MS")
+        .default_value("0")
+        .value_name("MS")
+        .value_parser(parse_statements_min_mean_time)
+}
+
+fn circuit_breaker_threshold_arg() -> Arg {
+    Arg::new("scrape.circuit-breaker-threshold")
+        .long("scrape.circuit-breaker-threshold")
+        .help("Consecutive failed connectivity checks before scrapes stop contacting PostgreSQL (0 disables)")
+        .long_help(
+            "Consecutive failed PostgreSQL connectivity checks before the circuit breaker opens.\n\n\
+             While open, /metrics answers with pg_up 0 without connecting to PostgreSQL, so a down \
+             or recovering database is not hit by a connection attempt on every scrape. While the \
+             breaker is enabled, every scrape that cannot reach PostgreSQL also serves the \
+             collector metrics of the last successful scrape. After \
+             --scrape.circuit-breaker-cooldown-ms one scrape probes the database (half-open): \
+             success closes the breaker, failure re-opens it. The state is exported as \
+             pg_exporter_circuit_breaker_state (0 closed, 1 open, 2 half-open). 0 disables it.\n\n\
+             Examples:\n\
+               --scrape.circuit-breaker-threshold 5\n\
+               --scrape.circuit-breaker-threshold 0\n\
+               PG_EXPORTER_CIRCUIT_BREAKER_THRESHOLD=3",
+        )
+        .env("PG_EXPORTER_CIRCUIT_BREAKER_THRESHOLD")
+        .default_value(CIRCUIT_BREAKER_THRESHOLD_DEFAULT)
+        .value_name("N")
+        .value_parser(value_parser!(u64))
+}
+
+fn circuit_breaker_cooldown_arg() -> Arg {
+    Arg::new("scrape.circuit-breaker-cooldown-ms")
+        .long("scrape.circuit-breaker-cooldown-ms")
+        .help("How long an open circuit breaker skips PostgreSQL before probing again, in milliseconds")
+        .long_help(
+            "How long an open circuit breaker skips PostgreSQL before a half-open probe, in \
+             milliseconds. Longer values protect a recovering database more; shorter values \
+             report recovery (pg_up 1) sooner.\n\n\
+             Examples:\n\
+               --scrape.circuit-breaker-cooldown-ms 30000\n\
+               --scrape.circuit-breaker-cooldown-ms 60000\n\
+               PG_EXPORTER_CIRCUIT_BREAKER_COOLDOWN_MS=15000",
+        )
+        .env("PG_EXPORTER_CIRCUIT_BREAKER_COOLDOWN_MS")
+        .default_value(CIRCUIT_BREAKER_COOLDOWN_MS_DEFAULT)
+        .value_name("MS")
+        .value_parser(value_parser!(NonZeroU64))
+}
+
+fn fail_fast_on_exhaustion_arg() -> Arg {
+    Arg::new("db.fail-fast-on-exhaustion")
+        .long("db.fail-fast-on-exhaustion")
+        .help("Fail the scrape immediately instead of waiting when the shared connection pool is exhausted")
+        .long_help(
+            "Fail the scrape immediately instead of waiting for a connection when the shared \
+             connection pool is exhausted.\n\n\
+             By default a scrape that finds every pooled connection busy waits for one, up to \
+             --scrape.connect-timeout-ms, which stretches scrape latency unpredictably. With this \
+             flag every shared pool acquire uses try_acquire and waits at most 100ms: a collector \
+             whose query finds the pool still exhausted fails and records an error, and when not \
+             even the connectivity check gets a connection every collector is skipped and \
+             /metrics answers 503 at once. This bounds scrape latency at the cost of missing \
+             samples during contention. Skips and acquire timeouts are counted in \
+             pg_exporter_pool_acquire_timeouts_total.\n\n\
+             Examples:\n\
+               --db.fail-fast-on-exhaustion\n\
+               PG_EXPORTER_DB_FAIL_FAST_ON_EXHAUSTION=true",
+        )
+        .env("PG_EXPORTER_DB_FAIL_FAST_ON_EXHAUSTION")
+        .action(ArgAction::SetTrue)
+}
+
+fn fail_scrape_on_db_down_arg() -> Arg {
+    Arg::new("fail-scrape-on-db-down")
+        .long("fail-scrape-on-db-down")
+        .help("Answer /metrics with 503 instead of 200 when PostgreSQL is unreachable")
+        .long_help(
+            "Answer /metrics with 503 instead of 200 when PostgreSQL is unreachable.\n\n\
+             By default a database-down scrape succeeds with pg_up 0 and the exporter-status \
+             metrics only, which suits alerting on pg_up. With this flag the same body is \
+             returned with a 503 status, so alerting on the scrape itself (up == 0 or an HTTP \
+             probe) also fires while pg_up 0 stays available to metric-based rules.\n\n\
+             Examples:\n\
+               --fail-scrape-on-db-down\n\
+               PG_EXPORTER_FAIL_SCRAPE_ON_DB_DOWN=true",
+        )
+        .env("PG_EXPORTER_FAIL_SCRAPE_ON_DB_DOWN")
+        .action(ArgAction::SetTrue)
+}
+
+fn sequential_collection_arg() -> Arg {
+    Arg::new("sequential-collection")
+        .long("sequential-collection")
+        .help("Run collectors and their sub-collectors one at a time instead of concurrently")
+        .long_help(
+            "Run collectors and their sub-collectors one at a time instead of concurrently.\n\n\
+             Each scrape awaits one collector (and umbrella collectors each sub-collector) before \
+             starting the next, instead of running them all at once. This lowers peak CPU and connection use on \
+             small or edge databases at the cost of longer scrapes; keep --scrape.timeout-ms \
+             large enough for the sum of all collectors. Multi-database collectors still fan out \
+             per database; combine with --collectors.max-db-concurrency 1 to serialize those too.\n\n\
+             Examples:\n\
+               --sequential-collection\n\
+               --sequential-collection --collectors.max-db-concurrency 1\n\
+               PG_EXPORTER_SEQUENTIAL_COLLECTION=true",
+        )
+        .env("PG_EXPORTER_SEQUENTIAL_COLLECTION")
+        .action(ArgAction::SetTrue)
+}
+
+fn preflight_arg() -> Arg {
+    Arg::new("preflight")
+        .long("preflight")
+        .help("Probe every enabled collector at startup and exit with an error if any cannot collect")
+        .long_help(
+            "Probe every enabled collector at startup and exit with an error if any cannot collect.\n\n\
+             Before serving /metrics, each enabled collector runs one collection against \
+             PostgreSQL and a per-collector readiness table is printed. A collector is not ready \
+             when its probe fails or times out (for example a missing privilege), when a \
+             required extension such as pg_stat_statements is missing, or when the server is too \
+             old for its data source. If any collector is not ready, or PostgreSQL cannot be \
+             reached, the exporter exits nonzero instead of starting. Without this flag the \
+             exporter starts anyway and such collectors export nothing.\n\n\
+             Examples:\n\
+               --preflight\n\
+               PG_EXPORTER_PREFLIGHT=true",
+        )
+        .env("PG_EXPORTER_PREFLIGHT")
+        .action(ArgAction::SetTrue)
+}
+
+fn counter_reset_detection_arg() -> Arg {
+    Arg::new("counter-reset-detection")
+        .long("counter-reset-detection")
+        .help("Export pg_exporter_counter_reset when a server counter decreases between scrapes")
+        .long_help(
+            "Export pg_exporter_counter_reset{metric,labels} when a server counter decreases between scrapes.\n\n\
+             Cumulative statistics such as pg_stat_database_xact_commit are exported as gauges \
+             and drop back to zero after pg_stat_reset() or a server restart, which rate() \
+             cannot tell from real activity. With this flag every pg_stat_database counter \
+             (--collector.database) and pg_stat_bgwriter / pg_stat_checkpointer counter \
+             (--collector.default) is compared with its value at the previous scrape, and each \
+             decrease increments pg_exporter_counter_reset for that metric and label set. \
+             Use it in recording rules to discard rates across a reset.\n\n\
+             Examples:\n\
+               --counter-reset-detection\n\
+               PG_EXPORTER_COUNTER_RESET_DETECTION=true",
+        )
+        .env("PG_EXPORTER_COUNTER_RESET_DETECTION")
+        .action(ArgAction::SetTrue)
+}
+
+fn serial_collectors_arg() -> Arg {
+    Arg::new("collectors.serial")
+        .long("collectors.serial")
+        .help("Comma-separated collectors that run one at a time after the concurrent ones")
+        .long_help(
+            "Comma-separated list of collectors that do not run alongside the others: each \
+             scrape first runs every other collector concurrently, then these one at a time.\n\n\
+             Use it for collectors that fan out over many databases or run expensive queries \
+             (stat, index, statements) so they do not all open connections and hit the server \
+             at the same moment. The scrape gets longer by their runtime; keep \
+             --scrape.timeout-ms large enough. Empty by default: every collector runs \
+             concurrently.\n\n\
+             Examples:\n\
+               --collectors.serial stat,index\n\
+               --collectors.serial stat,index,statements\n\
+               PG_EXPORTER_COLLECTORS_SERIAL=stat,index",
+        )
+        .env("PG_EXPORTER_COLLECTORS_SERIAL")
+        .value_name("stat,index,...")
+        .value_delimiter(',')
+        .action(ArgAction::Append)
+        .value_parser(clap::builder::PossibleValuesParser::new(COLLECTOR_NAMES))
+        .hide_possible_values(true)
+}
+
+fn log_scrape_summary_arg() -> Arg {
+    Arg::new("log-scrape-summary")
+        .long("log-scrape-summary")
+        .help("Log the total duration and the 3 slowest collectors of every /metrics scrape")
+        .long_help(
+            "Log one info line at the end of every /metrics scrape with the total scrape \
+             duration and the 3 slowest collectors, e.g.\n\
+               scrape summary total_ms=412 slowest=\"stat=301ms, index=88ms, default=12ms\"\n\n\
+             This gives a per-scrape performance view in the logs without Prometheus queries. \
+             It is off by default to keep log volume low; collectors running on their own \
+             --collector.<name>.interval are not part of the scrape and are not listed.\n\n\
+             Examples:\n\
+               --log-scrape-summary\n\
+               PG_EXPORTER_LOG_SCRAPE_SUMMARY=true",
+        )
+        .env("PG_EXPORTER_LOG_SCRAPE_SUMMARY")
+        .action(ArgAction::SetTrue)
+}
+
+fn null_datname_label_arg() -> Arg {
+    Arg::new("null-datname-label")
+        .long("null-datname-label")
+        .help("datname label value for rows without a database")
+        .long_help(
+            "datname label value exported for rows whose database is NULL: shared catalogs in \
+             pg_stat_database, background workers in pg_stat_activity, and locks on transaction ids \
+             or shared catalogs in pg_locks.\n\n\
+             Every collector uses the same value, so the same NULL-database concept appears \
+             under one label everywhere.\n\n\
+             Examples:\n\
+               --null-datname-label global\n\
+               PG_EXPORTER_NULL_DATNAME_LABEL=global",
+        )
+        .env("PG_EXPORTER_NULL_DATNAME_LABEL")
+        .default_value(NULL_DATNAME_LABEL_DEFAULT)
+        .value_name("LABEL")
+        .value_parser(clap::builder::NonEmptyStringValueParser::new())
+}
+
+fn anonymize_labels_arg() -> Arg {
+    let long_help = format!(
+        "Replace the values of these labels of every collector with stable salted hashes (h_ \
+         followed by 16 hex characters of SHA-256), so tenant names never reach a shared \
+         observability platform: {}.\n\n\
+         The same value always maps to the same hash, so dashboards and alerts keep working. \
+         Requires --anonymize-salt.\n\n\
+         Examples:\n\
+           --anonymize-labels --anonymize-salt \"$(cat /run/secrets/pg_exporter_salt)\"\n\
+           PG_EXPORTER_ANONYMIZE_LABELS=true PG_EXPORTER_ANONYMIZE_SALT=...",
+        ANONYMIZED_LABELS.join(", ")
+    );
+
+    Arg::new("anonymize-labels")
+        .long("anonymize-labels")
+        .help("Replace object names, roles and query text in labels with salted hashes")
+        .long_help(long_help)
+        .env("PG_EXPORTER_ANONYMIZE_LABELS")
+        .action(ArgAction::SetTrue)
+        .requires("anonymize-salt")
+}
+
+fn anonymize_salt_arg() -> Arg {
+    Arg::new("anonymize-salt")
+        .long("anonymize-salt")
+        .help("Secret salt mixed into --anonymize-labels hashes")
+        .long_help(
+            "Secret salt mixed into every --anonymize-labels hash so hashes of well-known names \
+             cannot be guessed or precomputed. Keep it stable across restarts, or every series \
+             changes identity; keep it secret, since it is all that protects the names.\n\n\
+             Examples:\n\
+               --anonymize-salt \"$(cat /run/secrets/pg_exporter_salt)\"\n\
+               PG_EXPORTER_ANONYMIZE_SALT=...",
+        )
+        .env("PG_EXPORTER_ANONYMIZE_SALT")
+        .hide_env_values(true)
+        .value_name("SALT")
+        .value_parser(clap::builder::NonEmptyStringValueParser::new())
+}
+
+fn metric_allowlist_arg() -> Arg {
+    Arg::new("metric-allowlist")
+        .long("metric-allowlist")
+        .help("Only expose metrics whose name matches one of these comma-separated regexes")
+        .long_help(
+            "Only expose metric families whose name matches one of these comma-separated regexes \
+             (default: all).\n\n\
+             Each pattern is fully anchored, like Prometheus relabeling regexes, so it must match \
+             the whole metric name. Filtering happens when /metrics is encoded: collectors still \
+             run, so use --no-collector.<name> to save the query cost of a whole collector. \
+             --metric-denylist takes precedence. Keep pg_up allowed, or database-down scrapes \
+             become empty.\n\n\
+             Examples:\n\
+               --metric-allowlist 'pg_up,pg_stat_database_.*'\n\
+               PG_EXPORTER_METRIC_ALLOWLIST='pg_up,pg_stat_.*'",
+        )
+        .env("PG_EXPORTER_METRIC_ALLOWLIST")
+        .value_name("REGEX[,REGEX...]")
+        .value_parser(parse_metric_patterns)
+}
+
+fn metric_denylist_arg() -> Arg {
+    Arg::new("metric-denylist")
+        .long("metric-denylist")
+        .help("Drop metrics whose name matches one of these comma-separated regexes")
+        .long_help(
+            "Drop metric families whose name matches one of these comma-separated regexes, even \
+             when they match --metric-allowlist.\n\n\
+             Each pattern is fully anchored and applied when /metrics is encoded, so it trims \
+             unused or high-cardinality metrics while the rest of the collector's output stays.\n\n\
+             Examples:\n\
+               --metric-denylist 'pg_stat_user_tables_.*_blks_.*,pg_stat_activity_query_age_bucket'\n\
+               PG_EXPORTER_METRIC_DENYLIST='pg_stat_user_tables_.*'",
+        )
+        .env("PG_EXPORTER_METRIC_DENYLIST")
+        .value_name("REGEX[,REGEX...]")
+        .value_parser(parse_metric_patterns)
+}
+
+fn timestamp_unit_arg() -> Arg {
+    Arg::new("timestamp-unit")
+        .long("timestamp-unit")
+        .help("Unit of Unix timestamp metrics: seconds (Prometheus convention) or milliseconds")
+        .long_help(
+            "Unit of metrics holding a Unix timestamp, such as \
+             pg_stat_user_tables_last_vacuum_timestamp_seconds, pg_stat_database_stats_reset, or \
+             pg_postmaster_start_time_seconds.\n\n\
+             seconds (default) follows the Prometheus convention. milliseconds is non-standard and \
+             meant for pipelines bridging to systems that expect epoch milliseconds: the values are \
+             multiplied by 1000 and every name ends in _milliseconds (replacing a _seconds \
+             suffix, appended otherwise), so dashboards and alerts written for the standard \
+             names stop matching. Ages, durations, and other \
+             metrics are unchanged, and --metric-allowlist / --metric-denylist match the \
+             standard names.\n\n\
+             Examples:\n\
+               --timestamp-unit milliseconds\n\
+               PG_EXPORTER_TIMESTAMP_UNIT=milliseconds",
+        )
+        .env("PG_EXPORTER_TIMESTAMP_UNIT")
+        .default_value("seconds")
+        .value_name("UNIT")
+        .value_parser(clap::builder::PossibleValuesParser::new(["seconds", "milliseconds"]))
+}
+
+/// Combine comma-separated metric name patterns into one anchored regex.
+fn parse_metric_patterns(value: &str) -> Result<Regex, String> {
+    let patterns: Vec<&str> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|pattern| !pattern.is_empty())
+        .collect();
+    if patterns.is_empty() {
+        return Err("value must contain at least one pattern".to_string());
+    }
+
+    for pattern in &patterns {
+        Regex::new(pattern).map_err(|error| format!("invalid regex {pattern:?}: {error}"))?;
+    }
+
+    let alternatives = patterns
+        .iter()
+        .map(|pattern| format!("(?:{pattern})"))
+        .collect::<Vec<_>>()
+        .join("|");
+    Regex::new(&format!("^(?:{alternatives})$")).map_err(|error| format!("invalid regex: {error}"))
+}
+
+fn tls_cert_pem_arg() -> Arg {
+    Arg::new("tls.cert-pem")
+        .long("tls.cert-pem")
+        .help("Server certificate PEM for --collector.tls expiry metrics")
+        .long_help(
+            "PostgreSQL server certificate, PEM encoded, used by --collector.tls to export the \
+             pg_ssl_certificate_* expiry and validity metrics instead of reading the \
+             ssl_cert_file path from the exporter's filesystem.\n\n\
+             Use this when the exporter runs remotely and cannot read the database host's \
+             files. Only the certificate is needed, never the private key.\n\n\
+             Examples:\n\
+               --tls.cert-pem \"$(cat server.crt)\"\n\
+               PG_EXPORTER_TLS_CERT_PEM=\"$(kubectl get secret pg-tls -o jsonpath='{.data.tls\\.crt}' | base64 -d)\"",
+        )
+        .env("PG_EXPORTER_TLS_CERT_PEM")
+        .value_name("PEM")
+        .allow_hyphen_values(true)
+        .value_parser(parse_cert_pem)
+        .conflicts_with("tls.cert-pem-file")
+}
+
+fn tls_cert_pem_file_arg() -> Arg {
+    Arg::new("tls.cert-pem-file")
+        .long("tls.cert-pem-file")
+        .help("File on the exporter host holding the server certificate PEM for --collector.tls")
+        .long_help(
+            "Path, on the exporter host, of the PostgreSQL server certificate in PEM (or DER) \
+             format, used by --collector.tls instead of the ssl_cert_file path.\n\n\
+             The file is re-read on every scrape, so a mounted secret that is rotated with the \
+             certificate is picked up without restarting the exporter.\n\n\
+             Examples:\n\
+               --tls.cert-pem-file /run/secrets/pg_server_crt\n\
+               PG_EXPORTER_TLS_CERT_PEM_FILE=/etc/pg_exporter/server.crt",
+        )
+        .env("PG_EXPORTER_TLS_CERT_PEM_FILE")
+        .value_name("PATH")
+        .value_parser(value_parser!(PathBuf))
+}
+
+fn search_path_arg() -> Arg {
+    Arg::new("db.search-path")
+        .long("db.search-path")
+        .help("PostgreSQL search_path applied to every scrape connection")
+        .long_help(
+            "PostgreSQL search_path applied to every scrape connection, including the ephemeral \
+             per-database connections used by multi-database collectors.\n\n\
+             Use this when monitoring helpers or extensions such as pg_stat_statements live in a \
+             dedicated schema. It is sent as a connection startup option (-c search_path=...). \
+             The DSN/PGOPTIONS value wins when it explicitly sets search_path.\n\n\
+             Examples:\n\
+               --db.search-path monitoring,public\n\
+               --db.search-path '\"$user\", public, extensions'\n\
+               PG_EXPORTER_DB_SEARCH_PATH=monitoring,public",
+        )
+        .env("PG_EXPORTER_DB_SEARCH_PATH")
+        .value_name("SCHEMAS")
+        .value_parser(parse_search_path)
 }
 
 fn sequences_min_ratio_arg() -> Arg {
@@ -106,6 +575,167 @@ fn sequences_min_ratio_arg() -> Arg {
         .value_parser(parse_sequences_min_ratio)
 }
 
+fn stat_seconds_ago_arg() -> Arg {
+    Arg::new("stat.seconds-ago")
+        .long("stat.seconds-ago")
+        .help("Also export server-computed pg_stat_user_tables_*_seconds_ago gauges")
+        .long_help(
+            "Also export pg_stat_user_tables_last_autovacuum_seconds_ago and \
+             pg_stat_user_tables_last_autoanalyze_seconds_ago, computed server-side at scrape \
+             time.\n\n\
+             --collector.stat always exports the raw *_timestamp_seconds gauges; compute ages in \
+             PromQL with time() - pg_stat_user_tables_last_autovacuum_timestamp_seconds so they \
+             do not drift with scrape timing. Enable this only for dashboards that still use \
+             the *_seconds_ago series.\n\n\
+             Examples:\n\
+               --stat.seconds-ago\n\
+               PG_EXPORTER_STAT_SECONDS_AGO=true",
+        )
+        .env("PG_EXPORTER_STAT_SECONDS_AGO")
+        .action(ArgAction::SetTrue)
+}
+
+fn stat_include_system_arg() -> Arg {
+    Arg::new("stat.include-system")
+        .long("stat.include-system")
+        .help("Include system catalog and TOAST tables in --collector.stat (pg_stat_all_tables)")
+        .long_help(
+            "Read --collector.stat table statistics from pg_stat_all_tables and \
+             pg_statio_all_tables instead of their _user_tables variants, so system catalog \
+             (pg_catalog, information_schema) and TOAST tables are exported too.\n\n\
+             Metric names stay pg_stat_user_tables_*; filter on schemaname to separate catalogs. \
+             Use it to diagnose catalog churn or bloat, e.g. from heavy temporary-table or DDL \
+             use. It adds a few hundred series per database, so it is off by default.\n\n\
+             Examples:\n\
+               --collector.stat --stat.include-system\n\
+               PG_EXPORTER_STAT_INCLUDE_SYSTEM=true",
+        )
+        .env("PG_EXPORTER_STAT_INCLUDE_SYSTEM")
+        .action(ArgAction::SetTrue)
+}
+
+fn activity_query_age_buckets_arg() -> Arg {
+    Arg::new("activity.query-age-buckets")
+        .long("activity.query-age-buckets")
+        .help("Comma-separated upper bounds (seconds) of pg_stat_activity_query_age_bucket")
+        .long_help(
+            "Comma-separated upper bounds, in seconds, of the buckets exported by \
+             --collector.activity as pg_stat_activity_query_age_bucket{le,state}.\n\n\
+             Buckets are cumulative like a Prometheus histogram: each counts the non-idle \
+             queries whose age (now() - query_start) is at most le seconds, and le=\"+Inf\" is \
+             always added as the total. Each extra bucket adds one series per state.\n\n\
+             Examples:\n\
+               --activity.query-age-buckets 1,10,60\n\
+               --activity.query-age-buckets 0.5,5,30,300\n\
+               PG_EXPORTER_ACTIVITY_QUERY_AGE_BUCKETS=1,10,60,600",
+        )
+        .env("PG_EXPORTER_ACTIVITY_QUERY_AGE_BUCKETS")
+        .default_value(ACTIVITY_QUERY_AGE_BUCKETS_DEFAULT)
+        .value_name("SECONDS,...")
+        .value_parser(parse_query_age_buckets)
+}
+
+fn activity_stuck_threshold_arg() -> Arg {
+    Arg::new("activity.stuck-threshold-seconds")
+        .long("activity.stuck-threshold-seconds")
+        .help("Seconds an active backend's state may stay unchanged before it counts as stuck")
+        .long_help(
+            "Seconds an active client backend's state_change may stay unchanged before \
+             --collector.activity counts it in pg_stat_activity_stuck_backends{datname}.\n\n\
+             Backends waiting on a lock are not counted; they show up as blocked connections \
+             instead. Set this above your longest legitimate query so the metric only flags \
+             wedged backends, such as ones stuck in a hung external call.\n\n\
+             Examples:\n\
+               --activity.stuck-threshold-seconds 300\n\
+               --activity.stuck-threshold-seconds 3600\n\
+               PG_EXPORTER_ACTIVITY_STUCK_THRESHOLD_SECONDS=900",
+        )
+        .env("PG_EXPORTER_ACTIVITY_STUCK_THRESHOLD_SECONDS")
+        .default_value(ACTIVITY_STUCK_THRESHOLD_SECONDS_DEFAULT)
+        .value_name("SECONDS")
+        .value_parser(value_parser!(NonZeroU64))
+}
+
+fn activity_slow_query_threshold_arg() -> Arg {
+    Arg::new("activity.slow-query-threshold-seconds")
+        .long("activity.slow-query-threshold-seconds")
+        .help("Seconds an active query may run before it counts as slow")
+        .long_help(
+            "Seconds an active client query (now() - query_start) may run before \
+             --collector.activity counts it in pg_stat_activity_slow_queries{datname}.\n\n\
+             Idle sessions and the exporter's own backend are never counted. Alert on the \
+             count to catch many slow queries at once; pg_stat_activity_oldest_query_age_seconds \
+             only shows the single slowest one.\n\n\
+             Examples:\n\
+               --activity.slow-query-threshold-seconds 60\n\
+               --activity.slow-query-threshold-seconds 5\n\
+               PG_EXPORTER_ACTIVITY_SLOW_QUERY_THRESHOLD_SECONDS=30",
+        )
+        .env("PG_EXPORTER_ACTIVITY_SLOW_QUERY_THRESHOLD_SECONDS")
+        .default_value(ACTIVITY_SLOW_QUERY_THRESHOLD_SECONDS_DEFAULT)
+        .value_name("SECONDS")
+        .value_parser(value_parser!(NonZeroU64))
+}
+
+fn activity_by_application_arg() -> Arg {
+    Arg::new("activity.by-application")
+        .long("activity.by-application")
+        .help("Export pg_stat_activity_backends_by_application{application_name,state}")
+        .long_help(
+            "Export pg_stat_activity_backends_by_application{application_name,state}, the \
+             cluster-wide number of client backends per application_name and state, to \
+             attribute connections to the services that opened them.\n\n\
+             Cardinality is driven by the clients, so it is off by default. Have every \
+             service set a stable application_name; backends without one are counted as \
+             \"[unknown]\". See --activity.by-application-limit.\n\n\
+             Examples:\n\
+               --activity.by-application\n\
+               PG_EXPORTER_ACTIVITY_BY_APPLICATION=true",
+        )
+        .env("PG_EXPORTER_ACTIVITY_BY_APPLICATION")
+        .action(ArgAction::SetTrue)
+}
+
+fn activity_by_application_limit_arg() -> Arg {
+    Arg::new("activity.by-application-limit")
+        .long("activity.by-application-limit")
+        .help("Distinct application names exported with --activity.by-application")
+        .long_help(
+            "Maximum number of distinct application_name values exported by \
+             --activity.by-application.\n\n\
+             The applications with the most backends keep their name; the backends of all \
+             others are summed under application_name=\"[other]\", so the total stays exact \
+             while the series count stays bounded.\n\n\
+             Examples:\n\
+               --activity.by-application-limit 20\n\
+               PG_EXPORTER_ACTIVITY_BY_APPLICATION_LIMIT=100",
+        )
+        .env("PG_EXPORTER_ACTIVITY_BY_APPLICATION_LIMIT")
+        .default_value(ACTIVITY_BY_APPLICATION_LIMIT_DEFAULT)
+        .value_name("N")
+        .value_parser(value_parser!(NonZeroUsize))
+}
+
+fn fdw_probe_arg() -> Arg {
+    Arg::new("fdw.probe")
+        .long("fdw.probe")
+        .help("Probe foreign servers of --collector.fdw with a query through a foreign table")
+        .long_help(
+            "Probe every foreign server listed by --collector.fdw by reading one row of one of \
+             its foreign tables (SELECT 1 FROM <table> LIMIT 1), exported as \
+             pg_foreign_server_up and pg_foreign_server_probe_duration_seconds.\n\n\
+             The query runs through the wrapper, so it checks the user mapping and the remote \
+             side as well as the network. Servers without a foreign table the exporter role can \
+             SELECT from are not probed. Each probe is bounded by the statement_timeout of the \
+             exporter's connections.\n\n\
+             Examples:\n\
+               --collector.fdw --fdw.probe\n\
+               PG_EXPORTER_FDW_PROBE=true",
+        )
+        .env("PG_EXPORTER_FDW_PROBE")
+        .action(ArgAction::SetTrue)
+}
+
 fn max_db_concurrency_arg() -> Arg {
     Arg::new("collectors.max-db-concurrency")
         .long("collectors.max-db-concurrency")
@@ -139,8 +769,10 @@ fn connect_timeout_arg() -> Arg {
         .long_help(
             "PostgreSQL connection-establishment timeout, in milliseconds.\n\n\
              This bounds DNS, TCP, TLS, authentication, and shared-pool connection acquisition \
-             before any server-side PostgreSQL timeout can apply. It must be positive and lower \
-             than --scrape.timeout-ms.\n\n\
+             before any server-side PostgreSQL timeout can apply. It is also how long startup \
+             waits for the initial version detection, so raise it for managed databases that \
+             are slow to accept connections. It must be positive and lower than \
+             --scrape.timeout-ms.\n\n\
              Examples:\n\
                --scrape.connect-timeout-ms 5000\n\
                --scrape.connect-timeout-ms 10000\n\
@@ -160,6 +792,18 @@ const LOCK_TIMEOUT_MS_DEFAULT: &str = "2000";
 const STATEMENT_TIMEOUT_MS_DEFAULT: &str = "10000";
 const SCRAPE_TIMEOUT_MS_DEFAULT: &str = "15000";
 const SEQUENCES_MIN_RATIO_DEFAULT: &str = "0.5";
+/// String form of [`crate::collectors::activity::query_age::DEFAULT_QUERY_AGE_BUCKETS`].
+const ACTIVITY_QUERY_AGE_BUCKETS_DEFAULT: &str = "1,10,60";
+/// String form of [`crate::collectors::activity::stuck::DEFAULT_STUCK_THRESHOLD`].
+const ACTIVITY_STUCK_THRESHOLD_SECONDS_DEFAULT: &str = "300";
+/// String form of [`crate::collectors::activity::slow::DEFAULT_SLOW_QUERY_THRESHOLD`].
+const ACTIVITY_SLOW_QUERY_THRESHOLD_SECONDS_DEFAULT: &str = "60";
+/// String form of [`crate::collectors::activity::applications::DEFAULT_APPLICATION_LIMIT`].
+const ACTIVITY_BY_APPLICATION_LIMIT_DEFAULT: &str = "50";
+/// String form of [`crate::collectors::util::DEFAULT_NULL_DATNAME_LABEL`].
+const NULL_DATNAME_LABEL_DEFAULT: &str = "[unknown]";
+const CIRCUIT_BREAKER_THRESHOLD_DEFAULT: &str = "5";
+const CIRCUIT_BREAKER_COOLDOWN_MS_DEFAULT: &str = "30000";
 
 fn parse_sequences_min_ratio(value: &str) -> Result<f64, String> {
     let parsed = value
@@ -173,6 +817,61 @@ fn parse_sequences_min_ratio(value: &str) -> Result<f64, String> {
     Ok(parsed)
 }
 
+fn parse_statements_min_mean_time(value: &str) -> Result<f64, String> {
+    let parsed = value
+        .parse::<f64>()
+        .map_err(|_| "statements min-mean-time must be a number of milliseconds".to_string())?;
+
+    if !parsed.is_finite() || parsed < 0.0 {
+        return Err("statements min-mean-time must be zero or a positive number".to_string());
+    }
+
+    Ok(parsed)
+}
+
+fn parse_query_age_buckets(value: &str) -> Result<Vec<f64>, String> {
+    let mut buckets = Vec::new();
+    for part in value
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        let bound = part
+            .parse::<f64>()
+            .map_err(|_| format!("query age bucket '{part}' is not a number"))?;
+        if !bound.is_finite() || bound <= 0.0 {
+            return Err(format!(
+                "query age bucket '{part}' must be a positive number of seconds"
+            ));
+        }
+        buckets.push(bound);
+    }
+
+    if buckets.is_empty() {
+        return Err("at least one query age bucket is required".to_string());
+    }
+
+    buckets.sort_by(f64::total_cmp);
+    buckets.dedup();
+    Ok(buckets)
+}
+
+fn parse_search_path(value: &str) -> Result<String, String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err("search_path must not be empty".to_string());
+    }
+
+    Ok(trimmed.to_string())
+}
+
+fn parse_cert_pem(value: &str) -> Result<String, String> {
+    x509_parser::pem::parse_x509_pem(value.as_bytes())
+        .map_err(|e| format!("not a PEM encoded certificate: {e}"))?;
+
+    Ok(value.to_string())
+}
+
 fn parse_max_db_concurrency(value: &str) -> Result<NonZeroUsize, String> {
     let parsed = value.parse::<NonZeroUsize>().map_err(|_| {
         format!(
@@ -194,6 +893,70 @@ mod tests {
     use super::*;
     use crate::cli::commands;
 
+    #[test]
+    fn test_metric_patterns_are_anchored_alternatives() {
+        let matches = commands::new().get_matches_from(vec![
+            "pg_exporter",
+            "--metric-allowlist",
+            "pg_up, pg_stat_database_.*",
+        ]);
+        let regex = matches.get_one::<Regex>("metric-allowlist");
+
+        assert!(regex.is_some_and(|regex| regex.is_match("pg_up")));
+        assert!(regex.is_some_and(|regex| regex.is_match("pg_stat_database_xact_commit")));
+        assert!(regex.is_some_and(|regex| !regex.is_match("pg_upstream")));
+        assert!(regex.is_some_and(|regex| !regex.is_match("pg_locks_count")));
+    }
+
+    #[test]
+    fn test_metric_patterns_reject_empty_and_invalid_values() {
+        for value in [" , ", "pg_(up"] {
+            let result = commands::new().try_get_matches_from(vec![
+                "pg_exporter",
+                "--metric-denylist",
+                value,
+            ]);
+            assert!(result.is_err(), "{value:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_preflight_flag_and_env() {
+        temp_env::with_var("PG_EXPORTER_PREFLIGHT", None::<String>, || {
+            let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+            assert!(!matches.get_flag("preflight"));
+
+            let matches = commands::new().get_matches_from(vec!["pg_exporter", "--preflight"]);
+            assert!(matches.get_flag("preflight"));
+        });
+
+        temp_env::with_var("PG_EXPORTER_PREFLIGHT", Some("true"), || {
+            let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+            assert!(matches.get_flag("preflight"));
+        });
+    }
+
+    #[test]
+    fn test_counter_reset_detection_flag_and_env() {
+        temp_env::with_var(
+            "PG_EXPORTER_COUNTER_RESET_DETECTION",
+            None::<String>,
+            || {
+                let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+                assert!(!matches.get_flag("counter-reset-detection"));
+
+                let matches = commands::new()
+                    .get_matches_from(vec!["pg_exporter", "--counter-reset-detection"]);
+                assert!(matches.get_flag("counter-reset-detection"));
+            },
+        );
+
+        temp_env::with_var("PG_EXPORTER_COUNTER_RESET_DETECTION", Some("true"), || {
+            let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+            assert!(matches.get_flag("counter-reset-detection"));
+        });
+    }
+
     #[test]
     fn test_statements_top_n_default() {
         temp_env::with_var("PG_EXPORTER_STATEMENTS_TOP_N", None::<String>, || {
@@ -255,6 +1018,46 @@ mod tests {
         assert!(help.contains("--statements.top-n"));
     }
 
+    #[test]
+    fn test_search_path_unset_by_default() {
+        temp_env::with_var("PG_EXPORTER_DB_SEARCH_PATH", None::<String>, || {
+            let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+            assert_eq!(matches.get_one::<String>("db.search-path"), None);
+        });
+    }
+
+    #[test]
+    fn test_search_path_from_env_and_cli() {
+        temp_env::with_var("PG_EXPORTER_DB_SEARCH_PATH", Some("monitoring"), || {
+            let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+            assert_eq!(
+                matches
+                    .get_one::<String>("db.search-path")
+                    .map(String::as_str),
+                Some("monitoring")
+            );
+
+            let matches = commands::new().get_matches_from(vec![
+                "pg_exporter",
+                "--db.search-path",
+                " ext, public ",
+            ]);
+            assert_eq!(
+                matches
+                    .get_one::<String>("db.search-path")
+                    .map(String::as_str),
+                Some("ext, public")
+            );
+        });
+    }
+
+    #[test]
+    fn test_search_path_rejects_blank() {
+        let result =
+            commands::new().try_get_matches_from(vec!["pg_exporter", "--db.search-path", "  "]);
+        assert!(result.is_err(), "Should reject a blank search_path");
+    }
+
     #[test]
     fn test_max_db_concurrency_default() {
         temp_env::with_var("PG_EXPORTER_MAX_DB_CONCURRENCY", None::<String>, || {
@@ -445,6 +1248,343 @@ mod tests {
         assert!(result.is_err(), "Should reject a zero concurrency limit");
     }
 
+    #[test]
+    fn test_activity_query_age_buckets() {
+        temp_env::with_var(
+            "PG_EXPORTER_ACTIVITY_QUERY_AGE_BUCKETS",
+            None::<String>,
+            || {
+                let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+                assert_eq!(
+                    matches.get_one::<Vec<f64>>("activity.query-age-buckets"),
+                    Some(
+                        &crate::collectors::activity::query_age::DEFAULT_QUERY_AGE_BUCKETS.to_vec()
+                    )
+                );
+
+                let matches = commands::new().get_matches_from(vec![
+                    "pg_exporter",
+                    "--activity.query-age-buckets",
+                    "300, 0.5,30,30",
+                ]);
+                assert_eq!(
+                    matches.get_one::<Vec<f64>>("activity.query-age-buckets"),
+                    Some(&vec![0.5, 30.0, 300.0])
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_activity_query_age_buckets_rejects_invalid() {
+        for value in ["", "abc", "0", "-1", "1,inf", "1,NaN"] {
+            let result = commands::new().try_get_matches_from(vec![
+                "pg_exporter",
+                "--activity.query-age-buckets",
+                value,
+            ]);
+            assert!(result.is_err(), "Should reject query age buckets {value:?}");
+        }
+    }
+
+    #[test]
+    fn test_activity_stuck_threshold() {
+        temp_env::with_var(
+            "PG_EXPORTER_ACTIVITY_STUCK_THRESHOLD_SECONDS",
+            None::<&str>,
+            || {
+                let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+                assert_eq!(
+                    matches
+                        .get_one::<NonZeroU64>("activity.stuck-threshold-seconds")
+                        .map(|value| value.get()),
+                    Some(crate::collectors::activity::stuck::DEFAULT_STUCK_THRESHOLD.as_secs())
+                );
+
+                let result = commands::new().try_get_matches_from(vec![
+                    "pg_exporter",
+                    "--activity.stuck-threshold-seconds",
+                    "0",
+                ]);
+                assert!(result.is_err(), "Should reject a zero stuck threshold");
+            },
+        );
+    }
+
+    #[test]
+    fn test_activity_slow_query_threshold() {
+        temp_env::with_var(
+            "PG_EXPORTER_ACTIVITY_SLOW_QUERY_THRESHOLD_SECONDS",
+            None::<&str>,
+            || {
+                let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+                assert_eq!(
+                    matches
+                        .get_one::<NonZeroU64>("activity.slow-query-threshold-seconds")
+                        .map(|value| value.get()),
+                    Some(crate::collectors::activity::slow::DEFAULT_SLOW_QUERY_THRESHOLD.as_secs())
+                );
+
+                let result = commands::new().try_get_matches_from(vec![
+                    "pg_exporter",
+                    "--activity.slow-query-threshold-seconds",
+                    "0",
+                ]);
+                assert!(result.is_err(), "Should reject a zero slow-query threshold");
+            },
+        );
+        temp_env::with_var(
+            "PG_EXPORTER_ACTIVITY_SLOW_QUERY_THRESHOLD_SECONDS",
+            Some("15"),
+            || {
+                let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+                assert_eq!(
+                    matches
+                        .get_one::<NonZeroU64>("activity.slow-query-threshold-seconds")
+                        .map(|value| value.get()),
+                    Some(15)
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_activity_by_application_options() {
+        temp_env::with_vars(
+            [
+                ("PG_EXPORTER_ACTIVITY_BY_APPLICATION", None::<&str>),
+                ("PG_EXPORTER_ACTIVITY_BY_APPLICATION_LIMIT", None),
+            ],
+            || {
+                let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+                assert!(!matches.get_flag("activity.by-application"));
+                assert_eq!(
+                    matches
+                        .get_one::<NonZeroUsize>("activity.by-application-limit")
+                        .map(|value| value.get()),
+                    Some(crate::collectors::activity::applications::DEFAULT_APPLICATION_LIMIT)
+                );
+
+                let matches = commands::new().get_matches_from(vec![
+                    "pg_exporter",
+                    "--activity.by-application",
+                    "--activity.by-application-limit",
+                    "5",
+                ]);
+                assert!(matches.get_flag("activity.by-application"));
+                assert_eq!(
+                    matches
+                        .get_one::<NonZeroUsize>("activity.by-application-limit")
+                        .map(|value| value.get()),
+                    Some(5)
+                );
+
+                let result = commands::new().try_get_matches_from(vec![
+                    "pg_exporter",
+                    "--activity.by-application-limit",
+                    "0",
+                ]);
+                assert!(result.is_err(), "Should reject a zero application limit");
+            },
+        );
+    }
+
+    /// Self-signed test certificate, valid until 2126.
+    const TEST_CERT_PEM: &str = "-----BEGIN CERTIFICATE-----\n\
+        MIIBjjCCATOgAwIBAgIUdFJgcHc5J10mPufnl+l+zXcz/LEwCgYIKoZIzj0EAwIw\n\
+        GzEZMBcGA1UEAwwQcGctZXhwb3J0ZXItdGVzdDAgFw0yNjEwMTcwMzU3MTRaGA8y\n\
+        MTI2MDkyMzAzNTcxNFowGzEZMBcGA1UEAwwQcGctZXhwb3J0ZXItdGVzdDBZMBMG\n\
+        ByqGSM49AgEGCCqGSM49AwEHA0IABOK4DOpAhrTZ6ThyNWHRb+WFC4x7pKj8ANW1\n\
+        p3WyYD9Z245cK6MYG8j2+sYnDymKszoG5h0u9lMSErOtTM6CU1ujUzBRMB0GA1Ud\n\
+        DgQWBBQxve4kx4LBHYWShRQXMeRbvldIGzAfBgNVHSMEGDAWgBQxve4kx4LBHYWS\n\
+        hRQXMeRbvldIGzAPBgNVHRMBAf8EBTADAQH/MAoGCCqGSM49BAMCA0kAMEYCIQDP\n\
+        FSimZ1AJPucnu2EQl9S7oC5Jt2G3PK/c23cMxjAadAIhAOrv0JXXKnuyzp3sCJgJ\n\
+        0YeN2y/2QUadQxwFc7eFqLgx\n\
+        -----END CERTIFICATE-----\n";
+
+    #[test]
+    fn test_tls_cert_pem_options() {
+        temp_env::with_vars(
+            [
+                ("PG_EXPORTER_TLS_CERT_PEM", None::<&str>),
+                ("PG_EXPORTER_TLS_CERT_PEM_FILE", None),
+            ],
+            || {
+                let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+                assert!(matches.get_one::<String>("tls.cert-pem").is_none());
+                assert!(matches.get_one::<PathBuf>("tls.cert-pem-file").is_none());
+
+                let matches = commands::new().get_matches_from(vec![
+                    "pg_exporter",
+                    "--tls.cert-pem",
+                    TEST_CERT_PEM,
+                ]);
+                assert_eq!(
+                    matches
+                        .get_one::<String>("tls.cert-pem")
+                        .map(String::as_str),
+                    Some(TEST_CERT_PEM)
+                );
+
+                let result = commands::new().try_get_matches_from(vec![
+                    "pg_exporter",
+                    "--tls.cert-pem",
+                    "not a certificate",
+                ]);
+                assert!(result.is_err(), "Should reject a value that is not PEM");
+
+                let result = commands::new().try_get_matches_from(vec![
+                    "pg_exporter",
+                    "--tls.cert-pem",
+                    TEST_CERT_PEM,
+                    "--tls.cert-pem-file",
+                    "/run/secrets/pg_server_crt",
+                ]);
+                assert!(result.is_err(), "Inline PEM and PEM file are exclusive");
+            },
+        );
+    }
+
+    #[test]
+    fn test_null_datname_label() {
+        temp_env::with_var("PG_EXPORTER_NULL_DATNAME_LABEL", None::<&str>, || {
+            let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+            assert_eq!(
+                matches
+                    .get_one::<String>("null-datname-label")
+                    .map(String::as_str),
+                Some(crate::collectors::util::DEFAULT_NULL_DATNAME_LABEL)
+            );
+
+            let matches = commands::new().get_matches_from(vec![
+                "pg_exporter",
+                "--null-datname-label",
+                "global",
+            ]);
+            assert_eq!(
+                matches
+                    .get_one::<String>("null-datname-label")
+                    .map(String::as_str),
+                Some("global")
+            );
+
+            let result = commands::new().try_get_matches_from(vec![
+                "pg_exporter",
+                "--null-datname-label",
+                "",
+            ]);
+            assert!(result.is_err(), "Should reject an empty label");
+        });
+        temp_env::with_var("PG_EXPORTER_NULL_DATNAME_LABEL", Some("shared"), || {
+            let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+            assert_eq!(
+                matches
+                    .get_one::<String>("null-datname-label")
+                    .map(String::as_str),
+                Some("shared")
+            );
+        });
+    }
+
+    #[test]
+    fn test_timestamp_unit_defaults_to_seconds() {
+        temp_env::with_vars([("PG_EXPORTER_TIMESTAMP_UNIT", None::<&str>)], || {
+            let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+            assert_eq!(
+                matches
+                    .get_one::<String>("timestamp-unit")
+                    .map(String::as_str),
+                Some("seconds")
+            );
+
+            let matches = commands::new().get_matches_from(vec![
+                "pg_exporter",
+                "--timestamp-unit",
+                "milliseconds",
+            ]);
+            assert_eq!(
+                matches
+                    .get_one::<String>("timestamp-unit")
+                    .map(String::as_str),
+                Some("milliseconds")
+            );
+
+            let result = commands::new().try_get_matches_from(vec![
+                "pg_exporter",
+                "--timestamp-unit",
+                "minutes",
+            ]);
+            assert!(result.is_err(), "Should reject unknown units");
+        });
+    }
+
+    #[test]
+    fn test_anonymize_labels_requires_salt() {
+        temp_env::with_vars(
+            [
+                ("PG_EXPORTER_ANONYMIZE_LABELS", None::<&str>),
+                ("PG_EXPORTER_ANONYMIZE_SALT", None),
+            ],
+            || {
+                let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+                assert!(!matches.get_flag("anonymize-labels"));
+
+                let result =
+                    commands::new().try_get_matches_from(vec!["pg_exporter", "--anonymize-labels"]);
+                assert!(result.is_err(), "Should require --anonymize-salt");
+
+                let matches = commands::new().get_matches_from(vec![
+                    "pg_exporter",
+                    "--anonymize-labels",
+                    "--anonymize-salt",
+                    "s3cret",
+                ]);
+                assert!(matches.get_flag("anonymize-labels"));
+                assert_eq!(
+                    matches
+                        .get_one::<String>("anonymize-salt")
+                        .map(String::as_str),
+                    Some("s3cret")
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_anonymize_labels_help_lists_every_label() {
+        let help = commands::new().render_long_help().to_string();
+        for label in ANONYMIZED_LABELS {
+            assert!(help.contains(label), "help should list {label}");
+        }
+    }
+
+    #[test]
+    fn test_log_scrape_summary_flag() {
+        temp_env::with_var("PG_EXPORTER_LOG_SCRAPE_SUMMARY", None::<&str>, || {
+            let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+            assert!(!matches.get_flag("log-scrape-summary"));
+
+            let matches =
+                commands::new().get_matches_from(vec!["pg_exporter", "--log-scrape-summary"]);
+            assert!(matches.get_flag("log-scrape-summary"));
+        });
+        temp_env::with_var("PG_EXPORTER_LOG_SCRAPE_SUMMARY", Some("true"), || {
+            let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+            assert!(matches.get_flag("log-scrape-summary"));
+        });
+    }
+
+    #[test]
+    fn test_fdw_probe_options() {
+        temp_env::with_vars([("PG_EXPORTER_FDW_PROBE", None::<&str>)], || {
+            let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+            assert!(!matches.get_flag("fdw.probe"));
+
+            let matches = commands::new().get_matches_from(vec!["pg_exporter", "--fdw.probe"]);
+            assert!(matches.get_flag("fdw.probe"));
+        });
+    }
+
     #[test]
     fn max_db_concurrency_default_matches_const() {
         // The CLI default string must stay in sync with the compile-time fallback constant.
@@ -0,0 +1,171 @@
+use crate::cli::telemetry::{OtlpProtocol, validate_otlp_endpoint};
+use clap::{Arg, ArgAction, Command};
+
+pub fn add_telemetry_args(cmd: Command) -> Command {
+    cmd.arg(no_telemetry_arg())
+        .arg(otlp_endpoint_arg())
+        .arg(otlp_protocol_arg())
+        .arg(otlp_headers_arg())
+}
+
+fn no_telemetry_arg() -> Arg {
+    Arg::new("no-telemetry")
+        .long("no-telemetry")
+        .help("Disable OpenTelemetry tracing, keep plain logs")
+        .long_help(
+            "Disable OpenTelemetry tracing entirely, keeping plain tracing logs.\n\n\
+             No tracer provider or OTLP exporter is created, even when --otlp-endpoint or \
+             OTEL_EXPORTER_OTLP_ENDPOINT is set, so there is no span export overhead and no \
+             \"failed to export spans\" warnings. Use this when only Prometheus metrics and logs \
+             are wanted.\n\n\
+             Examples:\n\
+               --no-telemetry\n\
+               PG_EXPORTER_NO_TELEMETRY=true",
+        )
+        .env("PG_EXPORTER_NO_TELEMETRY")
+        .action(ArgAction::SetTrue)
+}
+
+fn otlp_endpoint_arg() -> Arg {
+    Arg::new("otlp-endpoint")
+        .long("otlp-endpoint")
+        .help("OTLP collector endpoint; enables tracing (default: OTEL_EXPORTER_OTLP_ENDPOINT)")
+        .long_help(
+            "OTLP collector endpoint that spans are exported to. Setting it enables tracing.\n\n\
+             Falls back to OTEL_EXPORTER_OTLP_ENDPOINT when not given. An endpoint without a \
+             scheme defaults to https://. For --otlp-protocol http, /v1/traces is appended \
+             unless the URL already ends with it. The URL is validated at startup.\n\n\
+             Examples:\n\
+               --otlp-endpoint http://localhost:4317\n\
+               --otlp-endpoint http://localhost:4318 --otlp-protocol http\n\
+               PG_EXPORTER_OTLP_ENDPOINT=https://otel.example.com:4317",
+        )
+        .env("PG_EXPORTER_OTLP_ENDPOINT")
+        .value_name("URL")
+        .value_parser(parse_otlp_endpoint)
+}
+
+fn otlp_protocol_arg() -> Arg {
+    Arg::new("otlp-protocol")
+        .long("otlp-protocol")
+        .help("OTLP transport: grpc or http (default: OTEL_EXPORTER_OTLP_PROTOCOL, then grpc)")
+        .long_help(
+            "OTLP transport used to export spans:\n\
+             - grpc: OTLP over gRPC, usually port 4317\n\
+             - http: OTLP over HTTP with protobuf payloads, usually port 4318\n\n\
+             Falls back to OTEL_EXPORTER_OTLP_PROTOCOL ('grpc' or 'http/protobuf'), then grpc.\n\n\
+             Examples:\n\
+               --otlp-protocol http\n\
+               PG_EXPORTER_OTLP_PROTOCOL=grpc",
+        )
+        .env("PG_EXPORTER_OTLP_PROTOCOL")
+        .value_name("PROTOCOL")
+        .value_parser(parse_otlp_protocol)
+}
+
+fn otlp_headers_arg() -> Arg {
+    Arg::new("otlp-headers")
+        .long("otlp-headers")
+        .help("Comma-separated key=value headers sent to the OTLP collector")
+        .long_help(
+            "Comma-separated key=value headers sent with every export request, e.g. for \
+             authentication. Falls back to OTEL_EXPORTER_OTLP_HEADERS. For grpc, keys ending \
+             in -bin are sent as binary metadata and their values must be base64-encoded.\n\n\
+             Prefer the environment variable so credentials do not show up in the process list.\n\n\
+             Examples:\n\
+               --otlp-headers 'x-honeycomb-team=KEY'\n\
+               PG_EXPORTER_OTLP_HEADERS='authorization=Bearer TOKEN,x-tenant=ops'",
+        )
+        .env("PG_EXPORTER_OTLP_HEADERS")
+        .value_name("KEY=VALUE,...")
+        .value_parser(parse_otlp_headers)
+}
+
+fn parse_otlp_endpoint(value: &str) -> Result<String, String> {
+    validate_otlp_endpoint(value).map_err(|e| e.to_string())
+}
+
+fn parse_otlp_protocol(value: &str) -> Result<OtlpProtocol, String> {
+    OtlpProtocol::parse(value).ok_or_else(|| format!("must be 'grpc' or 'http', got '{value}'"))
+}
+
+fn parse_otlp_headers(value: &str) -> Result<String, String> {
+    for pair in value.split(',').filter(|pair| !pair.trim().is_empty()) {
+        match pair.split_once('=') {
+            Some((key, _)) if !key.trim().is_empty() => {}
+            _ => return Err(format!("expected key=value, got '{}'", pair.trim())),
+        }
+    }
+
+    Ok(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command() -> Command {
+        add_telemetry_args(Command::new("pg_exporter"))
+    }
+
+    #[test]
+    fn test_no_telemetry_flag() {
+        temp_env::with_var("PG_EXPORTER_NO_TELEMETRY", None::<String>, || {
+            let matches = command().get_matches_from(vec!["pg_exporter"]);
+            assert!(!matches.get_flag("no-telemetry"));
+
+            let matches = command().get_matches_from(vec!["pg_exporter", "--no-telemetry"]);
+            assert!(matches.get_flag("no-telemetry"));
+        });
+
+        temp_env::with_var("PG_EXPORTER_NO_TELEMETRY", Some("true"), || {
+            let matches = command().get_matches_from(vec!["pg_exporter"]);
+            assert!(matches.get_flag("no-telemetry"));
+        });
+    }
+
+    #[test]
+    fn test_otlp_args() {
+        let matches = command().get_matches_from(vec![
+            "pg_exporter",
+            "--otlp-endpoint",
+            "otel.example.com:4318",
+            "--otlp-protocol",
+            "http",
+            "--otlp-headers",
+            "authorization=Bearer abc,x-tenant=ops",
+        ]);
+
+        assert_eq!(
+            matches
+                .get_one::<String>("otlp-endpoint")
+                .map(String::as_str),
+            Some("https://otel.example.com:4318")
+        );
+        assert_eq!(
+            matches.get_one::<OtlpProtocol>("otlp-protocol").copied(),
+            Some(OtlpProtocol::Http)
+        );
+        assert_eq!(
+            matches
+                .get_one::<String>("otlp-headers")
+                .map(String::as_str),
+            Some("authorization=Bearer abc,x-tenant=ops")
+        );
+    }
+
+    #[test]
+    fn test_otlp_args_rejects_invalid_values() {
+        for args in [
+            vec!["pg_exporter", "--otlp-endpoint", "http://"],
+            vec!["pg_exporter", "--otlp-protocol", "udp"],
+            vec!["pg_exporter", "--otlp-headers", "novalue"],
+            vec!["pg_exporter", "--otlp-headers", "=value"],
+        ] {
+            assert!(
+                command().try_get_matches_from(args.clone()).is_err(),
+                "should reject {args:?}"
+            );
+        }
+    }
+}
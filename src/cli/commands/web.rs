@@ -0,0 +1,318 @@
+use clap::{Arg, ArgAction, Command};
+use regex::Regex;
+use std::num::NonZeroU32;
+
+pub fn add_web_args(cmd: Command) -> Command {
+    cmd.arg(route_prefix_arg())
+        .arg(allowed_user_agents_arg())
+        .arg(enable_admin_endpoints_arg())
+        .arg(admin_token_arg())
+        .arg(listen_backlog_arg())
+        .arg(reuse_port_arg())
+}
+
+fn route_prefix_arg() -> Arg {
+    Arg::new("web.route-prefix")
+        .long("web.route-prefix")
+        .help("Path prefix all HTTP endpoints are mounted under (default: none)")
+        .long_help(
+            "Path prefix all HTTP endpoints are mounted under, for exporters served behind a \
+             shared reverse proxy without path rewriting.\n\n\
+             /metrics, /health and the admin endpoints all move below the prefix and the \
+             unprefixed paths answer 404. The prefix must start with '/'; a trailing '/' is \
+             ignored and '/' alone means no prefix. The healthcheck subcommand applies it to \
+             its default URL as well.\n\n\
+             Examples:\n\
+               --web.route-prefix /pg01     Serves /pg01/metrics and /pg01/health\n\
+               PG_EXPORTER_WEB_ROUTE_PREFIX=/pg01",
+        )
+        .env("PG_EXPORTER_WEB_ROUTE_PREFIX")
+        .global(true)
+        .value_name("PATH")
+        .value_parser(parse_route_prefix)
+}
+
+fn allowed_user_agents_arg() -> Arg {
+    Arg::new("web.allowed-user-agents")
+        .long("web.allowed-user-agents")
+        .help("Regex the User-Agent header must match to scrape /metrics (default: allow all)")
+        .long_help(
+            "Regex the User-Agent header must match to scrape /metrics.\n\n\
+             Non-matching clients, including requests without a User-Agent header, receive \
+             403 Forbidden. The regex is fully anchored, like Prometheus relabeling regexes, so \
+             it must match the whole header value. /health is never restricted so liveness \
+             probes keep working. This is a lightweight guard against accidental scrapes, not \
+             authentication: any client can send an arbitrary User-Agent.\n\n\
+             Examples:\n\
+               --web.allowed-user-agents 'Prometheus/.*'\n\
+               --web.allowed-user-agents '(Prometheus|vmagent)/.*'\n\
+               PG_EXPORTER_WEB_ALLOWED_USER_AGENTS='Prometheus/.*'",
+        )
+        .env("PG_EXPORTER_WEB_ALLOWED_USER_AGENTS")
+        .value_name("REGEX")
+        .value_parser(parse_allowed_user_agents)
+}
+
+fn enable_admin_endpoints_arg() -> Arg {
+    Arg::new("enable-admin-endpoints")
+        .long("enable-admin-endpoints")
+        .help("Expose POST /admin/reset-statements, /admin/reset-stats and GET /debug/config (requires --web.admin-token)")
+        .long_help(
+            "Expose the admin endpoints:\n\
+             - POST /admin/reset-statements runs pg_stat_statements_reset()\n\
+             - POST /admin/reset-stats runs pg_stat_reset() on the DSN database\n\
+             - GET /debug/config returns the running configuration as reproducible flags (secrets redacted)\n\n\
+             Disabled by default. Every request must send 'Authorization: Bearer <token>' \
+             matching --web.admin-token, and every invocation is logged with its request id. \
+             Intended for benchmarking harnesses that need to reset statistics at a known point.\n\n\
+             Examples:\n\
+               --enable-admin-endpoints --web.admin-token \"$TOKEN\"\n\
+               PG_EXPORTER_ENABLE_ADMIN_ENDPOINTS=true PG_EXPORTER_WEB_ADMIN_TOKEN=...",
+        )
+        .env("PG_EXPORTER_ENABLE_ADMIN_ENDPOINTS")
+        .action(ArgAction::SetTrue)
+}
+
+fn admin_token_arg() -> Arg {
+    Arg::new("web.admin-token")
+        .long("web.admin-token")
+        .help("Bearer token required by the admin endpoints")
+        .long_help(
+            "Bearer token required by the admin endpoints enabled with --enable-admin-endpoints.\n\n\
+             Prefer the environment variable so the token does not show up in the process list.\n\n\
+             Examples:\n\
+               PG_EXPORTER_WEB_ADMIN_TOKEN=$(cat /run/secrets/pg_exporter_admin_token)",
+        )
+        .env("PG_EXPORTER_WEB_ADMIN_TOKEN")
+        .hide_env_values(true)
+        .value_name("TOKEN")
+        .value_parser(parse_admin_token)
+}
+
+fn listen_backlog_arg() -> Arg {
+    Arg::new("web.listen-backlog")
+        .long("web.listen-backlog")
+        .help("Pending-connection queue length of the HTTP listener")
+        .long_help(
+            "Pending-connection queue length passed to listen(2) for the HTTP listener.\n\n\
+             Raise it when many scrapers (several Prometheus replicas, federation, ad-hoc \
+             curl) connect at once and the kernel drops or resets connections while the \
+             exporter is busy. The kernel caps the value silently, on Linux at \
+             net.core.somaxconn.\n\n\
+             Examples:\n\
+               --web.listen-backlog 4096\n\
+               PG_EXPORTER_WEB_LISTEN_BACKLOG=4096",
+        )
+        .env("PG_EXPORTER_WEB_LISTEN_BACKLOG")
+        .default_value("1024")
+        .value_name("CONNECTIONS")
+        .value_parser(clap::value_parser!(NonZeroU32))
+}
+
+fn reuse_port_arg() -> Arg {
+    Arg::new("web.reuse-port")
+        .long("web.reuse-port")
+        .help("Set SO_REUSEPORT on the HTTP listener (Unix only)")
+        .long_help(
+            "Set SO_REUSEPORT on the HTTP listener so several exporter processes can bind the \
+             same address and port, e.g. to overlap the old and new process during a restart.\n\n\
+             SO_REUSEADDR is always set on Unix regardless of this flag. Ignored, with a \
+             warning, on other platforms.\n\n\
+             Examples:\n\
+               --web.reuse-port\n\
+               PG_EXPORTER_WEB_REUSE_PORT=true",
+        )
+        .env("PG_EXPORTER_WEB_REUSE_PORT")
+        .action(ArgAction::SetTrue)
+}
+
+fn parse_admin_token(value: &str) -> Result<String, String> {
+    let token = value.trim();
+    if token.is_empty() {
+        return Err("value must not be empty".to_string());
+    }
+
+    Ok(token.to_string())
+}
+
+/// Normalizes a route prefix to `/segment[/segment...]` without a trailing slash;
+/// `/` yields the empty string (no prefix).
+fn parse_route_prefix(value: &str) -> Result<String, String> {
+    let prefix = value.trim();
+    if !prefix.starts_with('/') {
+        return Err("value must start with '/'".to_string());
+    }
+
+    let prefix = prefix.trim_end_matches('/');
+    if prefix.contains("//") {
+        return Err("value must not contain empty path segments".to_string());
+    }
+
+    // Keep router syntax ({param}, wildcards) and query/fragment characters out
+    if let Some(c) = prefix
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '/' | '-' | '_' | '.' | '~')))
+    {
+        return Err(format!("invalid character {c:?} in route prefix"));
+    }
+
+    Ok(prefix.to_string())
+}
+
+fn parse_allowed_user_agents(value: &str) -> Result<Regex, String> {
+    let pattern = value.trim();
+    if pattern.is_empty() {
+        return Err("value must not be empty".to_string());
+    }
+
+    Regex::new(&format!("^(?:{pattern})$")).map_err(|error| format!("invalid regex: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command() -> Command {
+        add_web_args(Command::new("pg_exporter"))
+    }
+
+    #[test]
+    fn test_allowed_user_agents_defaults_to_none() {
+        temp_env::with_var(
+            "PG_EXPORTER_WEB_ALLOWED_USER_AGENTS",
+            None::<String>,
+            || {
+                let matches = command().get_matches_from(vec!["pg_exporter"]);
+                assert!(
+                    matches
+                        .get_one::<Regex>("web.allowed-user-agents")
+                        .is_none()
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_allowed_user_agents_is_anchored() {
+        let matches = command().get_matches_from(vec![
+            "pg_exporter",
+            "--web.allowed-user-agents",
+            "Prometheus/.*",
+        ]);
+        let regex = matches.get_one::<Regex>("web.allowed-user-agents");
+
+        assert!(regex.is_some_and(|regex| regex.is_match("Prometheus/3.1.0")));
+        assert!(regex.is_some_and(|regex| !regex.is_match("curl/8.5.0 Prometheus/3.1.0")));
+    }
+
+    #[test]
+    fn test_listen_backlog_defaults_to_1024() {
+        temp_env::with_vars(
+            [
+                ("PG_EXPORTER_WEB_LISTEN_BACKLOG", None::<&str>),
+                ("PG_EXPORTER_WEB_REUSE_PORT", None::<&str>),
+            ],
+            || {
+                let matches = command().get_matches_from(vec!["pg_exporter"]);
+                assert_eq!(
+                    matches
+                        .get_one::<NonZeroU32>("web.listen-backlog")
+                        .map(|backlog| backlog.get()),
+                    Some(1024)
+                );
+                assert!(!matches.get_flag("web.reuse-port"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_listen_backlog_rejects_zero() {
+        let result =
+            command().try_get_matches_from(vec!["pg_exporter", "--web.listen-backlog", "0"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_route_prefix_defaults_to_none() {
+        temp_env::with_var("PG_EXPORTER_WEB_ROUTE_PREFIX", None::<String>, || {
+            let matches = command().get_matches_from(vec!["pg_exporter"]);
+            assert!(matches.get_one::<String>("web.route-prefix").is_none());
+        });
+    }
+
+    #[test]
+    fn test_route_prefix_is_normalized() {
+        for (value, expected) in [
+            ("/pg01", "/pg01"),
+            ("/pg01/", "/pg01"),
+            (" /exporters/pg01 ", "/exporters/pg01"),
+            ("/", ""),
+        ] {
+            let matches =
+                command().get_matches_from(vec!["pg_exporter", "--web.route-prefix", value]);
+            assert_eq!(
+                matches
+                    .get_one::<String>("web.route-prefix")
+                    .map(String::as_str),
+                Some(expected),
+                "{value:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_route_prefix_rejects_invalid_values() {
+        for value in ["pg01", "", "/pg01//metrics", "/{db}", "/pg01?x=1", "/a b"] {
+            let result =
+                command().try_get_matches_from(vec!["pg_exporter", "--web.route-prefix", value]);
+            assert!(result.is_err(), "{value:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_admin_endpoints_disabled_by_default() {
+        temp_env::with_vars(
+            [
+                ("PG_EXPORTER_ENABLE_ADMIN_ENDPOINTS", None::<&str>),
+                ("PG_EXPORTER_WEB_ADMIN_TOKEN", None::<&str>),
+            ],
+            || {
+                let matches = command().get_matches_from(vec!["pg_exporter"]);
+                assert!(!matches.get_flag("enable-admin-endpoints"));
+                assert!(matches.get_one::<String>("web.admin-token").is_none());
+            },
+        );
+    }
+
+    #[test]
+    fn test_admin_token_is_trimmed_and_rejects_blank() {
+        let matches = command().get_matches_from(vec![
+            "pg_exporter",
+            "--enable-admin-endpoints",
+            "--web.admin-token",
+            " s3cret ",
+        ]);
+        assert!(matches.get_flag("enable-admin-endpoints"));
+        assert_eq!(
+            matches
+                .get_one::<String>("web.admin-token")
+                .map(String::as_str),
+            Some("s3cret")
+        );
+
+        let result = command().try_get_matches_from(vec!["pg_exporter", "--web.admin-token", "  "]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_allowed_user_agents_rejects_invalid_and_blank_values() {
+        for value in ["(unclosed", "   "] {
+            let result = command().try_get_matches_from(vec![
+                "pg_exporter",
+                "--web.allowed-user-agents",
+                value,
+            ]);
+            assert!(result.is_err(), "{value:?} should be rejected");
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use anyhow::{Context, Result};
+use clap::ArgMatches;
+use serde::Deserialize;
+use std::{fs, path::Path, path::PathBuf};
+
+/// Exclusion lists read from `--exclusions-file`; every key is optional.
+#[derive(Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub(super) struct Exclusions {
+    pub databases: Vec<String>,
+    pub schemas: Vec<String>,
+    pub tables: Vec<String>,
+    pub users: Vec<String>,
+}
+
+impl Exclusions {
+    fn from_file(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read exclusions file '{}'", path.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse exclusions file '{}'", path.display()))
+    }
+
+    /// Lists from `--exclusions-file`, empty when the flag is not set.
+    fn from_matches(matches: &ArgMatches) -> Result<Self> {
+        matches
+            .get_one::<PathBuf>("exclusions-file")
+            .map_or_else(|| Ok(Self::default()), |path| Self::from_file(path))
+    }
+
+    /// Each `--exclude-*` flag (CLI or env) merged with its list from `--exclusions-file`.
+    pub(super) fn from_flags_and_file(matches: &ArgMatches) -> Result<Self> {
+        let file = Self::from_matches(matches)?;
+
+        Ok(Self {
+            databases: merge(flag_values(matches, "exclude-databases"), &file.databases),
+            schemas: merge(flag_values(matches, "exclude-schemas"), &file.schemas),
+            tables: merge(flag_values(matches, "exclude-tables"), &file.tables),
+            users: merge(flag_values(matches, "exclude-users"), &file.users),
+        })
+    }
+}
+
+/// Values of a comma-separated exclusion flag (CLI or env), trimmed and non-empty.
+fn flag_values(matches: &ArgMatches, id: &str) -> Vec<String> {
+    matches
+        .get_many::<String>(id)
+        .map(|vals| {
+            vals.map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Union of a flag's values and the file's list: flag values first, then file entries
+/// not already present. Neither source overrides the other.
+fn merge(flag: Vec<String>, file: &[String]) -> Vec<String> {
+    let mut merged = flag;
+    for name in file.iter().map(|name| name.trim()) {
+        if !name.is_empty() && !merged.iter().any(|existing| existing == name) {
+            merged.push(name.to_string());
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::commands;
+
+    #[test]
+    fn test_exclusions_parse_all_keys_optional() -> Result<()> {
+        let exclusions: Exclusions = toml::from_str(
+            r#"
+            databases = ["template0", "tenant_archive"]
+            tables = ["public.events"]
+            "#,
+        )?;
+
+        assert_eq!(exclusions.databases, vec!["template0", "tenant_archive"]);
+        assert!(exclusions.schemas.is_empty());
+        assert_eq!(exclusions.tables, vec!["public.events"]);
+        assert!(exclusions.users.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_exclusions_reject_unknown_keys() {
+        let result: Result<Exclusions, _> = toml::from_str(r#"database = ["typo"]"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_merge_keeps_flag_values_first_and_deduplicates() {
+        let merged = merge(
+            vec!["template0".to_string(), "template1".to_string()],
+            &[
+                "template1".to_string(),
+                " tenant_archive ".to_string(),
+                String::new(),
+            ],
+        );
+
+        assert_eq!(merged, vec!["template0", "template1", "tenant_archive"]);
+        assert_eq!(merge(Vec::new(), &["audit".to_string()]), vec!["audit"]);
+        assert_eq!(merge(vec!["audit".to_string()], &[]), vec!["audit"]);
+    }
+
+    #[test]
+    fn test_from_flags_and_file_merges_both_sources() -> Result<()> {
+        let path = std::env::temp_dir().join(format!(
+            "pg_exporter_exclusions_{}.toml",
+            std::process::id()
+        ));
+        fs::write(
+            &path,
+            "databases = [\"db2\", \"db3\"]\nschemas = [\"audit\"]\nusers = [\"replicator\"]\n",
+        )?;
+
+        let matches = commands::new().get_matches_from(vec![
+            "pg_exporter".into(),
+            "--exclude-databases".into(),
+            "db1,db2".into(),
+            "--exclude-tables".into(),
+            "public.events".into(),
+            "--exclusions-file".into(),
+            path.as_os_str().to_owned(),
+        ]);
+        let exclusions = Exclusions::from_flags_and_file(&matches);
+        fs::remove_file(&path)?;
+
+        let exclusions = exclusions?;
+        assert_eq!(exclusions.databases, vec!["db1", "db2", "db3"]);
+        assert_eq!(exclusions.schemas, vec!["audit"]);
+        assert_eq!(exclusions.tables, vec!["public.events"]);
+        assert_eq!(exclusions.users, vec!["replicator"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_matches_fails_on_missing_file() {
+        let matches = commands::new().get_matches_from(vec![
+            "pg_exporter",
+            "--exclusions-file",
+            "/nonexistent/pg_exporter/exclusions.toml",
+        ]);
+
+        assert!(Exclusions::from_matches(&matches).is_err());
+    }
+
+    #[test]
+    fn test_from_matches_without_flag_is_empty() -> Result<()> {
+        temp_env::with_var("PG_EXPORTER_EXCLUSIONS_FILE", None::<String>, || {
+            let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+            assert_eq!(Exclusions::from_matches(&matches)?, Exclusions::default());
+            Ok(())
+        })
+    }
+}
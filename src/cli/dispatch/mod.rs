@@ -1,21 +1,40 @@
 use crate::{
-    cli::actions::Action,
+    cli::{actions::Action, commands::DEFAULT_HEALTHCHECK_TIMEOUT_MS},
     collectors::{
         COLLECTOR_NAMES, Collector, all_factories,
-        config::CollectorConfig,
+        config::{CollectorConfig, SCRAPE_ONLY_COLLECTORS},
+        tls::certificate::CertificateSource,
         util::{
-            get_excluded_databases, set_excluded_databases, set_max_db_concurrency,
-            set_scrape_timeouts,
+            get_excluded_databases, get_excluded_schemas, get_excluded_tables, get_excluded_users,
+            set_anonymize_salt, set_circuit_breaker, set_counter_reset_detection,
+            set_excluded_databases, set_excluded_schemas, set_excluded_tables, set_excluded_users,
+            set_fail_fast_on_exhaustion, set_fail_scrape_on_db_down, set_log_scrape_summary,
+            set_max_db_concurrency, set_metric_filters, set_null_datname_label,
+            set_scrape_timeouts, set_search_path, set_sequential_collection,
+            set_timestamp_milliseconds,
         },
     },
+    exporter::{
+        DEFAULT_LISTEN_BACKLOG, ListenOptions, set_admin_token, set_allowed_user_agents,
+        set_effective_flags, set_listen_options, set_preflight, set_route_prefix,
+    },
 };
 use anyhow::{Result, anyhow};
 use clap::ArgMatches;
-use secrecy::SecretString;
+use regex::Regex;
+use secrecy::{ExposeSecret, SecretString};
 use std::fs;
-use std::num::{NonZeroU64, NonZeroUsize};
+use std::net::IpAddr;
+use std::num::{NonZeroU32, NonZeroU64, NonZeroUsize};
+use std::path::PathBuf;
+use std::time::Duration;
 use tracing::info;
 
+mod exclusions;
+mod invocation;
+
+use exclusions::Exclusions;
+
 /// Read DSN with priority: `PG_EXPORTER_DSN_FILE` > `PG_EXPORTER_DSN`/--dsn > default
 ///
 /// # Errors
@@ -40,8 +59,13 @@ fn get_dsn(matches: &ArgMatches) -> Result<String> {
 ///
 /// Returns an error if required arguments are missing or collector validation fails
 pub fn handler(matches: &clap::ArgMatches) -> Result<Action> {
-    // Initialize global excluded database list once from CLI/env
-    init_excluded_databases(matches);
+    // The healthcheck subcommand is a tiny HTTP client and needs none of the exporter state
+    if let Some(sub) = matches.subcommand_matches("healthcheck") {
+        return healthcheck_action(sub);
+    }
+
+    // Initialize global exclusion lists once from CLI/env merged with --exclusions-file
+    init_exclusions(matches)?;
 
     // Initialize the per-database collection concurrency limit once from CLI/env
     init_max_db_concurrency(matches);
@@ -49,7 +73,76 @@ pub fn handler(matches: &clap::ArgMatches) -> Result<Action> {
     // Initialize scrape timeout defaults once from CLI/env
     init_scrape_timeouts(matches);
 
+    // Initialize the scrape circuit breaker once from CLI/env
+    init_circuit_breaker(matches);
+
+    // Skip collectors instead of waiting when the shared pool is exhausted
+    set_fail_fast_on_exhaustion(matches.get_flag("db.fail-fast-on-exhaustion"));
+
+    // Answer 503 (still with pg_up 0) when PostgreSQL is unreachable
+    set_fail_scrape_on_db_down(matches.get_flag("fail-scrape-on-db-down"));
+
+    // Run collectors one at a time on low-resource hosts
+    set_sequential_collection(matches.get_flag("sequential-collection"));
+
+    // Refuse to start when an enabled collector cannot collect
+    set_preflight(matches.get_flag("preflight"));
+
+    // Log total scrape time and the slowest collectors after every scrape
+    set_log_scrape_summary(matches.get_flag("log-scrape-summary"));
+
+    // One datname sentinel for shared objects and database-less rows across collectors
+    if let Some(label) = matches.get_one::<String>("null-datname-label") {
+        set_null_datname_label(label.clone());
+    }
+
+    // Hash tenant database/schema/relation names in exported labels
+    if matches.get_flag("anonymize-labels")
+        && let Some(salt) = matches.get_one::<String>("anonymize-salt")
+    {
+        set_anonymize_salt(salt.clone());
+    }
+
+    // Hide metric families by name at encode time
+    set_metric_filters(
+        matches.get_one::<Regex>("metric-allowlist").cloned(),
+        matches.get_one::<Regex>("metric-denylist").cloned(),
+    );
+
+    // Non-standard epoch-millisecond timestamps for non-Prometheus pipelines
+    set_timestamp_milliseconds(
+        matches
+            .get_one::<String>("timestamp-unit")
+            .is_some_and(|unit| unit == "milliseconds"),
+    );
+
+    // Count server counters that go backwards (stats reset, restart) between scrapes
+    set_counter_reset_detection(matches.get_flag("counter-reset-detection"));
+
+    // Initialize the optional scrape-connection search_path once from CLI/env
+    set_search_path(matches.get_one::<String>("db.search-path").cloned());
+
+    // Mount every route under the optional --web.route-prefix
+    set_route_prefix(matches.get_one::<String>("web.route-prefix").cloned());
+
+    // Listener backlog and SO_REUSEPORT for the HTTP socket
+    set_listen_options(ListenOptions {
+        backlog: matches
+            .get_one::<NonZeroU32>("web.listen-backlog")
+            .map_or(DEFAULT_LISTEN_BACKLOG, |backlog| backlog.get()),
+        reuse_port: matches.get_flag("web.reuse-port"),
+    });
+
+    // Initialize the optional /metrics User-Agent allowlist once from CLI/env
+    set_allowed_user_agents(matches.get_one::<Regex>("web.allowed-user-agents").cloned());
+
+    // Admin endpoints are opt-in and always require a bearer token
+    init_admin_endpoints(matches)?;
+
     info!("Excluded databases: {:?}", get_excluded_databases());
+    info!("Excluded users: {:?}", get_excluded_users());
+    info!("Excluded schemas: {:?}", get_excluded_schemas());
+    info!("Excluded tables: {:?}", get_excluded_tables());
 
     // Get the port or return an error
     let port = matches
@@ -65,27 +158,90 @@ pub fn handler(matches: &clap::ArgMatches) -> Result<Action> {
     // Get the DSN (checks PG_EXPORTER_DSN_FILE first, then env/flag)
     let dsn = SecretString::from(get_dsn(matches)?);
 
+    // GET /debug/config (an admin endpoint) replays the invocation with secrets redacted
+    if matches.get_flag("enable-admin-endpoints") {
+        set_effective_flags(invocation::effective_flags(matches, dsn.expose_secret()));
+    }
+
     Ok(Action::Run {
         port,
         listen,
         dsn,
-        collector_config: get_collector_config(matches)?,
+        collector_config: Box::new(get_collector_config(matches)?),
     })
 }
 
-fn init_excluded_databases(matches: &ArgMatches) {
-    // Collect values from Clap (supports --exclude-databases a,b and env)
-    let excludes: Vec<String> = matches
-        .get_many::<String>("exclude-databases")
-        .map(|vals| {
-            vals.map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty())
-                .collect()
-        })
-        .unwrap_or_default();
+fn healthcheck_action(matches: &ArgMatches) -> Result<Action> {
+    let url = if let Some(url) = matches.get_one::<String>("healthcheck.url") {
+        url.clone()
+    } else {
+        let port = matches
+            .get_one::<u16>("port")
+            .copied()
+            .ok_or_else(|| anyhow!("Port is required. Please provide it using the --port flag."))?;
+        default_healthcheck_url(
+            matches.get_one::<String>("listen").map(String::as_str),
+            port,
+            matches
+                .get_one::<String>("web.route-prefix")
+                .map_or("", String::as_str),
+        )
+    };
+
+    let timeout_ms = matches
+        .get_one::<NonZeroU64>("healthcheck.timeout-ms")
+        .map_or(DEFAULT_HEALTHCHECK_TIMEOUT_MS, |value| value.get());
+
+    Ok(Action::Healthcheck {
+        url,
+        timeout: Duration::from_millis(timeout_ms),
+    })
+}
 
-    // Set once globally for all collectors
-    set_excluded_databases(excludes);
+/// Build the local /health URL from the exporter's listen address and route prefix.
+///
+/// Wildcard (or unset) listen addresses are reached through loopback; the default
+/// `[::]` bind accepts IPv4 too, so 127.0.0.1 works for it as well.
+fn default_healthcheck_url(listen: Option<&str>, port: u16, prefix: &str) -> String {
+    let host = match listen.map(str::trim).and_then(|l| l.parse::<IpAddr>().ok()) {
+        None => "127.0.0.1".to_string(),
+        Some(IpAddr::V4(ip)) if ip.is_unspecified() => "127.0.0.1".to_string(),
+        Some(IpAddr::V6(ip)) if ip.is_unspecified() => "127.0.0.1".to_string(),
+        Some(IpAddr::V4(ip)) => ip.to_string(),
+        Some(IpAddr::V6(ip)) => format!("[{ip}]"),
+    };
+
+    format!("http://{host}:{port}{prefix}/health")
+}
+
+fn init_exclusions(matches: &ArgMatches) -> Result<()> {
+    let exclusions = Exclusions::from_flags_and_file(matches)?;
+
+    // Set once globally for all collectors; names from either source are excluded
+    set_excluded_databases(exclusions.databases);
+    set_excluded_users(exclusions.users);
+    set_excluded_schemas(exclusions.schemas);
+    set_excluded_tables(exclusions.tables);
+
+    Ok(())
+}
+
+fn init_admin_endpoints(matches: &ArgMatches) -> Result<()> {
+    if !matches.get_flag("enable-admin-endpoints") {
+        return Ok(());
+    }
+
+    let token = matches
+        .get_one::<String>("web.admin-token")
+        .cloned()
+        .ok_or_else(|| {
+            anyhow!(
+                "--enable-admin-endpoints requires --web.admin-token (or PG_EXPORTER_WEB_ADMIN_TOKEN)"
+            )
+        })?;
+    set_admin_token(SecretString::from(token));
+
+    Ok(())
 }
 
 fn init_max_db_concurrency(matches: &ArgMatches) {
@@ -125,6 +281,21 @@ fn init_scrape_timeouts(matches: &ArgMatches) {
     );
 }
 
+fn init_circuit_breaker(matches: &ArgMatches) {
+    let threshold = matches
+        .get_one::<u64>("scrape.circuit-breaker-threshold")
+        .copied()
+        .unwrap_or(crate::collectors::DEFAULT_CIRCUIT_BREAKER_THRESHOLD);
+    let cooldown_ms = matches
+        .get_one::<NonZeroU64>("scrape.circuit-breaker-cooldown-ms")
+        .map_or(
+            crate::collectors::DEFAULT_CIRCUIT_BREAKER_COOLDOWN_MS,
+            |value| value.get(),
+        );
+
+    set_circuit_breaker(threshold, cooldown_ms);
+}
+
 #[must_use]
 pub fn get_enabled_collectors(matches: &ArgMatches) -> Vec<String> {
     let factories = all_factories();
@@ -159,6 +330,18 @@ pub fn get_enabled_collectors(matches: &ArgMatches) -> Vec<String> {
         .collect()
 }
 
+/// Certificate source of the tls collector: inline PEM, a PEM file on the exporter host, or
+/// the server's `ssl_cert_file` (clap rejects setting both PEM options).
+fn get_tls_certificate_source(matches: &ArgMatches) -> CertificateSource {
+    if let Some(pem) = matches.get_one::<String>("tls.cert-pem") {
+        CertificateSource::Pem(pem.clone())
+    } else if let Some(path) = matches.get_one::<PathBuf>("tls.cert-pem-file") {
+        CertificateSource::PemFile(path.clone())
+    } else {
+        CertificateSource::ServerFile
+    }
+}
+
 /// Build typed collector config from Clap-resolved arguments.
 ///
 /// # Errors
@@ -175,6 +358,13 @@ pub fn get_collector_config(matches: &ArgMatches) -> Result<CollectorConfig> {
         })?
         .get();
 
+    let statements_min_mean_time = matches
+        .get_one::<f64>("statements.min-mean-time-ms")
+        .copied()
+        .ok_or_else(|| {
+            anyhow!("internal CLI error: missing resolved value for --statements.min-mean-time-ms")
+        })?;
+
     let sequences_min_ratio = matches
         .get_one::<f64>("sequences.min-ratio")
         .copied()
@@ -182,9 +372,81 @@ pub fn get_collector_config(matches: &ArgMatches) -> Result<CollectorConfig> {
             anyhow!("internal CLI error: missing resolved value for --sequences.min-ratio")
         })?;
 
-    Ok(CollectorConfig::new(statements_top_n)
+    let query_age_buckets = matches
+        .get_one::<Vec<f64>>("activity.query-age-buckets")
+        .ok_or_else(|| {
+            anyhow!("internal CLI error: missing resolved value for --activity.query-age-buckets")
+        })?;
+
+    let stuck_threshold = matches
+        .get_one::<NonZeroU64>("activity.stuck-threshold-seconds")
+        .map(|secs| Duration::from_secs(secs.get()))
+        .ok_or_else(|| {
+            anyhow!(
+                "internal CLI error: missing resolved value for --activity.stuck-threshold-seconds"
+            )
+        })?;
+
+    let slow_query_threshold = matches
+        .get_one::<NonZeroU64>("activity.slow-query-threshold-seconds")
+        .map(|secs| Duration::from_secs(secs.get()))
+        .ok_or_else(|| {
+            anyhow!(
+                "internal CLI error: missing resolved value for --activity.slow-query-threshold-seconds"
+            )
+        })?;
+
+    let by_application_limit = matches
+        .get_one::<NonZeroUsize>("activity.by-application-limit")
+        .map(|limit| limit.get())
+        .ok_or_else(|| {
+            anyhow!(
+                "internal CLI error: missing resolved value for --activity.by-application-limit"
+            )
+        })?;
+
+    let mut config = CollectorConfig::new(statements_top_n)
+        .with_statements_normalize(matches.get_flag("statements.normalize"))
+        .with_statements_min_mean_time(statements_min_mean_time)
         .with_sequences_min_ratio(sequences_min_ratio)
-        .with_enabled(&enabled))
+        .with_stat_seconds_ago(matches.get_flag("stat.seconds-ago"))
+        .with_stat_include_system(matches.get_flag("stat.include-system"))
+        .with_activity_query_age_buckets(query_age_buckets)
+        .with_activity_stuck_threshold(stuck_threshold)
+        .with_activity_slow_query_threshold(slow_query_threshold)
+        .with_activity_by_application(
+            matches
+                .get_flag("activity.by-application")
+                .then_some(by_application_limit),
+        )
+        .with_fdw_probe(matches.get_flag("fdw.probe"))
+        .with_tls_certificate(get_tls_certificate_source(matches))
+        .with_enabled(&enabled);
+
+    // Per-collector background intervals only apply to collectors that are enabled;
+    // --collection-interval is the fallback for the ones without their own. The
+    // exporter's self-metrics describe the scrape itself and always run on it.
+    let collection_interval = matches.get_one::<Duration>("collection-interval").copied();
+    for name in enabled
+        .iter()
+        .filter(|name| !SCRAPE_ONLY_COLLECTORS.contains(&name.as_str()))
+    {
+        if let Some(interval) = matches
+            .get_one::<Duration>(&format!("collector.{name}.interval"))
+            .copied()
+            .or(collection_interval)
+        {
+            config = config.with_interval(name, interval);
+        }
+    }
+    if let Some(jitter) = matches.get_one::<Duration>("collection-jitter").copied() {
+        config = config.with_collection_jitter(jitter);
+    }
+    if let Some(serial) = matches.get_many::<String>("collectors.serial") {
+        config = config.with_serial_collectors(&serial.cloned().collect::<Vec<_>>());
+    }
+
+    Ok(config)
 }
 
 #[cfg(test)]
@@ -192,6 +454,34 @@ mod tests {
     use super::*;
     use crate::cli::commands;
 
+    #[test]
+    fn test_default_healthcheck_url() {
+        assert_eq!(
+            default_healthcheck_url(None, 9432, ""),
+            "http://127.0.0.1:9432/health"
+        );
+        assert_eq!(
+            default_healthcheck_url(Some("0.0.0.0"), 9432, ""),
+            "http://127.0.0.1:9432/health"
+        );
+        assert_eq!(
+            default_healthcheck_url(Some("::"), 9187, ""),
+            "http://127.0.0.1:9187/health"
+        );
+        assert_eq!(
+            default_healthcheck_url(Some("192.168.1.10"), 9432, ""),
+            "http://192.168.1.10:9432/health"
+        );
+        assert_eq!(
+            default_healthcheck_url(Some("::1"), 9432, ""),
+            "http://[::1]:9432/health"
+        );
+        assert_eq!(
+            default_healthcheck_url(None, 9432, "/pg01"),
+            "http://127.0.0.1:9432/pg01/health"
+        );
+    }
+
     #[test]
     fn test_get_enabled_collectors_defaults() {
         let command = commands::new();
@@ -271,6 +561,272 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_get_collector_config_with_statements_normalize() -> Result<()> {
+        temp_env::with_var("PG_EXPORTER_STATEMENTS_NORMALIZE", None::<String>, || {
+            let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+            assert!(!get_collector_config(&matches)?.statements.normalize);
+
+            let matches =
+                commands::new().get_matches_from(vec!["pg_exporter", "--statements.normalize"]);
+            assert!(get_collector_config(&matches)?.statements.normalize);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_get_collector_config_with_statements_min_mean_time() -> Result<()> {
+        temp_env::with_var(
+            "PG_EXPORTER_STATEMENTS_MIN_MEAN_TIME_MS",
+            None::<String>,
+            || {
+                let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+                let config = get_collector_config(&matches)?;
+                assert!(config.statements.min_mean_time_ms.abs() < f64::EPSILON);
+
+                let matches = commands::new().get_matches_from(vec![
+                    "pg_exporter",
+                    "--statements.min-mean-time-ms",
+                    "2.5",
+                ]);
+                let config = get_collector_config(&matches)?;
+                assert!((config.statements.min_mean_time_ms - 2.5).abs() < f64::EPSILON);
+
+                assert!(
+                    commands::new()
+                        .try_get_matches_from(vec![
+                            "pg_exporter",
+                            "--statements.min-mean-time-ms",
+                            "-1",
+                        ])
+                        .is_err()
+                );
+                Ok(())
+            },
+        )
+    }
+
+    #[test]
+    fn test_get_collector_config_with_stat_seconds_ago() -> Result<()> {
+        temp_env::with_var("PG_EXPORTER_STAT_SECONDS_AGO", None::<String>, || {
+            let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+            assert!(!get_collector_config(&matches)?.stat.seconds_ago);
+
+            let matches =
+                commands::new().get_matches_from(vec!["pg_exporter", "--stat.seconds-ago"]);
+            assert!(get_collector_config(&matches)?.stat.seconds_ago);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_get_collector_config_with_stat_include_system() -> Result<()> {
+        temp_env::with_var("PG_EXPORTER_STAT_INCLUDE_SYSTEM", None::<String>, || {
+            let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+            assert!(!get_collector_config(&matches)?.stat.include_system);
+
+            let matches =
+                commands::new().get_matches_from(vec!["pg_exporter", "--stat.include-system"]);
+            assert!(get_collector_config(&matches)?.stat.include_system);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_get_collector_config_with_fdw_probe() -> Result<()> {
+        let matches =
+            commands::new().get_matches_from(vec!["pg_exporter", "--collector.fdw", "--fdw.probe"]);
+        let config = get_collector_config(&matches)?;
+        assert!(config.is_enabled("fdw"));
+        assert!(config.fdw.probe);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_collector_config_with_activity_query_age_buckets() -> Result<()> {
+        let matches = commands::new().get_matches_from(vec![
+            "pg_exporter",
+            "--activity.query-age-buckets",
+            "5,1",
+        ]);
+        assert_eq!(
+            get_collector_config(&matches)?.activity.query_age_buckets,
+            vec![1.0, 5.0]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_collector_config_with_activity_stuck_threshold() -> Result<()> {
+        let matches = commands::new().get_matches_from(vec![
+            "pg_exporter",
+            "--activity.stuck-threshold-seconds",
+            "90",
+        ]);
+        assert_eq!(
+            get_collector_config(&matches)?.activity.stuck_threshold,
+            Duration::from_secs(90)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_collector_config_with_activity_slow_query_threshold() -> Result<()> {
+        let matches = commands::new().get_matches_from(vec![
+            "pg_exporter",
+            "--activity.slow-query-threshold-seconds",
+            "15",
+        ]);
+        assert_eq!(
+            get_collector_config(&matches)?
+                .activity
+                .slow_query_threshold,
+            Duration::from_secs(15)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_collector_config_with_activity_by_application() -> Result<()> {
+        temp_env::with_vars(
+            [
+                ("PG_EXPORTER_ACTIVITY_BY_APPLICATION", None::<&str>),
+                ("PG_EXPORTER_ACTIVITY_BY_APPLICATION_LIMIT", None),
+            ],
+            || {
+                let matches = commands::new().get_matches_from(vec![
+                    "pg_exporter",
+                    "--activity.by-application-limit",
+                    "10",
+                ]);
+                assert_eq!(
+                    get_collector_config(&matches)?.activity.by_application,
+                    None
+                );
+
+                let matches = commands::new().get_matches_from(vec![
+                    "pg_exporter",
+                    "--activity.by-application",
+                    "--activity.by-application-limit",
+                    "10",
+                ]);
+                assert_eq!(
+                    get_collector_config(&matches)?.activity.by_application,
+                    Some(10)
+                );
+                Ok(())
+            },
+        )
+    }
+
+    #[test]
+    fn test_get_collector_config_with_tls_cert_pem_file() -> Result<()> {
+        let matches = commands::new().get_matches_from(vec![
+            "pg_exporter",
+            "--tls.cert-pem-file",
+            "/run/secrets/pg_server_crt",
+        ]);
+        assert_eq!(
+            get_collector_config(&matches)?.tls.certificate,
+            CertificateSource::PemFile(PathBuf::from("/run/secrets/pg_server_crt"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_collector_config_with_intervals() -> Result<()> {
+        let matches = commands::new().get_matches_from(vec![
+            "pg_exporter",
+            "--collector.statements",
+            "--collector.statements.interval",
+            "5m",
+            "--collector.locks.interval",
+            "30s",
+        ]);
+        let config = get_collector_config(&matches)?;
+
+        assert_eq!(config.interval("statements"), Some(Duration::from_mins(5)));
+        // Intervals for disabled collectors are ignored.
+        assert_eq!(config.interval("locks"), None);
+        assert_eq!(config.interval("default"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_collector_config_with_collection_interval() -> Result<()> {
+        let matches = commands::new().get_matches_from(vec![
+            "pg_exporter",
+            "--collector.statements",
+            "--collector.exporter",
+            "--collector.statements.interval",
+            "5m",
+            "--collection-interval",
+            "30s",
+            "--collection-jitter",
+            "5s",
+        ]);
+        let config = get_collector_config(&matches)?;
+
+        // The per-collector interval wins over --collection-interval.
+        assert_eq!(config.interval("statements"), Some(Duration::from_mins(5)));
+        assert_eq!(config.interval("default"), Some(Duration::from_secs(30)));
+        assert_eq!(config.interval("locks"), None);
+        assert_eq!(config.collection_jitter, Duration::from_secs(5));
+        // The exporter's self-metrics always run on the scrape.
+        assert!(config.is_enabled("exporter"));
+        assert_eq!(config.interval("exporter"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_exporter_collector_has_no_interval_flag() {
+        let result = commands::new().try_get_matches_from(vec![
+            "pg_exporter",
+            "--collector.exporter.interval",
+            "30s",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_get_collector_config_with_serial_collectors() -> Result<()> {
+        temp_env::with_var("PG_EXPORTER_COLLECTORS_SERIAL", None::<&str>, || {
+            let matches = commands::new().get_matches_from(vec!["pg_exporter"]);
+            assert!(get_collector_config(&matches)?.serial_collectors.is_empty());
+
+            let matches = commands::new().get_matches_from(vec![
+                "pg_exporter",
+                "--collectors.serial",
+                "stat,index",
+            ]);
+            let serial = get_collector_config(&matches)?.serial_collectors;
+            assert_eq!(serial.len(), 2);
+            assert!(serial.contains("stat") && serial.contains("index"));
+
+            assert!(
+                commands::new()
+                    .try_get_matches_from(vec!["pg_exporter", "--collectors.serial", "nope"])
+                    .is_err(),
+                "unknown collector names are rejected"
+            );
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_admin_endpoints_require_token() {
+        temp_env::with_var("PG_EXPORTER_WEB_ADMIN_TOKEN", None::<String>, || {
+            let command = commands::new();
+            let matches = command.get_matches_from(vec!["pg_exporter", "--enable-admin-endpoints"]);
+            let result = init_admin_endpoints(&matches);
+            assert!(
+                result
+                    .err()
+                    .is_some_and(|e| e.to_string().contains("--web.admin-token"))
+            );
+        });
+    }
+
     #[test]
     #[allow(clippy::unwrap_used)]
     fn test_get_dsn_from_env() {
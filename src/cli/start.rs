@@ -1,5 +1,11 @@
-use crate::cli::{actions::Action, commands, dispatch::handler, telemetry};
+use crate::cli::{
+    actions::Action,
+    commands,
+    dispatch::handler,
+    telemetry::{self, OtlpProtocol, TelemetryConfig},
+};
 use anyhow::Result;
+use clap::ArgMatches;
 
 /// Map verbosity count to tracing level
 const fn get_verbosity_level(verbose_count: u8) -> Option<tracing::Level> {
@@ -11,6 +17,16 @@ const fn get_verbosity_level(verbose_count: u8) -> Option<tracing::Level> {
     }
 }
 
+/// Collect the telemetry flags; unset OTLP options fall back to `OTEL_*` env vars later
+fn get_telemetry_config(matches: &ArgMatches) -> TelemetryConfig {
+    TelemetryConfig {
+        disabled: matches.get_flag("no-telemetry"),
+        otlp_endpoint: matches.get_one::<String>("otlp-endpoint").cloned(),
+        otlp_protocol: matches.get_one::<OtlpProtocol>("otlp-protocol").copied(),
+        otlp_headers: matches.get_one::<String>("otlp-headers").cloned(),
+    }
+}
+
 /// Start the CLI
 ///
 /// # Errors
@@ -21,7 +37,7 @@ pub fn start() -> Result<Action> {
 
     let verbosity_level = get_verbosity_level(matches.get_count("verbose"));
 
-    telemetry::init(verbosity_level)?;
+    telemetry::init(verbosity_level, &get_telemetry_config(&matches))?;
 
     let action = handler(&matches)?;
 
@@ -32,6 +48,26 @@ pub fn start() -> Result<Action> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_get_telemetry_config() {
+        let matches = commands::new().get_matches_from(vec![
+            "pg_exporter",
+            "--no-telemetry",
+            "--otlp-endpoint",
+            "http://localhost:4318",
+            "--otlp-protocol",
+            "http",
+        ]);
+        let config = get_telemetry_config(&matches);
+
+        assert!(config.disabled);
+        assert_eq!(
+            config.otlp_endpoint.as_deref(),
+            Some("http://localhost:4318")
+        );
+        assert_eq!(config.otlp_protocol, Some(OtlpProtocol::Http));
+    }
+
     #[test]
     fn test_get_verbosity_level_none() {
         assert_eq!(get_verbosity_level(0), None);
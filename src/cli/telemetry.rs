@@ -1,15 +1,23 @@
+use crate::cli::commands::built_info;
 use anyhow::{Result, anyhow};
 use base64::{Engine, engine::general_purpose};
 use once_cell::sync::OnceCell;
 use opentelemetry::propagation::TextMapCompositePropagator;
 use opentelemetry::{KeyValue, global, trace::TracerProvider as _};
-use opentelemetry_otlp::{Compression, WithExportConfig, WithTonicConfig};
+use opentelemetry_otlp::{
+    Compression, Protocol, WithExportConfig, WithHttpConfig, WithTonicConfig,
+};
 use opentelemetry_sdk::{
     Resource,
     propagation::{BaggagePropagator, TraceContextPropagator},
     trace::{SdkTracerProvider, Tracer},
 };
-use std::{collections::HashMap, env::var, time::Duration};
+use std::{
+    collections::HashMap,
+    env::var,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
 use tonic::{
     metadata::{Ascii, Binary, MetadataKey, MetadataMap, MetadataValue},
     transport::ClientTlsConfig,
@@ -17,9 +25,54 @@ use tonic::{
 use tracing::{Level, debug};
 use tracing_subscriber::{EnvFilter, Registry, fmt, layer::SubscriberExt};
 use ulid::Ulid;
+use url::Url;
 
 static TRACER_PROVIDER: OnceCell<SdkTracerProvider> = OnceCell::new();
 
+/// Set by `--no-telemetry`; makes `shutdown_tracer` a no-op.
+static TELEMETRY_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Path the OTLP/HTTP exporter posts spans to, appended to the base endpoint.
+const OTLP_HTTP_TRACES_PATH: &str = "/v1/traces";
+
+/// Transport used to export spans
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OtlpProtocol {
+    /// OTLP over gRPC (tonic), usually port 4317
+    #[default]
+    Grpc,
+    /// OTLP over HTTP with protobuf payloads, usually port 4318
+    Http,
+}
+
+impl OtlpProtocol {
+    /// Parse a protocol name; accepts the `OTEL_EXPORTER_OTLP_PROTOCOL` spelling `http/protobuf`.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "grpc" => Some(Self::Grpc),
+            "http" | "http/protobuf" => Some(Self::Http),
+            _ => None,
+        }
+    }
+}
+
+/// Telemetry settings from the command line
+///
+/// Every `otlp_*` field left as `None` falls back to the matching `OTEL_EXPORTER_OTLP_*`
+/// environment variable.
+#[derive(Clone, Debug, Default)]
+pub struct TelemetryConfig {
+    /// `--no-telemetry`: never create a tracer, even if an endpoint is configured
+    pub disabled: bool,
+    /// `--otlp-endpoint`
+    pub otlp_endpoint: Option<String>,
+    /// `--otlp-protocol`
+    pub otlp_protocol: Option<OtlpProtocol>,
+    /// `--otlp-headers`, comma-separated `key=value` pairs
+    pub otlp_headers: Option<String>,
+}
+
 fn parse_headers_env(headers_str: &str) -> HashMap<String, String> {
     headers_str
         .split(',')
@@ -74,31 +127,59 @@ fn normalize_endpoint(ep: String) -> String {
     }
 }
 
-fn init_tracer() -> Result<Tracer> {
-    // We only support gRPC now. If the user set a different protocol, log and ignore.
-    if let Ok(proto) = var("OTEL_EXPORTER_OTLP_PROTOCOL")
-        && proto != "grpc"
-    {
-        debug!(
-            "OTEL_EXPORTER_OTLP_PROTOCOL='{}' ignored: only 'grpc' is supported now",
-            proto
-        );
+/// Normalize and validate an OTLP endpoint, returning the normalized URL.
+///
+/// # Errors
+///
+/// Returns an error if the endpoint is not an absolute http(s) URL with a host
+pub fn validate_otlp_endpoint(endpoint: &str) -> Result<String> {
+    let endpoint = normalize_endpoint(endpoint.trim().to_string());
+    let url =
+        Url::parse(&endpoint).map_err(|e| anyhow!("invalid OTLP endpoint '{endpoint}': {e}"))?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(anyhow!(
+            "invalid OTLP endpoint '{endpoint}': scheme must be http or https"
+        ));
+    }
+
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err(anyhow!("invalid OTLP endpoint '{endpoint}': missing host"));
     }
 
-    // gRPC sensible default
-    let default_ep = "http://localhost:4317";
-    let endpoint = var("OTEL_EXPORTER_OTLP_ENDPOINT").unwrap_or_else(|_| default_ep.to_string());
-    let endpoint = normalize_endpoint(endpoint);
+    Ok(endpoint)
+}
+
+// OTLP/HTTP uses the endpoint as-is, so append the traces path to a base URL.
+fn http_traces_endpoint(endpoint: &str) -> String {
+    let base = endpoint.trim_end_matches('/');
+    if base.ends_with(OTLP_HTTP_TRACES_PATH) {
+        base.to_string()
+    } else {
+        format!("{base}{OTLP_HTTP_TRACES_PATH}")
+    }
+}
 
-    let headers = var("OTEL_EXPORTER_OTLP_HEADERS")
-        .ok()
-        .map(|s| parse_headers_env(&s))
-        .unwrap_or_default();
+fn protocol_from_env() -> OtlpProtocol {
+    match var("OTEL_EXPORTER_OTLP_PROTOCOL") {
+        Ok(proto) => OtlpProtocol::parse(&proto).unwrap_or_else(|| {
+            debug!(
+                "OTEL_EXPORTER_OTLP_PROTOCOL='{}' ignored: only 'grpc' and 'http/protobuf' are supported",
+                proto
+            );
+            OtlpProtocol::Grpc
+        }),
+        Err(_) => OtlpProtocol::Grpc,
+    }
+}
 
-    // Build gRPC exporter
+fn build_grpc_exporter(
+    endpoint: &str,
+    headers: &HashMap<String, String>,
+) -> Result<opentelemetry_otlp::SpanExporter> {
     let mut builder = opentelemetry_otlp::SpanExporter::builder()
         .with_tonic()
-        .with_endpoint(&endpoint)
+        .with_endpoint(endpoint)
         .with_compression(Compression::Gzip)
         .with_timeout(Duration::from_secs(3));
 
@@ -115,11 +196,38 @@ fn init_tracer() -> Result<Tracer> {
     }
 
     if !headers.is_empty() {
-        let metadata = headers_to_metadata(&headers)?;
+        let metadata = headers_to_metadata(headers)?;
         builder = builder.with_metadata(metadata);
     }
 
-    let exporter = builder.build()?;
+    Ok(builder.build()?)
+}
+
+fn build_http_exporter(
+    endpoint: &str,
+    headers: HashMap<String, String>,
+) -> Result<opentelemetry_otlp::SpanExporter> {
+    let mut builder = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(http_traces_endpoint(endpoint))
+        .with_protocol(Protocol::HttpBinary)
+        .with_compression(Compression::Gzip)
+        .with_timeout(Duration::from_secs(3));
+
+    if !headers.is_empty() {
+        builder = builder.with_headers(headers);
+    }
+
+    Ok(builder.build()?)
+}
+
+fn init_tracer(endpoint: &str, protocol: OtlpProtocol, headers: &str) -> Result<Tracer> {
+    let headers = parse_headers_env(headers);
+
+    let exporter = match protocol {
+        OtlpProtocol::Grpc => build_grpc_exporter(endpoint, &headers)?,
+        OtlpProtocol::Http => build_http_exporter(endpoint, headers)?,
+    };
 
     // Generate or take service.instance.id
     let instance_id = var("OTEL_SERVICE_INSTANCE_ID").unwrap_or_else(|_| Ulid::r#gen().to_string());
@@ -129,8 +237,8 @@ fn init_tracer() -> Result<Tracer> {
         .with_resource(
             Resource::builder_empty()
                 .with_attributes(vec![
-                    KeyValue::new("service.name", env!("CARGO_PKG_NAME")),
-                    KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+                    KeyValue::new("service.name", built_info::PKG_NAME),
+                    KeyValue::new("service.version", built_info::PKG_VERSION),
                     KeyValue::new("service.instance.id", instance_id),
                 ])
                 .build(),
@@ -148,16 +256,25 @@ fn init_tracer() -> Result<Tracer> {
         Box::new(BaggagePropagator::new()),
     ]));
 
-    Ok(trace_provider.tracer(env!("CARGO_PKG_NAME")))
+    Ok(trace_provider.tracer(built_info::PKG_NAME))
 }
 
 /// Initialize logging + (optional) tracing exporter
-/// Tracing is enabled if `OTEL_EXPORTER_OTLP_ENDPOINT` is set (gRPC only).
+/// Tracing is enabled if an OTLP endpoint is configured (`--otlp-endpoint` or
+/// `OTEL_EXPORTER_OTLP_ENDPOINT`) and telemetry is not disabled. With `--no-telemetry` only
+/// the plain log layer is installed.
 ///
 /// # Errors
 ///
-/// Returns an error if tracer or subscriber initialization fails
-pub fn init(verbosity_level: Option<Level>) -> Result<()> {
+/// Returns an error if the endpoint is invalid or tracer/subscriber initialization fails
+pub fn init(verbosity_level: Option<Level>, config: &TelemetryConfig) -> Result<()> {
+    TELEMETRY_DISABLED.store(config.disabled, Ordering::Relaxed);
+
+    let endpoint = config
+        .otlp_endpoint
+        .clone()
+        .or_else(|| var("OTEL_EXPORTER_OTLP_ENDPOINT").ok());
+
     let verbosity_level = verbosity_level.unwrap_or(Level::ERROR);
 
     let fmt_layer = fmt::layer()
@@ -175,8 +292,16 @@ pub fn init(verbosity_level: Option<Level>) -> Result<()> {
         .add_directive("tokio=error".parse()?)
         .add_directive("opentelemetry_sdk=warn".parse()?);
 
-    if var("OTEL_EXPORTER_OTLP_ENDPOINT").is_ok() {
-        let tracer = init_tracer()?;
+    if let Some(endpoint) = endpoint.as_deref().filter(|_| !config.disabled) {
+        let endpoint = validate_otlp_endpoint(endpoint)?;
+        let protocol = config.otlp_protocol.unwrap_or_else(protocol_from_env);
+        let headers = config
+            .otlp_headers
+            .clone()
+            .or_else(|| var("OTEL_EXPORTER_OTLP_HEADERS").ok())
+            .unwrap_or_default();
+
+        let tracer = init_tracer(&endpoint, protocol, &headers)?;
         let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
 
         let subscriber = Registry::default()
@@ -187,13 +312,21 @@ pub fn init(verbosity_level: Option<Level>) -> Result<()> {
     } else {
         let subscriber = Registry::default().with(fmt_layer).with(filter);
         tracing::subscriber::set_global_default(subscriber)?;
+
+        if config.disabled && endpoint.is_some() {
+            debug!("OTLP endpoint ignored: telemetry disabled by --no-telemetry");
+        }
     }
 
     Ok(())
 }
 
-/// Gracefully shut down tracer provider (noop if not initialized)
+/// Gracefully shut down tracer provider (noop if not initialized or disabled by `--no-telemetry`)
 pub fn shutdown_tracer() {
+    if TELEMETRY_DISABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
     if let Some(tp) = TRACER_PROVIDER.get() {
         debug!("shutting down tracer provider");
         let _ = tp.shutdown();
@@ -344,9 +477,60 @@ mod tests {
         assert_eq!(result, "https://api.example.com:4317/v1/traces");
     }
 
+    #[test]
+    fn test_otlp_protocol_parse() {
+        assert_eq!(OtlpProtocol::parse("grpc"), Some(OtlpProtocol::Grpc));
+        assert_eq!(OtlpProtocol::parse("http"), Some(OtlpProtocol::Http));
+        assert_eq!(
+            OtlpProtocol::parse("http/protobuf"),
+            Some(OtlpProtocol::Http)
+        );
+        assert_eq!(OtlpProtocol::parse("http/json"), None);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_validate_otlp_endpoint() {
+        assert_eq!(
+            validate_otlp_endpoint("http://localhost:4317").unwrap(),
+            "http://localhost:4317"
+        );
+        assert_eq!(
+            validate_otlp_endpoint("collector.example.com:4317").unwrap(),
+            "https://collector.example.com:4317"
+        );
+        assert!(validate_otlp_endpoint("http://").is_err());
+        assert!(validate_otlp_endpoint("http://exa mple.com").is_err());
+        assert!(validate_otlp_endpoint("").is_err());
+    }
+
+    #[test]
+    fn test_http_traces_endpoint() {
+        assert_eq!(
+            http_traces_endpoint("http://localhost:4318"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            http_traces_endpoint("http://localhost:4318/"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            http_traces_endpoint("https://otel.example.com/v1/traces"),
+            "https://otel.example.com/v1/traces"
+        );
+    }
+
     #[test]
     fn test_shutdown_tracer_no_provider() {
         // Should not panic when no provider is initialized
         shutdown_tracer();
     }
+
+    #[test]
+    fn test_shutdown_tracer_disabled() {
+        // Should return early without touching the provider
+        TELEMETRY_DISABLED.store(true, Ordering::Relaxed);
+        shutdown_tracer();
+        TELEMETRY_DISABLED.store(false, Ordering::Relaxed);
+    }
 }
@@ -0,0 +1,237 @@
+use crate::collectors::{
+    Collector,
+    util::{acquire_connection, get_excluded_databases, get_excluded_users},
+};
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{IntGaugeVec, Opts, Registry};
+use sqlx::{PgPool, Row};
+use std::collections::HashMap;
+use tracing::{debug, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// Default `--activity.by-application-limit`: distinct `application_name` values exported
+/// before the rest are folded into [`OTHER_APPLICATIONS`].
+pub const DEFAULT_APPLICATION_LIMIT: usize = 50;
+
+/// `application_name` label of backends that did not set one.
+const UNSET_APPLICATION: &str = "[unknown]";
+
+/// `application_name` label collecting every application beyond the limit.
+pub const OTHER_APPLICATIONS: &str = "[other]";
+
+/// Attributes client backends to the service that opened them:
+/// - `pg_stat_activity_backends_by_application{application_name,state}` (`IntGauge`)
+///
+/// Counted cluster-wide, so it answers "who owns the connections" during a saturation
+/// event where per-database counts cannot. Opt-in (`--activity.by-application`) because
+/// its cardinality is driven by clients: only the `limit` applications with the most
+/// backends keep their name, the others are summed under `application_name="[other]"`.
+#[derive(Clone)]
+pub struct ApplicationBackendsCollector {
+    backends: IntGaugeVec, // pg_stat_activity_backends_by_application{application_name,state}
+    limit: usize,
+}
+
+impl Default for ApplicationBackendsCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ApplicationBackendsCollector {
+    /// Creates a new `ApplicationBackendsCollector` with the default application limit
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_limit(DEFAULT_APPLICATION_LIMIT)
+    }
+
+    /// Creates a new `ApplicationBackendsCollector` exporting at most `limit` distinct
+    /// application names
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn with_limit(limit: usize) -> Self {
+        let backends = IntGaugeVec::new(
+            Opts::new(
+                "pg_stat_activity_backends_by_application",
+                "Number of client backends per application_name and state, cluster-wide",
+            ),
+            &["application_name", "state"],
+        )
+        .expect("Failed to create pg_stat_activity_backends_by_application metric");
+
+        Self { backends, limit }
+    }
+}
+
+/// Keep the `limit` applications with the most backends (ties by name) and sum the
+/// remaining ones per state under [`OTHER_APPLICATIONS`].
+fn cap_applications(
+    rows: Vec<(String, String, i64)>,
+    limit: usize,
+) -> HashMap<(String, String), i64> {
+    let mut totals: HashMap<&str, i64> = HashMap::new();
+    for (application, _, count) in &rows {
+        *totals.entry(application.as_str()).or_insert(0) += count;
+    }
+
+    let mut ranked: Vec<(&str, i64)> = totals.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    let kept: Vec<String> = ranked
+        .into_iter()
+        .take(limit)
+        .map(|(application, _)| application.to_string())
+        .collect();
+
+    let mut capped: HashMap<(String, String), i64> = HashMap::new();
+    for (application, state, count) in rows {
+        let application = if kept.contains(&application) {
+            application
+        } else {
+            OTHER_APPLICATIONS.to_string()
+        };
+        *capped.entry((application, state)).or_insert(0) += count;
+    }
+    capped
+}
+
+impl Collector for ApplicationBackendsCollector {
+    fn name(&self) -> &'static str {
+        "backends_by_application"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "backends_by_application")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.backends.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector="backends_by_application", otel.kind="internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let excluded: Vec<String> = get_excluded_databases().to_vec();
+            let excluded_users: Vec<String> = get_excluded_users().to_vec();
+
+            let query_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement =
+                    "SELECT application_name, state, COUNT(*) FROM pg_stat_activity (filtered)",
+                db.sql.table = "pg_stat_activity"
+            );
+
+            let rows = sqlx::query(
+                r"
+                SELECT
+                    COALESCE(NULLIF(application_name, ''), $3) AS application_name,
+                    COALESCE(state, 'unknown') AS state,
+                    COUNT(*)::bigint AS cnt
+                FROM pg_stat_activity
+                WHERE backend_type = 'client backend'
+                  AND pid != pg_backend_pid()
+                  AND NOT (COALESCE(datname, '') = ANY($1))
+                  AND COALESCE(usename, '') <> ALL($2)
+                GROUP BY 1, 2
+                ",
+            )
+            .bind(&excluded)
+            .bind(&excluded_users)
+            .bind(UNSET_APPLICATION)
+            .fetch_all(&mut *acquire_connection(pool).await?)
+            .instrument(query_span)
+            .await?;
+
+            let mut samples = Vec::with_capacity(rows.len());
+            for row in &rows {
+                samples.push((
+                    row.try_get::<String, _>("application_name")?,
+                    row.try_get::<String, _>("state")?,
+                    row.try_get::<i64, _>("cnt").unwrap_or(0),
+                ));
+            }
+
+            self.backends.reset();
+
+            let capped = cap_applications(samples, self.limit);
+            for ((application, state), count) in &capped {
+                self.backends
+                    .with_label_values(&[application, state])
+                    .set(*count);
+            }
+
+            debug!(
+                series = capped.len(),
+                limit = self.limit,
+                "updated backends by application metrics"
+            );
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(application: &str, state: &str, count: i64) -> (String, String, i64) {
+        (application.to_string(), state.to_string(), count)
+    }
+
+    fn get(capped: &HashMap<(String, String), i64>, application: &str, state: &str) -> Option<i64> {
+        capped
+            .get(&(application.to_string(), state.to_string()))
+            .copied()
+    }
+
+    #[test]
+    fn test_cap_applications_keeps_busiest_and_folds_the_rest() {
+        let rows = vec![
+            row("api", "active", 3),
+            row("api", "idle", 7),
+            row("worker", "idle", 4),
+            row("cron", "active", 1),
+            row("psql", "idle", 1),
+        ];
+
+        let capped = cap_applications(rows, 2);
+
+        assert_eq!(get(&capped, "api", "idle"), Some(7));
+        assert_eq!(get(&capped, "api", "active"), Some(3));
+        assert_eq!(get(&capped, "worker", "idle"), Some(4));
+        assert_eq!(get(&capped, OTHER_APPLICATIONS, "active"), Some(1));
+        assert_eq!(get(&capped, OTHER_APPLICATIONS, "idle"), Some(1));
+        assert_eq!(capped.values().sum::<i64>(), 16, "no backend is dropped");
+    }
+
+    #[test]
+    fn test_cap_applications_without_overflow() {
+        let capped = cap_applications(vec![row("api", "active", 2)], DEFAULT_APPLICATION_LIMIT);
+        assert_eq!(capped.len(), 1);
+        assert_eq!(get(&capped, OTHER_APPLICATIONS, "active"), None);
+        assert!(cap_applications(Vec::new(), 1).is_empty());
+    }
+
+    #[test]
+    fn test_cap_applications_breaks_ties_by_name() {
+        let capped = cap_applications(vec![row("b", "idle", 1), row("a", "idle", 1)], 1);
+        assert_eq!(get(&capped, "a", "idle"), Some(1));
+        assert_eq!(get(&capped, OTHER_APPLICATIONS, "idle"), Some(1));
+    }
+}
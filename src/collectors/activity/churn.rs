@@ -0,0 +1,252 @@
+use crate::collectors::{
+    Collector,
+    util::{
+        acquire_connection, get_excluded_databases, get_excluded_users, resolve_server_version,
+    },
+};
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{IntCounterVec, Opts, Registry};
+use sqlx::{PgPool, Row};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// Tracks how many connections clients open on servers older than `PostgreSQL` 14, to
+/// surface connection churn:
+/// - `pg_stat_activity_connections_opened_total{datname}` (counter): sessions established
+///
+/// `rate(pg_stat_activity_connections_opened_total[5m])` is the connection rate per
+/// second. An application opening a connection per request shows up here long before it
+/// shows up in the aggregate connection counts, which stay flat while backends are
+/// constantly forked and torn down.
+///
+/// `PostgreSQL` 14+ counts sessions exactly in `pg_stat_database.sessions`, which the
+/// `database` collector already exports as `pg_stat_database_sessions_total`, so this
+/// collector exports nothing there. Older servers have no session counter, so the collector
+/// samples `pg_stat_activity` on every run and counts client backends whose `backend_start`
+/// is newer than the previous sample. Connections that open and close between two samples
+/// are missed, so the sampled rate is a lower bound; run the `activity` collector on a
+/// short `--collector.activity.interval` to tighten it.
+#[derive(Clone)]
+pub struct ConnectionChurnCollector {
+    opened_total: IntCounterVec, // pg_stat_activity_connections_opened_total{datname}
+    sampler: Arc<Mutex<ChurnSampler>>,
+}
+
+/// Running totals for servers without `pg_stat_database.sessions`
+#[derive(Default)]
+struct ChurnSampler {
+    // Server time (epoch seconds) of the previous sample; None until the first one
+    watermark: Option<f64>,
+    totals: HashMap<String, u64>,
+}
+
+impl ChurnSampler {
+    /// Fold one sample taken at server time `now` after `since` into the totals. Returns
+    /// false, leaving the totals untouched, when another scrape already advanced the
+    /// watermark so the same connections are never counted twice.
+    fn advance(&mut self, since: Option<f64>, now: f64, opened: &[(String, u64)]) -> bool {
+        if self.watermark != since {
+            return false;
+        }
+
+        for (datname, count) in opened {
+            let total = self.totals.entry(datname.clone()).or_insert(0);
+            // The first sample only establishes the watermark
+            if since.is_some() {
+                *total = total.saturating_add(*count);
+            }
+        }
+
+        self.watermark = Some(now);
+        true
+    }
+}
+
+impl Default for ConnectionChurnCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConnectionChurnCollector {
+    /// Creates a new `ConnectionChurnCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let opened_total = IntCounterVec::new(
+            Opts::new(
+                "pg_stat_activity_connections_opened_total",
+                "Sessions established per database before PostgreSQL 14, sampled from \
+                 pg_stat_activity.backend_start; rate() gives the connection rate (use \
+                 pg_stat_database_sessions_total on PostgreSQL 14+)",
+            ),
+            &["datname"],
+        )
+        .expect("Failed to create pg_stat_activity_connections_opened_total metric");
+
+        Self {
+            opened_total,
+            sampler: Arc::new(Mutex::new(ChurnSampler::default())),
+        }
+    }
+
+    /// Before `PostgreSQL` 14: count backends started since the previous sample
+    async fn collect_sampled(&self, pool: &PgPool, excluded: &[String]) -> Result<()> {
+        let excluded_users: Vec<String> = get_excluded_users().to_vec();
+        let since = lock_sampler(&self.sampler).watermark;
+
+        let query_span = info_span!(
+            "db.query",
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.operation = "SELECT",
+            db.statement =
+                "SELECT backends started since last sample FROM pg_stat_activity (filtered)",
+            db.sql.table = "pg_stat_activity"
+        );
+
+        // $3 is NULL on the first sample, which matches nothing
+        let rows = sqlx::query(
+            r"
+            SELECT
+                d.datname,
+                COUNT(a.pid)::bigint AS opened,
+                EXTRACT(EPOCH FROM now())::float8 AS sampled_at
+            FROM pg_database d
+            LEFT JOIN pg_stat_activity a
+              ON a.datid = d.oid
+             AND a.backend_type = 'client backend'
+             AND a.backend_start > to_timestamp($3)
+             AND COALESCE(a.usename, '') <> ALL($2)
+            WHERE d.datallowconn
+              AND NOT d.datistemplate
+              AND NOT (d.datname = ANY($1))
+            GROUP BY d.datname
+            ORDER BY d.datname
+            ",
+        )
+        .bind(excluded)
+        .bind(&excluded_users)
+        .bind(since)
+        .fetch_all(&mut *acquire_connection(pool).await?)
+        .instrument(query_span)
+        .await?;
+
+        let mut sampled_at = None;
+        let mut opened = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let datname: String = row.try_get("datname")?;
+            let count: i64 = row.try_get("opened")?;
+            sampled_at = Some(row.try_get::<f64, _>("sampled_at")?);
+            opened.push((datname, u64::try_from(count).unwrap_or(0)));
+        }
+
+        let Some(now) = sampled_at else {
+            // Every database is excluded: nothing to sample
+            self.opened_total.reset();
+            return Ok(());
+        };
+
+        let mut sampler = lock_sampler(&self.sampler);
+        if !sampler.advance(since, now, &opened) {
+            debug!("connection churn sample raced with another scrape; skipped");
+            return Ok(());
+        }
+
+        self.opened_total.reset();
+        for (datname, _) in &opened {
+            let total = sampler.totals.get(datname).copied().unwrap_or(0);
+            self.opened_total
+                .with_label_values(&[datname])
+                .inc_by(total);
+        }
+
+        debug!(databases = opened.len(), "updated sampled connection churn");
+
+        Ok(())
+    }
+}
+
+fn lock_sampler(sampler: &Mutex<ChurnSampler>) -> std::sync::MutexGuard<'_, ChurnSampler> {
+    match sampler.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+impl Collector for ConnectionChurnCollector {
+    fn name(&self) -> &'static str {
+        "connection_churn"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "connection_churn")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.opened_total.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector="connection_churn", otel.kind="internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            // pg_stat_database_sessions_total already carries the exact count
+            if resolve_server_version(pool).await? >= 140_000 {
+                self.opened_total.reset();
+                return Ok(());
+            }
+
+            let excluded: Vec<String> = get_excluded_databases().to_vec();
+            self.collect_sampled(pool, &excluded).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampler_first_sample_only_sets_watermark() {
+        let mut sampler = ChurnSampler::default();
+        assert!(sampler.advance(None, 100.0, &[("app".to_string(), 7)]));
+        assert_eq!(sampler.watermark, Some(100.0));
+        assert_eq!(sampler.totals.get("app"), Some(&0));
+    }
+
+    #[test]
+    fn test_sampler_accumulates_new_backends() {
+        let mut sampler = ChurnSampler::default();
+        assert!(sampler.advance(None, 100.0, &[("app".to_string(), 7)]));
+        assert!(sampler.advance(Some(100.0), 115.0, &[("app".to_string(), 3)]));
+        assert!(sampler.advance(Some(115.0), 130.0, &[("app".to_string(), 2)]));
+        assert_eq!(sampler.totals.get("app"), Some(&5));
+        assert_eq!(sampler.watermark, Some(130.0));
+    }
+
+    #[test]
+    fn test_sampler_skips_stale_sample() {
+        let mut sampler = ChurnSampler::default();
+        assert!(sampler.advance(None, 100.0, &[]));
+        assert!(sampler.advance(Some(100.0), 115.0, &[("app".to_string(), 3)]));
+        // A concurrent scrape that started from the old watermark must not double count
+        assert!(!sampler.advance(Some(100.0), 116.0, &[("app".to_string(), 3)]));
+        assert_eq!(sampler.totals.get("app"), Some(&3));
+        assert_eq!(sampler.watermark, Some(115.0));
+    }
+}
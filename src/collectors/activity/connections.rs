@@ -1,4 +1,7 @@
-use crate::collectors::{Collector, i64_to_f64, util::get_excluded_databases};
+use crate::collectors::{
+    Collector, i64_to_f64,
+    util::{acquire_connection, datname_label, get_excluded_databases, get_excluded_users},
+};
 use anyhow::Result;
 use futures::future::BoxFuture;
 use prometheus::{Gauge, IntGauge, IntGaugeVec, Opts, Registry};
@@ -15,6 +18,7 @@ use tracing_futures::Instrument as _;
 /// - `pg_stat_activity_idle_connections`{`datname`}
 /// - `pg_stat_activity_waiting_connections`{`datname`}
 /// - `pg_stat_activity_blocked_connections`{`datname`}
+/// - `pg_stat_activity_writing_backends`{`datname`} - Backends holding a real transaction id
 ///
 /// **New Pool Saturation Metrics (K8s-focused):**
 /// - `pg_stat_activity_max_connections` - Maximum allowed connections
@@ -25,6 +29,10 @@ use tracing_futures::Instrument as _;
 /// - `pg_stat_activity_idle_in_transaction_aborted`{`datname`} - Even worse
 /// - `pg_stat_activity_connections_by_application`{`datname`, `application_name`}
 /// - `pg_stat_activity_idle_age_seconds`{`datname`, bucket} - Idle connection age buckets
+///
+/// **Memory Sizing:**
+/// - `pg_stat_activity_backends`{`backend_type`} - Every server process, unfiltered; with the
+///   `pg_settings_*` memory gauges it feeds the worst-case memory estimate
 #[derive(Clone)]
 pub struct ConnectionsCollector {
     // Existing metrics (unchanged for backward compatibility)
@@ -37,6 +45,10 @@ pub struct ConnectionsCollector {
     // CPU pressure: active backends NOT waiting on anything = running on CPU.
     on_cpu_backends: IntGaugeVec, // pg_stat_activity_on_cpu_backends{datname}
 
+    // Write load: backends whose transaction was assigned a real xid (it wrote);
+    // read-only transactions only ever get a virtual xid.
+    writing_backends: IntGaugeVec, // pg_stat_activity_writing_backends{datname}
+
     // Connection pool saturation metrics (new - K8s focused)
     // Help prevent connection exhaustion in containerized environments
     max_connections: IntGauge, // Total allowed connections (from pg_settings)
@@ -44,6 +56,10 @@ pub struct ConnectionsCollector {
     utilization_ratio: Gauge,  // GOLD: used/max ratio (alert >0.8)
     available_connections: IntGauge, // Connections still available
 
+    // Every process of the server (client and background), the per-process term of
+    // the memory-sizing formula; not subject to database/user exclusions.
+    backends_by_type: IntGaugeVec, // {backend_type}
+
     // Dangerous states that indicate application bugs
     idle_in_transaction: IntGaugeVec, // Holding locks while idle
     idle_in_transaction_aborted: IntGaugeVec, // Even worse - failed tx not cleaned
@@ -76,7 +92,7 @@ impl ConnectionsCollector {
     pub fn new() -> Self {
         let count_by_state = int_gauge_vec(
             "pg_stat_activity_count",
-            "Number of client backends by database and state (from pg_stat_activity)",
+            "Number of client backends by database and state (from pg_stat_activity; state is \"unknown\" for sessions whose state the exporter role cannot see)",
             &["datname", "state"],
         );
         let active_connections = int_gauge_vec(
@@ -104,6 +120,17 @@ impl ConnectionsCollector {
             "Number of active client backends NOT waiting on any event (running on CPU) per database; compare to vCPU count to gauge CPU saturation",
             &["datname"],
         );
+        let writing_backends = int_gauge_vec(
+            "pg_stat_activity_writing_backends",
+            "Number of client backends holding a real transaction id (backend_xid IS NOT NULL, the transaction wrote) per database; compare to pg_stat_activity_count for the read/write mix",
+            &["datname"],
+        );
+
+        let backends_by_type = int_gauge_vec(
+            "pg_stat_activity_backends",
+            "Number of server processes by backend_type, including background workers and excluded databases (\"unknown\" for sessions the exporter role cannot inspect); each holds its own work_mem and temp_buffers",
+            &["backend_type"],
+        );
 
         // Connection pool saturation metrics (new)
         let max_connections = int_gauge(
@@ -139,13 +166,8 @@ impl ConnectionsCollector {
             &["datname", "application_name"],
         );
 
-        let (
-            idle_age_short,
-            idle_age_medium,
-            idle_age_extended,
-            idle_age_prolonged,
-            idle_age_old,
-        ) = idle_age_gauges();
+        let (idle_age_short, idle_age_medium, idle_age_extended, idle_age_prolonged, idle_age_old) =
+            idle_age_gauges();
 
         Self {
             count_by_state,
@@ -154,10 +176,12 @@ impl ConnectionsCollector {
             waiting_connections,
             blocked_connections,
             on_cpu_backends,
+            writing_backends,
             max_connections,
             used_connections,
             utilization_ratio,
             available_connections,
+            backends_by_type,
             idle_in_transaction,
             idle_in_transaction_aborted,
             connections_by_application,
@@ -176,6 +200,8 @@ impl ConnectionsCollector {
         self.waiting_connections.reset();
         self.blocked_connections.reset();
         self.on_cpu_backends.reset();
+        self.writing_backends.reset();
+        self.backends_by_type.reset();
         self.idle_in_transaction.reset();
         self.idle_in_transaction_aborted.reset();
         self.connections_by_application.reset();
@@ -206,12 +232,14 @@ impl Collector for ConnectionsCollector {
         registry.register(Box::new(self.waiting_connections.clone()))?;
         registry.register(Box::new(self.blocked_connections.clone()))?;
         registry.register(Box::new(self.on_cpu_backends.clone()))?;
+        registry.register(Box::new(self.writing_backends.clone()))?;
 
         // Register new pool saturation metrics
         registry.register(Box::new(self.max_connections.clone()))?;
         registry.register(Box::new(self.used_connections.clone()))?;
         registry.register(Box::new(self.utilization_ratio.clone()))?;
         registry.register(Box::new(self.available_connections.clone()))?;
+        registry.register(Box::new(self.backends_by_type.clone()))?;
         registry.register(Box::new(self.idle_in_transaction.clone()))?;
         registry.register(Box::new(self.idle_in_transaction_aborted.clone()))?;
         registry.register(Box::new(self.connections_by_application.clone()))?;
@@ -237,6 +265,7 @@ impl Collector for ConnectionsCollector {
 
             // Build exclusion list from global OnceCell (set at startup via Clap/env).
             let excluded: Vec<String> = get_excluded_databases().to_vec();
+            let excluded_users: Vec<String> = get_excluded_users().to_vec();
 
             // NEW: Get max_connections setting
             let max_conn_query = info_span!(
@@ -251,14 +280,16 @@ impl Collector for ConnectionsCollector {
             let max_conn: i64 = sqlx::query_scalar(
                 "SELECT setting::bigint FROM pg_settings WHERE name = 'max_connections'",
             )
-            .fetch_one(pool)
+            .fetch_one(&mut *acquire_connection(pool).await?)
             .instrument(max_conn_query)
             .await?;
 
             self.max_connections.set(max_conn);
 
             // 1) Compatibility metric: count by state (EXISTING - unchanged)
-            //    Only count client backends to avoid background processes.
+            //    Only count client backends to avoid background processes. Without
+            //    pg_read_all_stats other roles' sessions hide their backend_type and state;
+            //    they still hold a connection slot, so they count as state 'unknown'.
             let q_state = info_span!(
                 "db.query",
                 otel.kind = "client",
@@ -275,30 +306,36 @@ impl Collector for ConnectionsCollector {
                     COALESCE(state, 'unknown') AS state,
                     COUNT(*)::bigint AS cnt
                 FROM pg_stat_activity
-                WHERE backend_type = 'client backend'
+                WHERE (backend_type = 'client backend'
+                       -- sessions the role may not inspect: backend_type and state are NULL
+                       OR (backend_type IS NULL AND datid IS NOT NULL AND usesysid IS NOT NULL))
                   AND pid != pg_backend_pid()
                   AND NOT (COALESCE(datname, '') = ANY($1))
+                  AND COALESCE(usename, '') <> ALL($2)
                 GROUP BY datname, COALESCE(state, 'unknown')
                 ORDER BY datname, COALESCE(state, 'unknown')
                 ",
             )
             .bind(&excluded)
-            .fetch_all(pool)
+            .bind(&excluded_users)
+            .fetch_all(&mut *acquire_connection(pool).await?)
             .instrument(q_state)
             .await?;
 
+            // Used connections are summed from the same rows as pg_stat_activity_count so
+            // the per-state series always add up to it, even while backends come and go.
+            let mut total_connections: i64 = 0;
             let mut dbs_seen: HashSet<String> = HashSet::new();
             let mut active_map: HashMap<String, i64> = HashMap::new();
             let mut idle_map: HashMap<String, i64> = HashMap::new();
 
             for row in &state_rows {
-                let db: String = row
-                    .try_get::<Option<String>, _>("datname")?
-                    .unwrap_or_else(|| "[unknown]".to_string());
+                let db: String = datname_label(row.try_get("datname")?);
                 let state: String = row.try_get::<String, _>("state")?;
                 let cnt: i64 = row.try_get::<i64, _>("cnt").unwrap_or(0);
 
                 dbs_seen.insert(db.clone());
+                total_connections += cnt;
 
                 // Emit pg_stat_activity_count (EXISTING)
                 self.count_by_state
@@ -339,36 +376,40 @@ impl Collector for ConnectionsCollector {
                     a.datname,
                     COUNT(*) FILTER (WHERE a.wait_event IS NOT NULL)::bigint AS waiting,
                     COUNT(*) FILTER (WHERE cardinality(pg_blocking_pids(a.pid)) > 0)::bigint AS blocked,
-                    COUNT(*) FILTER (WHERE a.state = 'active' AND a.wait_event IS NULL)::bigint AS on_cpu
+                    COUNT(*) FILTER (WHERE a.state = 'active' AND a.wait_event IS NULL)::bigint AS on_cpu,
+                    COUNT(*) FILTER (WHERE a.backend_xid IS NOT NULL)::bigint AS writing
                 FROM pg_stat_activity a
                 WHERE a.backend_type = 'client backend'
                   AND a.pid != pg_backend_pid()
                   AND NOT (COALESCE(a.datname, '') = ANY($1))
+                  AND COALESCE(a.usename, '') <> ALL($2)
                 GROUP BY a.datname
                 ORDER BY a.datname
                 ",
             )
             .bind(&excluded)
-            .fetch_all(pool)
+            .bind(&excluded_users)
+            .fetch_all(&mut *acquire_connection(pool).await?)
             .instrument(q_wait_block)
             .await?;
 
             let mut waiting_map: HashMap<String, i64> = HashMap::new();
             let mut blocked_map: HashMap<String, i64> = HashMap::new();
             let mut on_cpu_map: HashMap<String, i64> = HashMap::new();
+            let mut writing_map: HashMap<String, i64> = HashMap::new();
 
             for row in &wait_block_rows {
-                let db: String = row
-                    .try_get::<Option<String>, _>("datname")?
-                    .unwrap_or_else(|| "[unknown]".to_string());
+                let db: String = datname_label(row.try_get("datname")?);
                 let waiting: i64 = row.try_get::<i64, _>("waiting").unwrap_or(0);
                 let blocked: i64 = row.try_get::<i64, _>("blocked").unwrap_or(0);
                 let on_cpu: i64 = row.try_get::<i64, _>("on_cpu").unwrap_or(0);
+                let writing: i64 = row.try_get::<i64, _>("writing").unwrap_or(0);
 
                 dbs_seen.insert(db.clone());
                 waiting_map.insert(db.clone(), waiting);
                 blocked_map.insert(db.clone(), blocked);
                 on_cpu_map.insert(db.clone(), on_cpu);
+                writing_map.insert(db.clone(), writing);
 
                 self.waiting_connections
                     .with_label_values(&[&db])
@@ -377,8 +418,9 @@ impl Collector for ConnectionsCollector {
                     .with_label_values(&[&db])
                     .set(blocked);
                 self.on_cpu_backends.with_label_values(&[&db]).set(on_cpu);
+                self.writing_backends.with_label_values(&[&db]).set(writing);
 
-                debug!(database=%db, waiting, blocked, on_cpu, "set waiting/blocked/on_cpu gauges");
+                debug!(database=%db, waiting, blocked, on_cpu, writing, "set waiting/blocked/on_cpu/writing gauges");
             }
 
             // Ensure zeroes for databases seen in state query but not in wait/blocked (EXISTING)
@@ -392,9 +434,49 @@ impl Collector for ConnectionsCollector {
                 if !on_cpu_map.contains_key(db) {
                     self.on_cpu_backends.with_label_values(&[db]).set(0);
                 }
+                if !writing_map.contains_key(db) {
+                    self.writing_backends.with_label_values(&[db]).set(0);
+                }
             }
 
-            // 3) NEW: Pool saturation metrics and detailed connection analysis
+            // 3) Every server process by backend_type. Memory is a server-wide budget, so
+            //    neither the exclusions nor the exporter's own session are filtered out.
+            let q_backends = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement =
+                    "SELECT backend_type, COUNT(*) FROM pg_stat_activity GROUP BY backend_type",
+                db.sql.table = "pg_stat_activity"
+            );
+
+            let backend_rows = sqlx::query(
+                r"
+                SELECT
+                    COALESCE(backend_type, 'unknown') AS backend_type,
+                    COUNT(*)::bigint AS cnt
+                FROM pg_stat_activity
+                GROUP BY COALESCE(backend_type, 'unknown')
+                ",
+            )
+            .fetch_all(&mut *acquire_connection(pool).await?)
+            .instrument(q_backends)
+            .await?;
+
+            for row in &backend_rows {
+                let backend_type: String = row.try_get("backend_type")?;
+                let cnt: i64 = row.try_get::<i64, _>("cnt").unwrap_or(0);
+                self.backends_by_type
+                    .with_label_values(&[&backend_type])
+                    .set(cnt);
+            }
+            debug!(
+                backend_types = backend_rows.len(),
+                "set backends by type gauges"
+            );
+
+            // 4) NEW: Pool saturation metrics and detailed connection analysis
             let q_detailed = info_span!(
                 "db.query",
                 otel.kind = "client",
@@ -413,19 +495,22 @@ impl Collector for ConnectionsCollector {
                     EXTRACT(EPOCH FROM (now() - state_change))::bigint AS state_duration_seconds,
                     COUNT(*)::bigint AS cnt
                 FROM pg_stat_activity
-                WHERE backend_type = 'client backend'
+                WHERE (backend_type = 'client backend'
+                       -- sessions the role may not inspect: backend_type and state are NULL
+                       OR (backend_type IS NULL AND datid IS NOT NULL AND usesysid IS NOT NULL))
                   AND pid != pg_backend_pid()
                   AND NOT (COALESCE(datname, '') = ANY($1))
+                  AND COALESCE(usename, '') <> ALL($2)
                 GROUP BY datname, COALESCE(state, 'unknown'), application_name, EXTRACT(EPOCH FROM (now() - state_change))::bigint
                 ",
             )
             .bind(&excluded)
-            .fetch_all(pool)
+            .bind(&excluded_users)
+            .fetch_all(&mut *acquire_connection(pool).await?)
             .instrument(q_detailed)
             .await?;
 
-            // Track totals and categorized metrics
-            let mut total_connections: i64 = 0;
+            // Track categorized metrics
             let mut idle_in_tx_map: HashMap<String, i64> = HashMap::new();
             let mut idle_in_tx_aborted_map: HashMap<String, i64> = HashMap::new();
             let mut app_conn_map: HashMap<(String, String), i64> = HashMap::new();
@@ -438,9 +523,7 @@ impl Collector for ConnectionsCollector {
             let mut idle_old_map: HashMap<String, i64> = HashMap::new();
 
             for row in &detailed_rows {
-                let db: String = row
-                    .try_get::<Option<String>, _>("datname")?
-                    .unwrap_or_else(|| "[unknown]".to_string());
+                let db: String = datname_label(row.try_get("datname")?);
                 let state: String = row.try_get::<String, _>("state")?;
                 let app_name: String = row
                     .try_get::<Option<String>, _>("application_name")?
@@ -449,7 +532,6 @@ impl Collector for ConnectionsCollector {
                     row.try_get::<i64, _>("state_duration_seconds").unwrap_or(0);
                 let cnt: i64 = row.try_get::<i64, _>("cnt").unwrap_or(0);
 
-                total_connections += cnt;
                 dbs_seen.insert(db.clone());
 
                 // Track idle in transaction states (dangerous!)
@@ -616,10 +698,7 @@ mod tests {
 
     macro_rules! set_stale_db_metric {
         ($collector:ident, $field:ident) => {
-            $collector
-                .$field
-                .with_label_values(&["stale_db"])
-                .set(1);
+            $collector.$field.with_label_values(&["stale_db"]).set(1);
         };
     }
 
@@ -642,6 +721,7 @@ mod tests {
         set_stale_db_metric!(collector, waiting_connections);
         set_stale_db_metric!(collector, blocked_connections);
         set_stale_db_metric!(collector, on_cpu_backends);
+        set_stale_db_metric!(collector, writing_backends);
         set_stale_db_metric!(collector, idle_in_transaction);
         set_stale_db_metric!(collector, idle_in_transaction_aborted);
         collector
@@ -656,12 +736,16 @@ mod tests {
 
         collector.reset_label_metrics();
 
-        assert_eq!(collected_metric_count(&collector.count_by_state.collect()), 0);
+        assert_eq!(
+            collected_metric_count(&collector.count_by_state.collect()),
+            0
+        );
         assert_no_series!(collector, active_connections);
         assert_no_series!(collector, idle_connections);
         assert_no_series!(collector, waiting_connections);
         assert_no_series!(collector, blocked_connections);
         assert_no_series!(collector, on_cpu_backends);
+        assert_no_series!(collector, writing_backends);
         assert_no_series!(collector, idle_in_transaction);
         assert_no_series!(collector, idle_in_transaction_aborted);
         assert_eq!(
@@ -0,0 +1,182 @@
+use crate::collectors::{
+    Collector,
+    util::{acquire_connection, get_excluded_databases, get_excluded_users},
+};
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{GaugeVec, IntGaugeVec, Opts, Registry};
+use sqlx::{PgPool, Row};
+use tracing::{debug, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// Tracks idle-in-transaction sessions that still hold locks, the subset most likely to
+/// cause a pileup:
+/// - `pg_stat_activity_idle_in_transaction_holding_locks{datname}` (`IntGauge`)
+/// - `pg_stat_activity_idle_in_transaction_holding_locks_max_age_seconds{datname}` (`Gauge`):
+///   transaction age (`now() - xact_start`) of the oldest such session, 0 when there is none
+///
+/// A session counts when it is `idle in transaction` and holds a granted `pg_locks` entry
+/// other than its own `virtualxid`, which every open transaction holds. Relation locks and the
+/// `transactionid` lock that row-lock waiters queue on are what block other sessions. Every
+/// connectable, non-excluded database gets a series, so `0` is exported when nothing is held.
+#[derive(Clone)]
+pub struct IdleInTransactionLocksCollector {
+    holding_locks: IntGaugeVec, // pg_stat_activity_idle_in_transaction_holding_locks{datname}
+    max_age_seconds: GaugeVec, // pg_stat_activity_idle_in_transaction_holding_locks_max_age_seconds{datname}
+}
+
+impl Default for IdleInTransactionLocksCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdleInTransactionLocksCollector {
+    /// Creates a new `IdleInTransactionLocksCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let holding_locks = IntGaugeVec::new(
+            Opts::new(
+                "pg_stat_activity_idle_in_transaction_holding_locks",
+                "Client backends idle in transaction that hold a granted lock (besides their own \
+                 virtualxid), per database",
+            ),
+            &["datname"],
+        )
+        .expect("Failed to create pg_stat_activity_idle_in_transaction_holding_locks metric");
+
+        let max_age_seconds = GaugeVec::new(
+            Opts::new(
+                "pg_stat_activity_idle_in_transaction_holding_locks_max_age_seconds",
+                "Transaction age in seconds of the oldest idle-in-transaction backend holding a \
+                 granted lock, per database (0 when there is none)",
+            ),
+            &["datname"],
+        )
+        .expect(
+            "Failed to create pg_stat_activity_idle_in_transaction_holding_locks_max_age_seconds metric",
+        );
+
+        Self {
+            holding_locks,
+            max_age_seconds,
+        }
+    }
+}
+
+impl Collector for IdleInTransactionLocksCollector {
+    fn name(&self) -> &'static str {
+        "idle_in_transaction_locks"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "idle_in_transaction_locks")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.holding_locks.clone()))?;
+        registry.register(Box::new(self.max_age_seconds.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector="idle_in_transaction_locks", otel.kind="internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let excluded: Vec<String> = get_excluded_databases().to_vec();
+            let excluded_users: Vec<String> = get_excluded_users().to_vec();
+
+            let query_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement = "SELECT idle in transaction backends holding locks per db FROM pg_stat_activity JOIN pg_locks (filtered)",
+                db.sql.table = "pg_stat_activity"
+            );
+
+            let rows = sqlx::query(
+                r"
+                SELECT
+                    d.datname,
+                    COUNT(a.pid)::bigint AS holding,
+                    COALESCE(
+                        MAX(GREATEST(EXTRACT(EPOCH FROM (now() - a.xact_start)), 0)),
+                        0
+                    )::double precision AS max_age_seconds
+                FROM pg_database d
+                LEFT JOIN pg_stat_activity a
+                  ON a.datid = d.oid
+                 AND a.backend_type = 'client backend'
+                 AND a.pid != pg_backend_pid()
+                 AND a.state = 'idle in transaction'
+                 AND COALESCE(a.usename, '') <> ALL($2)
+                 AND EXISTS (
+                     SELECT 1
+                     FROM pg_locks l
+                     WHERE l.pid = a.pid
+                       AND l.granted
+                       AND l.locktype <> 'virtualxid'
+                 )
+                WHERE d.datallowconn
+                  AND NOT d.datistemplate
+                  AND NOT (d.datname = ANY($1))
+                GROUP BY d.datname
+                ORDER BY d.datname
+                ",
+            )
+            .bind(&excluded)
+            .bind(&excluded_users)
+            .fetch_all(&mut *acquire_connection(pool).await?)
+            .instrument(query_span)
+            .await?;
+
+            self.holding_locks.reset();
+            self.max_age_seconds.reset();
+
+            for row in &rows {
+                let datname: String = row.try_get("datname")?;
+                let holding: i64 = row.try_get("holding").unwrap_or(0);
+                let max_age: f64 = row.try_get("max_age_seconds").unwrap_or(0.0);
+
+                self.holding_locks
+                    .with_label_values(&[&datname])
+                    .set(holding);
+                self.max_age_seconds
+                    .with_label_values(&[&datname])
+                    .set(max_age);
+            }
+
+            debug!(
+                databases = rows.len(),
+                "updated idle in transaction lock holder metrics"
+            );
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_in_transaction_locks_registers_metrics() {
+        let collector = IdleInTransactionLocksCollector::new();
+        let registry = Registry::new();
+        assert_eq!(collector.name(), "idle_in_transaction_locks");
+        assert!(collector.register_metrics(&registry).is_ok());
+    }
+}
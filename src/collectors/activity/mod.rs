@@ -1,12 +1,11 @@
-use crate::collectors::Collector;
+use crate::collectors::{Collector, util::collect_subs};
 use anyhow::Result;
 use futures::future::BoxFuture;
-use futures::stream::{FuturesUnordered, StreamExt};
 use prometheus::Registry;
 use sqlx::PgPool;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{debug, info_span, instrument, warn};
-use tracing_futures::Instrument as _;
 
 pub mod connections;
 use connections::ConnectionsCollector;
@@ -17,6 +16,29 @@ use wait::WaitEventsCollector;
 pub mod queries;
 use queries::QueriesCollector;
 
+pub mod parallel;
+use parallel::ParallelQueriesCollector;
+
+pub mod query_age;
+use query_age::{DEFAULT_QUERY_AGE_BUCKETS, QueryAgeCollector};
+
+pub mod stuck;
+use stuck::{DEFAULT_STUCK_THRESHOLD, StuckBackendsCollector};
+
+pub mod slow;
+use slow::{DEFAULT_SLOW_QUERY_THRESHOLD, SlowQueriesCollector};
+
+mod threshold;
+
+pub mod churn;
+use churn::ConnectionChurnCollector;
+
+pub mod idle_in_transaction;
+use idle_in_transaction::IdleInTransactionLocksCollector;
+
+pub mod applications;
+use applications::ApplicationBackendsCollector;
+
 #[derive(Clone, Default)]
 pub struct ActivityCollector {
     subs: Vec<Arc<dyn Collector + Send + Sync>>,
@@ -25,14 +47,55 @@ pub struct ActivityCollector {
 impl ActivityCollector {
     #[must_use]
     pub fn new() -> Self {
+        Self::with_query_age_buckets(&DEFAULT_QUERY_AGE_BUCKETS)
+    }
+
+    /// Build the activity collector with custom `pg_stat_activity_query_age_bucket` upper
+    /// bounds in seconds (`--activity.query-age-buckets`).
+    #[must_use]
+    pub fn with_query_age_buckets(buckets: &[f64]) -> Self {
+        Self::with_options(
+            buckets,
+            DEFAULT_STUCK_THRESHOLD,
+            DEFAULT_SLOW_QUERY_THRESHOLD,
+        )
+    }
+
+    /// Build the activity collector with custom query age buckets, the
+    /// `pg_stat_activity_stuck_backends` threshold (`--activity.stuck-threshold-seconds`)
+    /// and the `pg_stat_activity_slow_queries` threshold
+    /// (`--activity.slow-query-threshold-seconds`).
+    #[must_use]
+    pub fn with_options(
+        buckets: &[f64],
+        stuck_threshold: Duration,
+        slow_query_threshold: Duration,
+    ) -> Self {
         Self {
             subs: vec![
                 Arc::new(ConnectionsCollector::new()),
                 Arc::new(WaitEventsCollector::new()),
                 Arc::new(QueriesCollector::new()),
+                Arc::new(ParallelQueriesCollector::new()),
+                Arc::new(QueryAgeCollector::with_buckets(buckets)),
+                Arc::new(StuckBackendsCollector::with_threshold(stuck_threshold)),
+                Arc::new(SlowQueriesCollector::with_threshold(slow_query_threshold)),
+                Arc::new(ConnectionChurnCollector::new()),
+                Arc::new(IdleInTransactionLocksCollector::new()),
             ],
         }
     }
+
+    /// Also export `pg_stat_activity_backends_by_application`, keeping at most `limit`
+    /// distinct application names (`--activity.by-application`). `None` leaves it off.
+    #[must_use]
+    pub fn with_backends_by_application(mut self, limit: Option<usize>) -> Self {
+        if let Some(limit) = limit {
+            self.subs
+                .push(Arc::new(ApplicationBackendsCollector::with_limit(limit)));
+        }
+        self
+    }
 }
 
 impl Collector for ActivityCollector {
@@ -71,26 +134,7 @@ impl Collector for ActivityCollector {
         fields(collector = "activity", otel.kind = "internal")
     )]
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
-        Box::pin(async move {
-            // Collect sub-collectors concurrently (unordered join)
-            let mut tasks = FuturesUnordered::new();
-
-            for sub in &self.subs {
-                let span = info_span!(
-                    "collector.collect",
-                    sub_collector = %sub.name(),
-                    otel.kind = "internal"
-                );
-
-                tasks.push(sub.collect(pool).instrument(span));
-            }
-
-            while let Some(res) = tasks.next().await {
-                res?;
-            }
-
-            Ok(())
-        })
+        Box::pin(async move { collect_subs(&self.subs, pool).await })
     }
 
     fn enabled_by_default(&self) -> bool {
@@ -0,0 +1,142 @@
+use crate::collectors::{
+    Collector,
+    util::{
+        acquire_connection, get_excluded_databases, get_excluded_users, resolve_server_version,
+    },
+};
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{IntGauge, Opts, Registry};
+use sqlx::{PgPool, Row};
+use tracing::{debug, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// `pg_stat_activity.leader_pid` was added in `PostgreSQL` 13.
+const MIN_LEADER_PID_VERSION: i32 = 130_000;
+
+/// Tracks parallel query groups from `pg_stat_activity.leader_pid` (`PostgreSQL` 13+):
+/// - `pg_stat_activity_parallel_groups` (`IntGauge`): distinct leaders with running workers
+/// - `pg_stat_activity_parallel_max_workers_per_group` (`IntGauge`): workers in the largest group
+///
+/// Both are 0 when no parallel query is running, and stay 0 on servers without `leader_pid`.
+#[derive(Clone)]
+pub struct ParallelQueriesCollector {
+    groups: IntGauge,                // pg_stat_activity_parallel_groups
+    max_workers_per_group: IntGauge, // pg_stat_activity_parallel_max_workers_per_group
+}
+
+impl Default for ParallelQueriesCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParallelQueriesCollector {
+    /// Creates a new `ParallelQueriesCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let groups = IntGauge::with_opts(Opts::new(
+            "pg_stat_activity_parallel_groups",
+            "Number of parallel query leaders that currently have parallel workers",
+        ))
+        .expect("Failed to create pg_stat_activity_parallel_groups metric");
+
+        let max_workers_per_group = IntGauge::with_opts(Opts::new(
+            "pg_stat_activity_parallel_max_workers_per_group",
+            "Number of parallel workers in the largest current parallel query group",
+        ))
+        .expect("Failed to create pg_stat_activity_parallel_max_workers_per_group metric");
+
+        Self {
+            groups,
+            max_workers_per_group,
+        }
+    }
+}
+
+impl Collector for ParallelQueriesCollector {
+    fn name(&self) -> &'static str {
+        "parallel_queries"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "parallel_queries")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.groups.clone()))?;
+        registry.register(Box::new(self.max_workers_per_group.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector="parallel_queries", otel.kind="internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if resolve_server_version(pool).await? < MIN_LEADER_PID_VERSION {
+                self.groups.set(0);
+                self.max_workers_per_group.set(0);
+                debug!("pg_stat_activity.leader_pid requires PostgreSQL 13+; skipping");
+                return Ok(());
+            }
+
+            let excluded: Vec<String> = get_excluded_databases().to_vec();
+            let excluded_users: Vec<String> = get_excluded_users().to_vec();
+
+            let query_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement =
+                    "SELECT parallel groups by leader_pid FROM pg_stat_activity (filtered)",
+                db.sql.table = "pg_stat_activity"
+            );
+
+            // Early PostgreSQL 13 releases report the leader's own pid as its leader_pid,
+            // so only rows pointing at a different process are counted as workers.
+            let row = sqlx::query(
+                r"
+                SELECT
+                    COUNT(*)::bigint AS groups,
+                    COALESCE(MAX(workers), 0)::bigint AS max_workers
+                FROM (
+                    SELECT leader_pid, COUNT(*) AS workers
+                    FROM pg_stat_activity
+                    WHERE leader_pid IS NOT NULL
+                      AND pid <> leader_pid
+                      AND NOT (COALESCE(datname, '') = ANY($1))
+                      AND COALESCE(usename, '') <> ALL($2)
+                    GROUP BY leader_pid
+                ) g
+                ",
+            )
+            .bind(&excluded)
+            .bind(&excluded_users)
+            .fetch_one(&mut *acquire_connection(pool).await?)
+            .instrument(query_span)
+            .await?;
+
+            let groups: i64 = row.try_get("groups").unwrap_or(0);
+            let max_workers: i64 = row.try_get("max_workers").unwrap_or(0);
+
+            self.groups.set(groups);
+            self.max_workers_per_group.set(max_workers);
+
+            debug!(groups, max_workers, "updated parallel query metrics");
+
+            Ok(())
+        })
+    }
+}
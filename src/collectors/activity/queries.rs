@@ -1,4 +1,7 @@
-use crate::collectors::{Collector, i64_to_f64, util::get_excluded_databases};
+use crate::collectors::{
+    Collector, i64_to_f64,
+    util::{acquire_connection, datname_label, get_excluded_databases, get_excluded_users},
+};
 use anyhow::Result;
 use futures::future::BoxFuture;
 use prometheus::{Gauge, GaugeVec, IntGauge, IntGaugeVec, Opts, Registry};
@@ -28,23 +31,23 @@ use tracing_futures::Instrument as _;
 #[derive(Clone)]
 pub struct QueriesCollector {
     // Duration bucket counters (primary metrics for alerting)
-    queries_over_5m: IntGaugeVec,   // {datname} - queries >5 minutes
-    queries_over_15m: IntGaugeVec,  // {datname} - queries >15 minutes
-    queries_over_1h: IntGaugeVec,   // {datname} - queries >1 hour
-    queries_over_6h: IntGaugeVec,   // {datname} - queries >6 hours (critical!)
+    queries_over_5m: IntGaugeVec,  // {datname} - queries >5 minutes
+    queries_over_15m: IntGaugeVec, // {datname} - queries >15 minutes
+    queries_over_1h: IntGaugeVec,  // {datname} - queries >1 hour
+    queries_over_6h: IntGaugeVec,  // {datname} - queries >6 hours (critical!)
 
     // Per-database aggregates
-    max_query_duration: GaugeVec,   // {datname} - longest query in seconds
+    max_query_duration: GaugeVec, // {datname} - longest query in seconds
 
     // Global metrics (cross-database)
-    oldest_query_age: Gauge,        // Oldest query across all databases (seconds)
-    total_long_running: IntGauge,   // Total queries running >5 minutes
+    oldest_query_age: Gauge, // Oldest query across all databases (seconds)
+    total_long_running: IntGauge, // Total queries running >5 minutes
 
     // Breakdown by state (what are slow queries doing?)
-    long_running_by_state: IntGaugeVec,  // {datname, state} - active, waiting, etc.
+    long_running_by_state: IntGaugeVec, // {datname, state} - active, waiting, etc.
 
     // Breakdown by wait event (what are slow queries waiting on?)
-    long_running_by_wait_event: IntGaugeVec,  // {datname, wait_event_type} - Lock, IO, etc.
+    long_running_by_wait_event: IntGaugeVec, // {datname, wait_event_type} - Lock, IO, etc.
 }
 
 impl Default for QueriesCollector {
@@ -196,6 +199,7 @@ impl Collector for QueriesCollector {
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
         Box::pin(async move {
             let excluded: Vec<String> = get_excluded_databases().to_vec();
+            let excluded_users: Vec<String> = get_excluded_users().to_vec();
 
             // Query for long-running queries
             // Only track queries running >5 minutes to avoid noise
@@ -223,10 +227,12 @@ impl Collector for QueriesCollector {
                   AND (now() - query_start) > interval '5 minutes'
                   AND query NOT LIKE 'autovacuum:%'
                   AND NOT (COALESCE(datname, '') = ANY($1))
+                  AND COALESCE(usename, '') <> ALL($2)
                 ",
             )
             .bind(&excluded)
-            .fetch_all(pool)
+            .bind(&excluded_users)
+            .fetch_all(&mut *acquire_connection(pool).await?)
             .instrument(query_span)
             .await?;
 
@@ -247,9 +253,7 @@ impl Collector for QueriesCollector {
             let mut global_oldest = 0f64;
 
             for row in &rows {
-                let db: String = row
-                    .try_get::<Option<String>, _>("datname")?
-                    .unwrap_or_else(|| "[unknown]".to_string());
+                let db: String = datname_label(row.try_get("datname")?);
                 let state: String = row.try_get("state")?;
                 let wait_event_type: String = row.try_get("wait_event_type")?;
                 let duration: i64 = row.try_get("duration_seconds").unwrap_or(0);
@@ -315,7 +319,9 @@ impl Collector for QueriesCollector {
 
             // Set max duration per database
             for (db, max_dur) in &db_max_duration {
-                self.max_query_duration.with_label_values(&[db]).set(*max_dur);
+                self.max_query_duration
+                    .with_label_values(&[db])
+                    .set(*max_dur);
             }
 
             // Set state breakdown
@@ -359,7 +365,10 @@ mod tests {
     fn test_reset_metrics_clears_previous_long_running_series() {
         let collector = QueriesCollector::new();
 
-        collector.queries_over_5m.with_label_values(&["postgres"]).set(1);
+        collector
+            .queries_over_5m
+            .with_label_values(&["postgres"])
+            .set(1);
         collector
             .max_query_duration
             .with_label_values(&["postgres"])
@@ -377,7 +386,10 @@ mod tests {
 
         collector.reset_metrics();
 
-        assert_eq!(collected_metric_count(&collector.queries_over_5m.collect()), 0);
+        assert_eq!(
+            collected_metric_count(&collector.queries_over_5m.collect()),
+            0
+        );
         assert_eq!(
             collected_metric_count(&collector.max_query_duration.collect()),
             0
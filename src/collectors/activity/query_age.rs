@@ -0,0 +1,212 @@
+use crate::collectors::{
+    Collector,
+    util::{acquire_connection, get_excluded_databases, get_excluded_users},
+};
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{IntGaugeVec, Opts, Registry};
+use sqlx::{PgPool, Row};
+use std::collections::BTreeMap;
+use tracing::{debug, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// Default upper bounds (seconds) of the query age buckets: <1s, 1-10s, 10-60s, >60s.
+pub const DEFAULT_QUERY_AGE_BUCKETS: [f64; 3] = [1.0, 10.0, 60.0];
+
+/// Counts running queries from `pg_stat_activity` by age, like a Prometheus histogram:
+/// - `pg_stat_activity_query_age_bucket{le,state}` (`IntGaugeVec`): client backends in `state`
+///   whose current query started at most `le` seconds ago; `le="+Inf"` is the total.
+///
+/// Buckets are cumulative and computed in Rust from `now() - query_start`, so this needs no
+/// extension. `state="active"` is always exported (zero when idle); other non-idle states
+/// such as `idle in transaction` appear only while a backend is in them.
+#[derive(Clone)]
+pub struct QueryAgeCollector {
+    buckets: Vec<f64>,
+    query_age_bucket: IntGaugeVec, // {le, state}
+}
+
+impl Default for QueryAgeCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QueryAgeCollector {
+    /// Creates a new `QueryAgeCollector` with [`DEFAULT_QUERY_AGE_BUCKETS`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_buckets(&DEFAULT_QUERY_AGE_BUCKETS)
+    }
+
+    /// Creates a new `QueryAgeCollector` with custom bucket upper bounds in seconds.
+    /// Non-finite values are dropped and the rest sorted and deduplicated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn with_buckets(buckets: &[f64]) -> Self {
+        let mut buckets: Vec<f64> = buckets.iter().copied().filter(|b| b.is_finite()).collect();
+        buckets.sort_by(f64::total_cmp);
+        buckets.dedup();
+
+        let query_age_bucket = IntGaugeVec::new(
+            Opts::new(
+                "pg_stat_activity_query_age_bucket",
+                "Number of non-idle client backends by state whose current query started at most le seconds ago",
+            ),
+            &["le", "state"],
+        )
+        .expect("Failed to create pg_stat_activity_query_age_bucket metric");
+
+        Self {
+            buckets,
+            query_age_bucket,
+        }
+    }
+
+    /// Cumulative count per bucket (plus the trailing `+Inf` total) for the given ages.
+    fn bucket_counts(&self, ages: &[f64]) -> Vec<i64> {
+        let mut counts = vec![0i64; self.buckets.len() + 1];
+        for age in ages {
+            for (bound, count) in self.buckets.iter().zip(counts.iter_mut()) {
+                if age <= bound {
+                    *count += 1;
+                }
+            }
+            if let Some(total) = counts.last_mut() {
+                *total += 1;
+            }
+        }
+        counts
+    }
+
+    fn bucket_labels(&self) -> Vec<String> {
+        self.buckets
+            .iter()
+            .map(ToString::to_string)
+            .chain(std::iter::once("+Inf".to_string()))
+            .collect()
+    }
+}
+
+impl Collector for QueryAgeCollector {
+    fn name(&self) -> &'static str {
+        "query_age"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "query_age")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.query_age_bucket.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector="query_age", otel.kind="internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let excluded: Vec<String> = get_excluded_databases().to_vec();
+            let excluded_users: Vec<String> = get_excluded_users().to_vec();
+
+            let query_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement = "SELECT state, query age FROM pg_stat_activity (filtered)",
+                db.sql.table = "pg_stat_activity"
+            );
+
+            let rows = sqlx::query(
+                r"
+                SELECT
+                    state,
+                    GREATEST(EXTRACT(EPOCH FROM (now() - query_start)), 0)::double precision
+                        AS age_seconds
+                FROM pg_stat_activity
+                WHERE backend_type = 'client backend'
+                  AND pid != pg_backend_pid()
+                  AND state IS NOT NULL
+                  AND state != 'idle'
+                  AND query_start IS NOT NULL
+                  AND NOT (COALESCE(datname, '') = ANY($1))
+                  AND COALESCE(usename, '') <> ALL($2)
+                ",
+            )
+            .bind(&excluded)
+            .bind(&excluded_users)
+            .fetch_all(&mut *acquire_connection(pool).await?)
+            .instrument(query_span)
+            .await?;
+
+            let mut ages_by_state: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+            ages_by_state.entry("active".to_string()).or_default();
+
+            for row in &rows {
+                let state: String = row.try_get("state")?;
+                let age: f64 = row.try_get("age_seconds").unwrap_or(0.0);
+                ages_by_state.entry(state).or_default().push(age);
+            }
+
+            // Point-in-time snapshot: drop states that are no longer present.
+            self.query_age_bucket.reset();
+
+            let labels = self.bucket_labels();
+            for (state, ages) in &ages_by_state {
+                for (le, count) in labels.iter().zip(self.bucket_counts(ages)) {
+                    self.query_age_bucket
+                        .with_label_values(&[le.as_str(), state.as_str()])
+                        .set(count);
+                }
+            }
+
+            debug!(
+                queries = rows.len(),
+                states = ages_by_state.len(),
+                "updated query age bucket metrics"
+            );
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_buckets_sorts_and_dedups() {
+        let collector = QueryAgeCollector::with_buckets(&[60.0, 1.0, 10.0, 10.0, f64::NAN]);
+        assert_eq!(collector.buckets, vec![1.0, 10.0, 60.0]);
+        assert_eq!(collector.bucket_labels(), vec!["1", "10", "60", "+Inf"]);
+    }
+
+    #[test]
+    fn test_bucket_counts_are_cumulative() {
+        let collector = QueryAgeCollector::with_buckets(&[0.5, 1.0, 10.0, 60.0]);
+        let counts = collector.bucket_counts(&[0.2, 1.0, 5.0, 30.0, 120.0, 3600.0]);
+        assert_eq!(counts, vec![1, 2, 3, 4, 6]);
+        assert_eq!(
+            collector.bucket_labels(),
+            vec!["0.5", "1", "10", "60", "+Inf"]
+        );
+    }
+
+    #[test]
+    fn test_bucket_counts_empty() {
+        let collector = QueryAgeCollector::new();
+        assert_eq!(collector.bucket_counts(&[]), vec![0, 0, 0, 0]);
+    }
+}
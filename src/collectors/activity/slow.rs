@@ -0,0 +1,128 @@
+use super::threshold::{ActiveBackendFilter, count_active_backends};
+use crate::collectors::Collector;
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{IntGaugeVec, Opts, Registry};
+use sqlx::PgPool;
+use std::time::Duration;
+use tracing::{debug, instrument};
+
+/// Default `--activity.slow-query-threshold-seconds`: an active query running longer than
+/// this is counted as slow.
+pub const DEFAULT_SLOW_QUERY_THRESHOLD: Duration = Duration::from_mins(1);
+
+const SLOW_QUERIES: ActiveBackendFilter = ActiveBackendFilter {
+    predicate: "a.query_start < now() - make_interval(secs => $3)",
+    statement: "SELECT slow active queries per db FROM pg_stat_activity (filtered)",
+};
+
+/// Counts the queries that are slow right now:
+/// - `pg_stat_activity_slow_queries{datname}` (`IntGauge`): client backends with
+///   `state = 'active'` whose `now() - query_start` exceeds the threshold
+///
+/// Unlike `pg_stat_activity_oldest_query_age_seconds`, which only shows the single worst
+/// query, this gauge shows how many queries are affected, which is what an incident alert
+/// usually needs. Idle sessions and the exporter's own backend are never counted. Every
+/// connectable, non-excluded database gets a series, so `0` is exported when nothing is slow.
+#[derive(Clone)]
+pub struct SlowQueriesCollector {
+    slow_queries: IntGaugeVec, // pg_stat_activity_slow_queries{datname}
+    threshold: Duration,
+}
+
+impl Default for SlowQueriesCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SlowQueriesCollector {
+    /// Creates a new `SlowQueriesCollector` with the default threshold
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_threshold(DEFAULT_SLOW_QUERY_THRESHOLD)
+    }
+
+    /// Creates a new `SlowQueriesCollector` counting active queries that have been running
+    /// for longer than `threshold`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn with_threshold(threshold: Duration) -> Self {
+        let slow_queries = IntGaugeVec::new(
+            Opts::new(
+                "pg_stat_activity_slow_queries",
+                "Active queries running longer than the slow-query threshold, per database",
+            ),
+            &["datname"],
+        )
+        .expect("Failed to create pg_stat_activity_slow_queries metric");
+
+        Self {
+            slow_queries,
+            threshold,
+        }
+    }
+}
+
+impl Collector for SlowQueriesCollector {
+    fn name(&self) -> &'static str {
+        "slow_queries"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "slow_queries")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.slow_queries.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector="slow_queries", otel.kind="internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let databases =
+                count_active_backends(pool, &self.slow_queries, &SLOW_QUERIES, self.threshold)
+                    .await?;
+
+            debug!(
+                databases,
+                threshold_secs = self.threshold.as_secs_f64(),
+                "updated slow query metrics"
+            );
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slow_queries_default_threshold() {
+        let collector = SlowQueriesCollector::new();
+        assert_eq!(collector.threshold, DEFAULT_SLOW_QUERY_THRESHOLD);
+        assert_eq!(collector.name(), "slow_queries");
+    }
+
+    #[test]
+    fn test_slow_queries_registers_metric() {
+        let collector = SlowQueriesCollector::with_threshold(Duration::from_secs(30));
+        let registry = Registry::new();
+        assert!(collector.register_metrics(&registry).is_ok());
+        assert_eq!(collector.threshold, Duration::from_secs(30));
+    }
+}
@@ -0,0 +1,131 @@
+use super::threshold::{ActiveBackendFilter, count_active_backends};
+use crate::collectors::Collector;
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{IntGaugeVec, Opts, Registry};
+use sqlx::PgPool;
+use std::time::Duration;
+use tracing::{debug, instrument};
+
+/// Default `--activity.stuck-threshold-seconds`: an active backend whose state has not
+/// changed for this long is counted as stuck.
+pub const DEFAULT_STUCK_THRESHOLD: Duration = Duration::from_mins(5);
+
+const STUCK_BACKENDS: ActiveBackendFilter = ActiveBackendFilter {
+    predicate: "a.state_change < now() - make_interval(secs => $3) \
+                AND COALESCE(a.wait_event_type, '') <> 'Lock'",
+    statement: "SELECT stuck active backends per db FROM pg_stat_activity (filtered)",
+};
+
+/// Tracks active client backends that are not progressing:
+/// - `pg_stat_activity_stuck_backends{datname}` (`IntGauge`): backends with `state = 'active'`
+///   whose `state_change` is older than the threshold and that are not waiting on a
+///   heavyweight lock
+///
+/// Lock waits are excluded because `pg_stat_activity_blocked_connections` and the locks
+/// collector already explain them; what remains is the "active but wedged" pattern, such as
+/// a backend stuck in a hung external call, extension, or network write. Every connectable,
+/// non-excluded database gets a series, so `0` is exported when nothing is stuck.
+#[derive(Clone)]
+pub struct StuckBackendsCollector {
+    stuck_backends: IntGaugeVec, // pg_stat_activity_stuck_backends{datname}
+    threshold: Duration,
+}
+
+impl Default for StuckBackendsCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StuckBackendsCollector {
+    /// Creates a new `StuckBackendsCollector` with the default threshold
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_threshold(DEFAULT_STUCK_THRESHOLD)
+    }
+
+    /// Creates a new `StuckBackendsCollector` counting active backends whose state has not
+    /// changed for longer than `threshold`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn with_threshold(threshold: Duration) -> Self {
+        let stuck_backends = IntGaugeVec::new(
+            Opts::new(
+                "pg_stat_activity_stuck_backends",
+                "Active client backends whose state_change is older than the stuck threshold and \
+                 that are not waiting on a lock, per database",
+            ),
+            &["datname"],
+        )
+        .expect("Failed to create pg_stat_activity_stuck_backends metric");
+
+        Self {
+            stuck_backends,
+            threshold,
+        }
+    }
+}
+
+impl Collector for StuckBackendsCollector {
+    fn name(&self) -> &'static str {
+        "stuck_backends"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "stuck_backends")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.stuck_backends.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector="stuck_backends", otel.kind="internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let databases =
+                count_active_backends(pool, &self.stuck_backends, &STUCK_BACKENDS, self.threshold)
+                    .await?;
+
+            debug!(
+                databases,
+                threshold_secs = self.threshold.as_secs_f64(),
+                "updated stuck backend metrics"
+            );
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stuck_backends_default_threshold() {
+        let collector = StuckBackendsCollector::new();
+        assert_eq!(collector.threshold, DEFAULT_STUCK_THRESHOLD);
+        assert_eq!(collector.name(), "stuck_backends");
+    }
+
+    #[test]
+    fn test_stuck_backends_registers_metric() {
+        let collector = StuckBackendsCollector::with_threshold(Duration::from_secs(30));
+        let registry = Registry::new();
+        assert!(collector.register_metrics(&registry).is_ok());
+        assert_eq!(collector.threshold, Duration::from_secs(30));
+    }
+}
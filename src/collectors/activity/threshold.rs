@@ -0,0 +1,79 @@
+use crate::collectors::util::{acquire_connection, get_excluded_databases, get_excluded_users};
+use anyhow::Result;
+use prometheus::IntGaugeVec;
+use sqlx::{PgPool, Row};
+use std::time::Duration;
+use tracing::info_span;
+use tracing_futures::Instrument as _;
+
+/// Which active client backends a threshold gauge counts.
+///
+/// `predicate` is a static SQL condition on `pg_stat_activity a`; `$3` is bound to the
+/// threshold in seconds, e.g. `a.query_start < now() - make_interval(secs => $3)`.
+pub(super) struct ActiveBackendFilter {
+    pub predicate: &'static str,
+    pub statement: &'static str,
+}
+
+/// Sets `gauge{datname}` to the number of `state = 'active'` client backends matching
+/// `filter` past `threshold`, for every connectable, non-excluded database (`0` when none
+/// match). Returns the number of databases exported.
+pub(super) async fn count_active_backends(
+    pool: &PgPool,
+    gauge: &IntGaugeVec,
+    filter: &ActiveBackendFilter,
+    threshold: Duration,
+) -> Result<usize> {
+    let excluded: Vec<String> = get_excluded_databases().to_vec();
+    let excluded_users: Vec<String> = get_excluded_users().to_vec();
+
+    let query_span = info_span!(
+        "db.query",
+        otel.kind = "client",
+        db.system = "postgresql",
+        db.operation = "SELECT",
+        db.statement = filter.statement,
+        db.sql.table = "pg_stat_activity"
+    );
+
+    let query = format!(
+        r"
+        SELECT
+            d.datname,
+            COUNT(a.pid)::bigint AS backends
+        FROM pg_database d
+        LEFT JOIN pg_stat_activity a
+          ON a.datid = d.oid
+         AND a.backend_type = 'client backend'
+         AND a.pid != pg_backend_pid()
+         AND a.state = 'active'
+         AND {predicate}
+         AND COALESCE(a.usename, '') <> ALL($2)
+        WHERE d.datallowconn
+          AND NOT d.datistemplate
+          AND NOT (d.datname = ANY($1))
+        GROUP BY d.datname
+        ORDER BY d.datname
+        ",
+        predicate = filter.predicate
+    );
+
+    // The predicate is a compile-time constant, never user input.
+    let rows = sqlx::query(sqlx::AssertSqlSafe(query.as_str()))
+        .bind(&excluded)
+        .bind(&excluded_users)
+        .bind(threshold.as_secs_f64())
+        .fetch_all(&mut *acquire_connection(pool).await?)
+        .instrument(query_span)
+        .await?;
+
+    gauge.reset();
+
+    for row in &rows {
+        let datname: String = row.try_get("datname")?;
+        let backends: i64 = row.try_get("backends")?;
+        gauge.with_label_values(&[&datname]).set(backends);
+    }
+
+    Ok(rows.len())
+}
@@ -1,4 +1,7 @@
-use crate::collectors::{Collector, i64_to_f64, util::get_excluded_databases};
+use crate::collectors::{
+    Collector, i64_to_f64,
+    util::{acquire_connection, get_excluded_databases, get_excluded_users},
+};
 use anyhow::Result;
 use futures::future::BoxFuture;
 use prometheus::{GaugeVec, Opts, Registry};
@@ -88,6 +91,7 @@ impl Collector for WaitEventsCollector {
 
             // Exclusions (set globally via CLI/env)
             let excluded: Vec<String> = get_excluded_databases().to_vec();
+            let excluded_users: Vec<String> = get_excluded_users().to_vec();
 
             // DB query span (client)
             let query_span = info_span!(
@@ -115,13 +119,15 @@ impl Collector for WaitEventsCollector {
                   AND backend_type = 'client backend'
                   AND pid != pg_backend_pid()
                   AND NOT (COALESCE(datname, '') = ANY($1))
+                  AND COALESCE(usename, '') <> ALL($2)
                 GROUP BY COALESCE(wait_event_type, 'none'),
                          COALESCE(wait_event, 'none')
                 ORDER BY wait_event_type, wait_event
                 ",
             )
             .bind(&excluded)
-            .fetch_all(pool)
+            .bind(&excluded_users)
+            .fetch_all(&mut *acquire_connection(pool).await?)
             .instrument(query_span)
             .await?;
 
@@ -0,0 +1,213 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+/// Circuit breaker state, exported as `pg_exporter_circuit_breaker_state`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BreakerState {
+    /// Scrapes run normally.
+    Closed = 0,
+    /// The database is considered down; scrapes do not touch it until the cooldown ends.
+    Open = 1,
+    /// The cooldown ended; the next scrape probes the database.
+    HalfOpen = 2,
+}
+
+impl BreakerState {
+    #[must_use]
+    pub const fn as_i64(self) -> i64 {
+        self as i64
+    }
+}
+
+#[derive(Debug)]
+struct BreakerInner {
+    state: BreakerState,
+    consecutive_failures: u64,
+    opened_at: Option<Instant>,
+}
+
+/// Stops every scrape from hammering an unreachable database.
+///
+/// After `threshold` consecutive connectivity failures the breaker opens and scrapes
+/// are answered with `pg_up 0` without contacting `PostgreSQL`. Once `cooldown` has
+/// elapsed a single scrape is let through as a half-open probe: success closes the
+/// breaker, failure re-opens it for another cooldown. A `threshold` of 0 disables it.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    threshold: u64,
+    cooldown: Duration,
+    inner: Mutex<BreakerInner>,
+}
+
+impl CircuitBreaker {
+    #[must_use]
+    pub const fn new(threshold: u64, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            cooldown,
+            inner: Mutex::new(BreakerInner {
+                state: BreakerState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+            }),
+        }
+    }
+
+    /// Whether the breaker can open at all; a `threshold` of 0 disables it.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.threshold > 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerInner> {
+        match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    #[must_use]
+    pub fn state(&self) -> BreakerState {
+        self.lock().state
+    }
+
+    /// Whether this scrape may contact the database. An open breaker whose cooldown
+    /// has elapsed transitions to half-open and lets the probe through.
+    #[must_use]
+    pub fn allow_request(&self, now: Instant) -> bool {
+        let mut inner = self.lock();
+        match inner.state {
+            BreakerState::Closed | BreakerState::HalfOpen => true,
+            BreakerState::Open => {
+                let cooled_down = inner.opened_at.is_none_or(|opened_at| {
+                    now.saturating_duration_since(opened_at) >= self.cooldown
+                });
+                if cooled_down {
+                    inner.state = BreakerState::HalfOpen;
+                    info!("circuit breaker half-open; probing PostgreSQL");
+                }
+                cooled_down
+            }
+        }
+    }
+
+    /// Record a successful connectivity check, closing the breaker.
+    pub fn record_success(&self) {
+        let mut inner = self.lock();
+        if inner.state != BreakerState::Closed {
+            info!("circuit breaker closed; PostgreSQL reachable again");
+        }
+        inner.state = BreakerState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+    }
+
+    /// Record a failed connectivity check, opening the breaker once the threshold is
+    /// reached or immediately when a half-open probe fails.
+    pub fn record_failure(&self, now: Instant) {
+        if self.threshold == 0 {
+            return;
+        }
+
+        let mut inner = self.lock();
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+
+        let should_open =
+            inner.state == BreakerState::HalfOpen || inner.consecutive_failures >= self.threshold;
+        if should_open {
+            if inner.state != BreakerState::Open {
+                warn!(
+                    consecutive_failures = inner.consecutive_failures,
+                    cooldown_ms = u64::try_from(self.cooldown.as_millis()).unwrap_or(u64::MAX),
+                    "circuit breaker open; skipping PostgreSQL until cooldown elapses"
+                );
+            }
+            inner.state = BreakerState::Open;
+            inner.opened_at = Some(now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COOLDOWN: Duration = Duration::from_secs(30);
+
+    #[test]
+    fn test_opens_after_threshold_consecutive_failures() {
+        let breaker = CircuitBreaker::new(3, COOLDOWN);
+        let now = Instant::now();
+
+        breaker.record_failure(now);
+        breaker.record_failure(now);
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        assert!(breaker.allow_request(now));
+
+        breaker.record_failure(now);
+        assert_eq!(breaker.state(), BreakerState::Open);
+        assert!(!breaker.allow_request(now + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_success_resets_failure_count() {
+        let breaker = CircuitBreaker::new(2, COOLDOWN);
+        let now = Instant::now();
+
+        breaker.record_failure(now);
+        breaker.record_success();
+        breaker.record_failure(now);
+        assert_eq!(breaker.state(), BreakerState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_probe_closes_on_success() {
+        let breaker = CircuitBreaker::new(1, COOLDOWN);
+        let now = Instant::now();
+
+        breaker.record_failure(now);
+        assert!(!breaker.allow_request(now + COOLDOWN / 2));
+        assert!(breaker.allow_request(now + COOLDOWN));
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+
+        breaker.record_success();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_probe_failure_reopens_for_another_cooldown() {
+        let breaker = CircuitBreaker::new(5, COOLDOWN);
+        let now = Instant::now();
+        for _ in 0..5 {
+            breaker.record_failure(now);
+        }
+        let probe_at = now + COOLDOWN;
+        assert!(breaker.allow_request(probe_at));
+
+        breaker.record_failure(probe_at);
+        assert_eq!(breaker.state(), BreakerState::Open);
+        assert!(!breaker.allow_request(probe_at + COOLDOWN / 2));
+        assert!(breaker.allow_request(probe_at + COOLDOWN));
+    }
+
+    #[test]
+    fn test_zero_threshold_disables_breaker() {
+        let breaker = CircuitBreaker::new(0, COOLDOWN);
+        let now = Instant::now();
+        for _ in 0..100 {
+            breaker.record_failure(now);
+        }
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        assert!(breaker.allow_request(now));
+    }
+
+    #[test]
+    fn test_state_values_match_metric_contract() {
+        assert_eq!(BreakerState::Closed.as_i64(), 0);
+        assert_eq!(BreakerState::Open.as_i64(), 1);
+        assert_eq!(BreakerState::HalfOpen.as_i64(), 2);
+    }
+}
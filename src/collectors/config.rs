@@ -1,11 +1,30 @@
-use crate::collectors::COLLECTOR_NAMES;
-use std::collections::HashSet;
+use crate::collectors::{
+    COLLECTOR_NAMES,
+    activity::{
+        query_age::DEFAULT_QUERY_AGE_BUCKETS, slow::DEFAULT_SLOW_QUERY_THRESHOLD,
+        stuck::DEFAULT_STUCK_THRESHOLD,
+    },
+    tls::certificate::CertificateSource,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct StatementsConfig {
     pub top_n: usize,
+    /// Replace literals in exported query text with `?` (for servers older than PG14).
+    pub normalize: bool,
+    /// Only statements whose mean execution time is at least this many milliseconds are
+    /// ranked for the top-N; 0 keeps every statement.
+    pub min_mean_time_ms: f64,
 }
 
+/// Collectors that always run on the scrape and never get a background interval: the
+/// exporter's self-metrics describe the scrape that serves them.
+pub const SCRAPE_ONLY_COLLECTORS: &[&str] = &["exporter"];
+
 /// Default minimum `pg_sequences` used-ratio required for a sequence to be exported.
 pub const DEFAULT_SEQUENCES_MIN_RATIO: f64 = 0.5;
 
@@ -16,11 +35,69 @@ pub struct SequencesConfig {
     pub min_ratio: f64,
 }
 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StatConfig {
+    /// Also export the server-computed `pg_stat_user_tables_*_seconds_ago` gauges. The
+    /// `*_timestamp_seconds` gauges are always exported; prefer `time() - timestamp`.
+    pub seconds_ago: bool,
+    /// Read `pg_stat_all_tables` instead of `pg_stat_user_tables`, adding system catalogs.
+    pub include_system: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActivityConfig {
+    /// Upper bounds, in seconds, of the `pg_stat_activity_query_age_bucket` buckets.
+    pub query_age_buckets: Vec<f64>,
+    /// How long an active backend's state may stay unchanged before it counts as stuck.
+    pub stuck_threshold: Duration,
+    /// How long an active query may run before it counts as slow.
+    pub slow_query_threshold: Duration,
+    /// Distinct application names exported by `pg_stat_activity_backends_by_application`;
+    /// `None` keeps the metric off.
+    pub by_application: Option<usize>,
+}
+
+impl Default for ActivityConfig {
+    fn default() -> Self {
+        Self {
+            query_age_buckets: DEFAULT_QUERY_AGE_BUCKETS.to_vec(),
+            stuck_threshold: DEFAULT_STUCK_THRESHOLD,
+            slow_query_threshold: DEFAULT_SLOW_QUERY_THRESHOLD,
+            by_application: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FdwConfig {
+    /// Probe each foreign server with a query through one of its foreign tables.
+    pub probe: bool,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TlsConfig {
+    /// Where the certificate sub-collector reads the server certificate from.
+    pub certificate: CertificateSource,
+}
+
 #[derive(Clone, Debug)]
 pub struct CollectorConfig {
     pub enabled_collectors: HashSet<String>,
     pub statements: StatementsConfig,
     pub sequences: SequencesConfig,
+    pub stat: StatConfig,
+    pub activity: ActivityConfig,
+    pub fdw: FdwConfig,
+    pub tls: TlsConfig,
+    /// Collectors that run in the background on their own interval instead of on
+    /// every scrape. Empty keeps the pull-on-scrape model for every collector.
+    pub intervals: HashMap<String, Duration>,
+    /// Upper bound of the random delay added before every background run, so a fleet of
+    /// exporters does not query its databases in lockstep. Zero disables jitter.
+    pub collection_jitter: Duration,
+    /// Collectors that run one at a time after the concurrent batch of a scrape, so their
+    /// per-database fan-out does not overlap with the other collectors.
+    pub serial_collectors: HashSet<String>,
 }
 
 impl CollectorConfig {
@@ -31,10 +108,19 @@ impl CollectorConfig {
             enabled_collectors: HashSet::new(),
             statements: StatementsConfig {
                 top_n: statements_top_n,
+                normalize: false,
+                min_mean_time_ms: 0.0,
             },
             sequences: SequencesConfig {
                 min_ratio: DEFAULT_SEQUENCES_MIN_RATIO,
             },
+            stat: StatConfig::default(),
+            activity: ActivityConfig::default(),
+            fdw: FdwConfig::default(),
+            tls: TlsConfig::default(),
+            intervals: HashMap::new(),
+            collection_jitter: Duration::ZERO,
+            serial_collectors: HashSet::new(),
         }
     }
 
@@ -45,6 +131,104 @@ impl CollectorConfig {
         self
     }
 
+    /// Export the server-computed `*_seconds_ago` gauges of the stat collector.
+    #[must_use]
+    pub const fn with_stat_seconds_ago(mut self, seconds_ago: bool) -> Self {
+        self.stat.seconds_ago = seconds_ago;
+        self
+    }
+
+    /// Include system catalog and TOAST tables in the stat collector.
+    #[must_use]
+    pub const fn with_stat_include_system(mut self, include_system: bool) -> Self {
+        self.stat.include_system = include_system;
+        self
+    }
+
+    /// Set the `pg_stat_activity_query_age_bucket` upper bounds of the activity collector.
+    #[must_use]
+    pub fn with_activity_query_age_buckets(mut self, buckets: &[f64]) -> Self {
+        self.activity.query_age_buckets = buckets.to_vec();
+        self
+    }
+
+    /// Set the `pg_stat_activity_stuck_backends` threshold of the activity collector.
+    #[must_use]
+    pub const fn with_activity_stuck_threshold(mut self, threshold: Duration) -> Self {
+        self.activity.stuck_threshold = threshold;
+        self
+    }
+
+    /// Set the `pg_stat_activity_slow_queries` threshold of the activity collector.
+    #[must_use]
+    pub const fn with_activity_slow_query_threshold(mut self, threshold: Duration) -> Self {
+        self.activity.slow_query_threshold = threshold;
+        self
+    }
+
+    /// Export `pg_stat_activity_backends_by_application` with at most `limit` distinct
+    /// application names; `None` keeps it off.
+    #[must_use]
+    pub const fn with_activity_by_application(mut self, limit: Option<usize>) -> Self {
+        self.activity.by_application = limit;
+        self
+    }
+
+    /// Enable the foreign server reachability probe of the fdw collector.
+    #[must_use]
+    pub const fn with_fdw_probe(mut self, probe: bool) -> Self {
+        self.fdw.probe = probe;
+        self
+    }
+
+    /// Read the tls collector's server certificate from `source`.
+    #[must_use]
+    pub fn with_tls_certificate(mut self, source: CertificateSource) -> Self {
+        self.tls.certificate = source;
+        self
+    }
+
+    /// Enable literal normalization of `pg_stat_statements` query text.
+    #[must_use]
+    pub const fn with_statements_normalize(mut self, normalize: bool) -> Self {
+        self.statements.normalize = normalize;
+        self
+    }
+
+    /// Skip statements whose mean execution time is below `min_mean_time_ms`.
+    #[must_use]
+    pub const fn with_statements_min_mean_time(mut self, min_mean_time_ms: f64) -> Self {
+        self.statements.min_mean_time_ms = min_mean_time_ms;
+        self
+    }
+
+    /// Run a collector in the background every `interval` instead of on each scrape.
+    #[must_use]
+    pub fn with_interval(mut self, name: &str, interval: Duration) -> Self {
+        self.intervals.insert(name.to_string(), interval);
+        self
+    }
+
+    /// Delay every background run by a random amount up to `jitter`.
+    #[must_use]
+    pub const fn with_collection_jitter(mut self, jitter: Duration) -> Self {
+        self.collection_jitter = jitter;
+        self
+    }
+
+    /// Run these collectors one at a time after the concurrent ones on every scrape.
+    #[must_use]
+    pub fn with_serial_collectors(mut self, collectors: &[String]) -> Self {
+        self.serial_collectors = collectors.iter().cloned().collect();
+        self
+    }
+
+    /// Background interval configured for a collector, if any
+    #[must_use]
+    pub fn interval(&self, name: &str) -> Option<Duration> {
+        self.intervals.get(name).copied()
+    }
+
     /// Enable collectors by name
     #[must_use]
     pub fn with_enabled(mut self, collectors: &[String]) -> Self {
@@ -77,6 +261,66 @@ mod tests {
     fn test_new_sets_statements_top_n() {
         let config = CollectorConfig::new(25);
         assert_eq!(config.statements.top_n, 25);
+        assert!(!config.statements.normalize);
+        assert!(config.statements.min_mean_time_ms.abs() < f64::EPSILON);
+        assert!(!config.stat.seconds_ago);
+        assert!(!config.stat.include_system);
+        assert_eq!(
+            config.activity.query_age_buckets,
+            DEFAULT_QUERY_AGE_BUCKETS.to_vec()
+        );
+        assert_eq!(config.activity.stuck_threshold, DEFAULT_STUCK_THRESHOLD);
+        assert_eq!(
+            config.activity.slow_query_threshold,
+            DEFAULT_SLOW_QUERY_THRESHOLD
+        );
+        assert_eq!(config.fdw, FdwConfig::default());
+        assert_eq!(config.tls.certificate, CertificateSource::ServerFile);
+    }
+
+    #[test]
+    fn test_with_tls_certificate() {
+        let config = CollectorConfig::new(25)
+            .with_tls_certificate(CertificateSource::Pem("-----BEGIN CERTIFICATE-----".into()));
+        assert!(matches!(config.tls.certificate, CertificateSource::Pem(_)));
+    }
+
+    #[test]
+    fn test_with_activity_stuck_threshold() {
+        let config =
+            CollectorConfig::new(25).with_activity_stuck_threshold(Duration::from_secs(90));
+        assert_eq!(config.activity.stuck_threshold, Duration::from_secs(90));
+    }
+
+    #[test]
+    fn test_with_activity_slow_query_threshold() {
+        let config =
+            CollectorConfig::new(25).with_activity_slow_query_threshold(Duration::from_secs(30));
+        assert_eq!(
+            config.activity.slow_query_threshold,
+            Duration::from_secs(30)
+        );
+        assert_eq!(config.activity.stuck_threshold, DEFAULT_STUCK_THRESHOLD);
+    }
+
+    #[test]
+    fn test_with_activity_by_application() {
+        assert_eq!(CollectorConfig::new(25).activity.by_application, None);
+        let config = CollectorConfig::new(25).with_activity_by_application(Some(10));
+        assert_eq!(config.activity.by_application, Some(10));
+    }
+
+    #[test]
+    fn test_with_fdw_probe() {
+        let config = CollectorConfig::new(25).with_fdw_probe(true);
+        assert!(config.fdw.probe);
+    }
+
+    #[test]
+    fn test_with_interval() {
+        let config = CollectorConfig::new(25).with_interval("statements", Duration::from_mins(5));
+        assert_eq!(config.interval("statements"), Some(Duration::from_mins(5)));
+        assert_eq!(config.interval("default"), None);
     }
 
     #[test]
@@ -0,0 +1,234 @@
+//! `--counter-reset-detection`: spot server counters that went backwards between scrapes.
+//!
+//! Many cumulative `PostgreSQL` statistics are exported as gauges, and all of them drop
+//! back to zero after `pg_stat_reset()` or a server restart. `rate()` copes with that for
+//! real counters only, so the gauges' resets are otherwise invisible to recording rules.
+//! The detector compares every series of [`COUNTER_RESET_METRICS`] with its value at the
+//! previous scrape and counts decreases in `pg_exporter_counter_reset{metric,labels}`.
+
+use crate::collectors::util::{ANONYMIZED_LABELS, anonymize_label_value, get_anonymize_salt};
+use prometheus::{IntGaugeVec, Opts, core::Collector as _, proto::Metric, proto::MetricFamily};
+use std::{collections::HashMap, fmt::Write as _, sync::Mutex};
+use tracing::debug;
+
+/// Families wrapping cumulative server counters that are checked for resets: the
+/// `database` collector's `pg_stat_database` counters and the `default` collector's
+/// `pg_stat_bgwriter` / `pg_stat_checkpointer` counters.
+pub const COUNTER_RESET_METRICS: [&str; 30] = [
+    "pg_stat_database_xact_commit",
+    "pg_stat_database_xact_rollback",
+    "pg_stat_database_blks_read",
+    "pg_stat_database_blks_hit",
+    "pg_stat_database_tup_returned",
+    "pg_stat_database_tup_fetched",
+    "pg_stat_database_tup_inserted",
+    "pg_stat_database_tup_updated",
+    "pg_stat_database_tup_deleted",
+    "pg_stat_database_conflicts",
+    "pg_stat_database_temp_files",
+    "pg_stat_database_temp_bytes",
+    "pg_stat_database_deadlocks",
+    "pg_stat_database_blk_read_time",
+    "pg_stat_database_blk_write_time",
+    "pg_stat_database_active_time_seconds_total",
+    "pg_stat_database_sessions_total",
+    "pg_stat_database_sessions_abandoned_total",
+    "pg_stat_database_sessions_fatal_total",
+    "pg_stat_database_sessions_killed_total",
+    "pg_stat_database_session_time_seconds_total",
+    "pg_stat_database_checksum_failures_total",
+    "pg_stat_bgwriter_buffers_clean_total",
+    "pg_stat_bgwriter_maxwritten_clean_total",
+    "pg_stat_bgwriter_buffers_alloc_total",
+    "pg_stat_checkpointer_timed_total",
+    "pg_stat_checkpointer_requested_total",
+    "pg_stat_checkpointer_buffers_written_total",
+    "pg_stat_checkpointer_write_time_seconds_total",
+    "pg_stat_checkpointer_sync_time_seconds_total",
+];
+
+/// `(metric, labels)` of one series.
+type SeriesKey = (String, String);
+
+/// Remembers the last value of every [`COUNTER_RESET_METRICS`] series across scrapes.
+pub struct CounterResetDetector {
+    previous: Mutex<HashMap<SeriesKey, f64>>,
+    resets: IntGaugeVec,
+}
+
+impl Default for CounterResetDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CounterResetDetector {
+    /// Creates a new `CounterResetDetector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        Self {
+            previous: Mutex::new(HashMap::new()),
+            resets: IntGaugeVec::new(
+                Opts::new(
+                    "pg_exporter_counter_reset",
+                    "Times a server counter decreased between two scrapes (stats reset or restart) since the exporter started",
+                ),
+                &["metric", "labels"],
+            )
+            .expect("Failed to create pg_exporter_counter_reset"),
+        }
+    }
+
+    /// Compare the gathered `families` with the previous scrape and count every series
+    /// that decreased. Series missing from `families` are forgotten, so a dropped
+    /// database or a failed collector starts from a new baseline instead of a reset.
+    pub fn observe(&self, families: &[MetricFamily]) {
+        let mut previous = match self.previous.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        let mut current = HashMap::with_capacity(previous.len());
+        for family in families {
+            if !COUNTER_RESET_METRICS.contains(&family.name()) {
+                continue;
+            }
+            for metric in family.get_metric() {
+                let Some(value) = sample_value(metric) else {
+                    continue;
+                };
+                let key = (family.name().to_string(), labels_value(metric));
+                if previous.get(&key).is_some_and(|before| value < *before) {
+                    debug!(metric = %key.0, labels = %key.1, "counter reset detected");
+                    self.resets.with_label_values(&[&key.0, &key.1]).inc();
+                }
+                current.insert(key, value);
+            }
+        }
+
+        *previous = current;
+    }
+
+    /// The `pg_exporter_counter_reset` family, empty until a reset was detected.
+    #[must_use]
+    pub fn families(&self) -> Vec<MetricFamily> {
+        self.resets
+            .collect()
+            .into_iter()
+            .filter(|family| !family.get_metric().is_empty())
+            .collect()
+    }
+}
+
+/// Value of a gauge or counter sample; other metric types are not counters.
+fn sample_value(metric: &Metric) -> Option<f64> {
+    if let Some(gauge) = metric.gauge.as_ref() {
+        return Some(gauge.value());
+    }
+    metric
+        .counter
+        .as_ref()
+        .map(prometheus::proto::Counter::value)
+}
+
+/// The series' labels as `name="value"` pairs, e.g. `datid="5",datname="postgres"`.
+/// Database names are hashed like every other label under `--anonymize-labels`.
+fn labels_value(metric: &Metric) -> String {
+    let salt = get_anonymize_salt();
+    let mut labels = String::new();
+    for label in metric.get_label() {
+        let value = match salt {
+            Some(salt) if ANONYMIZED_LABELS.contains(&label.name()) => {
+                anonymize_label_value(label.value(), salt)
+            }
+            _ => label.value().to_string(),
+        };
+        if !labels.is_empty() {
+            labels.push(',');
+        }
+        let _ = write!(labels, "{}=\"{value}\"", label.name());
+    }
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus::{GaugeVec, Registry};
+
+    fn xact_commit(value: f64) -> prometheus::Result<Vec<MetricFamily>> {
+        let registry = Registry::new();
+        let gauge = GaugeVec::new(
+            Opts::new("pg_stat_database_xact_commit", "test"),
+            &["datid", "datname"],
+        )?;
+        registry.register(Box::new(gauge.clone()))?;
+        gauge.with_label_values(&["5", "postgres"]).set(value);
+        Ok(registry.gather())
+    }
+
+    fn resets(detector: &CounterResetDetector) -> Vec<(String, String, f64)> {
+        detector
+            .families()
+            .iter()
+            .flat_map(MetricFamily::get_metric)
+            .map(|metric| {
+                let label = |name: &str| {
+                    metric
+                        .get_label()
+                        .iter()
+                        .find(|l| l.name() == name)
+                        .map(|l| l.value().to_string())
+                        .unwrap_or_default()
+                };
+                (label("metric"), label("labels"), metric.get_gauge().value())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_counter_reset_detected_only_on_decrease() -> prometheus::Result<()> {
+        let detector = CounterResetDetector::new();
+
+        detector.observe(&xact_commit(100.0)?);
+        detector.observe(&xact_commit(150.0)?);
+        assert!(detector.families().is_empty(), "increase is not a reset");
+
+        detector.observe(&xact_commit(3.0)?);
+        detector.observe(&xact_commit(3.0)?);
+        assert_eq!(
+            resets(&detector),
+            vec![(
+                "pg_stat_database_xact_commit".to_string(),
+                "datid=\"5\",datname=\"postgres\"".to_string(),
+                1.0
+            )]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_counter_reset_forgets_missing_series() -> prometheus::Result<()> {
+        let detector = CounterResetDetector::new();
+
+        detector.observe(&xact_commit(100.0)?);
+        detector.observe(&[]);
+        detector.observe(&xact_commit(3.0)?);
+        assert!(
+            detector.families().is_empty(),
+            "no baseline after the series went away"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_counter_reset_ignores_unlisted_metrics() {
+        assert!(!COUNTER_RESET_METRICS.contains(&"pg_stat_database_blks_hit_ratio"));
+        assert!(!COUNTER_RESET_METRICS.contains(&"pg_stat_database_numbackends"));
+    }
+}
@@ -1,14 +1,27 @@
-use crate::collectors::{Collector, i64_to_f64, util::get_excluded_databases};
+use crate::collectors::{
+    Collector, i64_to_f64,
+    util::{acquire_connection, get_excluded_databases, resolve_server_version},
+};
 use anyhow::Result;
 use futures::future::BoxFuture;
-use prometheus::{GaugeVec, Opts, Registry};
-use sqlx::{PgPool, Row};
+use prometheus::{GaugeVec, IntGaugeVec, Opts, Registry};
+use sqlx::{PgPool, Row, postgres::PgRow};
 use tracing::{debug, info_span, instrument};
 use tracing_futures::Instrument as _;
 
 /// Tracks `pg_database` metrics:
 /// - `pg_database_size_bytes`{`datname`}
 /// - `pg_database_connection_limit`{`datname`}
+/// - `pg_database_is_template`{`datname`} (1 for template databases such as `template1`)
+/// - `pg_database_encoding_info`{`datname`,`encoding`,`collate`,`ctype`,`icu_locale`} (always 1)
+/// - `pg_server_locale_info`{`lc_collate`,`lc_ctype`} (always 1), the locale of the database
+///   the exporter connects to, i.e. what the `lc_collate`/`lc_ctype` settings reported
+///   before `PostgreSQL` 16 removed them
+///
+/// `icu_locale` is empty unless the database uses the ICU provider (`PostgreSQL` 15+).
+///
+/// Unlike the per-database collectors, this lists template databases too; use
+/// `pg_database_is_template == 0` to keep dashboards to real databases.
 ///
 /// Exclusions:
 /// - Set via CLI flag `--exclude-databases a,b,c` or env `PG_EXPORTER_EXCLUDE_DATABASES`.
@@ -17,6 +30,9 @@ use tracing_futures::Instrument as _;
 pub struct DatabaseSubCollector {
     size_bytes: GaugeVec,       // pg_database_size_bytes{datname}
     connection_limit: GaugeVec, // pg_database_connection_limit{datname}
+    is_template: IntGaugeVec,   // pg_database_is_template{datname}
+    encoding_info: IntGaugeVec, // pg_database_encoding_info{datname,encoding,collate,ctype,icu_locale}
+    server_locale: IntGaugeVec, // pg_server_locale_info{lc_collate,lc_ctype}
 }
 
 impl Default for DatabaseSubCollector {
@@ -49,11 +65,85 @@ impl DatabaseSubCollector {
         )
         .expect("register pg_database_connection_limit");
 
+        let is_template = IntGaugeVec::new(
+            Opts::new(
+                "pg_database_is_template",
+                "Whether the database is a template (1) or a regular database (0)",
+            ),
+            &["datname"],
+        )
+        .expect("register pg_database_is_template");
+
+        let encoding_info = IntGaugeVec::new(
+            Opts::new(
+                "pg_database_encoding_info",
+                "Character set encoding and locale of the database (always 1)",
+            ),
+            &["datname", "encoding", "collate", "ctype", "icu_locale"],
+        )
+        .expect("register pg_database_encoding_info");
+
+        let server_locale = IntGaugeVec::new(
+            Opts::new(
+                "pg_server_locale_info",
+                "Collation and character classification locale of the database the exporter connects to (always 1)",
+            ),
+            &["lc_collate", "lc_ctype"],
+        )
+        .expect("register pg_server_locale_info");
+
         Self {
             size_bytes,
             connection_limit,
+            is_template,
+            encoding_info,
+            server_locale,
         }
     }
+
+    /// Export the encoding and locale columns of one `pg_database` row.
+    fn record_locale(&self, row: &PgRow, datname: &str) -> Result<()> {
+        let encoding: String = row
+            .try_get::<Option<String>, _>("encoding")?
+            .unwrap_or_default();
+        let collate: String = row
+            .try_get::<Option<String>, _>("datcollate")?
+            .unwrap_or_default();
+        let ctype: String = row
+            .try_get::<Option<String>, _>("datctype")?
+            .unwrap_or_default();
+        let icu_locale: String = row
+            .try_get::<Option<String>, _>("icu_locale")?
+            .unwrap_or_default();
+
+        self.encoding_info
+            .with_label_values(&[datname, &encoding, &collate, &ctype, &icu_locale])
+            .set(1);
+
+        if row
+            .try_get::<Option<bool>, _>("is_current")?
+            .unwrap_or(false)
+        {
+            self.server_locale
+                .with_label_values(&[&collate, &ctype])
+                .set(1);
+        }
+
+        Ok(())
+    }
+}
+
+/// SQL expression for the ICU locale of a `pg_database` row, NULL unless the provider is
+/// ICU. `PostgreSQL` 15 added `daticulocale`, which 17 renamed to `datlocale` when it also
+/// started using that column for the builtin provider.
+const fn icu_locale_column(version: i32) -> &'static str {
+    if version >= 170_000 {
+        "CASE WHEN datlocprovider = 'i' THEN datlocale END"
+    } else if version >= 150_000 {
+        "daticulocale"
+    } else {
+        "NULL::text"
+    }
 }
 
 impl Collector for DatabaseSubCollector {
@@ -64,6 +154,9 @@ impl Collector for DatabaseSubCollector {
     fn register_metrics(&self, registry: &Registry) -> Result<()> {
         registry.register(Box::new(self.size_bytes.clone()))?;
         registry.register(Box::new(self.connection_limit.clone()))?;
+        registry.register(Box::new(self.is_template.clone()))?;
+        registry.register(Box::new(self.encoding_info.clone()))?;
+        registry.register(Box::new(self.server_locale.clone()))?;
         Ok(())
     }
 
@@ -78,35 +171,49 @@ impl Collector for DatabaseSubCollector {
             // 0) Reset all metrics to clear stale data (e.g. dropped databases)
             self.size_bytes.reset();
             self.connection_limit.reset();
+            self.is_template.reset();
+            self.encoding_info.reset();
+            self.server_locale.reset();
 
             // Build exclusion list from global OnceCell (set at startup via Clap/env).
             let excluded_list: Vec<String> = get_excluded_databases().to_vec();
 
-            // Single round-trip: size + connection limit per database, with server-side exclusion.
+            let version = resolve_server_version(pool).await?;
+
+            // Single round-trip: size, connection limit and locale per database, with server-side exclusion.
             let q_span = info_span!(
                 "db.query",
                 otel.kind = "client",
                 db.system = "postgresql",
                 db.operation = "SELECT",
-                db.statement = "SELECT datname, datconnlimit, pg_database_size(datname) FROM pg_database WHERE NOT (datname = ANY($1))",
+                db.statement = "SELECT datname, datconnlimit, datistemplate, pg_database_size(datname), encoding, datcollate, datctype FROM pg_database WHERE NOT (datname = ANY($1))",
                 db.sql.table = "pg_database"
             );
 
-            let rows = sqlx::query(
+            let query = format!(
                 r"
                 SELECT
                     datname,
                     datconnlimit,
-                    pg_database_size(datname)::bigint AS size
+                    datistemplate,
+                    pg_database_size(datname)::bigint AS size,
+                    pg_encoding_to_char(encoding)::text AS encoding,
+                    datcollate::text AS datcollate,
+                    datctype::text AS datctype,
+                    ({icu_locale})::text AS icu_locale,
+                    datname = current_database() AS is_current
                 FROM pg_database
                 WHERE NOT (datname = ANY($1))
                 ORDER BY datname
                 ",
-            )
-            .bind(&excluded_list)
-            .fetch_all(pool)
-            .instrument(q_span)
-            .await?;
+                icu_locale = icu_locale_column(version),
+            );
+
+            let rows = sqlx::query(sqlx::AssertSqlSafe(query.as_str()))
+                .bind(&excluded_list)
+                .fetch_all(&mut *acquire_connection(pool).await?)
+                .instrument(q_span)
+                .await?;
 
             let apply_span = info_span!("pg_database.apply_metrics", databases = rows.len());
             let _g = apply_span.enter();
@@ -129,6 +236,13 @@ impl Collector for DatabaseSubCollector {
                 let size_val = i64_to_f64(size.unwrap_or(0));
                 self.size_bytes.with_label_values(&[&dat]).set(size_val);
 
+                let is_template: bool = row.try_get("datistemplate").unwrap_or(false);
+                self.is_template
+                    .with_label_values(&[&dat])
+                    .set(i64::from(is_template));
+
+                self.record_locale(row, &dat)?;
+
                 debug!(
                     datname = %dat,
                     connection_limit = limit_val,
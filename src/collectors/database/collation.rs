@@ -0,0 +1,253 @@
+use crate::collectors::util::{
+    acquire_connection, acquire_db_query_permit, get_default_database, in_acquire_scope,
+    list_target_databases, open_db_connection, resolve_server_version,
+};
+use crate::collectors::{Collector, all_databases_failed};
+use anyhow::{Result, anyhow};
+use futures::future::BoxFuture;
+use prometheus::{IntGaugeVec, Opts, Registry};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use tokio::task::JoinSet;
+use tracing::{debug, error, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// `pg_database.datcollversion` and `pg_database_collation_actual_version()` were added in
+/// `PostgreSQL` 15; older servers are skipped.
+pub(crate) const MIN_COLLATION_VERSION_CHECK_VERSION: i32 = 150_000;
+
+/// Collector for collation library version mismatches per database
+///
+/// **What it measures:**
+/// `PostgreSQL` records the version of the collation library (glibc or ICU) a collation
+/// was created with. When the OS upgrades that library, sort order can change under
+/// existing text indexes, which then silently return wrong results or miss rows until
+/// they are rebuilt. The server only warns about it in the log of new sessions.
+///
+/// **Key metrics:**
+/// - `pg_collation_version_mismatch{datname,collname}`: 1 for every collation whose
+///   recorded `collversion` differs from `pg_collation_actual_version()`. The database's
+///   own default collation (`datcollversion`) is reported as `collname="default"`.
+///   Indexes using it should be reindexed, then `ALTER COLLATION ... REFRESH VERSION`
+///   (or `ALTER DATABASE ... REFRESH COLLATION VERSION`) records the new version.
+///
+/// Only mismatches are exported: ICU clusters carry hundreds of versioned collations,
+/// and a series for each would mostly be zeros. No series means no mismatch.
+///
+/// **Multi-database:**
+/// `pg_collation` is a per-database catalog, so this collector iterates every connectable,
+/// non-excluded database like the other per-database collectors.
+#[derive(Clone)]
+pub struct DatabaseCollationCollector {
+    version_mismatch: IntGaugeVec,
+}
+
+impl Default for DatabaseCollationCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Collations of the current database whose recorded library version no longer matches
+/// the library in use. Collations without a recorded version (e.g. `C`/`POSIX`) are
+/// never versioned and are skipped.
+const COLLATION_MISMATCH_QUERY: &str = r"
+    SELECT
+        current_database() AS datname,
+        c.collname::text AS collname
+    FROM pg_collation c
+    WHERE c.collversion IS NOT NULL
+      AND c.collversion IS DISTINCT FROM pg_collation_actual_version(c.oid)
+    UNION ALL
+    SELECT
+        d.datname::text AS datname,
+        'default' AS collname
+    FROM pg_database d
+    WHERE d.datname = current_database()
+      AND d.datcollversion IS NOT NULL
+      AND d.datcollversion IS DISTINCT FROM pg_database_collation_actual_version(d.oid)
+    ";
+
+impl DatabaseCollationCollector {
+    /// Creates a new `DatabaseCollationCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        Self {
+            version_mismatch: IntGaugeVec::new(
+                Opts::new(
+                    "pg_collation_version_mismatch",
+                    "Collation whose recorded library version differs from the library in use (1); indexes using it need a REINDEX",
+                ),
+                &["datname", "collname"],
+            )
+            .expect("Failed to create pg_collation_version_mismatch"),
+        }
+    }
+
+    fn sample_from_row(row: &PgRow) -> Result<(String, String)> {
+        Ok((row.try_get("datname")?, row.try_get("collname")?))
+    }
+}
+
+impl Collector for DatabaseCollationCollector {
+    fn name(&self) -> &'static str {
+        "database_collation"
+    }
+
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.version_mismatch.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector = "database_collation", otel.kind = "internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let version = resolve_server_version(pool).await?;
+            if version < MIN_COLLATION_VERSION_CHECK_VERSION {
+                debug!(
+                    server_version_num = version,
+                    "database_collation: collation version checks require PostgreSQL 15+; skipping"
+                );
+                self.version_mismatch.reset();
+                return Ok(());
+            }
+
+            // 1) Discover connectable, non-excluded databases via the shared pool.
+            let dbs = list_target_databases(pool).await?;
+
+            let shared_pool = pool.clone();
+            let default_db = get_default_database().map(std::string::ToString::to_string);
+
+            // 2) One task per DB. The default DB reuses the shared pool; every other database
+            // must pass through the global per-database connection limiter.
+            let mut tasks: JoinSet<Result<Vec<(String, String)>>> = JoinSet::new();
+
+            let num_dbs = dbs.len();
+            for datname in dbs {
+                let shared_pool = shared_pool.clone();
+                let default_db = default_db.clone();
+
+                tasks.spawn(in_acquire_scope(async move {
+                    let use_shared = default_db.as_deref() == Some(datname.as_str());
+
+                    let query_span = info_span!(
+                        "db.query",
+                        otel.kind = "client",
+                        db.system = "postgresql",
+                        db.operation = "SELECT",
+                        db.statement = "SELECT collname FROM pg_collation WHERE collversion IS DISTINCT FROM pg_collation_actual_version(oid)",
+                        db.sql.table = "pg_collation",
+                        datname = %datname,
+                        reuse_pool = use_shared
+                    );
+
+                    let db_query_permit = if use_shared {
+                        None
+                    } else {
+                        Some(acquire_db_query_permit().await.map_err(|e| {
+                            anyhow!("database_collation: failed to acquire database query permit: {e}")
+                        })?)
+                    };
+
+                    let rows: Vec<PgRow> = if use_shared {
+                        sqlx::query(COLLATION_MISMATCH_QUERY)
+                            .fetch_all(&mut *acquire_connection(&shared_pool).await?)
+                            .instrument(query_span)
+                            .await?
+                    } else {
+                        let Some(permit) = db_query_permit.as_ref() else {
+                            return Err(anyhow!("database_collation: missing database query permit"));
+                        };
+                        let mut conn = open_db_connection(&datname, permit).await?;
+                        sqlx::query(COLLATION_MISMATCH_QUERY)
+                            .fetch_all(&mut conn)
+                            .instrument(query_span)
+                            .await?
+                    };
+
+                    rows.iter().map(Self::sample_from_row).collect()
+                }));
+            }
+
+            let mut all_samples = Vec::new();
+            let mut failures = Vec::new();
+            let mut failed_db_count = 0;
+            while let Some(joined) = tasks.join_next().await {
+                match joined {
+                    Ok(Ok(samples)) => all_samples.extend(samples),
+                    Ok(Err(e)) => {
+                        error!(error=?e, "database_collation: task returned error");
+                        failures.push(e.to_string());
+                        failed_db_count += 1;
+                    }
+                    Err(e) => {
+                        error!(error=?e, "database_collation: task join error");
+                        failures.push(e.to_string());
+                        failed_db_count += 1;
+                    }
+                }
+            }
+
+            if all_databases_failed(num_dbs, failed_db_count) {
+                return Err(anyhow!(
+                    "database_collation collection failed for ALL {failed_db_count} database task(s): {}",
+                    failures.join("; ")
+                ));
+            }
+
+            if !failures.is_empty() {
+                error!(
+                    failed_databases = failed_db_count,
+                    errors = %failures.join("; "),
+                    "database_collation: continuing with partial snapshot after per-database failures"
+                );
+            }
+
+            self.version_mismatch.reset();
+
+            for (datname, collname) in &all_samples {
+                self.version_mismatch
+                    .with_label_values(&[datname.as_str(), collname.as_str()])
+                    .set(1);
+
+                debug!(datname = %datname, collname = %collname, "collation version mismatch");
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collation_collector_name() {
+        let collector = DatabaseCollationCollector::new();
+        assert_eq!(collector.name(), "database_collation");
+    }
+
+    #[test]
+    fn test_collation_collector_registers() {
+        let registry = Registry::new();
+        let collector = DatabaseCollationCollector::new();
+        assert!(collector.register_metrics(&registry).is_ok());
+    }
+
+    #[test]
+    fn test_collation_query_checks_collations_and_database_default() {
+        assert!(COLLATION_MISMATCH_QUERY.contains("pg_collation_actual_version(c.oid)"));
+        assert!(COLLATION_MISMATCH_QUERY.contains("pg_database_collation_actual_version(d.oid)"));
+        assert!(COLLATION_MISMATCH_QUERY.contains("'default' AS collname"));
+    }
+}
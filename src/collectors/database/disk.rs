@@ -0,0 +1,305 @@
+use crate::collectors::Collector;
+use crate::collectors::util::acquire_connection;
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{IntGaugeVec, Opts, Registry};
+use sqlx::{PgPool, Row};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+use tracing::{debug, info_span, instrument, warn};
+use tracing_futures::Instrument as _;
+
+/// SQLSTATE for `insufficient_privilege`.
+const INSUFFICIENT_PRIVILEGE: &str = "42501";
+
+/// Tablespaces the role may size: `pg_tablespace_size()` needs CREATE on the
+/// tablespace or `pg_read_all_stats`, except for the database's default tablespace.
+/// Filtering here keeps one unreadable tablespace from failing the whole query.
+const TABLESPACE_SIZE_QUERY: &str = r"
+    SELECT
+        spcname,
+        pg_tablespace_size(oid)::bigint AS size
+    FROM pg_tablespace
+    WHERE spcname = 'pg_default'
+       OR has_tablespace_privilege(oid, 'CREATE')
+       OR pg_has_role(current_user, 'pg_read_all_stats', 'USAGE')
+    ORDER BY spcname
+";
+
+/// `pg_ls_waldir()` needs superuser or `pg_monitor`.
+const WAL_DIRECTORY_SIZE_QUERY: &str =
+    "SELECT COALESCE(SUM(size), 0)::bigint AS size FROM pg_ls_waldir()";
+
+/// `data_directory` needs superuser or `pg_read_all_settings`.
+const DATA_DIRECTORY_QUERY: &str = "SELECT current_setting('data_directory') AS path";
+
+/// On-disk usage of the cluster, complementing the logical `pg_database_size_bytes`:
+/// - `pg_tablespace_size_bytes{tablespace}`
+/// - `pg_wal_directory_size_bytes` (sum of the files in `pg_wal`)
+/// - `pg_data_directory_size_bytes` (`pg_default` + `pg_global` + `pg_wal`)
+/// - `pg_data_directory_info{path}` (always 1)
+///
+/// Each source needs its own privilege. When the role lacks one, the affected series
+/// are not exported (rather than exported as a misleading 0) and a warning is logged
+/// once; the rest of the collector keeps working.
+#[derive(Clone)]
+pub struct DatabaseDiskCollector {
+    tablespace_size_bytes: IntGaugeVec, // pg_tablespace_size_bytes{tablespace}
+    wal_directory_size_bytes: IntGaugeVec, // pg_wal_directory_size_bytes
+    data_directory_size_bytes: IntGaugeVec, // pg_data_directory_size_bytes
+    data_directory_info: IntGaugeVec,   // pg_data_directory_info{path}
+    privilege_warned: Arc<AtomicBool>,
+}
+
+impl Default for DatabaseDiskCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DatabaseDiskCollector {
+    /// Creates a new `DatabaseDiskCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let tablespace_size_bytes = IntGaugeVec::new(
+            Opts::new(
+                "pg_tablespace_size_bytes",
+                "Disk space used by the tablespace, in bytes",
+            ),
+            &["tablespace"],
+        )
+        .expect("Failed to create pg_tablespace_size_bytes");
+
+        let wal_directory_size_bytes = IntGaugeVec::new(
+            Opts::new(
+                "pg_wal_directory_size_bytes",
+                "Total size of the files in the pg_wal directory, in bytes",
+            ),
+            &[],
+        )
+        .expect("Failed to create pg_wal_directory_size_bytes");
+
+        let data_directory_size_bytes = IntGaugeVec::new(
+            Opts::new(
+                "pg_data_directory_size_bytes",
+                "Disk space used by the data directory (pg_default + pg_global tablespaces + pg_wal), in bytes",
+            ),
+            &[],
+        )
+        .expect("Failed to create pg_data_directory_size_bytes");
+
+        let data_directory_info = IntGaugeVec::new(
+            Opts::new(
+                "pg_data_directory_info",
+                "Location of the PostgreSQL data directory (always 1)",
+            ),
+            &["path"],
+        )
+        .expect("Failed to create pg_data_directory_info");
+
+        Self {
+            tablespace_size_bytes,
+            wal_directory_size_bytes,
+            data_directory_size_bytes,
+            data_directory_info,
+            privilege_warned: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Turn a permission error into `None` so the caller can skip that source.
+    fn allow_missing_privilege<T>(
+        &self,
+        source: &str,
+        res: Result<T, sqlx::Error>,
+    ) -> Result<Option<T>> {
+        match res {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if is_insufficient_privilege(&e) => {
+                if !self.privilege_warned.swap(true, Ordering::Relaxed) {
+                    warn!(
+                        source,
+                        error = %e,
+                        "collector.database: missing privilege for disk usage; skipping those metrics \
+                         (grant pg_monitor to export them)"
+                    );
+                }
+                debug!(source, "skipping disk usage source without privilege");
+                Ok(None)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+pub(super) fn is_insufficient_privilege(error: &sqlx::Error) -> bool {
+    error
+        .as_database_error()
+        .and_then(sqlx::error::DatabaseError::code)
+        .is_some_and(|code| code == INSUFFICIENT_PRIVILEGE)
+}
+
+/// Data directory footprint, or `None` unless every part could be read.
+fn data_directory_size(
+    tablespaces: &[(String, i64)],
+    wal_directory_size: Option<i64>,
+) -> Option<i64> {
+    let size_of = |name: &str| {
+        tablespaces
+            .iter()
+            .find(|(spcname, _)| spcname == name)
+            .map(|(_, size)| *size)
+    };
+
+    Some(
+        size_of("pg_default")?
+            .saturating_add(size_of("pg_global")?)
+            .saturating_add(wal_directory_size?),
+    )
+}
+
+impl Collector for DatabaseDiskCollector {
+    fn name(&self) -> &'static str {
+        "database_disk"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "database_disk")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.tablespace_size_bytes.clone()))?;
+        registry.register(Box::new(self.wal_directory_size_bytes.clone()))?;
+        registry.register(Box::new(self.data_directory_size_bytes.clone()))?;
+        registry.register(Box::new(self.data_directory_info.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector="database_disk", otel.kind="internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let tablespace_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement = "SELECT spcname, pg_tablespace_size(oid) FROM pg_tablespace",
+                db.sql.table = "pg_tablespace"
+            );
+            let tablespace_rows = sqlx::query(TABLESPACE_SIZE_QUERY)
+                .fetch_all(&mut *acquire_connection(pool).await?)
+                .instrument(tablespace_span)
+                .await;
+            let tablespace_rows = self
+                .allow_missing_privilege("pg_tablespace_size", tablespace_rows)?
+                .unwrap_or_default();
+
+            let wal_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement = WAL_DIRECTORY_SIZE_QUERY
+            );
+            let wal_size = sqlx::query_scalar::<_, i64>(WAL_DIRECTORY_SIZE_QUERY)
+                .fetch_one(&mut *acquire_connection(pool).await?)
+                .instrument(wal_span)
+                .await;
+            let wal_size = self.allow_missing_privilege("pg_ls_waldir", wal_size)?;
+
+            let data_dir_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement = DATA_DIRECTORY_QUERY
+            );
+            let data_directory = sqlx::query_scalar::<_, String>(DATA_DIRECTORY_QUERY)
+                .fetch_one(&mut *acquire_connection(pool).await?)
+                .instrument(data_dir_span)
+                .await;
+            let data_directory = self.allow_missing_privilege("data_directory", data_directory)?;
+
+            let mut tablespaces = Vec::with_capacity(tablespace_rows.len());
+            for row in &tablespace_rows {
+                let spcname: String = row.try_get("spcname")?;
+                let size: i64 = row.try_get::<Option<i64>, _>("size")?.unwrap_or(0);
+                tablespaces.push((spcname, size));
+            }
+
+            self.tablespace_size_bytes.reset();
+            self.wal_directory_size_bytes.reset();
+            self.data_directory_size_bytes.reset();
+            self.data_directory_info.reset();
+
+            for (spcname, size) in &tablespaces {
+                self.tablespace_size_bytes
+                    .with_label_values(&[spcname.as_str()])
+                    .set(*size);
+            }
+            if let Some(size) = wal_size {
+                self.wal_directory_size_bytes
+                    .with_label_values(&[] as &[&str])
+                    .set(size);
+            }
+            if let Some(size) = data_directory_size(&tablespaces, wal_size) {
+                self.data_directory_size_bytes
+                    .with_label_values(&[] as &[&str])
+                    .set(size);
+            }
+            if let Some(path) = &data_directory {
+                self.data_directory_info
+                    .with_label_values(&[path.as_str()])
+                    .set(1);
+            }
+
+            debug!(
+                tablespaces = tablespaces.len(),
+                wal_directory_size = wal_size,
+                "updated disk usage metrics"
+            );
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tablespaces(entries: &[(&str, i64)]) -> Vec<(String, i64)> {
+        entries
+            .iter()
+            .map(|(name, size)| ((*name).to_string(), *size))
+            .collect()
+    }
+
+    #[test]
+    fn test_data_directory_size_sums_default_global_and_wal() {
+        let spaces = tablespaces(&[("pg_default", 100), ("pg_global", 10), ("fast_ssd", 5000)]);
+        assert_eq!(data_directory_size(&spaces, Some(16)), Some(126));
+    }
+
+    #[test]
+    fn test_data_directory_size_requires_every_part() {
+        let spaces = tablespaces(&[("pg_default", 100)]);
+        assert_eq!(data_directory_size(&spaces, Some(16)), None);
+
+        let spaces = tablespaces(&[("pg_default", 100), ("pg_global", 10)]);
+        assert_eq!(data_directory_size(&spaces, None), None);
+    }
+}
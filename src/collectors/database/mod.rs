@@ -1,12 +1,10 @@
-use crate::collectors::Collector;
+use crate::collectors::{Collector, util::collect_subs};
 use anyhow::Result;
 use futures::future::BoxFuture;
-use futures::stream::{FuturesUnordered, StreamExt};
 use prometheus::Registry;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tracing::{debug, info_span, instrument, warn};
-use tracing_futures::Instrument as _;
 
 // Sub-collectors under the "database" umbrella.
 pub mod stats;
@@ -15,6 +13,18 @@ use stats::DatabaseStatCollector;
 pub mod catalog;
 use catalog::DatabaseSubCollector;
 
+pub mod disk;
+use disk::DatabaseDiskCollector;
+
+pub mod temp_tables;
+use temp_tables::DatabaseTempTablesCollector;
+
+pub mod temp_files;
+use temp_files::DatabaseTempFilesCollector;
+
+pub mod collation;
+use collation::DatabaseCollationCollector;
+
 /// `DatabaseCollector` aggregates db-level metrics from multiple sources.
 /// Collect sub-collectors concurrently to reduce tail latency.
 #[derive(Clone, Default)]
@@ -29,6 +39,10 @@ impl DatabaseCollector {
             subs: vec![
                 Arc::new(DatabaseStatCollector::new()),
                 Arc::new(DatabaseSubCollector::new()),
+                Arc::new(DatabaseDiskCollector::new()),
+                Arc::new(DatabaseTempTablesCollector::new()),
+                Arc::new(DatabaseTempFilesCollector::new()),
+                Arc::new(DatabaseCollationCollector::new()),
             ],
         }
     }
@@ -68,20 +82,7 @@ impl Collector for DatabaseCollector {
         fields(collector = "database", otel.kind = "internal")
     )]
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
-        Box::pin(async move {
-            // Collect sub-collectors concurrently (they're independent).
-            let mut tasks = FuturesUnordered::new();
-
-            for sub in &self.subs {
-                let span = info_span!("collector.collect", sub_collector = %sub.name(), otel.kind="internal");
-                tasks.push(sub.collect(pool).instrument(span));
-            }
-
-            while let Some(res) = tasks.next().await {
-                res?;
-            }
-            Ok(())
-        })
+        Box::pin(async move { collect_subs(&self.subs, pool).await })
     }
 
     fn enabled_by_default(&self) -> bool {
@@ -1,6 +1,6 @@
 use crate::collectors::{
     Collector, i64_to_f64,
-    util::{MS_TO_SEC, get_excluded_databases},
+    util::{MS_TO_SEC, acquire_connection, datname_label, get_excluded_databases},
 };
 use anyhow::Result;
 use futures::future::BoxFuture;
@@ -40,6 +40,30 @@ use tracing_futures::Instrument as _;
 ///
 /// **NEW - Cache Hit Ratio Metrics (Critical for Performance):**
 /// - `pg_stat_database_blks_hit_ratio` {datid,datname} - Buffer cache hit ratio (0.0-1.0)
+/// - `pg_stat_database_scan_efficiency_ratio` {datid,datname} - `tup_returned / tup_fetched`
+/// - `pg_stat_database_rollback_ratio` {datid,datname} - `xact_rollback / (xact_commit + xact_rollback)`
+/// - `pg_stat_database_deadlocks_per_million_xacts` {datid,datname} - `deadlocks * 1e6 / (xact_commit + xact_rollback)`
+///
+/// **Understanding Deadlocks per Million Transactions:**
+///
+/// Raw deadlock counts grow with traffic, so they cannot be compared across databases. Normalized
+/// by finished transactions, a quiet database with frequent deadlocks stands out from a busy one
+/// with the occasional deadlock. Cumulative since the last stats reset and 0 before any
+/// transaction finished.
+///
+/// **Understanding Rollback Ratio:**
+///
+/// The share of finished transactions that rolled back. Applications that roll back on purpose
+/// keep a steady baseline; a rising ratio points at application errors, serialization failures
+/// or deadlocks. Cumulative since the last stats reset and 0 before any transaction finished.
+///
+/// **Understanding Scan Efficiency:**
+///
+/// `tup_returned` counts rows read by scans, `tup_fetched` the rows those scans actually
+/// produced for queries. A ratio far above 1 means scans read many more rows than they
+/// return, the signature of sequential scans filtering large tables. It is a database-wide
+/// hint; follow up with the per-table `seq_scan`/`idx_scan` counters from `--collector.stat`.
+/// The ratio is cumulative since the last stats reset and 0 when nothing was fetched yet.
 ///
 /// **Understanding Cache Hit Ratio:**
 ///
@@ -152,11 +176,11 @@ pub struct DatabaseStatCollector {
 
     stats_reset: GaugeVec,
 
-    active_time_seconds_total: GaugeVec, // PG >= 14
-    sessions_total: GaugeVec,            // PG >= 14
-    sessions_abandoned_total: GaugeVec,  // PG >= 14
-    sessions_fatal_total: GaugeVec,      // PG >= 14
-    sessions_killed_total: GaugeVec,     // PG >= 14
+    active_time_seconds_total: GaugeVec,  // PG >= 14
+    sessions_total: GaugeVec,             // PG >= 14
+    sessions_abandoned_total: GaugeVec,   // PG >= 14
+    sessions_fatal_total: GaugeVec,       // PG >= 14
+    sessions_killed_total: GaugeVec,      // PG >= 14
     session_time_seconds_total: GaugeVec, // PG >= 14
 
     checksum_failures_total: GaugeVec,                 // PG >= 12
@@ -166,6 +190,15 @@ pub struct DatabaseStatCollector {
     // Measures buffer cache efficiency: blks_hit / (blks_hit + blks_read)
     // Alert when < 0.90 (90% hit ratio indicates memory pressure)
     blks_hit_ratio: GaugeVec,
+
+    // Rows read per row fetched: tup_returned / tup_fetched
+    scan_efficiency_ratio: GaugeVec,
+
+    // Rolled-back share of finished transactions: xact_rollback / (xact_commit + xact_rollback)
+    rollback_ratio: GaugeVec,
+
+    // Deadlocks per million finished transactions: deadlocks * 1e6 / (xact_commit + xact_rollback)
+    deadlocks_per_million_xacts: GaugeVec,
 }
 
 impl Default for DatabaseStatCollector {
@@ -183,6 +216,13 @@ impl DatabaseStatCollector {
     #[must_use]
     #[allow(clippy::expect_used)]
     pub fn new() -> Self {
+        let [
+            blks_hit_ratio,
+            scan_efficiency_ratio,
+            rollback_ratio,
+            deadlocks_per_million_xacts,
+        ] = derived_gauges();
+
         Self {
             numbackends: db_gauge(
                 "pg_stat_database_numbackends",
@@ -266,16 +306,71 @@ impl DatabaseStatCollector {
                 "pg_stat_database_checksum_last_failure_timestamp_seconds",
                 "Time of the last data page checksum failure in this database (epoch seconds, PG >= 12).",
             ),
-            blks_hit_ratio: db_gauge(
-                "pg_stat_database_blks_hit_ratio",
-                "Buffer cache hit ratio (0.0-1.0). Alert when < 0.90 (90%). \
-                 Formula: blks_hit / (blks_hit + blks_read). \
-                 >99% = excellent, 95-98% = good, 90-94% = warning, <90% = critical memory pressure.",
-            ),
+            blks_hit_ratio,
+            scan_efficiency_ratio,
+            rollback_ratio,
+            deadlocks_per_million_xacts,
         }
     }
 }
 
+/// Ratios derived from the fetched counters, in struct field order: `blks_hit_ratio`,
+/// `scan_efficiency_ratio`, `rollback_ratio`, `deadlocks_per_million_xacts`
+fn derived_gauges() -> [GaugeVec; 4] {
+    [
+        db_gauge(
+            "pg_stat_database_blks_hit_ratio",
+            "Buffer cache hit ratio (0.0-1.0). Alert when < 0.90 (90%). \
+             Formula: blks_hit / (blks_hit + blks_read). \
+             >99% = excellent, 95-98% = good, 90-94% = warning, <90% = critical memory pressure.",
+        ),
+        db_gauge(
+            "pg_stat_database_scan_efficiency_ratio",
+            "Rows read by scans per row fetched (tup_returned / tup_fetched, 0 when nothing \
+             was fetched). High values point to sequential scans returning far more rows than needed.",
+        ),
+        db_gauge(
+            "pg_stat_database_rollback_ratio",
+            "Rolled-back share of finished transactions (xact_rollback / (xact_commit + \
+             xact_rollback), 0 before any finished). Rising values point to errors or deadlocks.",
+        ),
+        db_gauge(
+            "pg_stat_database_deadlocks_per_million_xacts",
+            "Deadlocks per million finished transactions (deadlocks * 1e6 / (xact_commit + \
+             xact_rollback), 0 before any finished), comparable across databases with different traffic.",
+        ),
+    ]
+}
+
+/// Rows read per row fetched; 0 when nothing was fetched so the series never divides by zero
+fn scan_efficiency_ratio(tup_returned: f64, tup_fetched: f64) -> f64 {
+    if tup_fetched > 0.0 {
+        tup_returned / tup_fetched
+    } else {
+        0.0
+    }
+}
+
+/// Rolled-back share of finished transactions; 0 before any transaction finished
+fn rollback_ratio(xact_commit: f64, xact_rollback: f64) -> f64 {
+    let total = xact_commit + xact_rollback;
+    if total > 0.0 {
+        xact_rollback / total
+    } else {
+        0.0
+    }
+}
+
+/// Deadlocks per million finished transactions; 0 before any transaction finished
+fn deadlocks_per_million_xacts(deadlocks: f64, xact_commit: f64, xact_rollback: f64) -> f64 {
+    let total = xact_commit + xact_rollback;
+    if total > 0.0 {
+        deadlocks * 1_000_000.0 / total
+    } else {
+        0.0
+    }
+}
+
 const DATABASE_LABELS: [&str; 2] = ["datid", "datname"];
 
 #[allow(clippy::expect_used)]
@@ -318,6 +413,9 @@ impl Collector for DatabaseStatCollector {
             self.checksum_last_failure_timestamp_seconds.clone(),
         ))?;
         registry.register(Box::new(self.blks_hit_ratio.clone()))?;
+        registry.register(Box::new(self.scan_efficiency_ratio.clone()))?;
+        registry.register(Box::new(self.rollback_ratio.clone()))?;
+        registry.register(Box::new(self.deadlocks_per_million_xacts.clone()))?;
         Ok(())
     }
 
@@ -331,7 +429,7 @@ impl Collector for DatabaseStatCollector {
         Box::pin(async move {
             // Version check for fields added to `pg_stat_database` in newer `PostgreSQL`.
             let vrow = sqlx::query(r"SELECT current_setting('server_version_num')::int AS v")
-                .fetch_one(pool)
+                .fetch_one(&mut *acquire_connection(pool).await?)
                 .await?;
             let version_num: i32 = vrow.try_get("v")?;
             let has_active_time = version_num >= 140_000;
@@ -371,6 +469,9 @@ impl Collector for DatabaseStatCollector {
                 self.checksum_last_failure_timestamp_seconds.reset();
             }
             self.blks_hit_ratio.reset();
+            self.scan_efficiency_ratio.reset();
+            self.rollback_ratio.reset();
+            self.deadlocks_per_million_xacts.reset();
 
             // Columns per postgres_exporter
             let mut cols: Vec<String> = vec![
@@ -438,7 +539,7 @@ impl Collector for DatabaseStatCollector {
 
             let rows = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
                 .bind(&excluded_list)
-                .fetch_all(pool)
+                .fetch_all(&mut *acquire_connection(pool).await?)
                 .instrument(span_q)
                 .await?;
 
@@ -448,43 +549,47 @@ impl Collector for DatabaseStatCollector {
             #[allow(clippy::cast_precision_loss)]
             for row in &rows {
                 let datid: String = row.try_get::<String, _>("datid")?;
-                let datname: String = row
-                    .try_get::<Option<String>, _>("datname")?
-                    .unwrap_or_else(|| "[unknown]".to_string());
+                let datname: String = datname_label(row.try_get("datname")?);
 
                 let labels = [&datid, &datname];
 
-                self.numbackends
-                    .with_label_values(&labels)
-                    .set(i64_to_f64(row.try_get::<i64, _>("numbackends").unwrap_or(0)));
+                self.numbackends.with_label_values(&labels).set(i64_to_f64(
+                    row.try_get::<i64, _>("numbackends").unwrap_or(0),
+                ));
 
-                self.xact_commit
-                    .with_label_values(&labels)
-                    .set(i64_to_f64(row.try_get::<i64, _>("xact_commit").unwrap_or(0)));
+                let xact_commit = i64_to_f64(row.try_get::<i64, _>("xact_commit").unwrap_or(0));
+                let xact_rollback = i64_to_f64(row.try_get::<i64, _>("xact_rollback").unwrap_or(0));
+                self.xact_commit.with_label_values(&labels).set(xact_commit);
                 self.xact_rollback
                     .with_label_values(&labels)
-                    .set(i64_to_f64(row.try_get::<i64, _>("xact_rollback").unwrap_or(0)));
+                    .set(xact_rollback);
+                self.rollback_ratio
+                    .with_label_values(&labels)
+                    .set(rollback_ratio(xact_commit, xact_rollback));
                 self.blks_read
                     .with_label_values(&labels)
                     .set(i64_to_f64(row.try_get::<i64, _>("blks_read").unwrap_or(0)));
                 self.blks_hit
                     .with_label_values(&labels)
                     .set(i64_to_f64(row.try_get::<i64, _>("blks_hit").unwrap_or(0)));
+                let tup_returned = i64_to_f64(row.try_get::<i64, _>("tup_returned").unwrap_or(0));
+                let tup_fetched = i64_to_f64(row.try_get::<i64, _>("tup_fetched").unwrap_or(0));
                 self.tup_returned
                     .with_label_values(&labels)
-                    .set(i64_to_f64(row.try_get::<i64, _>("tup_returned").unwrap_or(0)));
-                self.tup_fetched
+                    .set(tup_returned);
+                self.tup_fetched.with_label_values(&labels).set(tup_fetched);
+                self.scan_efficiency_ratio
                     .with_label_values(&labels)
-                    .set(i64_to_f64(row.try_get::<i64, _>("tup_fetched").unwrap_or(0)));
-                self.tup_inserted
-                    .with_label_values(&labels)
-                    .set(i64_to_f64(row.try_get::<i64, _>("tup_inserted").unwrap_or(0)));
-                self.tup_updated
-                    .with_label_values(&labels)
-                    .set(i64_to_f64(row.try_get::<i64, _>("tup_updated").unwrap_or(0)));
-                self.tup_deleted
-                    .with_label_values(&labels)
-                    .set(i64_to_f64(row.try_get::<i64, _>("tup_deleted").unwrap_or(0)));
+                    .set(scan_efficiency_ratio(tup_returned, tup_fetched));
+                self.tup_inserted.with_label_values(&labels).set(i64_to_f64(
+                    row.try_get::<i64, _>("tup_inserted").unwrap_or(0),
+                ));
+                self.tup_updated.with_label_values(&labels).set(i64_to_f64(
+                    row.try_get::<i64, _>("tup_updated").unwrap_or(0),
+                ));
+                self.tup_deleted.with_label_values(&labels).set(i64_to_f64(
+                    row.try_get::<i64, _>("tup_deleted").unwrap_or(0),
+                ));
                 self.conflicts
                     .with_label_values(&labels)
                     .set(i64_to_f64(row.try_get::<i64, _>("conflicts").unwrap_or(0)));
@@ -494,9 +599,15 @@ impl Collector for DatabaseStatCollector {
                 self.temp_bytes
                     .with_label_values(&labels)
                     .set(i64_to_f64(row.try_get::<i64, _>("temp_bytes").unwrap_or(0)));
-                self.deadlocks
+                let deadlocks = i64_to_f64(row.try_get::<i64, _>("deadlocks").unwrap_or(0));
+                self.deadlocks.with_label_values(&labels).set(deadlocks);
+                self.deadlocks_per_million_xacts
                     .with_label_values(&labels)
-                    .set(i64_to_f64(row.try_get::<i64, _>("deadlocks").unwrap_or(0)));
+                    .set(deadlocks_per_million_xacts(
+                        deadlocks,
+                        xact_commit,
+                        xact_rollback,
+                    ));
 
                 self.blk_read_time
                     .with_label_values(&labels)
@@ -573,9 +684,7 @@ impl Collector for DatabaseStatCollector {
                     self.checksum_failures_total
                         .with_label_values(&labels)
                         .set(i64_to_f64(
-                            row.try_get::<i64, _>("checksum_failures")
-                                .ok()
-                                .unwrap_or(0),
+                            row.try_get::<i64, _>("checksum_failures").ok().unwrap_or(0),
                         ));
                     self.checksum_last_failure_timestamp_seconds
                         .with_label_values(&labels)
@@ -0,0 +1,193 @@
+use super::disk::is_insufficient_privilege;
+use crate::collectors::Collector;
+use crate::collectors::util::acquire_connection;
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{IntGauge, IntGaugeVec, Opts, Registry};
+use sqlx::{PgPool, Row};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+use tracing::{debug, info_span, instrument, warn};
+use tracing_futures::Instrument as _;
+
+/// Temporary files on disk right now, per tablespace. `pg_ls_tmpdir()` needs superuser
+/// or `pg_monitor`; `pg_global` never holds temporary files.
+const TEMP_FILES_QUERY: &str = r"
+    SELECT
+        t.spcname,
+        COUNT(f.name)::bigint AS files,
+        COALESCE(SUM(f.size), 0)::bigint AS bytes
+    FROM pg_tablespace t
+    LEFT JOIN LATERAL pg_ls_tmpdir(t.oid) f ON true
+    WHERE t.spcname <> 'pg_global'
+    GROUP BY t.spcname
+    ORDER BY t.spcname
+";
+
+/// Backends sampled while waiting on temporary file (`BufFile`) I/O. Readable by any role.
+const TEMP_FILE_IO_BACKENDS_QUERY: &str = r"
+    SELECT COUNT(*)::bigint
+    FROM pg_stat_activity
+    WHERE wait_event_type = 'IO'
+      AND wait_event IN ('BufFileRead', 'BufFileWrite', 'BufFileTruncate')
+";
+
+/// Temporary file usage happening now, complementing the cumulative
+/// `pg_stat_database_temp_files` / `_temp_bytes` counters:
+/// - `pg_temp_files_current{tablespace}`: files in the tablespace's `pgsql_tmp` directory
+/// - `pg_temp_files_current_bytes{tablespace}`: their total size
+/// - `pg_temp_files_io_backends`: backends waiting on temporary file I/O at scrape time
+///
+/// Sorts, hashes and materializations that exceed `work_mem` spill to these files and
+/// delete them when the query ends, so the counters only show that a spill happened. The
+/// file gauges need `pg_monitor`; without it they are omitted (not reported as `0`) and a
+/// warning is logged once, while `pg_temp_files_io_backends` is still exported. The wait
+/// event count is a point-in-time sample: a spilling backend between two I/O calls is missed.
+#[derive(Clone)]
+pub struct DatabaseTempFilesCollector {
+    files: IntGaugeVec,    // pg_temp_files_current{tablespace}
+    bytes: IntGaugeVec,    // pg_temp_files_current_bytes{tablespace}
+    io_backends: IntGauge, // pg_temp_files_io_backends
+    privilege_warned: Arc<AtomicBool>,
+}
+
+impl Default for DatabaseTempFilesCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DatabaseTempFilesCollector {
+    /// Creates a new `DatabaseTempFilesCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let files = IntGaugeVec::new(
+            Opts::new(
+                "pg_temp_files_current",
+                "Temporary files currently in the tablespace's pgsql_tmp directory",
+            ),
+            &["tablespace"],
+        )
+        .expect("Failed to create pg_temp_files_current");
+
+        let bytes = IntGaugeVec::new(
+            Opts::new(
+                "pg_temp_files_current_bytes",
+                "Total size of the temporary files currently in the tablespace's pgsql_tmp directory, in bytes",
+            ),
+            &["tablespace"],
+        )
+        .expect("Failed to create pg_temp_files_current_bytes");
+
+        let io_backends = IntGauge::with_opts(Opts::new(
+            "pg_temp_files_io_backends",
+            "Backends waiting on temporary file I/O (BufFileRead/BufFileWrite/BufFileTruncate) at scrape time",
+        ))
+        .expect("Failed to create pg_temp_files_io_backends");
+
+        Self {
+            files,
+            bytes,
+            io_backends,
+            privilege_warned: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl Collector for DatabaseTempFilesCollector {
+    fn name(&self) -> &'static str {
+        "database_temp_files"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "database_temp_files")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.files.clone()))?;
+        registry.register(Box::new(self.bytes.clone()))?;
+        registry.register(Box::new(self.io_backends.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector="database_temp_files", otel.kind="internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let files_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement =
+                    "SELECT spcname, count, sum(size) FROM pg_tablespace, pg_ls_tmpdir(oid)",
+                db.sql.table = "pg_tablespace"
+            );
+            let file_rows = match sqlx::query(TEMP_FILES_QUERY)
+                .fetch_all(&mut *acquire_connection(pool).await?)
+                .instrument(files_span)
+                .await
+            {
+                Ok(rows) => Some(rows),
+                Err(e) if is_insufficient_privilege(&e) => {
+                    if !self.privilege_warned.swap(true, Ordering::Relaxed) {
+                        warn!(
+                            error = %e,
+                            "collector.database: missing privilege for pg_ls_tmpdir; skipping \
+                             temporary file metrics (grant pg_monitor to export them)"
+                        );
+                    }
+                    None
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            let io_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement =
+                    "SELECT count(*) FROM pg_stat_activity WHERE wait_event IN (BufFile*)",
+                db.sql.table = "pg_stat_activity"
+            );
+            let io_backends: i64 = sqlx::query_scalar(TEMP_FILE_IO_BACKENDS_QUERY)
+                .fetch_one(&mut *acquire_connection(pool).await?)
+                .instrument(io_span)
+                .await?;
+
+            self.files.reset();
+            self.bytes.reset();
+
+            for row in file_rows.iter().flatten() {
+                let spcname: String = row.try_get("spcname")?;
+                let files: i64 = row.try_get("files")?;
+                let bytes: i64 = row.try_get("bytes")?;
+
+                self.files.with_label_values(&[spcname.as_str()]).set(files);
+                self.bytes.with_label_values(&[spcname.as_str()]).set(bytes);
+            }
+            self.io_backends.set(io_backends);
+
+            debug!(
+                tablespaces = file_rows.as_ref().map(Vec::len),
+                io_backends, "updated temporary file metrics"
+            );
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,239 @@
+use crate::collectors::util::{
+    acquire_connection, acquire_db_query_permit, datname_label, get_default_database,
+    in_acquire_scope, list_target_databases, open_db_connection,
+};
+use crate::collectors::{Collector, all_databases_failed};
+use anyhow::{Result, anyhow};
+use futures::future::BoxFuture;
+use prometheus::{IntGaugeVec, Opts, Registry};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use tokio::task::JoinSet;
+use tracing::{debug, error, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// Collector for temporary tables per database
+///
+/// **What it measures:**
+/// Every session that creates a temporary table gets its own `pg_temp_N` schema, and each
+/// temporary table adds rows to `pg_class`, `pg_attribute` and friends. Applications that
+/// create temp tables without dropping them (or pooled sessions that never end) keep
+/// growing the catalogs and the per-backend catalog caches until lookups slow down.
+///
+/// **Key metrics:**
+/// - `pg_temp_tables_count{datname}`: tables (`relkind` `r`/`p`) in `pg_temp_%` schemas.
+///   A count that keeps growing points at a connection leaking temporary objects.
+///
+/// **Multi-database:**
+/// `pg_class` is a per-database catalog, so this collector iterates every connectable,
+/// non-excluded database like the other per-database collectors.
+#[derive(Clone)]
+pub struct DatabaseTempTablesCollector {
+    temp_tables: IntGaugeVec,
+}
+
+impl Default for DatabaseTempTablesCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Temporary tables of the current database. The `pg_temp_%` pattern skips the matching
+/// `pg_toast_temp_%` schemas, so TOAST tables of temporary tables are not double counted.
+const TEMP_TABLES_QUERY: &str = r"
+    SELECT
+        current_database() AS datname,
+        COUNT(*)::bigint AS temp_tables
+    FROM pg_class c
+    JOIN pg_namespace n ON c.relnamespace = n.oid
+    WHERE n.nspname LIKE 'pg\_temp\_%'
+      AND c.relkind IN ('r', 'p')
+    ";
+
+impl DatabaseTempTablesCollector {
+    /// Creates a new `DatabaseTempTablesCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        Self {
+            temp_tables: IntGaugeVec::new(
+                Opts::new(
+                    "pg_temp_tables_count",
+                    "Number of temporary tables currently present in the database",
+                ),
+                &["datname"],
+            )
+            .expect("Failed to create pg_temp_tables_count"),
+        }
+    }
+
+    fn sample_from_row(row: &PgRow) -> Result<(String, i64)> {
+        Ok((
+            datname_label(row.try_get("datname")?),
+            row.try_get("temp_tables").unwrap_or(0),
+        ))
+    }
+}
+
+impl Collector for DatabaseTempTablesCollector {
+    fn name(&self) -> &'static str {
+        "database_temp_tables"
+    }
+
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.temp_tables.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector = "database_temp_tables", otel.kind = "internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            // 1) Discover connectable, non-excluded databases via the shared pool.
+            let dbs = list_target_databases(pool).await?;
+
+            let shared_pool = pool.clone();
+            let default_db = get_default_database().map(std::string::ToString::to_string);
+
+            // 2) One task per DB. The default DB reuses the shared pool; every other database
+            // must pass through the global per-database connection limiter.
+            let mut tasks: JoinSet<Result<Option<(String, i64)>>> = JoinSet::new();
+
+            let num_dbs = dbs.len();
+            for datname in dbs {
+                let shared_pool = shared_pool.clone();
+                let default_db = default_db.clone();
+
+                tasks.spawn(in_acquire_scope(async move {
+                    let use_shared = default_db.as_deref() == Some(datname.as_str());
+
+                    let query_span = info_span!(
+                        "db.query",
+                        otel.kind = "client",
+                        db.system = "postgresql",
+                        db.operation = "SELECT",
+                        db.statement = "SELECT COUNT(*) FROM pg_class (temp schemas)",
+                        db.sql.table = "pg_class",
+                        datname = %datname,
+                        reuse_pool = use_shared
+                    );
+
+                    let db_query_permit = if use_shared {
+                        None
+                    } else {
+                        Some(acquire_db_query_permit().await.map_err(|e| {
+                            anyhow!(
+                                "database_temp_tables: failed to acquire database query permit: {e}"
+                            )
+                        })?)
+                    };
+
+                    let row_res: anyhow::Result<Option<PgRow>> = if use_shared {
+                        sqlx::query(TEMP_TABLES_QUERY)
+                            .fetch_optional(&mut *acquire_connection(&shared_pool).await?)
+                            .instrument(query_span)
+                            .await
+                            .map_err(Into::into)
+                    } else {
+                        let Some(permit) = db_query_permit.as_ref() else {
+                            return Err(anyhow!(
+                                "database_temp_tables: missing database query permit"
+                            ));
+                        };
+                        match open_db_connection(&datname, permit).await {
+                            Ok(mut conn) => sqlx::query(TEMP_TABLES_QUERY)
+                                .fetch_optional(&mut conn)
+                                .instrument(query_span)
+                                .await
+                                .map_err(Into::into),
+                            Err(e) => Err(e),
+                        }
+                    };
+
+                    match row_res? {
+                        Some(row) => Ok(Some(Self::sample_from_row(&row)?)),
+                        None => Ok(None),
+                    }
+                }));
+            }
+
+            let mut all_samples = Vec::new();
+            let mut failures = Vec::new();
+            let mut failed_db_count = 0;
+            while let Some(joined) = tasks.join_next().await {
+                match joined {
+                    Ok(Ok(Some(sample))) => all_samples.push(sample),
+                    Ok(Ok(None)) => {}
+                    Ok(Err(e)) => {
+                        error!(error=?e, "database_temp_tables: task returned error");
+                        failures.push(e.to_string());
+                        failed_db_count += 1;
+                    }
+                    Err(e) => {
+                        error!(error=?e, "database_temp_tables: task join error");
+                        failures.push(e.to_string());
+                        failed_db_count += 1;
+                    }
+                }
+            }
+
+            if all_databases_failed(num_dbs, failed_db_count) {
+                return Err(anyhow!(
+                    "database_temp_tables collection failed for ALL {failed_db_count} database task(s): {}",
+                    failures.join("; ")
+                ));
+            }
+
+            if !failures.is_empty() {
+                error!(
+                    failed_databases = failed_db_count,
+                    errors = %failures.join("; "),
+                    "database_temp_tables: continuing with partial snapshot after per-database failures"
+                );
+            }
+
+            self.temp_tables.reset();
+
+            for (datname, temp_tables) in &all_samples {
+                self.temp_tables
+                    .with_label_values(&[datname.as_str()])
+                    .set(*temp_tables);
+
+                debug!(datname = %datname, temp_tables, "updated temp table metrics");
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_temp_tables_collector_name() {
+        let collector = DatabaseTempTablesCollector::new();
+        assert_eq!(collector.name(), "database_temp_tables");
+    }
+
+    #[test]
+    fn test_temp_tables_collector_registers() {
+        let registry = Registry::new();
+        let collector = DatabaseTempTablesCollector::new();
+        assert!(collector.register_metrics(&registry).is_ok());
+    }
+
+    #[test]
+    fn test_temp_tables_query_matches_temp_schemas_only() {
+        assert!(TEMP_TABLES_QUERY.contains(r"LIKE 'pg\_temp\_%'"));
+        assert!(TEMP_TABLES_QUERY.contains("c.relkind IN ('r', 'p')"));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::collectors::Collector;
+use crate::collectors::util::acquire_connection;
 use anyhow::Result;
 use futures::future::BoxFuture;
 use prometheus::{IntCounter, IntGauge, Opts, Registry};
@@ -11,12 +12,14 @@ use tracing_futures::Instrument as _;
 /// - `pg_stat_archiver_failed_total` (`Counter`)
 /// - `pg_stat_archiver_last_archived_age_seconds` (`Gauge`)
 /// - `pg_stat_archiver_last_failed_age_seconds` (`Gauge`)
+/// - `pg_stat_archiver_lag_bytes` (`Gauge`)
 #[derive(Clone)]
 pub struct ArchiverCollector {
-    archived_count: IntCounter,      // pg_stat_archiver_archived_total
-    failed_count: IntCounter,        // pg_stat_archiver_failed_total
-    last_archived_age: IntGauge,     // pg_stat_archiver_last_archived_age_seconds
-    last_failed_age: IntGauge,       // pg_stat_archiver_last_failed_age_seconds
+    archived_count: IntCounter,  // pg_stat_archiver_archived_total
+    failed_count: IntCounter,    // pg_stat_archiver_failed_total
+    last_archived_age: IntGauge, // pg_stat_archiver_last_archived_age_seconds
+    last_failed_age: IntGauge,   // pg_stat_archiver_last_failed_age_seconds
+    lag_bytes: IntGauge,         // pg_stat_archiver_lag_bytes
 }
 
 impl Default for ArchiverCollector {
@@ -58,11 +61,18 @@ impl ArchiverCollector {
         ))
         .expect("Failed to create pg_stat_archiver_last_failed_age_seconds");
 
+        let lag_bytes = IntGauge::with_opts(Opts::new(
+            "pg_stat_archiver_lag_bytes",
+            "Bytes of WAL written past the end of the last archived segment (0 on standbys or before the first archive)",
+        ))
+        .expect("Failed to create pg_stat_archiver_lag_bytes");
+
         Self {
             archived_count,
             failed_count,
             last_archived_age,
             last_failed_age,
+            lag_bytes,
         }
     }
 }
@@ -83,6 +93,7 @@ impl Collector for ArchiverCollector {
         registry.register(Box::new(self.failed_count.clone()))?;
         registry.register(Box::new(self.last_archived_age.clone()))?;
         registry.register(Box::new(self.last_failed_age.clone()))?;
+        registry.register(Box::new(self.lag_bytes.clone()))?;
         Ok(())
     }
 
@@ -109,11 +120,17 @@ impl Collector for ArchiverCollector {
                     archived_count,
                     failed_count,
                     EXTRACT(EPOCH FROM (NOW() - last_archived_time))::bigint AS last_archived_age,
-                    EXTRACT(EPOCH FROM (NOW() - last_failed_time))::bigint AS last_failed_age
+                    EXTRACT(EPOCH FROM (NOW() - last_failed_time))::bigint AS last_failed_age,
+                    last_archived_wal,
+                    CASE WHEN pg_is_in_recovery() THEN NULL
+                         ELSE pg_wal_lsn_diff(pg_current_wal_lsn(), '0/0')::bigint
+                    END AS current_wal_lsn_bytes,
+                    (SELECT setting::bigint FROM pg_settings WHERE name = 'wal_segment_size')
+                        AS wal_segment_size
                 FROM pg_stat_archiver
                 ",
             )
-            .fetch_one(pool)
+            .fetch_one(&mut *acquire_connection(pool).await?)
             .instrument(query_span)
             .await;
 
@@ -134,13 +151,25 @@ impl Collector for ArchiverCollector {
             let failed_count: i64 = row.try_get("failed_count")?;
             let last_archived_age: Option<i64> = row.try_get("last_archived_age").ok();
             let last_failed_age: Option<i64> = row.try_get("last_failed_age").ok();
+            let last_archived_wal: Option<String> = row.try_get("last_archived_wal")?;
+            let current_wal_lsn: Option<i64> = row.try_get("current_wal_lsn_bytes")?;
+            let wal_segment_size: Option<i64> = row.try_get("wal_segment_size")?;
+
+            let lag_bytes = match (current_wal_lsn, last_archived_wal, wal_segment_size) {
+                (Some(current), Some(wal), Some(segment_size)) => {
+                    archive_lag_bytes(current, &wal, segment_size)
+                }
+                _ => None,
+            };
 
             // Reset and set the counter values
             self.archived_count.reset();
             self.failed_count.reset();
 
-            self.archived_count.inc_by(u64::try_from(archived_count).unwrap_or(0));
-            self.failed_count.inc_by(u64::try_from(failed_count).unwrap_or(0));
+            self.archived_count
+                .inc_by(u64::try_from(archived_count).unwrap_or(0));
+            self.failed_count
+                .inc_by(u64::try_from(failed_count).unwrap_or(0));
 
             // Set age gauges (may be NULL if never archived/failed)
             if let Some(age) = last_archived_age {
@@ -149,12 +178,14 @@ impl Collector for ArchiverCollector {
             if let Some(age) = last_failed_age {
                 self.last_failed_age.set(age);
             }
+            self.lag_bytes.set(lag_bytes.unwrap_or(0));
 
             debug!(
                 archived_count,
                 failed_count,
                 last_archived_age = ?last_archived_age,
                 last_failed_age = ?last_failed_age,
+                lag_bytes = ?lag_bytes,
                 "updated archiver metrics"
             );
 
@@ -166,3 +197,82 @@ impl Collector for ArchiverCollector {
         true
     }
 }
+
+/// Bytes between `current_lsn` and the end of the segment named by `last_archived_wal`.
+///
+/// WAL segment names are `TTTTTTTTXXXXXXXXYYYYYYYY` (timeline, then the segment number
+/// split into the high 32 bits of the LSN and the segment within that 4 GiB range).
+/// `.backup` names start with the segment they belong to; `.history` files carry no
+/// position, so they yield `None`.
+fn archive_lag_bytes(
+    current_lsn: i64,
+    last_archived_wal: &str,
+    wal_segment_size: i64,
+) -> Option<i64> {
+    if wal_segment_size <= 0 {
+        return None;
+    }
+
+    let name = last_archived_wal.get(..24)?;
+    if !name.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let log = i64::from_str_radix(name.get(8..16)?, 16).ok()?;
+    let seg = i64::from_str_radix(name.get(16..24)?, 16).ok()?;
+
+    let segment_end = log
+        .checked_mul(0x1_0000_0000)?
+        .checked_add(seg.checked_add(1)?.checked_mul(wal_segment_size)?)?;
+
+    Some(current_lsn.saturating_sub(segment_end).max(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEGMENT: i64 = 16 * 1024 * 1024;
+
+    #[test]
+    fn test_archive_lag_bytes_from_segment_name() {
+        // Segment 0/3 ends at 0/4000000
+        assert_eq!(
+            archive_lag_bytes(0x0500_0000, "000000010000000000000003", SEGMENT),
+            Some(0x0100_0000)
+        );
+        // High LSN word: segment 2/FF ends at 3/0
+        assert_eq!(
+            archive_lag_bytes(0x3_0000_0100, "0000000100000002000000FF", SEGMENT),
+            Some(0x100)
+        );
+        assert_eq!(
+            archive_lag_bytes(
+                0x0500_0000,
+                "000000010000000000000003.00000028.backup",
+                SEGMENT
+            ),
+            Some(0x0100_0000)
+        );
+        // Still writing the segment right after the archived one
+        assert_eq!(
+            archive_lag_bytes(0x0300_0000, "000000010000000000000003", SEGMENT),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_archive_lag_bytes_rejects_unpositioned_names() {
+        assert_eq!(
+            archive_lag_bytes(0x0500_0000, "00000002.history", SEGMENT),
+            None
+        );
+        assert_eq!(
+            archive_lag_bytes(0x0500_0000, "00000001000000000000000Z", SEGMENT),
+            None
+        );
+        assert_eq!(
+            archive_lag_bytes(0x0500_0000, "000000010000000000000003", 0),
+            None
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use crate::collectors::Collector;
+use crate::collectors::util::acquire_connection;
 use anyhow::Result;
 use futures::future::BoxFuture;
-use prometheus::{IntCounter, Opts, Registry};
+use prometheus::{Gauge, IntCounter, Opts, Registry};
 use sqlx::{PgPool, Row};
 use tracing::{debug, info_span, instrument};
 use tracing_futures::Instrument as _;
@@ -10,11 +11,13 @@ use tracing_futures::Instrument as _;
 /// - `pg_stat_bgwriter_buffers_clean_total` (`Counter`)
 /// - `pg_stat_bgwriter_maxwritten_clean_total` (`Counter`)
 /// - `pg_stat_bgwriter_buffers_alloc_total` (`Counter`)
+/// - `pg_stat_bgwriter_stats_reset_seconds` (`Gauge`, Unix epoch of the last counter reset)
 #[derive(Clone)]
 pub struct BgwriterCollector {
-    buffers_clean: IntCounter,     // pg_stat_bgwriter_buffers_clean_total
-    maxwritten_clean: IntCounter,  // pg_stat_bgwriter_maxwritten_clean_total
-    buffers_alloc: IntCounter,     // pg_stat_bgwriter_buffers_alloc_total
+    buffers_clean: IntCounter,    // pg_stat_bgwriter_buffers_clean_total
+    maxwritten_clean: IntCounter, // pg_stat_bgwriter_maxwritten_clean_total
+    buffers_alloc: IntCounter,    // pg_stat_bgwriter_buffers_alloc_total
+    stats_reset: Gauge,           // pg_stat_bgwriter_stats_reset_seconds
 }
 
 impl Default for BgwriterCollector {
@@ -50,10 +53,17 @@ impl BgwriterCollector {
         ))
         .expect("Failed to create pg_stat_bgwriter_buffers_alloc_total");
 
+        let stats_reset = Gauge::with_opts(Opts::new(
+            "pg_stat_bgwriter_stats_reset_seconds",
+            "Time at which the pg_stat_bgwriter counters were last reset, as a Unix timestamp",
+        ))
+        .expect("Failed to create pg_stat_bgwriter_stats_reset_seconds");
+
         Self {
             buffers_clean,
             maxwritten_clean,
             buffers_alloc,
+            stats_reset,
         }
     }
 }
@@ -73,6 +83,7 @@ impl Collector for BgwriterCollector {
         registry.register(Box::new(self.buffers_clean.clone()))?;
         registry.register(Box::new(self.maxwritten_clean.clone()))?;
         registry.register(Box::new(self.buffers_alloc.clone()))?;
+        registry.register(Box::new(self.stats_reset.clone()))?;
         Ok(())
     }
 
@@ -98,17 +109,19 @@ impl Collector for BgwriterCollector {
                 SELECT
                     buffers_clean,
                     maxwritten_clean,
-                    buffers_alloc
+                    buffers_alloc,
+                    EXTRACT(EPOCH FROM stats_reset)::double precision AS stats_reset_epoch
                 FROM pg_stat_bgwriter
                 ",
             )
-            .fetch_one(pool)
+            .fetch_one(&mut *acquire_connection(pool).await?)
             .instrument(query_span)
             .await?;
 
             let buffers_clean: i64 = row.try_get("buffers_clean")?;
             let maxwritten_clean: i64 = row.try_get("maxwritten_clean")?;
             let buffers_alloc: i64 = row.try_get("buffers_alloc")?;
+            let stats_reset: Option<f64> = row.try_get("stats_reset_epoch")?;
 
             // Reset and set the counter values
             // Since these are cumulative counters, we reset them first to avoid accumulation
@@ -116,15 +129,17 @@ impl Collector for BgwriterCollector {
             self.maxwritten_clean.reset();
             self.buffers_alloc.reset();
 
-            self.buffers_clean.inc_by(u64::try_from(buffers_clean).unwrap_or(0));
-            self.maxwritten_clean.inc_by(u64::try_from(maxwritten_clean).unwrap_or(0));
-            self.buffers_alloc.inc_by(u64::try_from(buffers_alloc).unwrap_or(0));
+            self.buffers_clean
+                .inc_by(u64::try_from(buffers_clean).unwrap_or(0));
+            self.maxwritten_clean
+                .inc_by(u64::try_from(maxwritten_clean).unwrap_or(0));
+            self.buffers_alloc
+                .inc_by(u64::try_from(buffers_alloc).unwrap_or(0));
+            self.stats_reset.set(stats_reset.unwrap_or(0.0));
 
             debug!(
                 buffers_clean,
-                maxwritten_clean,
-                buffers_alloc,
-                "updated bgwriter metrics"
+                maxwritten_clean, buffers_alloc, "updated bgwriter metrics"
             );
 
             Ok(())
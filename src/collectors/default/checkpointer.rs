@@ -1,5 +1,5 @@
-use crate::collectors::Collector;
-use crate::collectors::util::is_pg_version_at_least;
+use crate::collectors::util::{acquire_connection, is_pg_version_at_least};
+use crate::collectors::{Collector, i64_to_f64};
 use anyhow::Result;
 use futures::future::BoxFuture;
 use prometheus::{Gauge, IntCounter, Opts, Registry};
@@ -15,20 +15,24 @@ use tracing_futures::Instrument as _;
 /// - `pg_stat_checkpointer_buffers_written_total` (`Counter`)
 /// - `pg_stat_checkpointer_write_time_seconds_total` (`Counter`)
 /// - `pg_stat_checkpointer_sync_time_seconds_total` (`Counter`)
+/// - `pg_stat_checkpointer_stats_reset_seconds` (`Gauge`, Unix epoch of the last counter reset)
 ///
 /// From `pg_control_checkpoint()` (tuning-insight metrics, independent of the
 /// `PostgreSQL` 17 requirement above):
 /// - `pg_last_checkpoint_age_seconds` (`Gauge`)
 /// - `pg_wal_bytes_since_last_checkpoint` (`Gauge`)
+/// - `pg_wal_bytes_since_last_checkpoint_ratio` (`Gauge`, the same distance over `max_wal_size`)
 #[derive(Clone)]
 pub struct CheckpointerCollector {
-    timed: IntCounter,           // pg_stat_checkpointer_timed_total
-    requested: IntCounter,        // pg_stat_checkpointer_requested_total
-    buffers_written: IntCounter,  // pg_stat_checkpointer_buffers_written_total
-    write_time: IntCounter,       // pg_stat_checkpointer_write_time_seconds_total
-    sync_time: IntCounter,        // pg_stat_checkpointer_sync_time_seconds_total
-    last_checkpoint_age: Gauge,   // pg_last_checkpoint_age_seconds
+    timed: IntCounter,                 // pg_stat_checkpointer_timed_total
+    requested: IntCounter,             // pg_stat_checkpointer_requested_total
+    buffers_written: IntCounter,       // pg_stat_checkpointer_buffers_written_total
+    write_time: IntCounter,            // pg_stat_checkpointer_write_time_seconds_total
+    sync_time: IntCounter,             // pg_stat_checkpointer_sync_time_seconds_total
+    stats_reset: Gauge,                // pg_stat_checkpointer_stats_reset_seconds
+    last_checkpoint_age: Gauge,        // pg_last_checkpoint_age_seconds
     wal_bytes_since_checkpoint: Gauge, // pg_wal_bytes_since_last_checkpoint
+    wal_since_checkpoint_ratio: Gauge, // pg_wal_bytes_since_last_checkpoint_ratio
 }
 
 impl Default for CheckpointerCollector {
@@ -76,6 +80,12 @@ impl CheckpointerCollector {
         ))
         .expect("Failed to create pg_stat_checkpointer_sync_time_seconds_total");
 
+        let stats_reset = Gauge::with_opts(Opts::new(
+            "pg_stat_checkpointer_stats_reset_seconds",
+            "Time at which the pg_stat_checkpointer counters were last reset, as a Unix timestamp",
+        ))
+        .expect("Failed to create pg_stat_checkpointer_stats_reset_seconds");
+
         let last_checkpoint_age = Gauge::with_opts(Opts::new(
             "pg_last_checkpoint_age_seconds",
             "Seconds since the last completed checkpoint (now() - pg_control_checkpoint().checkpoint_time). \
@@ -90,14 +100,23 @@ impl CheckpointerCollector {
         ))
         .expect("Failed to create pg_wal_bytes_since_last_checkpoint");
 
+        let wal_since_checkpoint_ratio = Gauge::with_opts(Opts::new(
+            "pg_wal_bytes_since_last_checkpoint_ratio",
+            "WAL bytes since the last checkpoint's redo point divided by max_wal_size. \
+             A requested checkpoint starts near 1 / (1 + checkpoint_completion_target); staying close to it means checkpoint pressure",
+        ))
+        .expect("Failed to create pg_wal_bytes_since_last_checkpoint_ratio");
+
         Self {
             timed,
             requested,
             buffers_written,
             write_time,
             sync_time,
+            stats_reset,
             last_checkpoint_age,
             wal_bytes_since_checkpoint,
+            wal_since_checkpoint_ratio,
         }
     }
 
@@ -117,6 +136,17 @@ impl CheckpointerCollector {
             db.sql.table = "pg_control_checkpoint"
         );
 
+        let mut conn = match acquire_connection(pool).await {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!(
+                    error = %e,
+                    "No pooled connection for pg_control_checkpoint(); skipping checkpoint age metrics"
+                );
+                return;
+            }
+        };
+
         let row = match sqlx::query(
             r"
             SELECT
@@ -129,11 +159,12 @@ impl CheckpointerCollector {
                         END,
                         redo_lsn
                     ), 0
-                )::bigint AS wal_bytes_since_checkpoint
+                )::bigint AS wal_bytes_since_checkpoint,
+                pg_size_bytes(current_setting('max_wal_size'))::bigint AS max_wal_size_bytes
             FROM pg_control_checkpoint()
             ",
         )
-        .fetch_optional(pool)
+        .fetch_optional(&mut *conn)
         .instrument(query_span)
         .await
         {
@@ -146,7 +177,7 @@ impl CheckpointerCollector {
                 warn!(
                     error = %e,
                     "Could not read pg_control_checkpoint() (insufficient privilege or unsupported); \
-                     skipping pg_last_checkpoint_age_seconds and pg_wal_bytes_since_last_checkpoint"
+                     skipping pg_last_checkpoint_age_seconds and pg_wal_bytes_since_last_checkpoint(_ratio)"
                 );
                 return;
             }
@@ -159,14 +190,24 @@ impl CheckpointerCollector {
         // NULL on a standby that has not replayed any WAL yet.
         if let Ok(Some(wal_bytes)) = row.try_get::<Option<i64>, _>("wal_bytes_since_checkpoint") {
             #[allow(clippy::cast_precision_loss)]
-            self.wal_bytes_since_checkpoint
-                .set(wal_bytes.max(0) as f64);
+            self.wal_bytes_since_checkpoint.set(wal_bytes.max(0) as f64);
+
+            let max_wal_size = row.try_get::<i64, _>("max_wal_size_bytes").unwrap_or(0);
+            if let Some(ratio) = max_wal_size_ratio(wal_bytes, max_wal_size) {
+                self.wal_since_checkpoint_ratio.set(ratio);
+            }
         }
 
         debug!("updated checkpoint age / wal-since-checkpoint metrics");
     }
 }
 
+/// WAL distance since the last checkpoint as a fraction of `max_wal_size`, or `None`
+/// when the setting could not be read.
+fn max_wal_size_ratio(wal_bytes: i64, max_wal_size: i64) -> Option<f64> {
+    (max_wal_size > 0).then(|| i64_to_f64(wal_bytes.max(0)) / i64_to_f64(max_wal_size))
+}
+
 impl Collector for CheckpointerCollector {
     fn name(&self) -> &'static str {
         "checkpointer"
@@ -184,8 +225,10 @@ impl Collector for CheckpointerCollector {
         registry.register(Box::new(self.buffers_written.clone()))?;
         registry.register(Box::new(self.write_time.clone()))?;
         registry.register(Box::new(self.sync_time.clone()))?;
+        registry.register(Box::new(self.stats_reset.clone()))?;
         registry.register(Box::new(self.last_checkpoint_age.clone()))?;
         registry.register(Box::new(self.wal_bytes_since_checkpoint.clone()))?;
+        registry.register(Box::new(self.wal_since_checkpoint_ratio.clone()))?;
         Ok(())
     }
 
@@ -224,11 +267,12 @@ impl Collector for CheckpointerCollector {
                     num_requested,
                     buffers_written,
                     ROUND(GREATEST(write_time, 0))::bigint AS write_time_ms,
-                    ROUND(GREATEST(sync_time, 0))::bigint AS sync_time_ms
+                    ROUND(GREATEST(sync_time, 0))::bigint AS sync_time_ms,
+                    EXTRACT(EPOCH FROM stats_reset)::double precision AS stats_reset_epoch
                 FROM pg_stat_checkpointer
                 ",
             )
-            .fetch_one(pool)
+            .fetch_one(&mut *acquire_connection(pool).await?)
             .instrument(query_span)
             .await?;
 
@@ -237,6 +281,7 @@ impl Collector for CheckpointerCollector {
             let buffers_written: i64 = row.try_get("buffers_written")?;
             let write_time_ms: i64 = row.try_get("write_time_ms")?;
             let sync_time_ms: i64 = row.try_get("sync_time_ms")?;
+            let stats_reset: Option<f64> = row.try_get("stats_reset_epoch")?;
 
             // Reset and set the counter values
             self.timed.reset();
@@ -246,12 +291,15 @@ impl Collector for CheckpointerCollector {
             self.sync_time.reset();
 
             self.timed.inc_by(u64::try_from(num_timed).unwrap_or(0));
-            self.requested.inc_by(u64::try_from(num_requested).unwrap_or(0));
-            self.buffers_written.inc_by(u64::try_from(buffers_written).unwrap_or(0));
+            self.requested
+                .inc_by(u64::try_from(num_requested).unwrap_or(0));
+            self.buffers_written
+                .inc_by(u64::try_from(buffers_written).unwrap_or(0));
             self.write_time
                 .inc_by(u64::try_from(write_time_ms).unwrap_or(0));
             self.sync_time
                 .inc_by(u64::try_from(sync_time_ms).unwrap_or(0));
+            self.stats_reset.set(stats_reset.unwrap_or(0.0));
 
             debug!(
                 num_timed,
@@ -270,3 +318,15 @@ impl Collector for CheckpointerCollector {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_wal_size_ratio_guards_unknown_setting() {
+        assert_eq!(max_wal_size_ratio(512, 1024), Some(0.5));
+        assert_eq!(max_wal_size_ratio(-1, 1024), Some(0.0));
+        assert_eq!(max_wal_size_ratio(512, 0), None);
+    }
+}
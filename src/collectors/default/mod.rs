@@ -1,12 +1,10 @@
-use crate::collectors::Collector;
+use crate::collectors::{Collector, util::collect_subs};
 use anyhow::Result;
 use futures::future::BoxFuture;
-use futures::stream::{FuturesUnordered, StreamExt};
 use prometheus::Registry;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tracing::{debug, info_span, instrument, warn};
-use tracing_futures::Instrument as _;
 
 pub mod version;
 use version::VersionCollector;
@@ -29,6 +27,12 @@ use archiver::ArchiverCollector;
 pub mod wal;
 use wal::WalCollector;
 
+pub mod notify;
+use notify::NotifyCollector;
+
+pub mod pending_restart;
+use pending_restart::PendingRestartCollector;
+
 /// `DefaultCollector` is an umbrella for cheap, always-on signals.
 #[derive(Clone, Default)]
 pub struct DefaultCollector {
@@ -47,6 +51,8 @@ impl DefaultCollector {
                 Arc::new(CheckpointerCollector::new()),
                 Arc::new(ArchiverCollector::new()),
                 Arc::new(WalCollector::new()),
+                Arc::new(NotifyCollector::new()),
+                Arc::new(PendingRestartCollector::new()),
             ],
         }
     }
@@ -81,22 +87,7 @@ impl Collector for DefaultCollector {
 
     #[instrument(skip(self, pool), level = "info", err, fields(collector = "default", otel.kind = "internal"))]
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
-        Box::pin(async move {
-            // Collect sub-collectors concurrently (they're independent).
-            let mut tasks = FuturesUnordered::new();
-
-            for sub in &self.subs {
-                let span = info_span!("collector.collect", sub_collector = %sub.name(), otel.kind = "internal");
-
-                tasks.push(sub.collect(pool).instrument(span));
-            }
-
-            while let Some(res) = tasks.next().await {
-                res?;
-            }
-
-            Ok(())
-        })
+        Box::pin(async move { collect_subs(&self.subs, pool).await })
     }
 
     fn enabled_by_default(&self) -> bool {
@@ -0,0 +1,91 @@
+use crate::collectors::Collector;
+use crate::collectors::util::acquire_connection;
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{Gauge, Opts, Registry};
+use sqlx::PgPool;
+use tracing::{info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// Exposes how full the asynchronous `NOTIFY` queue is:
+/// - `pg_notification_queue_usage_ratio` (`Gauge`, 0..=1)
+///
+/// When the queue fills up, `NOTIFY` fails with "too many notifications in the
+/// NOTIFY queue"; a listener stuck in a long transaction is the usual cause.
+#[derive(Clone)]
+pub struct NotifyCollector {
+    queue_usage_ratio: Gauge, // pg_notification_queue_usage_ratio
+}
+
+impl Default for NotifyCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NotifyCollector {
+    /// Creates a new `NotifyCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let queue_usage_ratio = Gauge::with_opts(Opts::new(
+            "pg_notification_queue_usage_ratio",
+            "Fraction of the asynchronous NOTIFY/LISTEN queue currently in use (0..1)",
+        ))
+        .expect("create pg_notification_queue_usage_ratio");
+
+        Self { queue_usage_ratio }
+    }
+}
+
+impl Collector for NotifyCollector {
+    fn name(&self) -> &'static str {
+        "notify"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "notify")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.queue_usage_ratio.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector="notify", otel.kind="internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let q_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement = "SELECT pg_notification_queue_usage()::double precision"
+            );
+
+            let usage: f64 =
+                sqlx::query_scalar(r"SELECT pg_notification_queue_usage()::double precision")
+                    .fetch_one(&mut *acquire_connection(pool).await?)
+                    .instrument(q_span)
+                    .await?;
+
+            self.queue_usage_ratio.set(usage.clamp(0.0, 1.0));
+            Ok(())
+        })
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+}
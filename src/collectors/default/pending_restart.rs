@@ -0,0 +1,114 @@
+use crate::collectors::Collector;
+use crate::collectors::util::acquire_connection;
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{IntGauge, IntGaugeVec, Opts, Registry};
+use sqlx::PgPool;
+use tracing::{debug, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// Exposes configuration changes that were reloaded but only take effect after a restart:
+/// - `pg_settings_pending_restart_count` (`IntGauge`)
+/// - `pg_settings_pending_restart{name}` (`IntGauge`, always 1 per pending setting)
+///
+/// A postmaster-context setting such as `shared_buffers` edited in `postgresql.conf` (or via
+/// `ALTER SYSTEM`) and reloaded keeps its old value until the next restart; `pg_settings`
+/// flags it with `pending_restart`.
+#[derive(Clone)]
+pub struct PendingRestartCollector {
+    count: IntGauge,      // pg_settings_pending_restart_count
+    pending: IntGaugeVec, // pg_settings_pending_restart{name}
+}
+
+impl Default for PendingRestartCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PendingRestartCollector {
+    /// Creates a new `PendingRestartCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let count = IntGauge::with_opts(Opts::new(
+            "pg_settings_pending_restart_count",
+            "Number of settings changed in the configuration that only take effect after a server restart",
+        ))
+        .expect("create pg_settings_pending_restart_count");
+
+        let pending = IntGaugeVec::new(
+            Opts::new(
+                "pg_settings_pending_restart",
+                "Setting changed in the configuration but not applied until a server restart (always 1)",
+            ),
+            &["name"],
+        )
+        .expect("create pg_settings_pending_restart");
+
+        Self { count, pending }
+    }
+}
+
+impl Collector for PendingRestartCollector {
+    fn name(&self) -> &'static str {
+        "pending_restart"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "pending_restart")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.count.clone()))?;
+        registry.register(Box::new(self.pending.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector="pending_restart", otel.kind="internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let q_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement = "SELECT name FROM pg_settings WHERE pending_restart",
+                db.sql.table = "pg_settings"
+            );
+
+            let names: Vec<String> = sqlx::query_scalar(
+                r"SELECT name::text FROM pg_settings WHERE pending_restart ORDER BY name",
+            )
+            .fetch_all(&mut *acquire_connection(pool).await?)
+            .instrument(q_span)
+            .await?;
+
+            self.pending.reset();
+            for name in &names {
+                self.pending.with_label_values(&[name]).set(1);
+            }
+            self.count
+                .set(i64::try_from(names.len()).unwrap_or(i64::MAX));
+
+            debug!(pending = names.len(), "collected pending restart settings");
+
+            Ok(())
+        })
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+}
@@ -1,17 +1,26 @@
 #![allow(unused_imports)]
-use crate::collectors::{Collector, util::get_excluded_databases};
+use crate::collectors::{
+    Collector,
+    util::{acquire_connection, get_excluded_databases},
+};
 use anyhow::Result;
 use futures::future::BoxFuture;
-use prometheus::{IntGauge, Opts, Registry};
-use sqlx::PgPool;
+use prometheus::{Gauge, IntGauge, Opts, Registry};
+use sqlx::{PgPool, Row};
 use tracing::{info_span, instrument};
 use tracing_futures::Instrument as _;
 
-/// Exposes the `PostgreSQL` postmaster (server) start time as Unix epoch seconds:
-/// - `pg_postmaster_start_time_seconds` (`IntGauge`)
+/// Exposes the `PostgreSQL` postmaster (server) start time and uptime:
+/// - `pg_postmaster_start_time_seconds` (`IntGauge`): start time as Unix epoch seconds
+/// - `pg_postmaster_uptime_seconds` (`Gauge`): seconds since the postmaster started
+///
+/// Uptime is computed by the server (`now() - pg_postmaster_start_time()`), so clock skew
+/// between the exporter and the database host does not affect it. A drop to near zero
+/// means the server restarted or crashed.
 #[derive(Clone)]
 pub struct PostmasterCollector {
     start_time_epoch_seconds: IntGauge, // pg_postmaster_start_time_seconds
+    uptime_seconds: Gauge,              // pg_postmaster_uptime_seconds
 }
 
 impl Default for PostmasterCollector {
@@ -35,8 +44,15 @@ impl PostmasterCollector {
         ))
         .expect("create pg_postmaster_start_time_seconds");
 
+        let uptime_seconds = Gauge::with_opts(Opts::new(
+            "pg_postmaster_uptime_seconds",
+            "Seconds since the PostgreSQL postmaster (server) started",
+        ))
+        .expect("create pg_postmaster_uptime_seconds");
+
         Self {
             start_time_epoch_seconds,
+            uptime_seconds,
         }
     }
 }
@@ -54,6 +70,7 @@ impl Collector for PostmasterCollector {
     )]
     fn register_metrics(&self, registry: &Registry) -> Result<()> {
         registry.register(Box::new(self.start_time_epoch_seconds.clone()))?;
+        registry.register(Box::new(self.uptime_seconds.clone()))?;
         Ok(())
     }
 
@@ -70,18 +87,27 @@ impl Collector for PostmasterCollector {
                 otel.kind = "client",
                 db.system = "postgresql",
                 db.operation = "SELECT",
-                db.statement = "SELECT EXTRACT(EPOCH FROM pg_postmaster_start_time())::bigint"
+                db.statement =
+                    "SELECT pg_postmaster_start_time(), now() - pg_postmaster_start_time()"
             );
 
-            // Returns Unix epoch seconds
-            let epoch_seconds: i64 = sqlx::query_scalar(
-                r"SELECT EXTRACT(EPOCH FROM pg_postmaster_start_time())::bigint",
+            // Start time as Unix epoch seconds, uptime as fractional seconds
+            let row = sqlx::query(
+                r"
+                SELECT
+                    EXTRACT(EPOCH FROM pg_postmaster_start_time())::bigint AS start_time,
+                    EXTRACT(EPOCH FROM now() - pg_postmaster_start_time())::float8 AS uptime
+                ",
             )
-            .fetch_one(pool)
+            .fetch_one(&mut *acquire_connection(pool).await?)
             .instrument(q_span)
             .await?;
 
+            let epoch_seconds: i64 = row.try_get("start_time")?;
+            let uptime: f64 = row.try_get("uptime")?;
+
             self.start_time_epoch_seconds.set(epoch_seconds);
+            self.uptime_seconds.set(uptime);
             Ok(())
         })
     }
@@ -1,7 +1,8 @@
 use crate::collectors::Collector;
+use crate::collectors::util::acquire_connection;
 use anyhow::Result;
 use futures::future::BoxFuture;
-use prometheus::{IntGauge, Opts, Registry};
+use prometheus::{Gauge, IntGauge, Opts, Registry};
 use sqlx::{PgPool, Row};
 use std::collections::HashMap;
 use std::sync::RwLock;
@@ -12,6 +13,16 @@ use tracing_futures::Instrument as _;
 #[derive(Clone)]
 pub struct SettingsCollector {
     pub gauges: std::sync::Arc<RwLock<HashMap<String, IntGauge>>>,
+    /// Fractional settings (see [`PLANNER_COST_METRICS`] and [`FRACTIONAL_SETTINGS_METRICS`]).
+    pub cost_gauges: std::sync::Arc<RwLock<HashMap<String, Gauge>>>,
+}
+
+/// Normalized values read from `pg_settings`: integer settings (memory in bytes, enums
+/// mapped to numbers) and the fractional settings (planner costs, `hash_mem_multiplier`).
+#[derive(Default)]
+struct SettingsValues {
+    integers: Vec<(String, i64)>,
+    costs: Vec<(String, f64)>,
 }
 
 impl Default for SettingsCollector {
@@ -25,6 +36,7 @@ impl SettingsCollector {
     pub fn new() -> Self {
         Self {
             gauges: std::sync::Arc::new(RwLock::new(HashMap::new())),
+            cost_gauges: std::sync::Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -40,7 +52,7 @@ impl SettingsCollector {
             db.sql.table = "pg_settings"
         )
     )]
-    async fn fetch_settings(&self, pool: &PgPool) -> Result<Vec<(String, i64)>> {
+    async fn fetch_settings(&self, pool: &PgPool) -> Result<SettingsValues> {
         // DB query span (captures duration and errors)
         let query_span = info_span!(
             "db.query",
@@ -64,24 +76,42 @@ impl SettingsCollector {
                 'autovacuum_naptime',
                 'autovacuum_analyze_threshold',
                 'autovacuum_vacuum_threshold',
+                'autovacuum_work_mem',
                 'checkpoint_timeout',
+                'cpu_index_tuple_cost',
+                'cpu_operator_cost',
+                'cpu_tuple_cost',
                 'data_checksums',
+                'default_statistics_target',
+                'effective_cache_size',
+                'effective_io_concurrency',
                 'fsync',
+                'hash_mem_multiplier',
+                'huge_page_size',
+                'huge_pages',
+                'huge_pages_status',
                 'log_min_duration_statement',
                 'maintenance_work_mem',
                 'max_connections',
                 'max_locks_per_transaction',
+                'max_parallel_workers',
                 'max_wal_size',
+                'max_worker_processes',
                 'min_wal_size',
+                'random_page_cost',
+                'seq_page_cost',
                 'shared_buffers',
+                'shared_memory_size',
+                'shared_memory_size_in_huge_pages',
                 'synchronous_commit',
+                'temp_buffers',
                 'wal_buffers',
                 'work_mem'
             )
             ORDER BY name
             ",
         )
-        .fetch_all(pool)
+        .fetch_all(&mut *acquire_connection(pool).await?)
         .instrument(query_span)
         .await?;
 
@@ -89,44 +119,269 @@ impl SettingsCollector {
         let parse_span = info_span!("settings.parse_rows");
         let _g = parse_span.enter();
 
-        let mut metrics = Vec::with_capacity(rows.len());
+        let mut values = SettingsValues::default();
         for row in rows {
             let name: String = row.try_get("name")?;
             let setting: String = row.try_get("setting")?;
             let unit: Option<String> = row.try_get("unit").ok();
 
-            let mut value: i64 = setting.parse::<i64>().map_or(
-                match setting.as_str() {
-                    "on" => 1,
-                    _ => 0,
-                },
-                |v| v,
-            );
-
-            // Convert memory settings to bytes based on their units
-            if matches!(
-                name.as_str(),
-                "shared_buffers"
-                    | "maintenance_work_mem"
-                    | "work_mem"
-                    | "wal_buffers"
-                    | "max_wal_size"
-                    | "min_wal_size"
-            ) && let Some(ref u) = unit
-            {
-                value *= match u.as_str() {
-                    "8kB" => 8192,
-                    "kB" => 1024,
-                    "MB" => 1024 * 1024,
-                    "GB" => 1024 * 1024 * 1024,
-                    _ => 1,
-                };
+            if is_fractional_setting(&name) {
+                if let Ok(cost) = setting.parse::<f64>() {
+                    values.costs.push((name, cost));
+                }
+                continue;
             }
 
-            metrics.push((name, value));
+            let value: i64 = setting
+                .parse::<i64>()
+                .unwrap_or_else(|_| enum_setting_value(&name, &setting));
+
+            let value = memory_setting_bytes(&name, value, unit.as_deref());
+            values.integers.push((name, value));
+        }
+
+        Ok(values)
+    }
+}
+
+/// `pg_settings` name, exported metric name, and help text for every gauge.
+const SETTINGS_METRICS: &[(&str, &str, &str)] = &[
+    (
+        "autovacuum",
+        "pg_settings_autovacuum",
+        "PostgreSQL setting: autovacuum",
+    ),
+    (
+        "autovacuum_max_workers",
+        "pg_settings_autovacuum_max_workers",
+        "PostgreSQL setting: autovacuum_max_workers",
+    ),
+    (
+        "autovacuum_naptime",
+        "pg_settings_autovacuum_naptime_seconds",
+        "PostgreSQL setting: autovacuum_naptime in seconds",
+    ),
+    (
+        "autovacuum_analyze_threshold",
+        "pg_settings_autovacuum_analyze_threshold",
+        "PostgreSQL setting: autovacuum_analyze_threshold",
+    ),
+    (
+        "autovacuum_vacuum_threshold",
+        "pg_settings_autovacuum_vacuum_threshold",
+        "PostgreSQL setting: autovacuum_vacuum_threshold",
+    ),
+    (
+        "autovacuum_work_mem",
+        "pg_settings_autovacuum_work_mem_bytes",
+        "PostgreSQL setting: autovacuum_work_mem in bytes (-1 = use maintenance_work_mem)",
+    ),
+    (
+        "checkpoint_timeout",
+        "pg_settings_checkpoint_timeout_seconds",
+        "PostgreSQL setting: checkpoint_timeout in seconds",
+    ),
+    (
+        "data_checksums",
+        "pg_settings_data_checksums",
+        "PostgreSQL setting: data_checksums",
+    ),
+    (
+        "default_statistics_target",
+        "pg_settings_default_statistics_target",
+        "PostgreSQL setting: default_statistics_target",
+    ),
+    (
+        "effective_cache_size",
+        "pg_settings_effective_cache_size_bytes",
+        "PostgreSQL setting: effective_cache_size in bytes (planner estimate of the OS + shared_buffers cache)",
+    ),
+    (
+        "effective_io_concurrency",
+        "pg_settings_effective_io_concurrency",
+        "PostgreSQL setting: effective_io_concurrency",
+    ),
+    ("fsync", "pg_settings_fsync", "PostgreSQL setting: fsync"),
+    (
+        "huge_page_size",
+        "pg_settings_huge_page_size_bytes",
+        "PostgreSQL setting: huge_page_size in bytes (0 = system default huge page size, PG14+)",
+    ),
+    (
+        "huge_pages",
+        "pg_settings_huge_pages",
+        "PostgreSQL setting: huge_pages (0 = off, 1 = on, 2 = try)",
+    ),
+    (
+        "huge_pages_status",
+        "pg_settings_huge_pages_active",
+        "Whether huge pages actually back shared memory (1 = yes, 0 = no, -1 = unknown or PostgreSQL < 17)",
+    ),
+    (
+        "log_min_duration_statement",
+        "pg_settings_log_min_duration_statement_milliseconds",
+        "PostgreSQL setting: log_min_duration_statement in milliseconds",
+    ),
+    (
+        "maintenance_work_mem",
+        "pg_settings_maintenance_work_mem_bytes",
+        "PostgreSQL setting: maintenance_work_mem in bytes",
+    ),
+    (
+        "max_connections",
+        "pg_settings_max_connections",
+        "PostgreSQL setting: max_connections",
+    ),
+    (
+        "max_locks_per_transaction",
+        "pg_settings_max_locks_per_transaction",
+        "PostgreSQL setting: max_locks_per_transaction",
+    ),
+    (
+        "max_parallel_workers",
+        "pg_settings_max_parallel_workers",
+        "PostgreSQL setting: max_parallel_workers",
+    ),
+    (
+        "max_wal_size",
+        "pg_settings_max_wal_size_bytes",
+        "PostgreSQL setting: max_wal_size in bytes",
+    ),
+    (
+        "max_worker_processes",
+        "pg_settings_max_worker_processes",
+        "PostgreSQL setting: max_worker_processes",
+    ),
+    (
+        "min_wal_size",
+        "pg_settings_min_wal_size_bytes",
+        "PostgreSQL setting: min_wal_size in bytes",
+    ),
+    (
+        "shared_buffers",
+        "pg_settings_shared_buffers_bytes",
+        "PostgreSQL setting: shared_buffers in bytes",
+    ),
+    (
+        "shared_memory_size",
+        "pg_settings_shared_memory_size_bytes",
+        "PostgreSQL setting: shared_memory_size (main shared memory area) in bytes (PG15+)",
+    ),
+    (
+        "shared_memory_size_in_huge_pages",
+        "pg_settings_shared_memory_size_in_huge_pages",
+        "PostgreSQL setting: huge pages needed for the main shared memory area (-1 = not supported, PG15+)",
+    ),
+    (
+        "synchronous_commit",
+        "pg_settings_synchronous_commit",
+        "PostgreSQL setting: synchronous_commit",
+    ),
+    (
+        "temp_buffers",
+        "pg_settings_temp_buffers_bytes",
+        "PostgreSQL setting: temp_buffers in bytes (per session)",
+    ),
+    (
+        "wal_buffers",
+        "pg_settings_wal_buffers_bytes",
+        "PostgreSQL setting: wal_buffers in bytes",
+    ),
+    (
+        "work_mem",
+        "pg_settings_work_mem_bytes",
+        "PostgreSQL setting: work_mem in bytes",
+    ),
+];
+
+/// Planner cost settings exported as float gauges. Curated to the settings that most often
+/// cause bad plans when left at spinning-disk defaults (e.g. `random_page_cost = 4` on SSDs).
+const PLANNER_COST_METRICS: &[(&str, &str, &str)] = &[
+    (
+        "cpu_index_tuple_cost",
+        "pg_settings_cpu_index_tuple_cost",
+        "PostgreSQL setting: cpu_index_tuple_cost (default 0.005)",
+    ),
+    (
+        "cpu_operator_cost",
+        "pg_settings_cpu_operator_cost",
+        "PostgreSQL setting: cpu_operator_cost (default 0.0025)",
+    ),
+    (
+        "cpu_tuple_cost",
+        "pg_settings_cpu_tuple_cost",
+        "PostgreSQL setting: cpu_tuple_cost (default 0.01)",
+    ),
+    (
+        "random_page_cost",
+        "pg_settings_random_page_cost",
+        "PostgreSQL setting: random_page_cost (default 4, typically 1.1 on SSD storage)",
+    ),
+    (
+        "seq_page_cost",
+        "pg_settings_seq_page_cost",
+        "PostgreSQL setting: seq_page_cost (default 1)",
+    ),
+];
+
+/// Other fractional settings exported as float gauges, next to the planner costs.
+const FRACTIONAL_SETTINGS_METRICS: &[(&str, &str, &str)] = &[(
+    "hash_mem_multiplier",
+    "pg_settings_hash_mem_multiplier",
+    "PostgreSQL setting: hash_mem_multiplier (hash-based operations may use work_mem times this, PG13+)",
+)];
+
+/// Every setting exported as a float gauge.
+fn fractional_metrics() -> impl Iterator<Item = &'static (&'static str, &'static str, &'static str)>
+{
+    PLANNER_COST_METRICS
+        .iter()
+        .chain(FRACTIONAL_SETTINGS_METRICS)
+}
+
+fn is_fractional_setting(name: &str) -> bool {
+    fractional_metrics().any(|&(setting, _, _)| setting == name)
+}
+
+/// Converts memory settings to bytes based on their `pg_settings.unit`. Other settings and
+/// negative sentinels (`autovacuum_work_mem = -1`) are returned unchanged.
+fn memory_setting_bytes(name: &str, value: i64, unit: Option<&str>) -> i64 {
+    let is_memory = matches!(
+        name,
+        "shared_buffers"
+            | "effective_cache_size"
+            | "maintenance_work_mem"
+            | "autovacuum_work_mem"
+            | "work_mem"
+            | "temp_buffers"
+            | "wal_buffers"
+            | "max_wal_size"
+            | "min_wal_size"
+            | "huge_page_size"
+            | "shared_memory_size"
+    );
+    if !is_memory || value <= 0 {
+        return value;
+    }
+
+    value
+        * match unit {
+            Some("8kB") => 8192,
+            Some("kB") => 1024,
+            Some("MB") => 1024 * 1024,
+            Some("GB") => 1024 * 1024 * 1024,
+            _ => 1,
         }
+}
 
-        Ok(metrics)
+/// Maps non-numeric setting values to gauge values. Booleans are `on` = 1, anything
+/// else 0; `huge_pages` adds `try` = 2 and `huge_pages_status` reports `unknown` as -1.
+fn enum_setting_value(name: &str, setting: &str) -> i64 {
+    match (name, setting) {
+        (_, "on") => 1,
+        ("huge_pages", "try") => 2,
+        ("huge_pages_status", "unknown") => -1,
+        _ => 0,
     }
 }
 
@@ -142,98 +397,13 @@ impl Collector for SettingsCollector {
         fields(collector = "settings")
     )]
     fn register_metrics(&self, registry: &Registry) -> Result<()> {
-        let metric_configs = vec![
-            (
-                "autovacuum",
-                "pg_settings_autovacuum",
-                "PostgreSQL setting: autovacuum",
-            ),
-            (
-                "autovacuum_max_workers",
-                "pg_settings_autovacuum_max_workers",
-                "PostgreSQL setting: autovacuum_max_workers",
-            ),
-            (
-                "autovacuum_naptime",
-                "pg_settings_autovacuum_naptime_seconds",
-                "PostgreSQL setting: autovacuum_naptime in seconds",
-            ),
-            (
-                "autovacuum_analyze_threshold",
-                "pg_settings_autovacuum_analyze_threshold",
-                "PostgreSQL setting: autovacuum_analyze_threshold",
-            ),
-            (
-                "autovacuum_vacuum_threshold",
-                "pg_settings_autovacuum_vacuum_threshold",
-                "PostgreSQL setting: autovacuum_vacuum_threshold",
-            ),
-            (
-                "checkpoint_timeout",
-                "pg_settings_checkpoint_timeout_seconds",
-                "PostgreSQL setting: checkpoint_timeout in seconds",
-            ),
-            ("data_checksums", "pg_settings_data_checksums", "PostgreSQL setting: data_checksums"),
-            ("fsync", "pg_settings_fsync", "PostgreSQL setting: fsync"),
-            (
-                "log_min_duration_statement",
-                "pg_settings_log_min_duration_statement_milliseconds",
-                "PostgreSQL setting: log_min_duration_statement in milliseconds",
-            ),
-            (
-                "maintenance_work_mem",
-                "pg_settings_maintenance_work_mem_bytes",
-                "PostgreSQL setting: maintenance_work_mem in bytes",
-            ),
-            (
-                "max_connections",
-                "pg_settings_max_connections",
-                "PostgreSQL setting: max_connections",
-            ),
-            (
-                "max_locks_per_transaction",
-                "pg_settings_max_locks_per_transaction",
-                "PostgreSQL setting: max_locks_per_transaction",
-            ),
-            (
-                "max_wal_size",
-                "pg_settings_max_wal_size_bytes",
-                "PostgreSQL setting: max_wal_size in bytes",
-            ),
-            (
-                "min_wal_size",
-                "pg_settings_min_wal_size_bytes",
-                "PostgreSQL setting: min_wal_size in bytes",
-            ),
-            (
-                "shared_buffers",
-                "pg_settings_shared_buffers_bytes",
-                "PostgreSQL setting: shared_buffers in bytes",
-            ),
-            (
-                "synchronous_commit",
-                "pg_settings_synchronous_commit",
-                "PostgreSQL setting: synchronous_commit",
-            ),
-            (
-                "wal_buffers",
-                "pg_settings_wal_buffers_bytes",
-                "PostgreSQL setting: wal_buffers in bytes",
-            ),
-            (
-                "work_mem",
-                "pg_settings_work_mem_bytes",
-                "PostgreSQL setting: work_mem in bytes",
-            ),
-        ];
-
         {
             let mut gauges = self
                 .gauges
                 .write()
                 .map_err(|e| anyhow::anyhow!("Failed to acquire write lock: {e}"))?;
 
-            for (name, metric_name, help) in metric_configs {
+            for &(name, metric_name, help) in SETTINGS_METRICS {
                 let gauge = IntGauge::with_opts(Opts::new(metric_name, help))?;
                 registry.register(Box::new(gauge.clone()))?;
                 gauges.insert(name.to_string(), gauge);
@@ -241,6 +411,20 @@ impl Collector for SettingsCollector {
             }
         }
 
+        {
+            let mut cost_gauges = self
+                .cost_gauges
+                .write()
+                .map_err(|e| anyhow::anyhow!("Failed to acquire write lock: {e}"))?;
+
+            for &(name, metric_name, help) in fractional_metrics() {
+                let gauge = Gauge::with_opts(Opts::new(metric_name, help))?;
+                registry.register(Box::new(gauge.clone()))?;
+                cost_gauges.insert(name.to_string(), gauge);
+                debug!(metric = %metric_name, "registered planner cost gauge");
+            }
+        }
+
         Ok(())
     }
 
@@ -251,19 +435,40 @@ impl Collector for SettingsCollector {
             let settings = self.fetch_settings(pool).await?;
 
             // Apply metrics under its own span for clarity
-            let apply_span = info_span!("settings.apply_metrics", items = settings.len());
+            let apply_span = info_span!(
+                "settings.apply_metrics",
+                items = settings.integers.len() + settings.costs.len()
+            );
             let _g = apply_span.enter();
 
             let gauges = self
                 .gauges
                 .read()
                 .map_err(|e| anyhow::anyhow!("Failed to acquire read lock: {e}"))?;
-            for (name, value) in settings {
+            // huge_pages_status only exists on PostgreSQL 17+; report "unknown" instead
+            // of a misleading 0 ("not backed by huge pages") on older servers.
+            if let Some(gauge) = gauges.get("huge_pages_status") {
+                gauge.set(-1);
+            }
+
+            for (name, value) in settings.integers {
                 if let Some(gauge) = gauges.get(&name) {
                     gauge.set(value);
                     debug!(metric = %name, value, "updated settings gauge");
                 }
             }
+            drop(gauges);
+
+            let cost_gauges = self
+                .cost_gauges
+                .read()
+                .map_err(|e| anyhow::anyhow!("Failed to acquire read lock: {e}"))?;
+            for (name, value) in settings.costs {
+                if let Some(gauge) = cost_gauges.get(&name) {
+                    gauge.set(value);
+                    debug!(metric = %name, value, "updated planner cost gauge");
+                }
+            }
 
             Ok(())
         })
@@ -273,3 +478,53 @@ impl Collector for SettingsCollector {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{enum_setting_value, is_fractional_setting, memory_setting_bytes};
+
+    #[test]
+    fn test_enum_setting_value() {
+        assert_eq!(enum_setting_value("fsync", "on"), 1);
+        assert_eq!(enum_setting_value("fsync", "off"), 0);
+        assert_eq!(enum_setting_value("huge_pages", "on"), 1);
+        assert_eq!(enum_setting_value("huge_pages", "off"), 0);
+        assert_eq!(enum_setting_value("huge_pages", "try"), 2);
+        assert_eq!(enum_setting_value("huge_pages_status", "on"), 1);
+        assert_eq!(enum_setting_value("huge_pages_status", "off"), 0);
+        assert_eq!(enum_setting_value("huge_pages_status", "unknown"), -1);
+        assert_eq!(enum_setting_value("synchronous_commit", "try"), 0);
+    }
+
+    #[test]
+    fn test_is_fractional_setting() {
+        assert!(is_fractional_setting("random_page_cost"));
+        assert!(is_fractional_setting("cpu_operator_cost"));
+        assert!(is_fractional_setting("hash_mem_multiplier"));
+        // Integer planner settings stay in the IntGauge table
+        assert!(!is_fractional_setting("effective_cache_size"));
+        assert!(!is_fractional_setting("work_mem"));
+    }
+
+    #[test]
+    fn test_memory_setting_bytes() {
+        assert_eq!(
+            memory_setting_bytes("shared_buffers", 16_384, Some("8kB")),
+            134_217_728
+        );
+        assert_eq!(
+            memory_setting_bytes("work_mem", 4096, Some("kB")),
+            4_194_304
+        );
+        assert_eq!(
+            memory_setting_bytes("temp_buffers", 1024, Some("8kB")),
+            8_388_608
+        );
+        // -1 means autovacuum falls back to maintenance_work_mem
+        assert_eq!(
+            memory_setting_bytes("autovacuum_work_mem", -1, Some("kB")),
+            -1
+        );
+        assert_eq!(memory_setting_bytes("max_connections", 100, None), 100);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::collectors::Collector;
+use crate::collectors::util::acquire_connection;
 use anyhow::{Result, anyhow};
 use futures::future::BoxFuture;
 use prometheus::{IntGaugeVec, Opts, Registry};
@@ -83,7 +84,7 @@ impl VersionCollector {
                 inet_server_port() as port,
                 current_database() as database",
         )
-        .fetch_one(pool)
+        .fetch_one(&mut *acquire_connection(pool).await?)
         .instrument(span)
         .await;
 
@@ -102,7 +103,7 @@ impl VersionCollector {
                     db.statement = "SELECT current_database()"
                 );
                 match sqlx::query_scalar::<_, String>("SELECT current_database()")
-                    .fetch_one(pool)
+                    .fetch_one(&mut *acquire_connection(pool).await?)
                     .instrument(span)
                     .await
                 {
@@ -125,7 +126,7 @@ impl VersionCollector {
             db.statement = "SELECT version()"
         );
         if let Ok(version_str) = sqlx::query_scalar::<_, String>("SELECT version()")
-            .fetch_one(pool)
+            .fetch_one(&mut *acquire_connection(pool).await?)
             .instrument(span)
             .await
             && let Some(captures) = self.version_regex.captures(&version_str)
@@ -141,7 +142,7 @@ impl VersionCollector {
             db.statement = "SHOW server_version"
         );
         let server_version = sqlx::query_scalar::<_, String>("SHOW server_version")
-            .fetch_one(pool)
+            .fetch_one(&mut *acquire_connection(pool).await?)
             .instrument(span)
             .await?;
 
@@ -158,7 +159,11 @@ impl VersionCollector {
         let parts: Vec<&str> = version.split('.').collect();
         match parts.len() {
             1 => format!("{}.0.0", parts.first().copied().unwrap_or("0")),
-            2 => format!("{}.{}.0", parts.first().copied().unwrap_or("0"), parts.get(1).copied().unwrap_or("0")),
+            2 => format!(
+                "{}.{}.0",
+                parts.first().copied().unwrap_or("0"),
+                parts.get(1).copied().unwrap_or("0")
+            ),
             _ => version.to_string(),
         }
     }
@@ -193,7 +198,7 @@ impl Collector for VersionCollector {
                 otel.kind = "client"
             );
             let full_version = sqlx::query_scalar::<_, String>("SELECT version()")
-                .fetch_one(pool)
+                .fetch_one(&mut *acquire_connection(pool).await?)
                 .instrument(span)
                 .await?;
 
@@ -209,7 +214,7 @@ impl Collector for VersionCollector {
                 otel.kind = "client"
             );
             let server_version_num_str = sqlx::query_scalar::<_, String>("SHOW server_version_num")
-                .fetch_one(pool)
+                .fetch_one(&mut *acquire_connection(pool).await?)
                 .instrument(span)
                 .await?;
 
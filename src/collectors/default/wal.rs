@@ -1,7 +1,10 @@
-use crate::collectors::Collector;
+use crate::collectors::{
+    Collector,
+    util::{acquire_connection, resolve_server_version},
+};
 use anyhow::Result;
 use futures::future::BoxFuture;
-use prometheus::{IntCounter, Opts, Registry};
+use prometheus::{Counter, IntCounter, Opts, Registry};
 use sqlx::{PgPool, Row};
 use tracing::{debug, info_span, instrument};
 use tracing_futures::Instrument as _;
@@ -11,12 +14,72 @@ use tracing_futures::Instrument as _;
 /// - `pg_stat_wal_fpi_total` (`Counter`)
 /// - `pg_stat_wal_bytes_total` (`Counter`)
 /// - `pg_stat_wal_buffers_full_total` (`Counter`)
+/// - `pg_stat_wal_write_total` (`Counter`, times WAL buffers were written out to disk)
+/// - `pg_stat_wal_sync_total` (`Counter`, times WAL files were synced to disk)
+/// - `pg_stat_wal_write_time_seconds_total` (`Counter`, requires `track_wal_io_timing`)
+/// - `pg_stat_wal_sync_time_seconds_total` (`Counter`, requires `track_wal_io_timing`)
+///
+/// A high sync time per sync is storage fsync latency, which every commit waits for.
+/// `PostgreSQL` 18 moved the write/sync columns from `pg_stat_wal` to `pg_stat_io`
+/// (`object = 'wal'`), where they are summed over backend types and contexts.
 #[derive(Clone)]
 pub struct WalCollector {
     records: IntCounter,      // pg_stat_wal_records_total
-    fpi: IntCounter,           // pg_stat_wal_fpi_total
-    bytes: IntCounter,         // pg_stat_wal_bytes_total
-    buffers_full: IntCounter,  // pg_stat_wal_buffers_full_total
+    fpi: IntCounter,          // pg_stat_wal_fpi_total
+    bytes: IntCounter,        // pg_stat_wal_bytes_total
+    buffers_full: IntCounter, // pg_stat_wal_buffers_full_total
+    write: IntCounter,        // pg_stat_wal_write_total
+    sync: IntCounter,         // pg_stat_wal_sync_total
+    write_time: Counter,      // pg_stat_wal_write_time_seconds_total
+    sync_time: Counter,       // pg_stat_wal_sync_time_seconds_total
+}
+
+/// `PostgreSQL` 18 moved WAL write/sync counts and timings to `pg_stat_io`.
+const WAL_IO_IN_STAT_IO_VERSION: i32 = 180_000;
+
+/// `pg_stat_wal` on `PostgreSQL` 14-17, timings converted from milliseconds.
+const WAL_QUERY: &str = r"
+    SELECT
+        wal_records,
+        wal_fpi,
+        wal_bytes::bigint AS wal_bytes,
+        wal_buffers_full,
+        wal_write::bigint AS wal_write,
+        wal_sync::bigint AS wal_sync,
+        (wal_write_time / 1000.0)::double precision AS wal_write_time_seconds,
+        (wal_sync_time / 1000.0)::double precision AS wal_sync_time_seconds
+    FROM pg_stat_wal
+    ";
+
+/// `pg_stat_wal` plus the WAL rows of `pg_stat_io` on `PostgreSQL` 18+.
+const WAL_QUERY_PG18: &str = r"
+    SELECT
+        w.wal_records,
+        w.wal_fpi,
+        w.wal_bytes::bigint AS wal_bytes,
+        w.wal_buffers_full,
+        io.wal_write,
+        io.wal_sync,
+        io.wal_write_time_seconds,
+        io.wal_sync_time_seconds
+    FROM pg_stat_wal w
+    CROSS JOIN (
+        SELECT
+            COALESCE(SUM(writes), 0)::bigint AS wal_write,
+            COALESCE(SUM(fsyncs), 0)::bigint AS wal_sync,
+            (COALESCE(SUM(write_time), 0) / 1000.0)::double precision AS wal_write_time_seconds,
+            (COALESCE(SUM(fsync_time), 0) / 1000.0)::double precision AS wal_sync_time_seconds
+        FROM pg_stat_io
+        WHERE object = 'wal'
+    ) io
+    ";
+
+const fn wal_query(version_num: i32) -> &'static str {
+    if version_num >= WAL_IO_IN_STAT_IO_VERSION {
+        WAL_QUERY_PG18
+    } else {
+        WAL_QUERY
+    }
 }
 
 impl Default for WalCollector {
@@ -58,11 +121,39 @@ impl WalCollector {
         ))
         .expect("Failed to create pg_stat_wal_buffers_full_total");
 
+        let wal_write = IntCounter::with_opts(Opts::new(
+            "pg_stat_wal_write_total",
+            "Number of times WAL buffers were written out to disk",
+        ))
+        .expect("Failed to create pg_stat_wal_write_total");
+
+        let wal_sync = IntCounter::with_opts(Opts::new(
+            "pg_stat_wal_sync_total",
+            "Number of times WAL files were synced to disk",
+        ))
+        .expect("Failed to create pg_stat_wal_sync_total");
+
+        let wal_write_time = Counter::with_opts(Opts::new(
+            "pg_stat_wal_write_time_seconds_total",
+            "Time spent writing WAL buffers to disk, in seconds (requires track_wal_io_timing)",
+        ))
+        .expect("Failed to create pg_stat_wal_write_time_seconds_total");
+
+        let wal_sync_time = Counter::with_opts(Opts::new(
+            "pg_stat_wal_sync_time_seconds_total",
+            "Time spent syncing WAL files to disk, in seconds (requires track_wal_io_timing)",
+        ))
+        .expect("Failed to create pg_stat_wal_sync_time_seconds_total");
+
         Self {
             records: wal_records,
             fpi: wal_fpi,
             bytes: wal_bytes,
             buffers_full: wal_buffers_full,
+            write: wal_write,
+            sync: wal_sync,
+            write_time: wal_write_time,
+            sync_time: wal_sync_time,
         }
     }
 }
@@ -72,17 +163,16 @@ impl Collector for WalCollector {
         "wal"
     }
 
-    #[instrument(
-        skip(self, registry),
-        level = "info",
-        err,
-        fields(collector = "wal")
-    )]
+    #[instrument(skip(self, registry), level = "info", err, fields(collector = "wal"))]
     fn register_metrics(&self, registry: &Registry) -> Result<()> {
         registry.register(Box::new(self.records.clone()))?;
         registry.register(Box::new(self.fpi.clone()))?;
         registry.register(Box::new(self.bytes.clone()))?;
         registry.register(Box::new(self.buffers_full.clone()))?;
+        registry.register(Box::new(self.write.clone()))?;
+        registry.register(Box::new(self.sync.clone()))?;
+        registry.register(Box::new(self.write_time.clone()))?;
+        registry.register(Box::new(self.sync_time.clone()))?;
         Ok(())
     }
 
@@ -94,6 +184,8 @@ impl Collector for WalCollector {
     )]
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
         Box::pin(async move {
+            let version_num = resolve_server_version(pool).await?;
+
             let query_span = info_span!(
                 "db.query",
                 otel.kind = "client",
@@ -103,19 +195,10 @@ impl Collector for WalCollector {
                 db.sql.table = "pg_stat_wal"
             );
 
-            let row_result = sqlx::query(
-                r"
-                SELECT
-                    wal_records,
-                    wal_fpi,
-                    wal_bytes::bigint AS wal_bytes,
-                    wal_buffers_full
-                FROM pg_stat_wal
-                ",
-            )
-            .fetch_one(pool)
-            .instrument(query_span)
-            .await;
+            let row_result = sqlx::query(wal_query(version_num))
+                .fetch_one(&mut *acquire_connection(pool).await?)
+                .instrument(query_span)
+                .await;
 
             let row = match row_result {
                 Ok(row) => row,
@@ -133,24 +216,34 @@ impl Collector for WalCollector {
             let wal_fpi: i64 = row.try_get("wal_fpi")?;
             let wal_bytes: i64 = row.try_get("wal_bytes")?;
             let wal_buffers_full: i64 = row.try_get("wal_buffers_full")?;
+            let wal_write: i64 = row.try_get("wal_write")?;
+            let wal_sync: i64 = row.try_get("wal_sync")?;
+            let wal_write_time: f64 = row.try_get("wal_write_time_seconds")?;
+            let wal_sync_time: f64 = row.try_get("wal_sync_time_seconds")?;
 
             // Reset and set the counter values
             self.records.reset();
             self.fpi.reset();
             self.bytes.reset();
             self.buffers_full.reset();
+            self.write.reset();
+            self.sync.reset();
+            self.write_time.reset();
+            self.sync_time.reset();
 
             self.records.inc_by(u64::try_from(wal_records).unwrap_or(0));
             self.fpi.inc_by(u64::try_from(wal_fpi).unwrap_or(0));
             self.bytes.inc_by(u64::try_from(wal_bytes).unwrap_or(0));
-            self.buffers_full.inc_by(u64::try_from(wal_buffers_full).unwrap_or(0));
+            self.buffers_full
+                .inc_by(u64::try_from(wal_buffers_full).unwrap_or(0));
+            self.write.inc_by(u64::try_from(wal_write).unwrap_or(0));
+            self.sync.inc_by(u64::try_from(wal_sync).unwrap_or(0));
+            self.write_time.inc_by(wal_write_time.max(0.0));
+            self.sync_time.inc_by(wal_sync_time.max(0.0));
 
             debug!(
                 wal_records,
-                wal_fpi,
-                wal_bytes,
-                wal_buffers_full,
-                "updated WAL metrics"
+                wal_fpi, wal_bytes, wal_buffers_full, wal_write, wal_sync, "updated WAL metrics"
             );
 
             Ok(())
@@ -161,3 +254,15 @@ impl Collector for WalCollector {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wal_query_reads_pg_stat_io_on_pg18() {
+        assert!(wal_query(140_000).contains("wal_sync_time / 1000.0"));
+        assert!(wal_query(170_005).contains("wal_sync_time / 1000.0"));
+        assert!(wal_query(180_000).contains("WHERE object = 'wal'"));
+    }
+}
@@ -0,0 +1,202 @@
+use crate::collectors::util::{
+    anonymize_label_value, get_anonymize_salt, get_connect_timeout, get_excluded_databases,
+    get_excluded_users, get_lock_timeout_ms, get_max_db_concurrency, get_scrape_timeout,
+    get_sequential_collection, get_statement_timeout_ms,
+};
+use crate::exporter::{METRICS_PATH, get_route_prefix};
+use anyhow::Result;
+use futures::future::BoxFuture;
+use once_cell::sync::OnceCell;
+use prometheus::{IntGaugeVec, Opts, Registry};
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// Values only known once the HTTP server is up, set by `exporter::new`
+static RUNTIME_CONFIG: OnceCell<RuntimeConfig> = OnceCell::new();
+
+struct RuntimeConfig {
+    listen: String,
+    collectors: String,
+}
+
+/// Record the bound listen address and the enabled collectors once at startup so they are
+/// reported by `pg_exporter_config_info`.
+pub fn set_config_info(listen: &str, collectors: &[String]) {
+    let _ = RUNTIME_CONFIG.set(RuntimeConfig {
+        listen: listen.to_string(),
+        collectors: collectors.join(","),
+    });
+}
+
+const LABELS: [&str; 11] = [
+    "listen",
+    "telemetry_path",
+    "collectors",
+    "exclude_databases",
+    "exclude_users",
+    "scrape_timeout",
+    "statement_timeout",
+    "lock_timeout",
+    "connect_timeout",
+    "max_db_concurrency",
+    "sequential_collection",
+];
+
+/// Exposes the exporter's effective, non-secret configuration as an info metric
+///
+/// - `pg_exporter_config_info{listen,telemetry_path,collectors,...}` (`IntGaugeVec`, always 1)
+///
+/// Comparing this series across a fleet catches instances that drift from the standard
+/// configuration, such as a collector that was accidentally disabled. Only values that are
+/// safe to publish are included: the DSN, passwords, the admin token and the anonymization
+/// salt are never exported. Under `--anonymize-labels` every entry of `exclude_databases` and
+/// `exclude_users` is replaced by its salted hash, like the `datname` and `usename` labels.
+#[derive(Clone)]
+pub struct ConfigInfoCollector {
+    info: IntGaugeVec,
+}
+
+impl Default for ConfigInfoCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigInfoCollector {
+    /// Creates a new `ConfigInfoCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let info = IntGaugeVec::new(
+            Opts::new(
+                "pg_exporter_config_info",
+                "Effective non-secret pg_exporter configuration, always 1",
+            ),
+            &LABELS,
+        )
+        .expect("pg_exporter_config_info");
+
+        Self { info }
+    }
+}
+
+/// Comma-separated exclusion list, each entry hashed when label anonymization is enabled
+fn exclusion_list(names: &[String]) -> String {
+    match get_anonymize_salt() {
+        Some(salt) => names
+            .iter()
+            .map(|name| anonymize_label_value(name, salt))
+            .collect::<Vec<_>>()
+            .join(","),
+        None => names.join(","),
+    }
+}
+
+/// Current label values, in `LABELS` order
+fn config_label_values() -> [String; 11] {
+    let (listen, collectors) = RUNTIME_CONFIG
+        .get()
+        .map(|c| (c.listen.clone(), c.collectors.clone()))
+        .unwrap_or_default();
+
+    [
+        listen,
+        format!("{}{METRICS_PATH}", get_route_prefix()),
+        collectors,
+        exclusion_list(get_excluded_databases()),
+        exclusion_list(get_excluded_users()),
+        format!("{:?}", get_scrape_timeout()),
+        format!("{:?}", Duration::from_millis(get_statement_timeout_ms())),
+        format!("{:?}", Duration::from_millis(get_lock_timeout_ms())),
+        format!("{:?}", get_connect_timeout()),
+        get_max_db_concurrency().to_string(),
+        get_sequential_collection().to_string(),
+    ]
+}
+
+impl crate::collectors::Collector for ConfigInfoCollector {
+    fn name(&self) -> &'static str {
+        "config_info"
+    }
+
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.info.clone()))?;
+        Ok(())
+    }
+
+    fn collect<'a>(&'a self, _pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let values = config_label_values();
+            let values: Vec<&str> = values.iter().map(String::as_str).collect();
+
+            self.info.reset();
+            self.info.with_label_values(&values).set(1);
+
+            Ok(())
+        })
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::Collector;
+    use sqlx::postgres::PgPoolOptions;
+
+    #[tokio::test]
+    #[allow(clippy::expect_used)]
+    async fn test_config_info_exports_non_secret_values() -> Result<()> {
+        set_config_info(
+            "[::]:9432",
+            &["default".to_string(), "exporter".to_string()],
+        );
+
+        let collector = ConfigInfoCollector::new();
+        let registry = Registry::new();
+        collector.register_metrics(&registry)?;
+
+        // collect() never touches the pool
+        let pool = PgPoolOptions::new().connect_lazy("postgresql://localhost/postgres")?;
+        collector.collect(&pool).await?;
+
+        let families = registry.gather();
+        let family = families
+            .iter()
+            .find(|m| m.name() == "pg_exporter_config_info")
+            .expect("pg_exporter_config_info should be exported");
+        let metric = family.get_metric().first().expect("one series");
+        let label = |name: &str| {
+            metric
+                .get_label()
+                .iter()
+                .find(|l| l.name() == name)
+                .map(|l| l.value().to_string())
+        };
+
+        assert!((metric.get_gauge().value() - 1.0).abs() < f64::EPSILON);
+        assert_eq!(label("listen").as_deref(), Some("[::]:9432"));
+        assert_eq!(label("telemetry_path").as_deref(), Some("/metrics"));
+        assert_eq!(label("collectors").as_deref(), Some("default,exporter"));
+        assert_eq!(
+            label("scrape_timeout"),
+            Some(format!("{:?}", get_scrape_timeout()))
+        );
+        assert!(
+            metric
+                .get_label()
+                .iter()
+                .all(|l| !l.name().contains("dsn") && !l.name().contains("password")),
+            "no secret material in labels"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+use anyhow::Result;
+use futures::future::BoxFuture;
+use once_cell::sync::OnceCell;
+use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry};
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// Process-wide HTTP server metrics, shared by the tower layer that records
+/// them and the `HttpCollector` that registers them.
+static HTTP_METRICS: OnceCell<HttpMetrics> = OnceCell::new();
+
+#[derive(Clone)]
+struct HttpMetrics {
+    requests_total: IntCounterVec,
+    request_duration_seconds: HistogramVec,
+    requests_in_flight: IntGauge,
+}
+
+impl HttpMetrics {
+    #[allow(clippy::expect_used)]
+    fn new() -> Self {
+        let requests_total = IntCounterVec::new(
+            Opts::new(
+                "pg_exporter_http_requests_total",
+                "Total number of HTTP requests served by the exporter",
+            ),
+            &["path", "status"],
+        )
+        .expect("pg_exporter_http_requests_total");
+
+        let request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "pg_exporter_http_request_duration_seconds",
+                "Time spent serving HTTP requests in seconds",
+            )
+            .buckets(vec![
+                0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+            ]),
+            &["path"],
+        )
+        .expect("pg_exporter_http_request_duration_seconds");
+
+        let requests_in_flight = IntGauge::with_opts(Opts::new(
+            "pg_exporter_http_requests_in_flight",
+            "Number of HTTP requests currently being served",
+        ))
+        .expect("pg_exporter_http_requests_in_flight");
+
+        Self {
+            requests_total,
+            request_duration_seconds,
+            requests_in_flight,
+        }
+    }
+}
+
+fn http_metrics() -> &'static HttpMetrics {
+    HTTP_METRICS.get_or_init(HttpMetrics::new)
+}
+
+/// Record a completed HTTP request. `path` should be the matched route, not the
+/// raw URI, to keep label cardinality bounded.
+pub fn record_http_request(path: &str, status: u16, latency: Duration) {
+    let metrics = http_metrics();
+
+    metrics
+        .requests_total
+        .with_label_values(&[path, status.to_string().as_str()])
+        .inc();
+
+    metrics
+        .request_duration_seconds
+        .with_label_values(&[path])
+        .observe(latency.as_secs_f64());
+}
+
+/// Mark an HTTP request as in flight until the returned guard is dropped
+#[must_use]
+pub fn track_http_request_in_flight() -> InFlightGuard {
+    http_metrics().requests_in_flight.inc();
+    InFlightGuard(())
+}
+
+/// RAII guard decrementing `pg_exporter_http_requests_in_flight` on drop, so
+/// cancelled or panicking requests are not counted forever.
+pub struct InFlightGuard(());
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        http_metrics().requests_in_flight.dec();
+    }
+}
+
+/// Exposes request-level metrics for the exporter's own HTTP server
+///
+/// - `pg_exporter_http_requests_total{path,status}` (`IntCounterVec`)
+/// - `pg_exporter_http_request_duration_seconds{path}` (Histogram)
+/// - `pg_exporter_http_requests_in_flight` (`IntGauge`)
+///
+/// The values are recorded by the HTTP server's tower layers for every request;
+/// this collector only registers them so they are exported alongside the rest
+/// of the self-monitoring metrics.
+#[derive(Clone)]
+pub struct HttpCollector {
+    metrics: HttpMetrics,
+}
+
+impl Default for HttpCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpCollector {
+    /// Creates a new `HttpCollector` backed by the process-wide HTTP metrics
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            metrics: http_metrics().clone(),
+        }
+    }
+}
+
+impl crate::collectors::Collector for HttpCollector {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.metrics.requests_total.clone()))?;
+        registry.register(Box::new(self.metrics.request_duration_seconds.clone()))?;
+        registry.register(Box::new(self.metrics.requests_in_flight.clone()))?;
+        Ok(())
+    }
+
+    fn collect<'a>(&'a self, _pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        // Metrics are updated by the HTTP layer as requests are served
+        Box::pin(async { Ok(()) })
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::Collector;
+
+    #[test]
+    fn test_record_http_request_updates_counter_and_histogram() {
+        let collector = HttpCollector::new();
+        let path = "/test/record";
+
+        record_http_request(path, 200, Duration::from_millis(20));
+        record_http_request(path, 503, Duration::from_millis(40));
+
+        let ok = collector
+            .metrics
+            .requests_total
+            .with_label_values(&[path, "200"])
+            .get();
+        let unavailable = collector
+            .metrics
+            .requests_total
+            .with_label_values(&[path, "503"])
+            .get();
+        let observed = collector
+            .metrics
+            .request_duration_seconds
+            .with_label_values(&[path])
+            .get_sample_count();
+
+        assert_eq!(ok, 1);
+        assert_eq!(unavailable, 1);
+        assert_eq!(observed, 2);
+    }
+
+    #[test]
+    fn test_in_flight_guard_decrements_on_drop() {
+        let collector = HttpCollector::new();
+        let before = collector.metrics.requests_in_flight.get();
+
+        let guard = track_http_request_in_flight();
+        assert_eq!(collector.metrics.requests_in_flight.get(), before + 1);
+        drop(guard);
+
+        assert_eq!(collector.metrics.requests_in_flight.get(), before);
+    }
+
+    #[test]
+    fn test_http_collector_registers_metrics() {
+        let collector = HttpCollector::new();
+        let registry = Registry::new();
+        assert!(collector.register_metrics(&registry).is_ok());
+
+        record_http_request("/test/register", 200, Duration::from_millis(1));
+
+        let names: Vec<String> = registry
+            .gather()
+            .iter()
+            .map(|m| m.name().to_string())
+            .collect();
+        assert!(names.contains(&"pg_exporter_http_requests_total".to_string()));
+        assert!(names.contains(&"pg_exporter_http_request_duration_seconds".to_string()));
+        assert!(names.contains(&"pg_exporter_http_requests_in_flight".to_string()));
+    }
+}
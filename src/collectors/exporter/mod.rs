@@ -13,7 +13,7 @@
 ///
 /// # Architecture
 ///
-/// The exporter collector consists of two sub-collectors:
+/// The exporter collector consists of four sub-collectors:
 ///
 /// ## `ProcessCollector`
 /// Monitors the exporter's process resource consumption using the `sysinfo` crate:
@@ -29,6 +29,15 @@
 /// - Last scrape timestamp and success status
 /// - Total metric cardinality (for operators with limits)
 ///
+/// ## `HttpCollector`
+/// Exposes request-level metrics for the exporter's own HTTP server:
+/// - Requests per route and status code
+/// - Request latency (histogram) and requests in flight
+///
+/// ## `ConfigInfoCollector`
+/// Exposes the effective, non-secret configuration as `pg_exporter_config_info`
+/// for drift detection across a fleet
+///
 /// # Threading and Locking
 ///
 /// `ProcessCollector` keeps a small `Mutex`-protected `sysinfo::System` cache.
@@ -111,21 +120,23 @@
 /// - **macOS/Windows**: Basic metrics (CPU, memory), limited thread/FD info
 ///
 /// Platform-specific code is guarded with `#[cfg(target_os = "linux")]`.
+mod config_info;
+mod http;
 mod process;
 mod scraper;
 
+pub use config_info::{ConfigInfoCollector, set_config_info};
+pub use http::{HttpCollector, InFlightGuard, record_http_request, track_http_request_in_flight};
 pub use process::ProcessCollector;
 pub use scraper::{ScrapeTimer, ScraperCollector};
 
-use crate::collectors::Collector;
+use crate::collectors::{Collector, util::collect_subs};
 use anyhow::Result;
 use futures::future::BoxFuture;
-use futures::stream::{FuturesUnordered, StreamExt};
 use prometheus::Registry;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tracing::{debug, info_span, instrument, warn};
-use tracing_futures::Instrument as _;
 
 /// `ExporterCollector` combines all exporter self-monitoring
 #[derive(Clone)]
@@ -148,6 +159,8 @@ impl ExporterCollector {
             subs: vec![
                 Arc::new(ProcessCollector::new()),
                 Arc::clone(&scraper) as Arc<dyn Collector + Send + Sync>,
+                Arc::new(HttpCollector::new()),
+                Arc::new(ConfigInfoCollector::new()),
             ],
             scraper,
         }
@@ -201,20 +214,7 @@ impl Collector for ExporterCollector {
 
     #[instrument(skip(self, pool), level = "info", err, fields(collector = "exporter", otel.kind = "internal"))]
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
-        Box::pin(async move {
-            let mut tasks = FuturesUnordered::new();
-
-            for sub in &self.subs {
-                let span = info_span!("collector.collect", sub_collector = %sub.name(), otel.kind = "internal");
-                tasks.push(sub.collect(pool).instrument(span));
-            }
-
-            while let Some(res) = tasks.next().await {
-                res?;
-            }
-
-            Ok(())
-        })
+        Box::pin(async move { collect_subs(&self.subs, pool).await })
     }
 
     fn enabled_by_default(&self) -> bool {
@@ -230,7 +230,7 @@ mod tests {
     #[allow(clippy::unwrap_used)]
     fn test_exporter_collector_new() {
         let collector = ExporterCollector::new();
-        assert_eq!(collector.subs.len(), 2);
+        assert_eq!(collector.subs.len(), 4);
     }
 
     #[test]
@@ -0,0 +1,105 @@
+//! `fdw` collector umbrella.
+//!
+//! `mod.rs` is the entry point: it wires up the `servers` sub-collector and exposes
+//! it under the `--collector.fdw` CLI flag. The SQL and the reachability probe live
+//! in [`servers`].
+//!
+//! Foreign servers (`postgres_fdw` and other wrappers) are cross-database
+//! dependencies that are easy to lose track of. The inventory is cheap catalog
+//! reads; probing is opt-in via `--fdw.probe` because it queries through the foreign
+//! servers, reaching hosts outside the monitored cluster.
+
+use crate::collectors::{Collector, util::collect_subs};
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::Registry;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::{debug, info_span, instrument, warn};
+
+pub mod servers;
+use servers::ForeignServersCollector;
+
+/// Foreign server inventory and optional reachability, from `pg_foreign_server`.
+///
+/// This is the umbrella collector selected by `--collector.fdw`. It holds a single
+/// [`ForeignServersCollector`] sub-collector and fans registration and collection
+/// out to it, matching the structure used by the other collectors.
+#[derive(Clone)]
+pub struct FdwCollector {
+    subs: Vec<Arc<dyn Collector + Send + Sync>>,
+}
+
+impl Default for FdwCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FdwCollector {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_probe(false)
+    }
+
+    /// Create the collector, probing foreign servers with a query through one of their
+    /// foreign tables when `probe` is set.
+    #[must_use]
+    pub fn with_probe(probe: bool) -> Self {
+        Self {
+            subs: vec![Arc::new(ForeignServersCollector::with_probe(probe))],
+        }
+    }
+}
+
+impl Collector for FdwCollector {
+    fn name(&self) -> &'static str {
+        "fdw"
+    }
+
+    #[instrument(skip(self, registry), level = "info", err, fields(collector = "fdw"))]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        for sub in &self.subs {
+            let span = info_span!("collector.register_metrics", sub_collector = %sub.name());
+            let res = sub.register_metrics(registry);
+            match res {
+                Ok(()) => debug!(collector = sub.name(), "registered metrics"),
+                Err(ref e) => {
+                    warn!(collector = sub.name(), error = %e, "failed to register metrics");
+                }
+            }
+            res?;
+            drop(span);
+        }
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector = "fdw", otel.kind = "internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move { collect_subs(&self.subs, pool).await })
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fdw_collector_name() {
+        assert_eq!(FdwCollector::new().name(), "fdw");
+    }
+
+    #[test]
+    fn test_fdw_collector_not_enabled_by_default() {
+        assert!(!FdwCollector::new().enabled_by_default());
+    }
+}
@@ -0,0 +1,398 @@
+use crate::collectors::util::{
+    acquire_connection, acquire_db_query_permit, get_default_database, in_acquire_scope,
+    list_target_databases, open_db_connection,
+};
+use crate::collectors::{Collector, all_databases_failed};
+use anyhow::{Result, anyhow};
+use futures::future::BoxFuture;
+use prometheus::{GaugeVec, IntGaugeVec, Opts, Registry};
+use sqlx::{PgConnection, PgPool, Row, postgres::PgRow};
+use std::time::Instant;
+use tokio::task::JoinSet;
+use tracing::{debug, error, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// Foreign servers of the current database with their wrapper, number of user mappings
+/// and a foreign table to probe them through. `pg_user_mappings` is readable by everyone
+/// (`pg_user_mapping` is not), and only the mapping count is used, never the mapping
+/// options. `regclass::text` quotes and schema-qualifies the table name as needed.
+const FOREIGN_SERVERS_QUERY: &str = r"
+    SELECT
+        current_database() AS datname,
+        s.srvname::text AS server,
+        w.fdwname::text AS wrapper,
+        (SELECT COUNT(*) FROM pg_user_mappings um WHERE um.srvid = s.oid)::bigint AS user_mappings,
+        (SELECT ft.ftrelid::regclass::text
+         FROM pg_foreign_table ft
+         WHERE ft.ftserver = s.oid
+           AND has_table_privilege(ft.ftrelid, 'SELECT')
+         ORDER BY ft.ftrelid
+         LIMIT 1) AS probe_table
+    FROM pg_foreign_server s
+    JOIN pg_foreign_data_wrapper w ON w.oid = s.srvfdw
+    ORDER BY s.srvname
+    ";
+
+/// Outcome of probing one foreign server: whether the query succeeded and how long it took
+type ProbeResult = (bool, f64);
+
+/// One row of [`FOREIGN_SERVERS_QUERY`], plus the probe outcome when it was probed
+#[derive(Clone, Debug)]
+struct ForeignServer {
+    datname: String,
+    server: String,
+    wrapper: String,
+    user_mappings: i64,
+    probe_table: Option<String>,
+    probe: Option<ProbeResult>,
+}
+
+impl ForeignServer {
+    fn from_row(row: &PgRow) -> Result<Self> {
+        Ok(Self {
+            datname: row.try_get("datname")?,
+            server: row.try_get("server")?,
+            wrapper: row.try_get("wrapper")?,
+            user_mappings: row.try_get("user_mappings")?,
+            probe_table: row.try_get("probe_table")?,
+            probe: None,
+        })
+    }
+}
+
+/// Query that reads at most one row of `table` through its foreign server.
+fn probe_query(table: &str) -> String {
+    format!("SELECT 1 FROM {table} LIMIT 1")
+}
+
+/// Inventory of foreign servers (`postgres_fdw` and any other wrapper) per database
+///
+/// **Key metrics:**
+/// - `pg_foreign_servers_count{datname}`: foreign servers defined in the database
+/// - `pg_foreign_server_info{datname,server,wrapper}`: always 1
+/// - `pg_foreign_server_user_mappings{datname,server}`: user mappings for the server
+///
+/// **Reachability probe (opt-in):**
+/// With probing enabled, every server with a foreign table the exporter role can read
+/// gets `SELECT 1 FROM <table> LIMIT 1`, exported as `pg_foreign_server_up{datname,server}`
+/// and `pg_foreign_server_probe_duration_seconds{datname,server}`. The query goes through
+/// the wrapper, so it checks the user mapping and the remote side as well as the network.
+/// It is bounded by the connection's `statement_timeout` like any other collector query.
+///
+/// **Multi-database:**
+/// `pg_foreign_server` is a per-database catalog, so this collector iterates every
+/// connectable, non-excluded database like the other per-database collectors.
+#[derive(Clone)]
+pub struct ForeignServersCollector {
+    probe: bool,
+    servers_count: IntGaugeVec,
+    server_info: IntGaugeVec,
+    user_mappings: IntGaugeVec,
+    server_up: IntGaugeVec,
+    probe_duration: GaugeVec,
+}
+
+impl Default for ForeignServersCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ForeignServersCollector {
+    /// Creates a new `ForeignServersCollector` without reachability probing
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_probe(false)
+    }
+
+    /// Creates a new `ForeignServersCollector`, probing each server through one of its
+    /// foreign tables when `probe` is set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn with_probe(probe: bool) -> Self {
+        Self {
+            probe,
+            servers_count: IntGaugeVec::new(
+                Opts::new(
+                    "pg_foreign_servers_count",
+                    "Number of foreign servers defined in the database",
+                ),
+                &["datname"],
+            )
+            .expect("Failed to create pg_foreign_servers_count"),
+            server_info: IntGaugeVec::new(
+                Opts::new(
+                    "pg_foreign_server_info",
+                    "Foreign server definition with its wrapper (always 1)",
+                ),
+                &["datname", "server", "wrapper"],
+            )
+            .expect("Failed to create pg_foreign_server_info"),
+            user_mappings: IntGaugeVec::new(
+                Opts::new(
+                    "pg_foreign_server_user_mappings",
+                    "Number of user mappings defined for the foreign server",
+                ),
+                &["datname", "server"],
+            )
+            .expect("Failed to create pg_foreign_server_user_mappings"),
+            server_up: IntGaugeVec::new(
+                Opts::new(
+                    "pg_foreign_server_up",
+                    "Whether a query through one of the foreign server's tables succeeded (1) or not (0)",
+                ),
+                &["datname", "server"],
+            )
+            .expect("Failed to create pg_foreign_server_up"),
+            probe_duration: GaugeVec::new(
+                Opts::new(
+                    "pg_foreign_server_probe_duration_seconds",
+                    "Time taken by the last foreign server reachability probe in seconds",
+                ),
+                &["datname", "server"],
+            )
+            .expect("Failed to create pg_foreign_server_probe_duration_seconds"),
+        }
+    }
+
+    async fn fetch_database(
+        datname: String,
+        shared_pool: PgPool,
+        use_shared: bool,
+        probe: bool,
+    ) -> Result<(String, Vec<ForeignServer>)> {
+        let query_span = info_span!(
+            "db.query",
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.operation = "SELECT",
+            db.statement = "SELECT srvname, fdwname FROM pg_foreign_server",
+            db.sql.table = "pg_foreign_server",
+            datname = %datname,
+            reuse_pool = use_shared
+        );
+
+        let servers = if use_shared {
+            let mut conn = acquire_connection(&shared_pool).await?;
+            Self::sample_database(&mut conn, probe)
+                .instrument(query_span)
+                .await?
+        } else {
+            let permit = acquire_db_query_permit().await.map_err(|e| {
+                anyhow!("foreign_servers: failed to acquire database query permit: {e}")
+            })?;
+            let mut conn = open_db_connection(&datname, &permit).await?;
+            Self::sample_database(&mut conn, probe)
+                .instrument(query_span)
+                .await?
+        };
+
+        Ok((datname, servers))
+    }
+
+    async fn sample_database(conn: &mut PgConnection, probe: bool) -> Result<Vec<ForeignServer>> {
+        let rows = sqlx::query(FOREIGN_SERVERS_QUERY)
+            .fetch_all(&mut *conn)
+            .await?;
+
+        let mut servers = rows
+            .iter()
+            .map(ForeignServer::from_row)
+            .collect::<Result<Vec<_>>>()?;
+
+        if probe {
+            for server in &mut servers {
+                if let Some(table) = server.probe_table.as_deref() {
+                    server.probe = Some(Self::probe(conn, server, table).await);
+                }
+            }
+        }
+
+        Ok(servers)
+    }
+
+    /// Read one row of `table` through its foreign server. Any error (unreachable host,
+    /// rejected credentials, `statement_timeout`) marks the server as down.
+    async fn probe(conn: &mut PgConnection, server: &ForeignServer, table: &str) -> ProbeResult {
+        let query = probe_query(table);
+        let probe_span = info_span!(
+            "db.query",
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.operation = "SELECT",
+            db.statement = %query,
+            datname = %server.datname,
+            server = %server.server
+        );
+
+        let start = Instant::now();
+        let result = sqlx::query(sqlx::AssertSqlSafe(query.as_str()))
+            .fetch_optional(&mut *conn)
+            .instrument(probe_span)
+            .await;
+        let elapsed = start.elapsed().as_secs_f64();
+
+        match result {
+            Ok(_) => (true, elapsed),
+            Err(e) => {
+                debug!(
+                    datname = %server.datname,
+                    server = %server.server,
+                    error = %e,
+                    "foreign_servers: probe query failed"
+                );
+                (false, elapsed)
+            }
+        }
+    }
+}
+
+impl Collector for ForeignServersCollector {
+    fn name(&self) -> &'static str {
+        "foreign_servers"
+    }
+
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.servers_count.clone()))?;
+        registry.register(Box::new(self.server_info.clone()))?;
+        registry.register(Box::new(self.user_mappings.clone()))?;
+        registry.register(Box::new(self.server_up.clone()))?;
+        registry.register(Box::new(self.probe_duration.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector = "foreign_servers", otel.kind = "internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            // 1) Discover connectable, non-excluded databases via the shared pool.
+            let dbs = list_target_databases(pool).await?;
+            let default_db = get_default_database().map(std::string::ToString::to_string);
+
+            // 2) One task per DB. The default DB reuses the shared pool; every other database
+            // must pass through the global per-database connection limiter.
+            let mut tasks: JoinSet<Result<(String, Vec<ForeignServer>)>> = JoinSet::new();
+            let num_dbs = dbs.len();
+            for datname in dbs {
+                let use_shared = default_db.as_deref() == Some(datname.as_str());
+                tasks.spawn(in_acquire_scope(Self::fetch_database(
+                    datname,
+                    pool.clone(),
+                    use_shared,
+                    self.probe,
+                )));
+            }
+
+            let mut per_db = Vec::new();
+            let mut failures = Vec::new();
+            while let Some(joined) = tasks.join_next().await {
+                match joined {
+                    Ok(Ok(result)) => per_db.push(result),
+                    Ok(Err(e)) => {
+                        error!(error=?e, "foreign_servers: task returned error");
+                        failures.push(e.to_string());
+                    }
+                    Err(e) => {
+                        error!(error=?e, "foreign_servers: task join error");
+                        failures.push(e.to_string());
+                    }
+                }
+            }
+
+            if all_databases_failed(num_dbs, failures.len()) {
+                return Err(anyhow!(
+                    "foreign_servers collection failed for ALL {} database task(s): {}",
+                    failures.len(),
+                    failures.join("; ")
+                ));
+            }
+
+            if !failures.is_empty() {
+                error!(
+                    failed_databases = failures.len(),
+                    errors = %failures.join("; "),
+                    "foreign_servers: continuing with partial snapshot after per-database failures"
+                );
+            }
+
+            let servers: Vec<ForeignServer> = per_db
+                .iter()
+                .flat_map(|(_, servers)| servers.iter().cloned())
+                .collect();
+
+            self.servers_count.reset();
+            self.server_info.reset();
+            self.user_mappings.reset();
+            self.server_up.reset();
+            self.probe_duration.reset();
+
+            for (datname, db_servers) in &per_db {
+                let count = i64::try_from(db_servers.len()).unwrap_or(i64::MAX);
+                self.servers_count
+                    .with_label_values(&[datname.as_str()])
+                    .set(count);
+            }
+
+            let mut probed = 0;
+            for server in &servers {
+                let labels = [server.datname.as_str(), server.server.as_str()];
+                self.server_info
+                    .with_label_values(&[
+                        server.datname.as_str(),
+                        server.server.as_str(),
+                        server.wrapper.as_str(),
+                    ])
+                    .set(1);
+                self.user_mappings
+                    .with_label_values(&labels)
+                    .set(server.user_mappings);
+
+                if let Some((up, elapsed)) = server.probe {
+                    probed += 1;
+                    self.server_up.with_label_values(&labels).set(i64::from(up));
+                    self.probe_duration.with_label_values(&labels).set(elapsed);
+                }
+            }
+
+            debug!(
+                servers = servers.len(),
+                probed, "updated foreign server metrics"
+            );
+
+            Ok(())
+        })
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probe_query_reads_one_row() {
+        assert_eq!(
+            probe_query(r#"remote."Orders""#),
+            r#"SELECT 1 FROM remote."Orders" LIMIT 1"#
+        );
+    }
+
+    #[test]
+    fn test_foreign_servers_collector_registers() {
+        let registry = Registry::new();
+        let collector = ForeignServersCollector::new();
+        assert_eq!(collector.name(), "foreign_servers");
+        assert!(collector.register_metrics(&registry).is_ok());
+    }
+}
@@ -1,18 +1,18 @@
 mod stats;
 mod unused;
+mod validity;
 
 pub use stats::IndexStatsCollector;
 pub use unused::UnusedIndexCollector;
+pub use validity::IndexValidityCollector;
 
-use crate::collectors::Collector;
+use crate::collectors::{Collector, util::collect_subs};
 use anyhow::Result;
 use futures::future::BoxFuture;
-use futures::stream::{FuturesUnordered, StreamExt};
 use prometheus::Registry;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tracing::{debug, info_span, instrument, warn};
-use tracing_futures::Instrument as _;
 
 /// Main index health collector that combines stats and unused index tracking
 ///
@@ -20,7 +20,7 @@ use tracing_futures::Instrument as _;
 /// - Index usage statistics (scans, tuples read/fetched)
 /// - Index size and bloat estimation
 /// - Unused index detection (`idx_scan` = 0)
-/// - Invalid index identification
+/// - Invalid index and `NOT VALID` constraint identification
 ///
 /// Helps identify maintenance opportunities and problematic schemas that impact `query` performance.
 /// Unused indexes consume disk space and slow down write operations (INSERT/UPDATE/DELETE).
@@ -37,6 +37,7 @@ impl IndexCollector {
             subs: vec![
                 Arc::new(IndexStatsCollector::new()),
                 Arc::new(UnusedIndexCollector::new()),
+                Arc::new(IndexValidityCollector::new()),
             ],
         }
     }
@@ -47,12 +48,7 @@ impl Collector for IndexCollector {
         "index"
     }
 
-    #[instrument(
-        skip(self, registry),
-        level = "info",
-        err,
-        fields(collector = "index")
-    )]
+    #[instrument(skip(self, registry), level = "info", err, fields(collector = "index"))]
     fn register_metrics(&self, registry: &Registry) -> Result<()> {
         for sub in &self.subs {
             let span = info_span!("collector.register_metrics", sub_collector = %sub.name());
@@ -81,21 +77,7 @@ impl Collector for IndexCollector {
         fields(collector = "index", otel.kind = "internal")
     )]
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
-        Box::pin(async move {
-            let mut tasks = FuturesUnordered::new();
-
-            for sub in &self.subs {
-                let span = info_span!("collector.collect", sub_collector = %sub.name(), otel.kind = "internal");
-
-                tasks.push(sub.collect(pool).instrument(span));
-            }
-
-            while let Some(res) = tasks.next().await {
-                res?;
-            }
-
-            Ok(())
-        })
+        Box::pin(async move { collect_subs(&self.subs, pool).await })
     }
 
     fn enabled_by_default(&self) -> bool {
@@ -119,4 +101,3 @@ mod tests {
         assert!(!collector.enabled_by_default());
     }
 }
-
@@ -1,5 +1,6 @@
 use crate::collectors::util::{
-    acquire_db_query_permit, get_default_database, get_excluded_databases, open_db_connection,
+    acquire_connection, acquire_db_query_permit, datname_label, get_default_database,
+    in_acquire_scope, list_target_databases, open_db_connection,
 };
 use crate::collectors::{Collector, all_databases_failed, i64_to_f64};
 use anyhow::{Result, anyhow};
@@ -166,9 +167,7 @@ impl IndexStatsCollector {
 
     fn sample_from_row(row: &PgRow) -> Result<IndexStatsSample> {
         Ok(IndexStatsSample {
-            datname: row
-                .try_get::<Option<String>, _>("datname")?
-                .unwrap_or_else(|| "[unknown]".to_string()),
+            datname: datname_label(row.try_get("datname")?),
             scans: row.try_get("total_scans").unwrap_or(0),
             tuples_read: row.try_get("total_tup_read").unwrap_or(0),
             tuples_fetched: row.try_get("total_tup_fetch").unwrap_or(0),
@@ -205,29 +204,7 @@ impl Collector for IndexStatsCollector {
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
         Box::pin(async move {
             // 1) Discover connectable, non-excluded databases via the shared pool.
-            let excluded = get_excluded_databases().to_vec();
-            let db_list_span = info_span!(
-                "db.query",
-                otel.kind = "client",
-                db.system = "postgresql",
-                db.operation = "SELECT",
-                db.statement = "SELECT datname FROM pg_database WHERE datallowconn ...",
-                db.sql.table = "pg_database"
-            );
-            let dbs: Vec<String> = sqlx::query_scalar(
-                r"
-                SELECT datname
-                FROM pg_database
-                WHERE datallowconn
-                  AND NOT datistemplate
-                  AND NOT (datname = ANY($1))
-                ORDER BY datname
-                ",
-            )
-            .bind(&excluded)
-            .fetch_all(pool)
-            .instrument(db_list_span)
-            .await?;
+            let dbs = list_target_databases(pool).await?;
 
             let shared_pool = pool.clone();
             let default_db = get_default_database().map(std::string::ToString::to_string);
@@ -241,7 +218,7 @@ impl Collector for IndexStatsCollector {
                 let shared_pool = shared_pool.clone();
                 let default_db = default_db.clone();
 
-                tasks.spawn(async move {
+                tasks.spawn(in_acquire_scope(async move {
                     let use_shared = default_db.as_deref() == Some(datname.as_str());
 
                     let query_span = info_span!(
@@ -265,7 +242,7 @@ impl Collector for IndexStatsCollector {
 
                     let row_res: anyhow::Result<Option<PgRow>> = if use_shared {
                         sqlx::query(INDEX_STATS_QUERY)
-                            .fetch_optional(&shared_pool)
+                            .fetch_optional(&mut *acquire_connection(&shared_pool).await?)
                             .instrument(query_span)
                             .await
                             .map_err(Into::into)
@@ -287,7 +264,7 @@ impl Collector for IndexStatsCollector {
                         Some(row) => Ok(Some(Self::sample_from_row(&row)?)),
                         None => Ok(None),
                     }
-                });
+                }));
             }
 
             let mut all_samples = Vec::new();
@@ -1,5 +1,6 @@
 use crate::collectors::util::{
-    acquire_db_query_permit, get_default_database, get_excluded_databases, open_db_connection,
+    acquire_connection, acquire_db_query_permit, datname_label, get_default_database,
+    in_acquire_scope, list_target_databases, open_db_connection,
 };
 use crate::collectors::{Collector, all_databases_failed, i64_to_f64};
 use anyhow::{Result, anyhow};
@@ -140,9 +141,7 @@ impl UnusedIndexCollector {
 
     fn sample_from_row(row: &PgRow) -> Result<UnusedIndexSample> {
         Ok(UnusedIndexSample {
-            datname: row
-                .try_get::<Option<String>, _>("datname")?
-                .unwrap_or_else(|| "[unknown]".to_string()),
+            datname: datname_label(row.try_get("datname")?),
             unused_count: row.try_get("unused_count").unwrap_or(0),
             unused_size_bytes: row.try_get("unused_size_bytes").unwrap_or(0),
             invalid_count: row.try_get("invalid_count").unwrap_or(0),
@@ -171,29 +170,7 @@ impl Collector for UnusedIndexCollector {
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
         Box::pin(async move {
             // 1) Discover connectable, non-excluded databases via the shared pool.
-            let excluded = get_excluded_databases().to_vec();
-            let db_list_span = info_span!(
-                "db.query",
-                otel.kind = "client",
-                db.system = "postgresql",
-                db.operation = "SELECT",
-                db.statement = "SELECT datname FROM pg_database WHERE datallowconn ...",
-                db.sql.table = "pg_database"
-            );
-            let dbs: Vec<String> = sqlx::query_scalar(
-                r"
-                SELECT datname
-                FROM pg_database
-                WHERE datallowconn
-                  AND NOT datistemplate
-                  AND NOT (datname = ANY($1))
-                ORDER BY datname
-                ",
-            )
-            .bind(&excluded)
-            .fetch_all(pool)
-            .instrument(db_list_span)
-            .await?;
+            let dbs = list_target_databases(pool).await?;
 
             let shared_pool = pool.clone();
             let default_db = get_default_database().map(std::string::ToString::to_string);
@@ -207,7 +184,7 @@ impl Collector for UnusedIndexCollector {
                 let shared_pool = shared_pool.clone();
                 let default_db = default_db.clone();
 
-                tasks.spawn(async move {
+                tasks.spawn(in_acquire_scope(async move {
                     let use_shared = default_db.as_deref() == Some(datname.as_str());
 
                     let query_span = info_span!(
@@ -231,7 +208,7 @@ impl Collector for UnusedIndexCollector {
 
                     let row_res: anyhow::Result<Option<PgRow>> = if use_shared {
                         sqlx::query(UNUSED_INDEX_QUERY)
-                            .fetch_optional(&shared_pool)
+                            .fetch_optional(&mut *acquire_connection(&shared_pool).await?)
                             .instrument(query_span)
                             .await
                             .map_err(Into::into)
@@ -253,7 +230,7 @@ impl Collector for UnusedIndexCollector {
                         Some(row) => Ok(Some(Self::sample_from_row(&row)?)),
                         None => Ok(None),
                     }
-                });
+                }));
             }
 
             let mut all_samples = Vec::new();
@@ -0,0 +1,312 @@
+use crate::collectors::util::{
+    acquire_connection, acquire_db_query_permit, datname_label, get_default_database,
+    in_acquire_scope, list_target_databases, open_db_connection,
+};
+use crate::collectors::{Collector, all_databases_failed};
+use anyhow::{Result, anyhow};
+use futures::future::BoxFuture;
+use prometheus::{IntGaugeVec, Opts, Registry};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use tokio::task::JoinSet;
+use tracing::{debug, error, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// Collector for index and constraint validity (maintenance debt)
+///
+/// **What it measures:**
+/// A failed `CREATE INDEX CONCURRENTLY` / `REINDEX CONCURRENTLY` leaves an index behind that
+/// is not valid (`pg_index.indisvalid`) or not ready for inserts (`pg_index.indisready`):
+/// the planner ignores it, yet writes may still maintain it. `ALTER TABLE ... ADD CONSTRAINT
+/// ... NOT VALID` leaves constraints that are not enforced on existing rows until
+/// `VALIDATE CONSTRAINT` runs (`pg_constraint.convalidated`).
+///
+/// **Key metrics:**
+/// - `pg_invalid_indexes_count{datname}`: indexes that are not valid or not ready
+/// - `pg_index_is_valid{datname,schemaname,indexrelname}`: 0 for each such index. Only
+///   broken indexes are exported, so a healthy database adds no per-index series.
+/// - `pg_invalid_constraints_count{datname}`: constraints with `convalidated = false`
+///
+/// **Multi-database:**
+/// `pg_index` and `pg_constraint` are per-database catalogs, so this collector iterates every
+/// connectable, non-excluded database like the other index sub-collectors.
+#[derive(Clone)]
+pub struct IndexValidityCollector {
+    invalid_indexes: IntGaugeVec,
+    index_is_valid: IntGaugeVec,
+    invalid_constraints: IntGaugeVec,
+}
+
+impl Default for IndexValidityCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Invalid/not-ready indexes and `NOT VALID` constraints of the current database, returned
+/// as a single row so one round trip per database is enough.
+const INDEX_VALIDITY_QUERY: &str = r"
+    WITH invalid AS (
+        SELECT n.nspname::text AS schemaname, c.relname::text AS indexrelname
+        FROM pg_index i
+        JOIN pg_class c ON i.indexrelid = c.oid
+        JOIN pg_namespace n ON c.relnamespace = n.oid
+        WHERE (NOT i.indisvalid OR NOT i.indisready)
+          AND n.nspname NOT IN ('pg_catalog', 'information_schema')
+    )
+    SELECT
+        current_database() AS datname,
+        COALESCE((SELECT array_agg(schemaname ORDER BY schemaname, indexrelname) FROM invalid), '{}')
+            AS schemanames,
+        COALESCE((SELECT array_agg(indexrelname ORDER BY schemaname, indexrelname) FROM invalid), '{}')
+            AS indexrelnames,
+        (
+            SELECT COUNT(*)::bigint
+            FROM pg_constraint con
+            JOIN pg_namespace n ON con.connamespace = n.oid
+            WHERE NOT con.convalidated
+              AND n.nspname NOT IN ('pg_catalog', 'information_schema')
+        ) AS invalid_constraints
+    ";
+
+#[derive(Clone, Debug)]
+struct IndexValiditySample {
+    datname: String,
+    invalid_indexes: Vec<(String, String)>,
+    invalid_constraints: i64,
+}
+
+impl IndexValidityCollector {
+    /// Creates a new `IndexValidityCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        Self {
+            invalid_indexes: IntGaugeVec::new(
+                Opts::new(
+                    "pg_invalid_indexes_count",
+                    "Number of indexes that are not valid or not ready (e.g. failed CREATE INDEX CONCURRENTLY)",
+                ),
+                &["datname"],
+            )
+            .expect("Failed to create pg_invalid_indexes_count"),
+            index_is_valid: IntGaugeVec::new(
+                Opts::new(
+                    "pg_index_is_valid",
+                    "Whether the index is valid and ready (only exported for broken indexes, always 0)",
+                ),
+                &["datname", "schemaname", "indexrelname"],
+            )
+            .expect("Failed to create pg_index_is_valid"),
+            invalid_constraints: IntGaugeVec::new(
+                Opts::new(
+                    "pg_invalid_constraints_count",
+                    "Number of constraints added as NOT VALID and not yet validated",
+                ),
+                &["datname"],
+            )
+            .expect("Failed to create pg_invalid_constraints_count"),
+        }
+    }
+
+    fn reset_metrics(&self) {
+        self.invalid_indexes.reset();
+        self.index_is_valid.reset();
+        self.invalid_constraints.reset();
+    }
+
+    fn sample_from_row(row: &PgRow) -> Result<IndexValiditySample> {
+        let schemanames: Vec<String> = row.try_get("schemanames")?;
+        let indexrelnames: Vec<String> = row.try_get("indexrelnames")?;
+
+        Ok(IndexValiditySample {
+            datname: datname_label(row.try_get("datname")?),
+            invalid_indexes: schemanames.into_iter().zip(indexrelnames).collect(),
+            invalid_constraints: row.try_get("invalid_constraints").unwrap_or(0),
+        })
+    }
+}
+
+impl Collector for IndexValidityCollector {
+    fn name(&self) -> &'static str {
+        "index_validity"
+    }
+
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.invalid_indexes.clone()))?;
+        registry.register(Box::new(self.index_is_valid.clone()))?;
+        registry.register(Box::new(self.invalid_constraints.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector = "index_validity", otel.kind = "internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            // 1) Discover connectable, non-excluded databases via the shared pool.
+            let dbs = list_target_databases(pool).await?;
+
+            let shared_pool = pool.clone();
+            let default_db = get_default_database().map(std::string::ToString::to_string);
+
+            // 2) One task per DB. The default DB reuses the shared pool; every other database
+            // must pass through the global per-database connection limiter.
+            let mut tasks: JoinSet<Result<Option<IndexValiditySample>>> = JoinSet::new();
+
+            let num_dbs = dbs.len();
+            for datname in dbs {
+                let shared_pool = shared_pool.clone();
+                let default_db = default_db.clone();
+
+                tasks.spawn(in_acquire_scope(async move {
+                    let use_shared = default_db.as_deref() == Some(datname.as_str());
+
+                    let query_span = info_span!(
+                        "db.query",
+                        otel.kind = "client",
+                        db.system = "postgresql",
+                        db.operation = "SELECT",
+                        db.statement = "SELECT ... invalid indexes/constraints",
+                        db.sql.table = "pg_index",
+                        datname = %datname,
+                        reuse_pool = use_shared
+                    );
+
+                    let db_query_permit = if use_shared {
+                        None
+                    } else {
+                        Some(acquire_db_query_permit().await.map_err(|e| {
+                            anyhow!("index_validity: failed to acquire database query permit: {e}")
+                        })?)
+                    };
+
+                    let row_res: anyhow::Result<Option<PgRow>> = if use_shared {
+                        sqlx::query(INDEX_VALIDITY_QUERY)
+                            .fetch_optional(&mut *acquire_connection(&shared_pool).await?)
+                            .instrument(query_span)
+                            .await
+                            .map_err(Into::into)
+                    } else {
+                        let Some(permit) = db_query_permit.as_ref() else {
+                            return Err(anyhow!("index_validity: missing database query permit"));
+                        };
+                        match open_db_connection(&datname, permit).await {
+                            Ok(mut conn) => sqlx::query(INDEX_VALIDITY_QUERY)
+                                .fetch_optional(&mut conn)
+                                .instrument(query_span)
+                                .await
+                                .map_err(Into::into),
+                            Err(e) => Err(e),
+                        }
+                    };
+
+                    match row_res? {
+                        Some(row) => Ok(Some(Self::sample_from_row(&row)?)),
+                        None => Ok(None),
+                    }
+                }));
+            }
+
+            let mut all_samples = Vec::new();
+            let mut failures = Vec::new();
+            let mut failed_db_count = 0;
+            while let Some(joined) = tasks.join_next().await {
+                match joined {
+                    Ok(Ok(Some(sample))) => all_samples.push(sample),
+                    Ok(Ok(None)) => {}
+                    Ok(Err(e)) => {
+                        error!(error=?e, "index_validity: task returned error");
+                        failures.push(e.to_string());
+                        failed_db_count += 1;
+                    }
+                    Err(e) => {
+                        error!(error=?e, "index_validity: task join error");
+                        failures.push(e.to_string());
+                        failed_db_count += 1;
+                    }
+                }
+            }
+
+            if all_databases_failed(num_dbs, failed_db_count) {
+                return Err(anyhow!(
+                    "index_validity collection failed for ALL {failed_db_count} database task(s): {}",
+                    failures.join("; ")
+                ));
+            }
+
+            if !failures.is_empty() {
+                error!(
+                    failed_databases = failed_db_count,
+                    errors = %failures.join("; "),
+                    "index_validity: continuing with partial snapshot after per-database failures"
+                );
+            }
+
+            self.reset_metrics();
+
+            for sample in &all_samples {
+                let datname = sample.datname.as_str();
+                let invalid_indexes =
+                    i64::try_from(sample.invalid_indexes.len()).unwrap_or(i64::MAX);
+
+                self.invalid_indexes
+                    .with_label_values(&[datname])
+                    .set(invalid_indexes);
+                self.invalid_constraints
+                    .with_label_values(&[datname])
+                    .set(sample.invalid_constraints);
+
+                for (schemaname, indexrelname) in &sample.invalid_indexes {
+                    self.index_is_valid
+                        .with_label_values(&[datname, schemaname.as_str(), indexrelname.as_str()])
+                        .set(0);
+                }
+
+                debug!(
+                    datname = %sample.datname,
+                    invalid_indexes,
+                    invalid_constraints = sample.invalid_constraints,
+                    "updated index validity metrics"
+                );
+            }
+
+            Ok(())
+        })
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_validity_collector_name() {
+        let collector = IndexValidityCollector::new();
+        assert_eq!(collector.name(), "index_validity");
+    }
+
+    #[test]
+    fn test_index_validity_collector_registers() {
+        let registry = Registry::new();
+        let collector = IndexValidityCollector::new();
+        assert!(collector.register_metrics(&registry).is_ok());
+    }
+
+    #[test]
+    fn test_index_validity_query_checks_valid_and_ready() {
+        assert!(INDEX_VALIDITY_QUERY.contains("NOT i.indisvalid OR NOT i.indisready"));
+        assert!(INDEX_VALIDITY_QUERY.contains("NOT con.convalidated"));
+        assert!(INDEX_VALIDITY_QUERY.contains("current_database() AS datname"));
+    }
+}
@@ -0,0 +1,108 @@
+//! `largeobjects` collector umbrella.
+//!
+//! `mod.rs` is the entry point: it wires up the `pg_largeobject` sub-collector
+//! and exposes it under the `--collector.largeobjects` CLI flag. The actual metric
+//! definitions and SQL live in [`pg_largeobject`].
+//!
+//! Sizing large objects means reading every page of `pg_largeobject` in every
+//! database, so the collector is disabled by default and logs a warning when enabled.
+
+use crate::collectors::{Collector, util::collect_subs};
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::Registry;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::{debug, info_span, instrument, warn};
+
+pub mod pg_largeobject;
+use pg_largeobject::LargeObjectSizeCollector;
+
+/// Large object count and size per database, from `pg_largeobject_metadata` and
+/// `pg_largeobject`.
+///
+/// This is the umbrella collector selected by `--collector.largeobjects`. It holds a
+/// single [`LargeObjectSizeCollector`] sub-collector and fans registration and
+/// collection out to it, matching the structure used by the other collectors
+/// (`slru`, `memory`, `fdw`).
+#[derive(Clone)]
+pub struct LargeObjectsCollector {
+    subs: Vec<Arc<dyn Collector + Send + Sync>>,
+}
+
+impl Default for LargeObjectsCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LargeObjectsCollector {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            subs: vec![Arc::new(LargeObjectSizeCollector::new())],
+        }
+    }
+}
+
+impl Collector for LargeObjectsCollector {
+    fn name(&self) -> &'static str {
+        "largeobjects"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "largeobjects")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        warn!(
+            "largeobjects collector enabled: every scrape reads all of pg_largeobject in every \
+             database; consider --collector.largeobjects.interval on clusters with many large objects"
+        );
+
+        for sub in &self.subs {
+            let span = info_span!("collector.register_metrics", sub_collector = %sub.name());
+            let res = sub.register_metrics(registry);
+            match res {
+                Ok(()) => debug!(collector = sub.name(), "registered metrics"),
+                Err(ref e) => {
+                    warn!(collector = sub.name(), error = %e, "failed to register metrics");
+                }
+            }
+            res?;
+            drop(span);
+        }
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector = "largeobjects", otel.kind = "internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move { collect_subs(&self.subs, pool).await })
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_largeobjects_collector_name() {
+        assert_eq!(LargeObjectsCollector::new().name(), "largeobjects");
+    }
+
+    #[test]
+    fn test_largeobjects_collector_not_enabled_by_default() {
+        assert!(!LargeObjectsCollector::new().enabled_by_default());
+    }
+}
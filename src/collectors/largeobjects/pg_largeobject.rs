@@ -0,0 +1,255 @@
+use crate::collectors::util::{
+    acquire_connection, acquire_db_query_permit, get_default_database, in_acquire_scope,
+    list_target_databases, open_db_connection,
+};
+use crate::collectors::{Collector, all_databases_failed};
+use anyhow::{Result, anyhow};
+use futures::future::BoxFuture;
+use prometheus::{IntGaugeVec, Opts, Registry};
+use sqlx::{PgConnection, PgPool, Row};
+use tokio::task::JoinSet;
+use tracing::{debug, error, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// Collector for large objects per database
+///
+/// **What it measures:**
+/// Large objects live outside the tables that reference them: deleting the referencing
+/// row leaves the object behind unless the application calls `lo_unlink` (or uses the
+/// `lo_manage` trigger). Orphaned objects keep growing `pg_largeobject`, and since it is a
+/// system catalog the regular table-size metrics never show it.
+///
+/// **Key metrics:**
+/// - `pg_largeobjects_count{datname}`: rows in `pg_largeobject_metadata`, one per object
+/// - `pg_largeobjects_size_bytes{datname}`: summed `pg_largeobject` page data. Only
+///   exported when the role can read `pg_largeobject` (superuser, or an explicit
+///   `GRANT SELECT`); the count is always available.
+///
+/// **Multi-database:**
+/// Large objects are per-database, so this collector iterates every connectable,
+/// non-excluded database like the other per-database collectors.
+#[derive(Clone)]
+pub struct LargeObjectSizeCollector {
+    count: IntGaugeVec,      // pg_largeobjects_count{datname}
+    size_bytes: IntGaugeVec, // pg_largeobjects_size_bytes{datname}
+}
+
+/// Object count, plus whether `pg_largeobject` itself is readable. The metadata catalog is
+/// public, the data catalog is not.
+const LARGEOBJECT_COUNT_QUERY: &str = r"
+    SELECT
+        current_database() AS datname,
+        (SELECT COUNT(*) FROM pg_catalog.pg_largeobject_metadata)::bigint AS lo_count,
+        has_table_privilege('pg_catalog.pg_largeobject', 'SELECT') AS can_read_data
+    ";
+
+/// Total bytes stored across all large object pages. Reads the whole catalog.
+const LARGEOBJECT_SIZE_QUERY: &str = r"
+    SELECT COALESCE(SUM(octet_length(data)), 0)::bigint AS lo_bytes
+    FROM pg_catalog.pg_largeobject
+    ";
+
+/// One database's sample: name, object count and size (None when not readable)
+type LargeObjectSample = (String, i64, Option<i64>);
+
+impl Default for LargeObjectSizeCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LargeObjectSizeCollector {
+    /// Creates a new `LargeObjectSizeCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        Self {
+            count: IntGaugeVec::new(
+                Opts::new(
+                    "pg_largeobjects_count",
+                    "Number of large objects in the database (pg_largeobject_metadata)",
+                ),
+                &["datname"],
+            )
+            .expect("Failed to create pg_largeobjects_count"),
+            size_bytes: IntGaugeVec::new(
+                Opts::new(
+                    "pg_largeobjects_size_bytes",
+                    "Bytes of large object data stored in the database (summed pg_largeobject pages)",
+                ),
+                &["datname"],
+            )
+            .expect("Failed to create pg_largeobjects_size_bytes"),
+        }
+    }
+
+    async fn sample_database(conn: &mut PgConnection) -> Result<LargeObjectSample> {
+        let row = sqlx::query(LARGEOBJECT_COUNT_QUERY)
+            .fetch_one(&mut *conn)
+            .await?;
+
+        let datname: String = row.try_get("datname")?;
+        let count: i64 = row.try_get("lo_count").unwrap_or(0);
+        let can_read_data: bool = row.try_get("can_read_data").unwrap_or(false);
+
+        // Nothing to read when there are no objects
+        let size = if !can_read_data {
+            None
+        } else if count == 0 {
+            Some(0)
+        } else {
+            let bytes: i64 = sqlx::query_scalar(LARGEOBJECT_SIZE_QUERY)
+                .fetch_one(&mut *conn)
+                .await?;
+            Some(bytes)
+        };
+
+        Ok((datname, count, size))
+    }
+}
+
+impl Collector for LargeObjectSizeCollector {
+    fn name(&self) -> &'static str {
+        "largeobjects_size"
+    }
+
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.count.clone()))?;
+        registry.register(Box::new(self.size_bytes.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector = "largeobjects_size", otel.kind = "internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            // 1) Discover connectable, non-excluded databases via the shared pool.
+            let dbs = list_target_databases(pool).await?;
+
+            let shared_pool = pool.clone();
+            let default_db = get_default_database().map(std::string::ToString::to_string);
+
+            // 2) One task per DB. The default DB reuses the shared pool; every other database
+            // must pass through the global per-database connection limiter.
+            let mut tasks: JoinSet<Result<LargeObjectSample>> = JoinSet::new();
+
+            let num_dbs = dbs.len();
+            for datname in dbs {
+                let shared_pool = shared_pool.clone();
+                let default_db = default_db.clone();
+
+                tasks.spawn(in_acquire_scope(async move {
+                    let use_shared = default_db.as_deref() == Some(datname.as_str());
+
+                    let query_span = info_span!(
+                        "db.query",
+                        otel.kind = "client",
+                        db.system = "postgresql",
+                        db.operation = "SELECT",
+                        db.statement = "SELECT COUNT(*) FROM pg_largeobject_metadata, SUM(octet_length(data)) FROM pg_largeobject",
+                        db.sql.table = "pg_largeobject",
+                        datname = %datname,
+                        reuse_pool = use_shared
+                    );
+
+                    if use_shared {
+                        let mut conn = acquire_connection(&shared_pool).await?;
+                        return Self::sample_database(&mut conn)
+                            .instrument(query_span)
+                            .await;
+                    }
+
+                    let permit = acquire_db_query_permit().await.map_err(|e| {
+                        anyhow!("largeobjects_size: failed to acquire database query permit: {e}")
+                    })?;
+                    let mut conn = open_db_connection(&datname, &permit).await?;
+                    Self::sample_database(&mut conn)
+                        .instrument(query_span)
+                        .await
+                }));
+            }
+
+            let mut all_samples = Vec::new();
+            let mut failures = Vec::new();
+            let mut failed_db_count = 0;
+            while let Some(joined) = tasks.join_next().await {
+                match joined {
+                    Ok(Ok(sample)) => all_samples.push(sample),
+                    Ok(Err(e)) => {
+                        error!(error=?e, "largeobjects_size: task returned error");
+                        failures.push(e.to_string());
+                        failed_db_count += 1;
+                    }
+                    Err(e) => {
+                        error!(error=?e, "largeobjects_size: task join error");
+                        failures.push(e.to_string());
+                        failed_db_count += 1;
+                    }
+                }
+            }
+
+            if all_databases_failed(num_dbs, failed_db_count) {
+                return Err(anyhow!(
+                    "largeobjects_size collection failed for ALL {failed_db_count} database task(s): {}",
+                    failures.join("; ")
+                ));
+            }
+
+            if !failures.is_empty() {
+                error!(
+                    failed_databases = failed_db_count,
+                    errors = %failures.join("; "),
+                    "largeobjects_size: continuing with partial snapshot after per-database failures"
+                );
+            }
+
+            self.count.reset();
+            self.size_bytes.reset();
+
+            for (datname, count, size) in &all_samples {
+                self.count
+                    .with_label_values(&[datname.as_str()])
+                    .set(*count);
+                if let Some(bytes) = size {
+                    self.size_bytes
+                        .with_label_values(&[datname.as_str()])
+                        .set(*bytes);
+                }
+
+                debug!(datname = %datname, count, size = ?size, "updated large object metrics");
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_largeobject_size_collector_registers() {
+        let registry = Registry::new();
+        let collector = LargeObjectSizeCollector::new();
+        assert_eq!(collector.name(), "largeobjects_size");
+        assert!(collector.register_metrics(&registry).is_ok());
+    }
+
+    #[test]
+    fn test_largeobject_count_query_checks_data_privilege() {
+        assert!(LARGEOBJECT_COUNT_QUERY.contains("pg_largeobject_metadata"));
+        assert!(
+            LARGEOBJECT_COUNT_QUERY
+                .contains("has_table_privilege('pg_catalog.pg_largeobject', 'SELECT')")
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use crate::collectors::Collector;
+use crate::collectors::util::acquire_connection;
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{IntGauge, Opts, Registry};
+use sqlx::{PgPool, Row};
+use tracing::{debug, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// Tracks headroom in the shared lock table:
+/// - `pg_locks_table_capacity` (`IntGauge`):
+///   `max_locks_per_transaction * (max_connections + max_prepared_transactions)`
+/// - `pg_locks_locked_relations` (`IntGauge`): distinct relations currently locked
+///
+/// The sizing settings themselves are already exported as `pg_settings_*`, so only the
+/// derived capacity is published here.
+///
+/// Once the shared lock table is full, any backend taking a new lock fails with
+/// "out of shared memory". Transactions touching many partitions hit this first, so
+/// `pg_locks_locked_relations / pg_locks_table_capacity` predicts the error. The server
+/// also reserves slots for background workers, so the real table is slightly larger
+/// than the exported capacity, which keeps the ratio on the safe side.
+#[derive(Clone)]
+pub struct LockCapacityCollector {
+    table_capacity: IntGauge,   // pg_locks_table_capacity
+    locked_relations: IntGauge, // pg_locks_locked_relations
+}
+
+impl Default for LockCapacityCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LockCapacityCollector {
+    /// Creates a new `LockCapacityCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let table_capacity = IntGauge::with_opts(Opts::new(
+            "pg_locks_table_capacity",
+            "Estimated shared lock table size: max_locks_per_transaction * (max_connections + max_prepared_transactions)",
+        ))
+        .expect("Failed to create pg_locks_table_capacity metric");
+
+        let locked_relations = IntGauge::with_opts(Opts::new(
+            "pg_locks_locked_relations",
+            "Number of distinct relations currently locked across the cluster",
+        ))
+        .expect("Failed to create pg_locks_locked_relations metric");
+
+        Self {
+            table_capacity,
+            locked_relations,
+        }
+    }
+}
+
+/// Estimated shared lock table size, saturating instead of overflowing on absurd settings.
+const fn lock_table_capacity(
+    max_locks_per_transaction: i64,
+    max_connections: i64,
+    max_prepared_transactions: i64,
+) -> i64 {
+    max_locks_per_transaction
+        .saturating_mul(max_connections.saturating_add(max_prepared_transactions))
+}
+
+impl Collector for LockCapacityCollector {
+    fn name(&self) -> &'static str {
+        "lock_capacity"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "lock_capacity")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.table_capacity.clone()))?;
+        registry.register(Box::new(self.locked_relations.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector="lock_capacity", otel.kind="internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let query_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement = "SELECT lock table settings, count(DISTINCT relation) FROM pg_locks",
+                db.sql.table = "pg_locks"
+            );
+
+            // The lock table is shared, so every database counts: no exclusion filter here.
+            let row = sqlx::query(
+                r"
+                SELECT
+                    current_setting('max_locks_per_transaction')::bigint AS max_locks_per_transaction,
+                    current_setting('max_connections')::bigint AS max_connections,
+                    current_setting('max_prepared_transactions')::bigint AS max_prepared_transactions,
+                    (
+                        SELECT COUNT(DISTINCT (l.database, l.relation))
+                        FROM pg_locks l
+                        WHERE l.locktype = 'relation'
+                    )::bigint AS locked_relations
+                ",
+            )
+            .fetch_one(&mut *acquire_connection(pool).await?)
+            .instrument(query_span)
+            .await?;
+
+            let max_locks: i64 = row.try_get("max_locks_per_transaction")?;
+            let max_conn: i64 = row.try_get("max_connections")?;
+            let max_prepared: i64 = row.try_get("max_prepared_transactions")?;
+            let locked: i64 = row.try_get("locked_relations")?;
+            let capacity = lock_table_capacity(max_locks, max_conn, max_prepared);
+
+            self.table_capacity.set(capacity);
+            self.locked_relations.set(locked);
+
+            debug!(
+                max_locks_per_transaction = max_locks,
+                max_connections = max_conn,
+                max_prepared_transactions = max_prepared,
+                capacity,
+                locked_relations = locked,
+                "updated lock table capacity metrics"
+            );
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_table_capacity_formula() {
+        assert_eq!(lock_table_capacity(64, 100, 0), 6400);
+        assert_eq!(lock_table_capacity(64, 100, 10), 7040);
+        assert_eq!(lock_table_capacity(i64::MAX, 2, 0), i64::MAX);
+    }
+
+    #[test]
+    fn test_lock_capacity_registers_metrics() {
+        let collector = LockCapacityCollector::new();
+        let registry = Registry::new();
+        assert_eq!(collector.name(), "lock_capacity");
+        assert!(collector.register_metrics(&registry).is_ok());
+    }
+}
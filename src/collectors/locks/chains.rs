@@ -0,0 +1,213 @@
+use crate::collectors::{
+    Collector,
+    util::{acquire_connection, get_excluded_databases},
+};
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{IntGauge, Opts, Registry};
+use sqlx::{PgPool, Row};
+use std::collections::{BTreeMap, BTreeSet};
+use tracing::{debug, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// Tracks the depth of lock blocking chains built from `pg_blocking_pids()`:
+/// - `pg_locks_max_blocking_chain_depth` (`IntGauge`)
+///
+/// A session blocked directly by a lock holder has depth 1; a session waiting behind
+/// it has depth 2 (A blocks B blocks C), and so on. 0 means nothing is blocked.
+/// Deep chains are a precursor to cascading stalls.
+#[derive(Clone)]
+pub struct LockChainsCollector {
+    max_chain_depth: IntGauge, // pg_locks_max_blocking_chain_depth
+}
+
+impl Default for LockChainsCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LockChainsCollector {
+    /// Creates a new `LockChainsCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let max_chain_depth = IntGauge::with_opts(Opts::new(
+            "pg_locks_max_blocking_chain_depth",
+            "Length of the longest lock blocking chain (A blocks B blocks C = 2); 0 when nothing is blocked",
+        ))
+        .expect("Failed to create pg_locks_max_blocking_chain_depth metric");
+
+        Self { max_chain_depth }
+    }
+}
+
+/// Longest chain in the waiter -> blockers graph, counted in edges.
+///
+/// Sessions in a blocking cycle (a deadlock the detector has not resolved yet) do not
+/// extend the chain through the cycle, so the result is always bounded by the number
+/// of sessions. Waiters and their blockers are visited in ascending pid order, so a cycle
+/// is always cut at the same edge and the same graph always yields the same depth.
+fn max_chain_depth(edges: &BTreeMap<i32, Vec<i32>>) -> u64 {
+    let mut depth: BTreeMap<i32, u64> = BTreeMap::new();
+    let mut on_stack: BTreeSet<i32> = BTreeSet::new();
+
+    for &start in edges.keys() {
+        if depth.contains_key(&start) {
+            continue;
+        }
+
+        // Iterative DFS: (pid, index of the next blocker to visit).
+        let mut stack = vec![(start, 0_usize)];
+        on_stack.insert(start);
+
+        while let Some((pid, next)) = stack.last().copied() {
+            let blockers = edges.get(&pid).map_or(&[][..], Vec::as_slice);
+
+            if let Some(&blocker) = blockers.get(next) {
+                if let Some(top) = stack.last_mut() {
+                    top.1 += 1;
+                }
+                if !depth.contains_key(&blocker) && on_stack.insert(blocker) {
+                    stack.push((blocker, 0));
+                }
+                continue;
+            }
+
+            let pid_depth = blockers
+                .iter()
+                .filter_map(|blocker| depth.get(blocker))
+                .map(|d| d.saturating_add(1))
+                .max()
+                .unwrap_or(0);
+            depth.insert(pid, pid_depth);
+            on_stack.remove(&pid);
+            stack.pop();
+        }
+    }
+
+    depth.values().copied().max().unwrap_or(0)
+}
+
+impl Collector for LockChainsCollector {
+    fn name(&self) -> &'static str {
+        "locks_chains"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "locks_chains")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.max_chain_depth.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector="locks_chains", otel.kind="internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let excluded: Vec<String> = get_excluded_databases().to_vec();
+
+            let query_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement =
+                    "SELECT pid, pg_blocking_pids(pid) FROM pg_stat_activity (blocked only)",
+                db.sql.table = "pg_stat_activity"
+            );
+
+            // Only waiters are fetched; lock holders that wait on nothing are leaves.
+            let rows = sqlx::query(
+                r"
+                SELECT w.pid, w.blockers
+                FROM (
+                    SELECT a.pid, pg_blocking_pids(a.pid) AS blockers
+                    FROM pg_stat_activity a
+                    WHERE a.pid <> pg_backend_pid()
+                      AND NOT (COALESCE(a.datname, '') = ANY($1))
+                ) w
+                WHERE cardinality(w.blockers) > 0
+                ",
+            )
+            .bind(&excluded)
+            .fetch_all(&mut *acquire_connection(pool).await?)
+            .instrument(query_span)
+            .await?;
+
+            let mut edges: BTreeMap<i32, Vec<i32>> = BTreeMap::new();
+            for row in &rows {
+                let pid: i32 = row.try_get("pid")?;
+                let mut blockers: Vec<i32> = row.try_get("blockers")?;
+                // pg_blocking_pids() has no defined order and may repeat a pid
+                blockers.sort_unstable();
+                blockers.dedup();
+                edges.insert(pid, blockers);
+            }
+
+            let depth = max_chain_depth(&edges);
+            self.max_chain_depth
+                .set(i64::try_from(depth).unwrap_or(i64::MAX));
+
+            debug!(
+                waiters = edges.len(),
+                max_chain_depth = depth,
+                "updated lock chain metrics"
+            );
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(i32, &[i32])]) -> BTreeMap<i32, Vec<i32>> {
+        edges
+            .iter()
+            .map(|(pid, blockers)| (*pid, blockers.to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_max_chain_depth_empty_graph() {
+        assert_eq!(max_chain_depth(&BTreeMap::new()), 0);
+    }
+
+    #[test]
+    fn test_max_chain_depth_follows_longest_path() {
+        // 1 holds the lock; 2 waits on 1; 3 waits on 1 and 2; 4 waits on 3.
+        let edges = graph(&[(2, &[1]), (3, &[1, 2]), (4, &[3]), (5, &[1])]);
+        assert_eq!(max_chain_depth(&edges), 3);
+    }
+
+    #[test]
+    fn test_max_chain_depth_terminates_on_cycles() {
+        // 1 and 2 wait on each other (undetected deadlock); 3 waits behind 2. The walk
+        // starts at pid 1, so the cycle is cut at 2 -> 1: 2 has depth 0, 1 and 3 depth 1.
+        let edges = graph(&[(1, &[2]), (2, &[1]), (3, &[2])]);
+        assert_eq!(max_chain_depth(&edges), 1);
+    }
+
+    #[test]
+    fn test_max_chain_depth_cuts_longer_cycle_at_lowest_pid() {
+        // 1 -> 2 -> 3 -> 1 is a three-session cycle with 4 waiting behind 3. The walk from
+        // pid 1 cuts 3 -> 1, giving 3 depth 0, 2 depth 1, 1 depth 2 and 4 depth 1.
+        let edges = graph(&[(1, &[2]), (2, &[3]), (3, &[1]), (4, &[3])]);
+        assert_eq!(max_chain_depth(&edges), 2);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::collectors::{util::get_excluded_databases, Collector};
+use crate::collectors::{
+    Collector,
+    util::{acquire_connection, datname_label, get_excluded_databases},
+};
 use anyhow::Result;
 use futures::future::BoxFuture;
 use prometheus::{GaugeVec, IntGaugeVec, Opts, Registry};
@@ -116,14 +119,15 @@ impl Collector for LocksSubCollector {
                 otel.kind = "client",
                 db.system = "postgresql",
                 db.operation = "SELECT",
-                db.statement = "SELECT datname, mode, count FROM pg_locks + pg_database join (filtered)",
+                db.statement =
+                    "SELECT datname, mode, count FROM pg_locks + pg_database join (filtered)",
                 db.sql.table = "pg_locks"
             );
 
             let rows = sqlx::query(
                 r"
                 SELECT
-                    COALESCE(d.datname, '') AS datname,
+                    d.datname,
                     l.mode,
                     COUNT(*)::bigint AS count
                 FROM pg_locks l
@@ -134,7 +138,7 @@ impl Collector for LocksSubCollector {
                 ",
             )
             .bind(&excluded)
-            .fetch_all(pool)
+            .fetch_all(&mut *acquire_connection(pool).await?)
             .instrument(query_span)
             .await?;
 
@@ -146,7 +150,7 @@ impl Collector for LocksSubCollector {
             self.locks_count.reset();
 
             for row in &rows {
-                let datname: String = row.try_get("datname")?;
+                let datname = datname_label(row.try_get("datname")?);
                 let mode: String = row.try_get("mode")?;
                 let count: i64 = row.try_get("count").unwrap_or(0);
 
@@ -162,7 +166,10 @@ impl Collector for LocksSubCollector {
                 );
             }
 
-            info!("Collected lock metrics for {} database/mode combinations", rows.len());
+            info!(
+                "Collected lock metrics for {} database/mode combinations",
+                rows.len()
+            );
 
             // --- Blocking diagnostics (who is blocked / who is blocking, and for how long) ---
             // pg_blocking_pids() is PG9.6+. Visible to all users (only query TEXT is
@@ -172,7 +179,8 @@ impl Collector for LocksSubCollector {
                 otel.kind = "client",
                 db.system = "postgresql",
                 db.operation = "SELECT",
-                db.statement = "SELECT blocked/blocking sessions + longest wait from pg_stat_activity",
+                db.statement =
+                    "SELECT blocked/blocking sessions + longest wait from pg_stat_activity",
                 db.sql.table = "pg_stat_activity"
             );
 
@@ -224,7 +232,7 @@ impl Collector for LocksSubCollector {
                 ",
             )
             .bind(&excluded)
-            .fetch_all(pool)
+            .fetch_all(&mut *acquire_connection(pool).await?)
             .instrument(block_span)
             .await?;
 
@@ -233,7 +241,7 @@ impl Collector for LocksSubCollector {
             self.longest_blocked_seconds.reset();
 
             for row in &block_rows {
-                let datname: String = row.try_get("datname").unwrap_or_default();
+                let datname = datname_label(row.try_get("datname").unwrap_or_default());
                 let blocked: i64 = row.try_get("blocked_sessions").unwrap_or(0);
                 let blocking: i64 = row.try_get("blocking_sessions").unwrap_or(0);
                 let longest: f64 = row.try_get("longest_blocked_seconds").unwrap_or(0.0);
@@ -263,14 +271,15 @@ impl Collector for LocksSubCollector {
                 otel.kind = "client",
                 db.system = "postgresql",
                 db.operation = "SELECT",
-                db.statement = "SELECT datname, mode, count FROM pg_locks WHERE NOT granted (filtered)",
+                db.statement =
+                    "SELECT datname, mode, count FROM pg_locks WHERE NOT granted (filtered)",
                 db.sql.table = "pg_locks"
             );
 
             let wait_rows = sqlx::query(
                 r"
                 SELECT
-                    COALESCE(d.datname, '') AS datname,
+                    d.datname,
                     l.mode,
                     COUNT(*)::bigint AS count
                 FROM pg_locks l
@@ -282,14 +291,14 @@ impl Collector for LocksSubCollector {
                 ",
             )
             .bind(&excluded)
-            .fetch_all(pool)
+            .fetch_all(&mut *acquire_connection(pool).await?)
             .instrument(wait_span)
             .await?;
 
             self.lock_waits.reset();
 
             for row in &wait_rows {
-                let datname: String = row.try_get("datname").unwrap_or_default();
+                let datname = datname_label(row.try_get("datname").unwrap_or_default());
                 let mode: String = row.try_get("mode").unwrap_or_default();
                 let count: i64 = row.try_get("count").unwrap_or(0);
 
@@ -1,16 +1,20 @@
-use crate::collectors::Collector;
+use crate::collectors::{Collector, util::collect_subs};
 use anyhow::Result;
 use futures::future::BoxFuture;
-use futures::stream::{FuturesUnordered, StreamExt};
 use prometheus::Registry;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tracing::{debug, info_span, instrument, warn};
-use tracing_futures::Instrument as _;
 
 pub mod count;
 use count::LocksSubCollector;
 
+pub mod chains;
+use chains::LockChainsCollector;
+
+pub mod capacity;
+use capacity::LockCapacityCollector;
+
 #[derive(Clone, Default)]
 pub struct LocksCollector {
     subs: Vec<Arc<dyn Collector + Send + Sync>>,
@@ -20,7 +24,11 @@ impl LocksCollector {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            subs: vec![Arc::new(LocksSubCollector::new())],
+            subs: vec![
+                Arc::new(LocksSubCollector::new()),
+                Arc::new(LockChainsCollector::new()),
+                Arc::new(LockCapacityCollector::new()),
+            ],
         }
     }
 }
@@ -56,26 +64,7 @@ impl Collector for LocksCollector {
         fields(collector = "locks", otel.kind = "internal")
     )]
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
-        Box::pin(async move {
-            // Collect sub-collectors concurrently (unordered join).
-            let mut tasks = FuturesUnordered::new();
-
-            for sub in &self.subs {
-                let span = info_span!(
-                    "collector.collect",
-                    sub_collector = %sub.name(),
-                    otel.kind = "internal"
-                );
-
-                tasks.push(sub.collect(pool).instrument(span));
-            }
-
-            while let Some(res) = tasks.next().await {
-                res?;
-            }
-
-            Ok(())
-        })
+        Box::pin(async move { collect_subs(&self.subs, pool).await })
     }
 
     fn enabled_by_default(&self) -> bool {
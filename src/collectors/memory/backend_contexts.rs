@@ -0,0 +1,185 @@
+//! Memory held by the exporter's own backend from `pg_backend_memory_contexts`
+//! (`PostgreSQL` 14+).
+//!
+//! `pg_backend_memory_contexts` only describes the backend that runs the query, so
+//! these metrics reflect one of the exporter's pooled connections, not the whole
+//! server. That is still a useful trend: on clusters with many databases, relations
+//! or functions, the catalog and relation caches (`CacheMemoryContext`) of a
+//! long-lived connection grow with the schema, and the exporter's backend shows it.
+//! To inspect another backend, run `SELECT pg_log_backend_memory_contexts(pid)`
+//! manually and read the server log.
+//!
+//! # Version handling
+//!
+//! The view was introduced in `PostgreSQL` 14. On older servers the collector skips
+//! cleanly (no error, no populated series) and logs a single warning. `PostgreSQL`
+//! 14 restricts the view to superusers; 15+ also allows `pg_read_all_stats`.
+
+use crate::collectors::{
+    Collector,
+    util::{acquire_connection, resolve_server_version},
+};
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{IntGaugeVec, Opts, Registry};
+use sqlx::{PgPool, Row};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+use tracing::{debug, info_span, instrument, warn};
+use tracing_futures::Instrument as _;
+
+/// `pg_backend_memory_contexts` was introduced in `PostgreSQL` 14.
+pub(crate) const MIN_MEMORY_CONTEXTS_VERSION: i32 = 140_000;
+
+/// Label used for the root context (`TopMemoryContext`), which has no parent.
+const ROOT_PARENT: &str = "[root]";
+
+/// Memory per parent context. `total_bytes` only counts a context's own blocks, so
+/// summing the children of each parent never double counts.
+const BACKEND_MEMORY_CONTEXTS_QUERY: &str = r"
+    SELECT
+        COALESCE(parent, '[root]') AS parent,
+        SUM(total_bytes)::bigint AS total_bytes
+    FROM pg_backend_memory_contexts
+    GROUP BY 1
+";
+
+/// Exposes the exporter backend's memory contexts aggregated by parent context:
+/// - `pg_backend_memory_total_bytes{parent}` (`IntGauge`)
+///
+/// Children of the same parent are summed, so `CacheMemoryContext` growth shows up
+/// under `parent="CacheMemoryContext"` and the overall footprint is
+/// `sum(pg_backend_memory_total_bytes)`.
+#[derive(Clone)]
+pub struct BackendMemoryContextsCollector {
+    total_bytes: IntGaugeVec, // pg_backend_memory_total_bytes{parent}
+    /// Ensures the "requires `PostgreSQL` 14+" warning is logged at most once.
+    unsupported_warned: Arc<AtomicBool>,
+}
+
+impl Default for BackendMemoryContextsCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BackendMemoryContextsCollector {
+    /// Creates a new `BackendMemoryContextsCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let total_bytes = IntGaugeVec::new(
+            Opts::new(
+                "pg_backend_memory_total_bytes",
+                "Memory allocated by the exporter's own backend, summed over the child contexts of each parent memory context",
+            ),
+            &["parent"],
+        )
+        .expect("Failed to create pg_backend_memory_total_bytes");
+
+        Self {
+            total_bytes,
+            unsupported_warned: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl Collector for BackendMemoryContextsCollector {
+    fn name(&self) -> &'static str {
+        "backend_memory_contexts"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "backend_memory_contexts")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.total_bytes.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector = "backend_memory_contexts", otel.kind = "internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let version_num = resolve_server_version(pool).await?;
+
+            if version_num < MIN_MEMORY_CONTEXTS_VERSION {
+                if !self.unsupported_warned.swap(true, Ordering::Relaxed) {
+                    warn!(
+                        server_version_num = version_num,
+                        "collector.memory is enabled but pg_backend_memory_contexts requires PostgreSQL 14+; \
+                         skipping (no metrics will be exported until the server is upgraded)"
+                    );
+                }
+                debug!("Skipping pg_backend_memory_contexts metrics (requires PostgreSQL 14+)");
+                return Ok(());
+            }
+
+            let query_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement = "SELECT parent, SUM(total_bytes) FROM pg_backend_memory_contexts GROUP BY parent",
+                db.sql.table = "pg_backend_memory_contexts"
+            );
+
+            let rows = sqlx::query(BACKEND_MEMORY_CONTEXTS_QUERY)
+                .fetch_all(&mut *acquire_connection(pool).await?)
+                .instrument(query_span)
+                .await?;
+
+            self.total_bytes.reset();
+
+            for row in &rows {
+                let parent: String = row
+                    .try_get::<Option<String>, _>("parent")?
+                    .unwrap_or_else(|| ROOT_PARENT.to_string());
+                let bytes: i64 = row.try_get("total_bytes").unwrap_or(0);
+                self.total_bytes.with_label_values(&[&parent]).set(bytes);
+            }
+
+            debug!(
+                parents = rows.len(),
+                "updated backend memory context metrics"
+            );
+
+            Ok(())
+        })
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collector_is_disabled_by_default() {
+        assert!(!BackendMemoryContextsCollector::new().enabled_by_default());
+    }
+
+    #[test]
+    fn query_labels_root_context_and_casts_bytes() {
+        assert!(
+            BACKEND_MEMORY_CONTEXTS_QUERY.contains(&format!("COALESCE(parent, '{ROOT_PARENT}')"))
+        );
+        assert!(BACKEND_MEMORY_CONTEXTS_QUERY.contains("SUM(total_bytes)::bigint AS total_bytes"));
+    }
+}
@@ -0,0 +1,104 @@
+//! `memory` collector umbrella.
+//!
+//! `mod.rs` is the entry point: it wires up the `backend_contexts` sub-collector
+//! and exposes it under the `--collector.memory` CLI flag. The actual metric
+//! definitions, SQL, and version handling live in [`backend_contexts`].
+//!
+//! `pg_backend_memory_contexts` (`PostgreSQL` 14+) is **session-local**: it only
+//! describes the backend that runs the query, i.e. one of the exporter's own pooled
+//! connections. It is disabled by default because it is a targeted diagnostic.
+
+use crate::collectors::{Collector, util::collect_subs};
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::Registry;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::{debug, info_span, instrument, warn};
+
+pub mod backend_contexts;
+use backend_contexts::BackendMemoryContextsCollector;
+
+/// Memory held by the exporter's own backend, from `pg_backend_memory_contexts`
+/// (`PostgreSQL` 14+).
+///
+/// This is the umbrella collector selected by `--collector.memory`. It holds a
+/// single [`BackendMemoryContextsCollector`] sub-collector and fans registration
+/// and collection out to it, matching the structure used by the other collectors
+/// (`slru`, `stat`, `index`).
+#[derive(Clone)]
+pub struct MemoryCollector {
+    subs: Vec<Arc<dyn Collector + Send + Sync>>,
+}
+
+impl Default for MemoryCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryCollector {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            subs: vec![Arc::new(BackendMemoryContextsCollector::new())],
+        }
+    }
+}
+
+impl Collector for MemoryCollector {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "memory")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        for sub in &self.subs {
+            let span = info_span!("collector.register_metrics", sub_collector = %sub.name());
+            let res = sub.register_metrics(registry);
+            match res {
+                Ok(()) => debug!(collector = sub.name(), "registered metrics"),
+                Err(ref e) => {
+                    warn!(collector = sub.name(), error = %e, "failed to register metrics");
+                }
+            }
+            res?;
+            drop(span);
+        }
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector = "memory", otel.kind = "internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move { collect_subs(&self.subs, pool).await })
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_collector_name() {
+        assert_eq!(MemoryCollector::new().name(), "memory");
+    }
+
+    #[test]
+    fn test_memory_collector_not_enabled_by_default() {
+        assert!(!MemoryCollector::new().enabled_by_default());
+    }
+}
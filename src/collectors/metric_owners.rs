@@ -0,0 +1,276 @@
+//! Attribute gathered metric families back to the collector that registered them.
+//!
+//! A `Registry` does not record who registered what, so every collector registers its
+//! metrics into a private registry of its own, once, and the shared registry holds a
+//! [`CollectorMetrics`] forwarding to it. The families a private registry gathers are
+//! therefore exactly its collector's. Gathering the private registries just to learn
+//! family names is only needed when a scrape exposes a family not seen before (usually
+//! the first scrape, or a vec that got its first series), not on every scrape.
+//!
+//! The shared registry no longer sees the collectors' descriptors, so it cannot reject two
+//! collectors registering the same metric name; [`check_unique_metric_names`] does.
+
+use crate::collectors::{Collector, CollectorType};
+use anyhow::{Result, bail};
+use prometheus::{
+    Registry,
+    core::{Collector as PrometheusCollector, Desc},
+    proto::{MetricFamily, MetricType},
+};
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard},
+};
+use tracing::debug;
+
+/// A collector's private registry, registered into the shared registry as one collector.
+///
+/// Its descriptor only identifies the collector to the shared registry and never shows up
+/// in the exposition: gathering returns the families of the private registry.
+pub struct CollectorMetrics {
+    registry: Registry,
+    desc: Desc,
+}
+
+impl CollectorMetrics {
+    /// Forward to `registry`, the private registry of `collector`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the descriptor cannot be created
+    pub fn new(collector: &'static str, registry: Registry) -> prometheus::Result<Self> {
+        let desc = Desc::new(
+            "pg_exporter_collector_registry".to_string(),
+            "Private metric registry of one collector".to_string(),
+            Vec::new(),
+            HashMap::from([("collector".to_string(), collector.to_string())]),
+        )?;
+        Ok(Self { registry, desc })
+    }
+}
+
+impl PrometheusCollector for CollectorMetrics {
+    fn desc(&self) -> Vec<&Desc> {
+        vec![&self.desc]
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        self.registry.gather()
+    }
+}
+
+/// Register the metrics of every collector once more, into a throwaway registry that
+/// rejects a descriptor registered twice. Without it the shared registry would merge two
+/// collectors' same-name families at gather time instead of failing.
+///
+/// # Errors
+///
+/// Returns an error naming every collector whose metrics clash with an earlier one's
+pub fn check_unique_metric_names<'a>(
+    collectors: impl IntoIterator<Item = &'a CollectorType>,
+) -> Result<()> {
+    let claims = Registry::new();
+    let clashes: Vec<String> = collectors
+        .into_iter()
+        .filter_map(|collector| {
+            collector
+                .register_metrics(&claims)
+                .err()
+                .map(|e| format!("{}: {e}", collector.name()))
+        })
+        .collect();
+
+    if !clashes.is_empty() {
+        bail!(
+            "metric names registered by more than one collector: {}",
+            clashes.join("; ")
+        );
+    }
+    Ok(())
+}
+
+/// Family name to owning collector; `None` for the registry's own families (`pg_up`, ...).
+type Owners = HashMap<String, Option<&'static str>>;
+
+pub struct MetricOwners {
+    collectors: Vec<(&'static str, Registry)>,
+    owners: Mutex<Owners>,
+}
+
+impl MetricOwners {
+    /// Attribute families to the private registries collectors registered into.
+    #[must_use]
+    pub fn new(collectors: Vec<(&'static str, Registry)>) -> Self {
+        Self {
+            collectors,
+            owners: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Exposed series per collector in `families`, counted like `pg_exporter_metrics_total`
+    /// (one per sample line). Every collector is listed, with 0 when it exposed nothing.
+    pub fn series_per_collector(&self, families: &[MetricFamily]) -> Vec<(&'static str, i64)> {
+        let mut owners = self.lock_owners();
+        if families
+            .iter()
+            .any(|family| !owners.contains_key(family.name()))
+        {
+            self.refresh(&mut owners, families);
+        }
+
+        let mut counts: HashMap<&'static str, usize> =
+            self.collectors.iter().map(|(name, _)| (*name, 0)).collect();
+        for family in families {
+            if let Some(Some(owner)) = owners.get(family.name()) {
+                *counts.entry(owner).or_default() += exposed_series(family);
+            }
+        }
+
+        self.collectors
+            .iter()
+            .map(|(name, _)| {
+                let count = counts.get(name).copied().unwrap_or(0);
+                (*name, i64::try_from(count).unwrap_or(i64::MAX))
+            })
+            .collect()
+    }
+
+    /// Re-learn family ownership from the private registries. Families no collector
+    /// gathers are remembered as unowned so they do not trigger another refresh.
+    fn refresh(&self, owners: &mut Owners, families: &[MetricFamily]) {
+        for (name, registry) in &self.collectors {
+            for family in registry.gather() {
+                owners.insert(family.name().to_string(), Some(*name));
+            }
+        }
+        for family in families {
+            owners.entry(family.name().to_string()).or_insert(None);
+        }
+        debug!(families = owners.len(), "refreshed metric family owners");
+    }
+
+    fn lock_owners(&self) -> MutexGuard<'_, Owners> {
+        match self.owners.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// Sample lines the text encoder writes for `family`: histograms add `+Inf`, `_sum`
+/// and `_count` to their buckets, summaries `_sum` and `_count` to their quantiles.
+fn exposed_series(family: &MetricFamily) -> usize {
+    family
+        .get_metric()
+        .iter()
+        .map(|metric| match family.get_field_type() {
+            MetricType::HISTOGRAM => metric.get_histogram().get_bucket().len() + 3,
+            MetricType::SUMMARY => metric.get_summary().get_quantile().len() + 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::Collector;
+    use prometheus::{Histogram, HistogramOpts, IntGaugeVec, Opts};
+
+    #[test]
+    #[allow(clippy::expect_used)]
+    fn test_exposed_series_matches_text_encoding() {
+        let registry = Registry::new();
+        let gauges = IntGaugeVec::new(Opts::new("test_gauges", "help"), &["label"])
+            .expect("create test_gauges");
+        let histogram = Histogram::with_opts(
+            HistogramOpts::new("test_histogram", "help").buckets(vec![0.1, 1.0]),
+        )
+        .expect("create test_histogram");
+        registry
+            .register(Box::new(gauges.clone()))
+            .expect("register test_gauges");
+        registry
+            .register(Box::new(histogram.clone()))
+            .expect("register test_histogram");
+
+        gauges.with_label_values(&["a"]).set(1);
+        gauges.with_label_values(&["b"]).set(2);
+        histogram.observe(0.5);
+
+        let families = registry.gather();
+        let counted: usize = families.iter().map(exposed_series).sum();
+
+        let mut buffer = Vec::new();
+        prometheus::Encoder::encode(&prometheus::TextEncoder::new(), &families, &mut buffer)
+            .expect("encode families");
+        let text = String::from_utf8_lossy(&buffer);
+        let lines = text
+            .lines()
+            .filter(|line| !line.starts_with('#') && !line.trim().is_empty())
+            .count();
+
+        assert_eq!(counted, lines);
+        assert_eq!(counted, 2 + 2 + 3);
+    }
+
+    #[test]
+    fn test_check_unique_metric_names() {
+        let factories = crate::collectors::all_factories();
+        let build = |name: &str| factories.get(name).map(|factory| factory());
+        let (Some(exporter), Some(locks), Some(locks_again)) =
+            (build("exporter"), build("locks"), build("locks"))
+        else {
+            return;
+        };
+
+        assert!(check_unique_metric_names([&exporter, &locks]).is_ok());
+
+        // Two collectors registering the same metric names
+        let result = check_unique_metric_names([&exporter, &locks, &locks_again]);
+        assert!(
+            result
+                .as_ref()
+                .is_err_and(|e| e.to_string().contains("locks")),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn test_series_per_collector_attributes_families() -> prometheus::Result<()> {
+        let factories = crate::collectors::all_factories();
+        let shared = Registry::new();
+        let mut registries = Vec::new();
+        for name in ["exporter", "locks"] {
+            let Some(collector) = factories.get(name).map(|factory| factory()) else {
+                continue;
+            };
+            let own = Registry::new();
+            assert!(collector.register_metrics(&own).is_ok());
+            shared.register(Box::new(CollectorMetrics::new(
+                collector.name(),
+                own.clone(),
+            )?))?;
+            registries.push((collector.name(), own));
+        }
+
+        let owners = MetricOwners::new(registries);
+        let families = shared.gather();
+        let counts = owners.series_per_collector(&families);
+        assert_eq!(
+            counts.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+            vec!["exporter", "locks"]
+        );
+
+        // Only these two collectors registered, so every exposed series has an owner
+        let attributed: i64 = counts.iter().map(|(_, count)| count).sum();
+        let exposed: usize = families.iter().map(exposed_series).sum();
+        assert!(exposed > 0);
+        assert_eq!(
+            usize::try_from(attributed).ok(),
+            Some(exposed),
+            "{counts:?}"
+        );
+        Ok(())
+    }
+}
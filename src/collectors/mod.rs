@@ -62,7 +62,8 @@ const fn all_databases_failed(num_dbs: usize, failed_db_count: usize) -> bool {
 /// Maximum number of non-default-database scrape queries that may run concurrently across
 /// the whole exporter.
 ///
-/// The multi-database collectors (`index_stats`, `index_unused`, `stat_user_tables`)
+/// The multi-database collectors (`index_stats`, `index_unused`, `index_validity`,
+/// `stat_user_tables`, `database_temp_tables`, `foreign_servers`, `largeobjects_size`)
 /// open one connection per database (a `PostgreSQL` connection is bound to a single
 /// database). Without a cap, a cluster with N databases would open ~N connections
 /// simultaneously on every scrape — linear in the database count — which can exhaust
@@ -132,6 +133,13 @@ pub(crate) const DEFAULT_STATEMENT_TIMEOUT_MS: u64 = 10_000;
 /// or encoding still takes too long.
 pub(crate) const DEFAULT_SCRAPE_TIMEOUT_MS: u64 = 15_000;
 
+/// Default number of consecutive failed connectivity checks before the scrape circuit
+/// breaker opens and scrapes stop contacting `PostgreSQL`.
+pub(crate) const DEFAULT_CIRCUIT_BREAKER_THRESHOLD: u64 = 5;
+
+/// Default time an open circuit breaker waits before letting one scrape probe `PostgreSQL`.
+pub(crate) const DEFAULT_CIRCUIT_BREAKER_COOLDOWN_MS: u64 = 30_000;
+
 #[cfg(test)]
 mod tests {
     use super::{
@@ -204,9 +212,17 @@ register_collectors! {
     statements => StatementsCollector,
     exporter => ExporterCollector,
     tls => TlsCollector,
+    memory => MemoryCollector,
+    fdw => FdwCollector,
+    largeobjects => LargeObjectsCollector,
     // Add more collectors here - just follow the same pattern!
 }
 
 // Other modules
+pub mod circuit_breaker;
 pub mod config;
+pub mod counter_reset;
+mod metric_owners;
+pub mod preflight;
 pub mod registry;
+pub mod shared_pool;
@@ -0,0 +1,192 @@
+//! `--preflight`: run every enabled collector once at startup and report which ones can
+//! actually collect.
+//!
+//! A collector is not ready when its data source needs a newer server, when its probe
+//! collection errors (typically a missing privilege, or a query cancelled by
+//! `statement_timeout`), or when it reports `pg_exporter_collector_available 0` (a
+//! missing extension such as `pg_stat_statements`).
+//! Probes use fresh collector instances and private registries, so nothing they record
+//! leaks into the served metrics.
+
+use crate::collectors::{
+    Collector, CollectorType, all_factories, config::CollectorConfig,
+    memory::backend_contexts::MIN_MEMORY_CONTEXTS_VERSION, registry::build_collector,
+    slru::pg_stat_slru::MIN_PG_STAT_SLRU_VERSION, stat_io::pg_stat_io::MIN_PG_STAT_IO_VERSION,
+    util::get_pg_version,
+};
+use prometheus::Registry;
+use sqlx::PgPool;
+use std::fmt::Write as _;
+use tracing::{info, warn};
+
+/// Collectors whose only data source is a view added in a later `PostgreSQL` release;
+/// on older servers they run but never export anything.
+const MIN_SERVER_VERSIONS: [(&str, i32, &str); 3] = [
+    (
+        "memory",
+        MIN_MEMORY_CONTEXTS_VERSION,
+        "pg_backend_memory_contexts",
+    ),
+    ("slru", MIN_PG_STAT_SLRU_VERSION, "pg_stat_slru"),
+    ("stat_io", MIN_PG_STAT_IO_VERSION, "pg_stat_io"),
+];
+
+/// Outcome of one collector's preflight probe.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Readiness {
+    pub collector: String,
+    /// Why the collector cannot function, `None` when it is ready.
+    pub problem: Option<String>,
+}
+
+impl Readiness {
+    #[must_use]
+    pub const fn is_ready(&self) -> bool {
+        self.problem.is_none()
+    }
+}
+
+/// Probe every enabled collector in `config` one at a time, in collection order.
+///
+/// The server version must already be detected, otherwise version requirements are
+/// not checked.
+pub async fn run(config: &CollectorConfig, pool: &PgPool) -> Vec<Readiness> {
+    let factories = all_factories();
+    let mut results = Vec::new();
+
+    for name in config.enabled_collectors_in_order() {
+        let problem = match build_collector(&name, config, &factories) {
+            Some(collector) => probe(&collector, pool).await.err(),
+            None => Some("unknown collector".to_string()),
+        };
+
+        match &problem {
+            None => info!(collector = %name, "preflight: ready"),
+            Some(problem) => warn!(collector = %name, problem = %problem, "preflight: not ready"),
+        }
+
+        results.push(Readiness {
+            collector: name,
+            problem,
+        });
+    }
+
+    results
+}
+
+async fn probe(collector: &CollectorType, pool: &PgPool) -> Result<(), String> {
+    if let Some(problem) = version_problem(collector.name(), get_pg_version()) {
+        return Err(problem);
+    }
+
+    let registry = Registry::new();
+    collector
+        .register_metrics(&registry)
+        .map_err(|e| format!("cannot register metrics: {e}"))?;
+
+    // Slow probes are bounded by the pool's statement_timeout, which cancels the query
+    // on the server and surfaces here as the collector's error.
+    collector
+        .collect(pool)
+        .await
+        .map_err(|e| format!("{e:#}"))?;
+
+    match unavailable_sources(&registry) {
+        sources if sources.is_empty() => Ok(()),
+        sources => Err(format!("data source unavailable: {}", sources.join(", "))),
+    }
+}
+
+/// Why `collector` cannot run on server `version`, `None` when it can or the version
+/// is unknown (0).
+fn version_problem(collector: &str, version: i32) -> Option<String> {
+    let (_, min_version, view) = MIN_SERVER_VERSIONS
+        .iter()
+        .find(|(name, _, _)| *name == collector)?;
+
+    (version > 0 && version < *min_version).then(|| {
+        format!(
+            "{view} requires PostgreSQL {}+, server is {}",
+            min_version / 10_000,
+            version / 10_000
+        )
+    })
+}
+
+/// Sources a probe reported as unusable through `pg_exporter_collector_available 0`.
+fn unavailable_sources(registry: &Registry) -> Vec<String> {
+    registry
+        .gather()
+        .iter()
+        .filter(|family| family.name() == "pg_exporter_collector_available")
+        .flat_map(prometheus::proto::MetricFamily::get_metric)
+        .filter(|metric| metric.get_gauge().value() <= 0.0)
+        .filter_map(|metric| {
+            metric
+                .get_label()
+                .iter()
+                .find(|label| label.name() == "collector")
+                .map(|label| label.value().to_string())
+        })
+        .collect()
+}
+
+/// Render the per-collector readiness table printed by `--preflight`.
+#[must_use]
+pub fn format_table(results: &[Readiness]) -> String {
+    let width = results
+        .iter()
+        .map(|result| result.collector.len())
+        .chain(std::iter::once("COLLECTOR".len()))
+        .max()
+        .unwrap_or_default();
+
+    let mut table = format!("  {:<width$}  {:<9}  DETAIL", "COLLECTOR", "STATUS");
+    for result in results {
+        let (status, detail) = match &result.problem {
+            None => ("ready", ""),
+            Some(problem) => ("NOT READY", problem.as_str()),
+        };
+        let row = format!("  {:<width$}  {status:<9}  {detail}", result.collector);
+        let _ = write!(table, "\n{}", row.trim_end());
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_problem_flags_too_old_servers_only() {
+        assert_eq!(
+            version_problem("stat_io", 150_004),
+            Some("pg_stat_io requires PostgreSQL 16+, server is 15".to_string())
+        );
+        assert_eq!(version_problem("stat_io", 160_000), None);
+        assert_eq!(version_problem("stat_io", 0), None);
+        assert_eq!(version_problem("default", 90_600), None);
+    }
+
+    #[test]
+    fn test_format_table_aligns_columns() {
+        let table = format_table(&[
+            Readiness {
+                collector: "default".to_string(),
+                problem: None,
+            },
+            Readiness {
+                collector: "statements".to_string(),
+                problem: Some("data source unavailable: statements".to_string()),
+            },
+        ]);
+
+        assert_eq!(
+            table,
+            "  COLLECTOR   STATUS     DETAIL\n\
+             \x20 default     ready\n\
+             \x20 statements  NOT READY  data source unavailable: statements"
+        );
+    }
+}
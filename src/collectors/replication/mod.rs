@@ -1,12 +1,10 @@
-use crate::collectors::Collector;
+use crate::collectors::{Collector, util::collect_subs};
 use anyhow::Result;
 use futures::future::BoxFuture;
-use futures::stream::{FuturesUnordered, StreamExt};
 use prometheus::Registry;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tracing::{debug, info_span, instrument, warn};
-use tracing_futures::Instrument as _;
 
 pub mod replica;
 use replica::ReplicaCollector;
@@ -17,9 +15,21 @@ use stat_replication::StatReplicationCollector;
 pub mod stat_replication_slots;
 use stat_replication_slots::StatReplicationSlotsCollector;
 
+pub mod stat_subscription_stats;
+use stat_subscription_stats::StatSubscriptionStatsCollector;
+
 pub mod slots;
 use slots::ReplicationSlotsCollector;
 
+pub mod subscription;
+use subscription::SubscriptionCollector;
+
+pub mod wal_receiver;
+use wal_receiver::WalReceiverCollector;
+
+pub mod wal_senders;
+use wal_senders::WalSendersCollector;
+
 #[derive(Clone, Default)]
 pub struct ReplicationCollector {
     subs: Vec<Arc<dyn Collector + Send + Sync>>,
@@ -33,7 +43,11 @@ impl ReplicationCollector {
                 Arc::new(ReplicaCollector::new()),
                 Arc::new(StatReplicationCollector::new()),
                 Arc::new(StatReplicationSlotsCollector::new()),
+                Arc::new(StatSubscriptionStatsCollector::new()),
                 Arc::new(ReplicationSlotsCollector::new()),
+                Arc::new(SubscriptionCollector::new()),
+                Arc::new(WalReceiverCollector::new()),
+                Arc::new(WalSendersCollector::new()),
             ],
         }
     }
@@ -78,21 +92,7 @@ impl Collector for ReplicationCollector {
         fields(collector = "replication", otel.kind = "internal")
     )]
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
-        Box::pin(async move {
-            let mut tasks = FuturesUnordered::new();
-
-            for sub in &self.subs {
-                let span = info_span!("collector.collect", sub_collector = %sub.name(), otel.kind = "internal");
-
-                tasks.push(sub.collect(pool).instrument(span));
-            }
-
-            while let Some(res) = tasks.next().await {
-                res?;
-            }
-
-            Ok(())
-        })
+        Box::pin(async move { collect_subs(&self.subs, pool).await })
     }
 
     fn enabled_by_default(&self) -> bool {
@@ -1,4 +1,5 @@
 use crate::collectors::Collector;
+use crate::collectors::util::acquire_connection;
 use anyhow::Result;
 use futures::future::BoxFuture;
 use prometheus::{Gauge, Opts, Registry};
@@ -124,7 +125,7 @@ impl Collector for ReplicaCollector {
                     ) AS last_replay
                 ",
             )
-            .fetch_one(pool)
+            .fetch_one(&mut *acquire_connection(pool).await?)
             .instrument(query_span)
             .await?;
 
@@ -169,8 +170,7 @@ mod tests {
     #[tokio::test]
     #[allow(clippy::expect_used)]
     async fn test_replica_collector_metrics_on_primary() {
-        let database_url =
-            std::env::var("DATABASE_URL").unwrap_or_else(|_| String::new());
+        let database_url = std::env::var("DATABASE_URL").unwrap_or_else(|_| String::new());
 
         if database_url.is_empty() {
             eprintln!("Skipping test: DATABASE_URL not set");
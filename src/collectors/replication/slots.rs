@@ -1,7 +1,8 @@
 use crate::collectors::Collector;
+use crate::collectors::util::acquire_connection;
 use anyhow::Result;
 use futures::future::BoxFuture;
-use prometheus::{GaugeVec, Opts, Registry};
+use prometheus::{GaugeVec, IntGaugeVec, Opts, Registry};
 use sqlx::{PgPool, Row};
 use tracing::{debug, info_span, instrument};
 use tracing_futures::Instrument as _;
@@ -12,10 +13,45 @@ use tracing_futures::Instrument as _;
 /// Metrics (with labels: `slot_name`, `slot_type`, database, active):
 /// - `pg_replication_slots_pg_wal_lsn_diff`
 /// - `pg_replication_slots_active` (1 if active, 0 if not)
+/// - `pg_replication_slots_lag_seconds` (`replay_lag` of the slot's walsender; -1 when
+///   the slot has no connected consumer, so no time lag is computable)
+///
+/// Invalidation (labels: `slot_name`, `slot_type`, database, `wal_status`):
+/// - `pg_replication_slots_invalidated` (1 when `wal_status = 'lost'`: the WAL the slot
+///   needs was removed past `max_slot_wal_keep_size`, so its consumer must be rebuilt)
+///
+/// Consumer (labels: `slot_name`, `slot_type`, database, `application_name`, `client_addr`):
+/// - `pg_replication_slots_active_pid` (pid of the process using an active slot, resolved
+///   through `pg_stat_activity`; labels are empty when the role cannot see that session,
+///   and inactive slots export no series)
+///
+/// Inventory (labels: `slot_name`, `slot_type`, `plugin`, database):
+/// - `pg_replication_slots_info` (always 1; `plugin` is the logical decoding output
+///   plugin, empty for physical slots)
 #[derive(Clone)]
 pub struct ReplicationSlotsCollector {
     wal_lsn_diff: GaugeVec,
     active: GaugeVec,
+    lag_seconds: GaugeVec,
+    invalidated: IntGaugeVec,
+    active_pid: IntGaugeVec,
+    info: IntGaugeVec,
+}
+
+/// Value of `pg_replication_slots_lag_seconds` for a slot without a connected consumer.
+pub const SLOT_LAG_UNAVAILABLE: f64 = -1.0;
+
+/// `wal_status` of a slot whose required WAL has been removed; it can never be used again.
+const WAL_STATUS_LOST: &str = "lost";
+
+/// Time lag for a slot: the walsender's `replay_lag`, 0 when an attached consumer is
+/// caught up (`replay_lag` is NULL), or the sentinel when nothing consumes the slot.
+fn slot_lag_seconds(has_walsender: bool, replay_lag_seconds: Option<f64>) -> f64 {
+    if has_walsender {
+        replay_lag_seconds.unwrap_or(0.0).max(0.0)
+    } else {
+        SLOT_LAG_UNAVAILABLE
+    }
 }
 
 impl Default for ReplicationSlotsCollector {
@@ -53,9 +89,55 @@ impl ReplicationSlotsCollector {
         )
         .expect("Failed to create pg_replication_slots_active");
 
+        let lag_seconds = GaugeVec::new(
+            Opts::new(
+                "pg_replication_slots_lag_seconds",
+                "Replication slot lag in seconds (replay_lag of the consuming walsender; -1 when no consumer is connected)",
+            ),
+            labels,
+        )
+        .expect("Failed to create pg_replication_slots_lag_seconds");
+
+        let invalidated = IntGaugeVec::new(
+            Opts::new(
+                "pg_replication_slots_invalidated",
+                "Whether the replication slot was invalidated (1 when wal_status is lost, 0 otherwise)",
+            ),
+            &["slot_name", "slot_type", "database", "wal_status"],
+        )
+        .expect("Failed to create pg_replication_slots_invalidated");
+
+        let active_pid = IntGaugeVec::new(
+            Opts::new(
+                "pg_replication_slots_active_pid",
+                "Process ID of the consumer using the replication slot, with its application_name and client_addr (only active slots)",
+            ),
+            &[
+                "slot_name",
+                "slot_type",
+                "database",
+                "application_name",
+                "client_addr",
+            ],
+        )
+        .expect("Failed to create pg_replication_slots_active_pid");
+
+        let info = IntGaugeVec::new(
+            Opts::new(
+                "pg_replication_slots_info",
+                "Replication slot inventory with its output plugin (always 1; plugin is empty for physical slots)",
+            ),
+            &["slot_name", "slot_type", "plugin", "database"],
+        )
+        .expect("Failed to create pg_replication_slots_info");
+
         Self {
             wal_lsn_diff,
             active,
+            lag_seconds,
+            invalidated,
+            active_pid,
+            info,
         }
     }
 }
@@ -74,6 +156,10 @@ impl Collector for ReplicationSlotsCollector {
     fn register_metrics(&self, registry: &Registry) -> Result<()> {
         registry.register(Box::new(self.wal_lsn_diff.clone()))?;
         registry.register(Box::new(self.active.clone()))?;
+        registry.register(Box::new(self.lag_seconds.clone()))?;
+        registry.register(Box::new(self.invalidated.clone()))?;
+        registry.register(Box::new(self.active_pid.clone()))?;
+        registry.register(Box::new(self.info.clone()))?;
         Ok(())
     }
 
@@ -98,31 +184,50 @@ impl Collector for ReplicationSlotsCollector {
             let rows = sqlx::query(
                 r"
                 SELECT
-                    slot_name,
-                    slot_type,
-                    COALESCE(database, '') AS database,
-                    active,
+                    s.slot_name,
+                    s.slot_type,
+                    COALESCE(s.plugin, '') AS plugin,
+                    COALESCE(s.database, '') AS database,
+                    s.active,
+                    COALESCE(s.wal_status, '') AS wal_status,
                     (CASE pg_is_in_recovery() 
-                        WHEN 't' THEN pg_wal_lsn_diff(pg_last_wal_receive_lsn(), restart_lsn) 
-                        ELSE pg_wal_lsn_diff(pg_current_wal_lsn(), restart_lsn) 
-                    END) AS pg_wal_lsn_diff
-                FROM pg_replication_slots
+                        WHEN 't' THEN pg_wal_lsn_diff(pg_last_wal_receive_lsn(), s.restart_lsn) 
+                        ELSE pg_wal_lsn_diff(pg_current_wal_lsn(), s.restart_lsn) 
+                    END) AS pg_wal_lsn_diff,
+                    (r.pid IS NOT NULL) AS has_walsender,
+                    EXTRACT(EPOCH FROM r.replay_lag)::double precision AS replay_lag_seconds,
+                    s.active_pid::bigint AS active_pid,
+                    COALESCE(a.application_name, '') AS consumer_application_name,
+                    COALESCE(host(a.client_addr), '') AS consumer_client_addr
+                FROM pg_replication_slots s
+                LEFT JOIN pg_stat_replication r ON r.pid = s.active_pid
+                LEFT JOIN pg_stat_activity a ON a.pid = s.active_pid
                 ",
             )
-            .fetch_all(pool)
+            .fetch_all(&mut *acquire_connection(pool).await?)
             .instrument(query_span)
             .await?;
 
             // Reset all metrics
             self.wal_lsn_diff.reset();
             self.active.reset();
+            self.lag_seconds.reset();
+            self.invalidated.reset();
+            self.active_pid.reset();
+            self.info.reset();
 
             for row in &rows {
                 let slot_name: String = row.try_get("slot_name").unwrap_or_default();
                 let slot_type: String = row.try_get("slot_type").unwrap_or_default();
+                let plugin: String = row.try_get("plugin").unwrap_or_default();
                 let database: String = row.try_get("database").unwrap_or_default();
                 let is_active: bool = row.try_get("active").unwrap_or(false);
+                let wal_status: String = row.try_get("wal_status").unwrap_or_default();
                 let lsn_diff: f64 = row.try_get("pg_wal_lsn_diff").unwrap_or(0.0);
+                let has_walsender: bool = row.try_get("has_walsender").unwrap_or(false);
+                let replay_lag: Option<f64> = row.try_get("replay_lag_seconds").unwrap_or(None);
+                let lag_seconds = slot_lag_seconds(has_walsender, replay_lag);
+                let active_pid: Option<i64> = row.try_get("active_pid")?;
 
                 self.wal_lsn_diff
                     .with_label_values(&[&slot_name, &slot_type, &database])
@@ -132,17 +237,50 @@ impl Collector for ReplicationSlotsCollector {
                     .with_label_values(&[&slot_name, &slot_type, &database])
                     .set(if is_active { 1.0 } else { 0.0 });
 
+                self.lag_seconds
+                    .with_label_values(&[&slot_name, &slot_type, &database])
+                    .set(lag_seconds);
+
+                self.invalidated
+                    .with_label_values(&[&slot_name, &slot_type, &database, &wal_status])
+                    .set(i64::from(wal_status == WAL_STATUS_LOST));
+
+                if let Some(pid) = active_pid {
+                    let application_name: String = row.try_get("consumer_application_name")?;
+                    let client_addr: String = row.try_get("consumer_client_addr")?;
+                    self.active_pid
+                        .with_label_values(&[
+                            &slot_name,
+                            &slot_type,
+                            &database,
+                            &application_name,
+                            &client_addr,
+                        ])
+                        .set(pid);
+                }
+
+                self.info
+                    .with_label_values(&[&slot_name, &slot_type, &plugin, &database])
+                    .set(1);
+
                 debug!(
                     slot_name = %slot_name,
                     slot_type = %slot_type,
+                    plugin = %plugin,
                     database = %database,
                     active = is_active,
+                    active_pid = ?active_pid,
+                    wal_status = %wal_status,
                     wal_lsn_diff = lsn_diff,
+                    lag_seconds,
                     "collected pg_replication_slots metric"
                 );
             }
 
-            debug!(slots_count = rows.len(), "collected replication slots metrics");
+            debug!(
+                slots_count = rows.len(),
+                "collected replication slots metrics"
+            );
 
             Ok(())
         })
@@ -167,6 +305,14 @@ mod tests {
         assert!(collector.register_metrics(&registry).is_ok());
     }
 
+    #[test]
+    fn test_slot_lag_seconds() {
+        assert!((slot_lag_seconds(false, None) - SLOT_LAG_UNAVAILABLE).abs() < f64::EPSILON);
+        assert!((slot_lag_seconds(false, Some(3.0)) - SLOT_LAG_UNAVAILABLE).abs() < f64::EPSILON);
+        assert!(slot_lag_seconds(true, None).abs() < f64::EPSILON);
+        assert!((slot_lag_seconds(true, Some(2.5)) - 2.5).abs() < f64::EPSILON);
+    }
+
     #[tokio::test]
     #[allow(clippy::expect_used)]
     async fn test_replication_slots_collector_collection() {
@@ -1,7 +1,8 @@
+use crate::collectors::util::acquire_connection;
 use crate::collectors::{Collector, i64_to_f64};
 use anyhow::Result;
 use futures::future::BoxFuture;
-use prometheus::{GaugeVec, Opts, Registry};
+use prometheus::{GaugeVec, IntGauge, IntGaugeVec, Opts, Registry};
 use sqlx::{PgPool, Row};
 use tracing::{debug, info_span, instrument};
 use tracing_futures::Instrument as _;
@@ -16,12 +17,25 @@ use tracing_futures::Instrument as _;
 ///
 /// Additional metrics:
 /// - `pg_stat_replication_slots` (count of replication slots by `application_name` and `state`)
+///
+/// Synchronous replication:
+/// - `pg_stat_replication_sync_state{application_name,sync_priority,sync_state}` (always 1)
+/// - `pg_stat_replication_sync_standbys`: walsenders in `sync` or `quorum` state
+/// - `pg_stat_replication_sync_standbys_expected`: standbys `synchronous_standby_names`
+///   waits for (0 when synchronous replication is off)
+///
+/// Commits wait for the expected number of synchronous standbys, so
+/// `pg_stat_replication_sync_standbys < pg_stat_replication_sync_standbys_expected`
+/// means writes are stalling on the primary.
 #[derive(Clone)]
 pub struct StatReplicationCollector {
     current_wal_lsn_bytes: GaugeVec,
     wal_lsn_diff: GaugeVec,
     reply_time: GaugeVec,
     slots: GaugeVec,
+    sync_state: IntGaugeVec,
+    sync_standbys: IntGauge,
+    sync_standbys_expected: IntGauge,
 }
 
 impl Default for StatReplicationCollector {
@@ -77,15 +91,65 @@ impl StatReplicationCollector {
         )
         .expect("Failed to create pg_stat_replication_slots");
 
+        let sync_state = IntGaugeVec::new(
+            Opts::new(
+                "pg_stat_replication_sync_state",
+                "Synchronous replication state (async, potential, sync, quorum) and priority \
+                 of each walsender; always 1",
+            ),
+            &["application_name", "sync_state", "sync_priority"],
+        )
+        .expect("Failed to create pg_stat_replication_sync_state");
+
+        let sync_standbys = IntGauge::with_opts(Opts::new(
+            "pg_stat_replication_sync_standbys",
+            "Connected standbys whose sync_state is sync or quorum",
+        ))
+        .expect("Failed to create pg_stat_replication_sync_standbys");
+
+        let sync_standbys_expected = IntGauge::with_opts(Opts::new(
+            "pg_stat_replication_sync_standbys_expected",
+            "Synchronous standbys that synchronous_standby_names requires (0 = synchronous \
+             replication off); fewer connected ones stall commits",
+        ))
+        .expect("Failed to create pg_stat_replication_sync_standbys_expected");
+
         Self {
             current_wal_lsn_bytes,
             wal_lsn_diff,
             reply_time,
             slots,
+            sync_state,
+            sync_standbys,
+            sync_standbys_expected,
         }
     }
 }
 
+/// Number of synchronous standbys commits wait for under `synchronous_standby_names`.
+///
+/// `FIRST n (...)`, `ANY n (...)` and `n (...)` require `n`; a plain list (the pre-9.6
+/// syntax) means `FIRST 1`; an empty setting disables synchronous replication.
+fn expected_sync_standbys(setting: &str) -> i64 {
+    let setting = setting.trim();
+    if setting.is_empty() {
+        return 0;
+    }
+
+    let mut words = setting.split_whitespace();
+    let first = words.next().unwrap_or_default();
+    let count = if first.eq_ignore_ascii_case("FIRST") || first.eq_ignore_ascii_case("ANY") {
+        words.next()
+    } else {
+        Some(first)
+    };
+
+    count
+        .map(|word| word.split('(').next().unwrap_or(word))
+        .and_then(|word| word.parse::<i64>().ok())
+        .unwrap_or(1)
+}
+
 impl Collector for StatReplicationCollector {
     fn name(&self) -> &'static str {
         "stat_replication"
@@ -102,6 +166,9 @@ impl Collector for StatReplicationCollector {
         registry.register(Box::new(self.wal_lsn_diff.clone()))?;
         registry.register(Box::new(self.reply_time.clone()))?;
         registry.register(Box::new(self.slots.clone()))?;
+        registry.register(Box::new(self.sync_state.clone()))?;
+        registry.register(Box::new(self.sync_standbys.clone()))?;
+        registry.register(Box::new(self.sync_standbys_expected.clone()))?;
         Ok(())
     }
 
@@ -141,19 +208,29 @@ impl Collector for StatReplicationCollector {
                         WHEN 't' THEN pg_wal_lsn_diff(pg_last_wal_receive_lsn(), replay_lsn)::float 
                         ELSE pg_wal_lsn_diff(pg_current_wal_lsn(), replay_lsn)::float 
                     END) AS pg_wal_lsn_diff,
-                    EXTRACT(EPOCH FROM (now() - reply_time)) AS reply_time_seconds
+                    EXTRACT(EPOCH FROM (now() - reply_time)) AS reply_time_seconds,
+                    COALESCE(sync_state, '') AS sync_state,
+                    COALESCE(sync_priority, 0)::bigint AS sync_priority
                 FROM pg_stat_replication
                 ",
             )
-            .fetch_all(pool)
+            .fetch_all(&mut *acquire_connection(pool).await?)
             .instrument(query_span)
             .await?;
 
+            let sync_standby_names: String =
+                sqlx::query_scalar("SELECT current_setting('synchronous_standby_names')")
+                    .fetch_one(&mut *acquire_connection(pool).await?)
+                    .await?;
+
             // Reset all metrics
             self.current_wal_lsn_bytes.reset();
             self.wal_lsn_diff.reset();
             self.reply_time.reset();
             self.slots.reset();
+            self.sync_state.reset();
+
+            let mut sync_standbys: i64 = 0;
 
             // Track seen combinations for slot counting
             let mut slot_counts: std::collections::HashMap<(String, String), i64> =
@@ -179,6 +256,15 @@ impl Collector for StatReplicationCollector {
                     .with_label_values(&[&app_name, &client_addr, &state])
                     .set(reply_time);
 
+                let sync_state: String = row.try_get("sync_state").unwrap_or_default();
+                let sync_priority: i64 = row.try_get("sync_priority").unwrap_or(0);
+                self.sync_state
+                    .with_label_values(&[&app_name, &sync_state, &sync_priority.to_string()])
+                    .set(1);
+                if matches!(sync_state.as_str(), "sync" | "quorum") {
+                    sync_standbys += 1;
+                }
+
                 // Count slots
                 let key = (app_name.clone(), state.clone());
                 *slot_counts.entry(key).or_insert(0) += 1;
@@ -200,8 +286,14 @@ impl Collector for StatReplicationCollector {
                     .set(i64_to_f64(count));
             }
 
+            let expected = expected_sync_standbys(&sync_standby_names);
+            self.sync_standbys.set(sync_standbys);
+            self.sync_standbys_expected.set(expected);
+
             debug!(
                 replication_slots = rows.len(),
+                sync_standbys,
+                sync_standbys_expected = expected,
                 "collected stat_replication metrics"
             );
 
@@ -228,6 +320,20 @@ mod tests {
         assert!(collector.register_metrics(&registry).is_ok());
     }
 
+    #[test]
+    fn test_expected_sync_standbys() {
+        assert_eq!(expected_sync_standbys(""), 0);
+        assert_eq!(expected_sync_standbys("  "), 0);
+        assert_eq!(expected_sync_standbys("standby1"), 1);
+        assert_eq!(expected_sync_standbys("standby1, standby2"), 1);
+        assert_eq!(expected_sync_standbys("*"), 1);
+        assert_eq!(expected_sync_standbys("FIRST 2 (s1, s2, s3)"), 2);
+        assert_eq!(expected_sync_standbys("first 2(s1, s2)"), 2);
+        assert_eq!(expected_sync_standbys("ANY 3 (s1, s2, s3, s4)"), 3);
+        assert_eq!(expected_sync_standbys("2 (s1, s2, s3)"), 2);
+        assert_eq!(expected_sync_standbys("2(s1, s2)"), 2);
+    }
+
     #[tokio::test]
     #[allow(clippy::expect_used)]
     async fn test_stat_replication_collector_on_primary() {
@@ -9,7 +9,10 @@
 //! skips cleanly, exports no series, and logs a single warning so operators know
 //! why the `pg_stat_replication_slots_*` metrics are absent.
 
-use crate::collectors::{Collector, util::get_pg_version};
+use crate::collectors::{
+    Collector,
+    util::{acquire_connection, resolve_server_version},
+};
 use anyhow::Result;
 use futures::future::BoxFuture;
 use prometheus::{IntGaugeVec, Opts, Registry};
@@ -48,20 +51,6 @@ const fn supports_stat_replication_slots(version_num: i32) -> bool {
     version_num >= MIN_STAT_REPLICATION_SLOTS_VERSION
 }
 
-/// Resolves the server version, preferring the startup cache and falling back to
-/// a direct `PostgreSQL` query for tests that bypass exporter bootstrap.
-async fn resolve_server_version(pool: &PgPool) -> Result<i32> {
-    let cached = get_pg_version();
-    if cached > 0 {
-        return Ok(cached);
-    }
-
-    let row = sqlx::query("SELECT current_setting('server_version_num')::int AS v")
-        .fetch_one(pool)
-        .await?;
-    Ok(row.try_get::<i32, _>("v").unwrap_or(0))
-}
-
 /// Exposes `pg_stat_replication_slots` logical replication slot statistics
 /// (`PostgreSQL` 14+).
 ///
@@ -220,30 +209,18 @@ impl StatReplicationSlotsCollector {
         let total_txns = row.try_get::<i64, _>("total_txns").unwrap_or(0);
         let total_bytes = row.try_get::<i64, _>("total_bytes").unwrap_or(0);
 
-        self.spill_txns
-            .with_label_values(&labels)
-            .set(spill_txns);
-        self.spill_count
-            .with_label_values(&labels)
-            .set(spill_count);
-        self.spill_bytes
-            .with_label_values(&labels)
-            .set(spill_bytes);
-        self.stream_txns
-            .with_label_values(&labels)
-            .set(stream_txns);
+        self.spill_txns.with_label_values(&labels).set(spill_txns);
+        self.spill_count.with_label_values(&labels).set(spill_count);
+        self.spill_bytes.with_label_values(&labels).set(spill_bytes);
+        self.stream_txns.with_label_values(&labels).set(stream_txns);
         self.stream_count
             .with_label_values(&labels)
             .set(stream_count);
         self.stream_bytes
             .with_label_values(&labels)
             .set(stream_bytes);
-        self.total_txns
-            .with_label_values(&labels)
-            .set(total_txns);
-        self.total_bytes
-            .with_label_values(&labels)
-            .set(total_bytes);
+        self.total_txns.with_label_values(&labels).set(total_txns);
+        self.total_bytes.with_label_values(&labels).set(total_bytes);
 
         debug!(
             slot_name = %slot_name,
@@ -315,7 +292,7 @@ impl Collector for StatReplicationSlotsCollector {
             );
 
             let rows = sqlx::query(STAT_REPLICATION_SLOTS_QUERY)
-                .fetch_all(pool)
+                .fetch_all(&mut *acquire_connection(pool).await?)
                 .instrument(query_span)
                 .await?;
 
@@ -325,7 +302,10 @@ impl Collector for StatReplicationSlotsCollector {
                 self.apply_row(row);
             }
 
-            debug!(rows = rows.len(), "updated pg_stat_replication_slots metrics");
+            debug!(
+                rows = rows.len(),
+                "updated pg_stat_replication_slots metrics"
+            );
 
             Ok(())
         })
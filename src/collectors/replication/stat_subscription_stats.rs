@@ -0,0 +1,324 @@
+//! Logical replication subscription error and conflict counters from
+//! `pg_stat_subscription_stats` (`PostgreSQL` 15+, conflict counters 18+).
+//!
+//! `pg_subscription` is a shared catalog, so the view lists every subscription in
+//! the cluster and this collector reads only the shared pool. It is wired under
+//! the opt-in `--collector.replication` umbrella.
+//!
+//! On servers older than `PostgreSQL` 15 the view does not exist. The collector
+//! skips cleanly, exports no series, and logs a single warning so operators know
+//! why the `pg_stat_subscription_*` metrics are absent.
+
+use crate::collectors::{
+    Collector,
+    util::{acquire_connection, get_in_recovery, resolve_server_version},
+};
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{IntGaugeVec, Opts, Registry};
+use sqlx::{PgPool, Row};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+use tracing::{debug, info_span, instrument, warn};
+use tracing_futures::Instrument as _;
+
+/// `pg_stat_subscription_stats` was introduced in `PostgreSQL` 15.
+const MIN_STAT_SUBSCRIPTION_STATS_VERSION: i32 = 150_000;
+
+/// The `confl_*` conflict counters were added in `PostgreSQL` 18.
+const MIN_CONFLICT_COUNTERS_VERSION: i32 = 180_000;
+
+/// `PostgreSQL` 18 conflict counters, exported as the `conflict` label with the
+/// `confl_` prefix stripped.
+const CONFLICT_TYPES: [&str; 7] = [
+    "insert_exists",
+    "update_origin_differs",
+    "update_exists",
+    "update_missing",
+    "delete_origin_differs",
+    "delete_missing",
+    "multiple_unique_conflicts",
+];
+
+const STAT_SUBSCRIPTION_STATS_QUERY: &str = r"
+SELECT
+    subname::text AS subname,
+    apply_error_count::bigint AS apply_error_count,
+    sync_error_count::bigint AS sync_error_count
+FROM pg_stat_subscription_stats
+WHERE subname IS NOT NULL
+";
+
+const STAT_SUBSCRIPTION_STATS_QUERY_PG18: &str = r"
+SELECT
+    subname::text AS subname,
+    apply_error_count::bigint AS apply_error_count,
+    sync_error_count::bigint AS sync_error_count,
+    confl_insert_exists::bigint AS insert_exists,
+    confl_update_origin_differs::bigint AS update_origin_differs,
+    confl_update_exists::bigint AS update_exists,
+    confl_update_missing::bigint AS update_missing,
+    confl_delete_origin_differs::bigint AS delete_origin_differs,
+    confl_delete_missing::bigint AS delete_missing,
+    confl_multiple_unique_conflicts::bigint AS multiple_unique_conflicts
+FROM pg_stat_subscription_stats
+WHERE subname IS NOT NULL
+";
+
+/// Returns whether `pg_stat_subscription_stats` exists for `server_version_num`.
+#[must_use]
+const fn supports_stat_subscription_stats(version_num: i32) -> bool {
+    version_num >= MIN_STAT_SUBSCRIPTION_STATS_VERSION
+}
+
+/// Returns whether the view carries the `confl_*` counters for `server_version_num`.
+#[must_use]
+const fn supports_conflict_counters(version_num: i32) -> bool {
+    version_num >= MIN_CONFLICT_COUNTERS_VERSION
+}
+
+/// Exposes logical replication subscription error and conflict counters from
+/// `pg_stat_subscription_stats` (`PostgreSQL` 15+).
+///
+/// Values are cumulative since the last `pg_stat_reset_subscription_stats()`;
+/// use `increase()` in `PromQL` to alert on new errors or conflicts.
+///
+/// **Error metrics (`IntGauge`, label `subname`):**
+/// - `pg_stat_subscription_apply_error_count`
+/// - `pg_stat_subscription_sync_error_count`
+///
+/// **Conflict metrics (`IntGauge`, labels `subname`, `conflict`; `PostgreSQL` 18+):**
+/// - `pg_stat_subscription_conflict_count`
+#[derive(Clone)]
+pub struct StatSubscriptionStatsCollector {
+    apply_error_count: IntGaugeVec,
+    sync_error_count: IntGaugeVec,
+    conflict_count: IntGaugeVec,
+    unsupported_warned: Arc<AtomicBool>,
+}
+
+impl Default for StatSubscriptionStatsCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StatSubscriptionStatsCollector {
+    /// Creates a new `StatSubscriptionStatsCollector` with all metrics initialized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails, which only happens with an invalid
+    /// metric name or label set and therefore never at runtime.
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let apply_error_count = IntGaugeVec::new(
+            Opts::new(
+                "pg_stat_subscription_apply_error_count",
+                "Errors raised while applying changes, by subscription",
+            ),
+            &["subname"],
+        )
+        .expect("pg_stat_subscription_apply_error_count metric");
+
+        let sync_error_count = IntGaugeVec::new(
+            Opts::new(
+                "pg_stat_subscription_sync_error_count",
+                "Errors raised during initial table synchronization, by subscription",
+            ),
+            &["subname"],
+        )
+        .expect("pg_stat_subscription_sync_error_count metric");
+
+        let conflict_count = IntGaugeVec::new(
+            Opts::new(
+                "pg_stat_subscription_conflict_count",
+                "Logical replication conflicts detected while applying changes, by subscription \
+                 and conflict type (PostgreSQL 18+)",
+            ),
+            &["subname", "conflict"],
+        )
+        .expect("pg_stat_subscription_conflict_count metric");
+
+        Self {
+            apply_error_count,
+            sync_error_count,
+            conflict_count,
+            unsupported_warned: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn reset_all(&self) {
+        self.apply_error_count.reset();
+        self.sync_error_count.reset();
+        self.conflict_count.reset();
+    }
+
+    fn apply_row(&self, row: &sqlx::postgres::PgRow, with_conflicts: bool) {
+        let subname: String = row.try_get("subname").unwrap_or_default();
+
+        let apply_errors = row.try_get::<i64, _>("apply_error_count").unwrap_or(0);
+        let sync_errors = row.try_get::<i64, _>("sync_error_count").unwrap_or(0);
+
+        self.apply_error_count
+            .with_label_values(&[subname.as_str()])
+            .set(apply_errors);
+        self.sync_error_count
+            .with_label_values(&[subname.as_str()])
+            .set(sync_errors);
+
+        if with_conflicts {
+            for conflict in CONFLICT_TYPES {
+                let count = row.try_get::<i64, _>(conflict).unwrap_or(0);
+                self.conflict_count
+                    .with_label_values(&[subname.as_str(), conflict])
+                    .set(count);
+            }
+        }
+
+        debug!(
+            subname = %subname,
+            apply_errors,
+            sync_errors,
+            "collected pg_stat_subscription_stats metric"
+        );
+    }
+}
+
+impl Collector for StatSubscriptionStatsCollector {
+    fn name(&self) -> &'static str {
+        "stat_subscription_stats"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "stat_subscription_stats")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.apply_error_count.clone()))?;
+        registry.register(Box::new(self.sync_error_count.clone()))?;
+        registry.register(Box::new(self.conflict_count.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector = "stat_subscription_stats", otel.kind = "internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            // pg_subscription is replicated to standbys, but subscription workers only run on
+            // a primary: a standby would export the primary's subscriptions with counters
+            // that never move.
+            if get_in_recovery() {
+                self.reset_all();
+                debug!("Skipping pg_stat_subscription_stats metrics on a standby");
+                return Ok(());
+            }
+
+            let version_num = resolve_server_version(pool).await?;
+            if !supports_stat_subscription_stats(version_num) {
+                self.reset_all();
+                if !self.unsupported_warned.swap(true, Ordering::Relaxed) {
+                    warn!(
+                        server_version_num = version_num,
+                        "collector.replication stat_subscription_stats is enabled but \
+                         pg_stat_subscription_stats requires PostgreSQL 15+; skipping"
+                    );
+                }
+                debug!("Skipping pg_stat_subscription_stats metrics (requires PostgreSQL 15+)");
+                return Ok(());
+            }
+
+            let with_conflicts = supports_conflict_counters(version_num);
+            let query = if with_conflicts {
+                STAT_SUBSCRIPTION_STATS_QUERY_PG18
+            } else {
+                STAT_SUBSCRIPTION_STATS_QUERY
+            };
+
+            let query_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement = "SELECT ... FROM pg_stat_subscription_stats",
+                db.sql.table = "pg_stat_subscription_stats"
+            );
+
+            let rows = sqlx::query(query)
+                .fetch_all(&mut *acquire_connection(pool).await?)
+                .instrument(query_span)
+                .await?;
+
+            self.reset_all();
+
+            for row in &rows {
+                self.apply_row(row, with_conflicts);
+            }
+
+            debug!(
+                rows = rows.len(),
+                with_conflicts, "updated pg_stat_subscription_stats metrics"
+            );
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collector_name_is_stat_subscription_stats() {
+        assert_eq!(
+            StatSubscriptionStatsCollector::new().name(),
+            "stat_subscription_stats"
+        );
+    }
+
+    #[test]
+    fn versions_before_pg15_are_unsupported() {
+        assert!(!supports_stat_subscription_stats(0));
+        assert!(!supports_stat_subscription_stats(140_000));
+        assert!(!supports_stat_subscription_stats(149_999));
+        assert!(supports_stat_subscription_stats(150_000));
+    }
+
+    #[test]
+    fn conflict_counters_require_pg18() {
+        assert!(!supports_conflict_counters(170_000));
+        assert!(supports_conflict_counters(180_000));
+    }
+
+    #[test]
+    fn pg18_query_selects_every_conflict_type() {
+        for conflict in CONFLICT_TYPES {
+            assert!(
+                STAT_SUBSCRIPTION_STATS_QUERY_PG18
+                    .contains(&format!("confl_{conflict}::bigint AS {conflict}")),
+                "{conflict} must be selected and cast to bigint"
+            );
+            assert!(!STAT_SUBSCRIPTION_STATS_QUERY.contains(conflict));
+        }
+    }
+
+    #[test]
+    fn register_metrics_succeeds_with_unique_names() {
+        let registry = Registry::new();
+        assert!(
+            StatSubscriptionStatsCollector::new()
+                .register_metrics(&registry)
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,303 @@
+//! Logical replication subscription progress from `pg_stat_subscription`.
+//!
+//! The view has one row per subscription worker: the apply worker, plus table
+//! synchronization workers during the initial copy and, on `PostgreSQL` 16+,
+//! parallel apply workers. LSN and message times are taken from the apply worker
+//! only; `pg_stat_subscription_worker_count` counts every running worker.
+//!
+//! How the apply worker is identified depends on the server version: `PostgreSQL`
+//! 17 added `worker_type`, 16 added `leader_pid` for parallel apply workers, and
+//! before that the apply worker is the one without a `relid`.
+
+use crate::collectors::{
+    Collector,
+    util::{acquire_connection, get_in_recovery, resolve_server_version},
+};
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{GaugeVec, IntGaugeVec, Opts, Registry};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use tracing::{debug, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// `pg_stat_subscription.leader_pid` (parallel apply workers) was added in `PostgreSQL` 16.
+const MIN_LEADER_PID_VERSION: i32 = 160_000;
+
+/// `pg_stat_subscription.worker_type` was added in `PostgreSQL` 17.
+const MIN_WORKER_TYPE_VERSION: i32 = 170_000;
+
+const SUBSCRIPTION_LABELS: [&str; 2] = ["subname", "subid"];
+
+/// Returns the predicate selecting the apply worker's row for `server_version_num`.
+#[must_use]
+const fn apply_worker_filter(version_num: i32) -> &'static str {
+    if version_num >= MIN_WORKER_TYPE_VERSION {
+        "worker_type = 'apply'"
+    } else if version_num >= MIN_LEADER_PID_VERSION {
+        "relid IS NULL AND leader_pid IS NULL"
+    } else {
+        "relid IS NULL"
+    }
+}
+
+/// One row per subscription. `pg_stat_subscription` lists subscriptions without a
+/// running worker with a `NULL` pid, so those report `worker_count = 0` and no LSNs.
+fn subscription_query(version_num: i32) -> String {
+    let apply = apply_worker_filter(version_num);
+    format!(
+        r"
+SELECT
+    subname::text AS subname,
+    subid::text AS subid,
+    COUNT(pid)::bigint AS worker_count,
+    MAX((received_lsn - '0/0'::pg_lsn)::double precision)
+        FILTER (WHERE {apply}) AS received_lsn,
+    MAX((latest_end_lsn - '0/0'::pg_lsn)::double precision)
+        FILTER (WHERE {apply}) AS latest_end_lsn,
+    MAX(EXTRACT(EPOCH FROM (now() - last_msg_receipt_time))::double precision)
+        FILTER (WHERE {apply}) AS last_msg_receipt_age_seconds,
+    MAX(EXTRACT(EPOCH FROM (now() - latest_end_time))::double precision)
+        FILTER (WHERE {apply}) AS latest_end_age_seconds
+FROM pg_stat_subscription
+WHERE subname IS NOT NULL
+GROUP BY subname, subid
+"
+    )
+}
+
+/// Exposes logical replication subscription progress from `pg_stat_subscription`.
+///
+/// **Apply worker metrics (labels `subname`, `subid`; absent while no apply worker runs):**
+/// - `pg_stat_subscription_received_lsn`: last WAL position received from the publisher
+/// - `pg_stat_subscription_latest_end_lsn`: last WAL position reported back to the publisher
+/// - `pg_stat_subscription_last_msg_receipt_age_seconds`: seconds since the last message
+///   from the publisher
+/// - `pg_stat_subscription_latest_end_age_seconds`: seconds since `latest_end_lsn` was
+///   reported back
+///
+/// **Worker metric (labels `subname`, `subid`):**
+/// - `pg_stat_subscription_worker_count`: running apply, table sync and parallel apply
+///   workers; `0` for a disabled or failing subscription
+#[derive(Clone)]
+pub struct SubscriptionCollector {
+    received_lsn: GaugeVec,
+    latest_end_lsn: GaugeVec,
+    last_msg_receipt_age_seconds: GaugeVec,
+    latest_end_age_seconds: GaugeVec,
+    worker_count: IntGaugeVec,
+}
+
+impl Default for SubscriptionCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SubscriptionCollector {
+    /// Creates a new `SubscriptionCollector` with all metrics initialized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails, which only happens with an invalid
+    /// metric name or label set and therefore never at runtime.
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let received_lsn = GaugeVec::new(
+            Opts::new(
+                "pg_stat_subscription_received_lsn",
+                "Last write-ahead log location received by the subscription's apply worker, in bytes",
+            ),
+            &SUBSCRIPTION_LABELS,
+        )
+        .expect("pg_stat_subscription_received_lsn metric");
+
+        let latest_end_lsn = GaugeVec::new(
+            Opts::new(
+                "pg_stat_subscription_latest_end_lsn",
+                "Last write-ahead log location reported to the publisher by the subscription's \
+                 apply worker, in bytes",
+            ),
+            &SUBSCRIPTION_LABELS,
+        )
+        .expect("pg_stat_subscription_latest_end_lsn metric");
+
+        let last_msg_receipt_age_seconds = GaugeVec::new(
+            Opts::new(
+                "pg_stat_subscription_last_msg_receipt_age_seconds",
+                "Seconds since the subscription's apply worker last received a message from \
+                 the publisher",
+            ),
+            &SUBSCRIPTION_LABELS,
+        )
+        .expect("pg_stat_subscription_last_msg_receipt_age_seconds metric");
+
+        let latest_end_age_seconds = GaugeVec::new(
+            Opts::new(
+                "pg_stat_subscription_latest_end_age_seconds",
+                "Seconds since the subscription's apply worker last reported its position to \
+                 the publisher",
+            ),
+            &SUBSCRIPTION_LABELS,
+        )
+        .expect("pg_stat_subscription_latest_end_age_seconds metric");
+
+        let worker_count = IntGaugeVec::new(
+            Opts::new(
+                "pg_stat_subscription_worker_count",
+                "Running workers of the subscription (apply, table sync and parallel apply)",
+            ),
+            &SUBSCRIPTION_LABELS,
+        )
+        .expect("pg_stat_subscription_worker_count metric");
+
+        Self {
+            received_lsn,
+            latest_end_lsn,
+            last_msg_receipt_age_seconds,
+            latest_end_age_seconds,
+            worker_count,
+        }
+    }
+
+    fn reset_all(&self) {
+        self.received_lsn.reset();
+        self.latest_end_lsn.reset();
+        self.last_msg_receipt_age_seconds.reset();
+        self.latest_end_age_seconds.reset();
+        self.worker_count.reset();
+    }
+
+    fn apply_row(&self, row: &PgRow) {
+        let subname: String = row.try_get("subname").unwrap_or_default();
+        let subid: String = row.try_get("subid").unwrap_or_default();
+        let labels = [subname.as_str(), subid.as_str()];
+
+        let workers = row.try_get::<i64, _>("worker_count").unwrap_or(0);
+        self.worker_count.with_label_values(&labels).set(workers);
+
+        for (gauge, column) in [
+            (&self.received_lsn, "received_lsn"),
+            (&self.latest_end_lsn, "latest_end_lsn"),
+            (
+                &self.last_msg_receipt_age_seconds,
+                "last_msg_receipt_age_seconds",
+            ),
+            (&self.latest_end_age_seconds, "latest_end_age_seconds"),
+        ] {
+            if let Ok(Some(value)) = row.try_get::<Option<f64>, _>(column) {
+                gauge.with_label_values(&labels).set(value);
+            }
+        }
+
+        debug!(
+            subname = %subname,
+            subid = %subid,
+            workers,
+            "collected pg_stat_subscription metric"
+        );
+    }
+}
+
+impl Collector for SubscriptionCollector {
+    fn name(&self) -> &'static str {
+        "subscription"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "subscription")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.received_lsn.clone()))?;
+        registry.register(Box::new(self.latest_end_lsn.clone()))?;
+        registry.register(Box::new(self.last_msg_receipt_age_seconds.clone()))?;
+        registry.register(Box::new(self.latest_end_age_seconds.clone()))?;
+        registry.register(Box::new(self.worker_count.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector = "subscription", otel.kind = "internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            // Subscription workers only run on a primary; a standby lists the replicated
+            // subscriptions with no workers at all.
+            if get_in_recovery() {
+                self.reset_all();
+                debug!("Skipping pg_stat_subscription metrics on a standby");
+                return Ok(());
+            }
+
+            let version_num = resolve_server_version(pool).await?;
+            let query = subscription_query(version_num);
+
+            let query_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement = "SELECT ... FROM pg_stat_subscription GROUP BY subname, subid",
+                db.sql.table = "pg_stat_subscription"
+            );
+
+            let rows = sqlx::query(sqlx::AssertSqlSafe(query.as_str()))
+                .fetch_all(&mut *acquire_connection(pool).await?)
+                .instrument(query_span)
+                .await?;
+
+            self.reset_all();
+
+            for row in &rows {
+                self.apply_row(row);
+            }
+
+            debug!(rows = rows.len(), "updated pg_stat_subscription metrics");
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collector_name_is_subscription() {
+        assert_eq!(SubscriptionCollector::new().name(), "subscription");
+    }
+
+    #[test]
+    fn apply_worker_filter_follows_server_version() {
+        assert_eq!(apply_worker_filter(150_000), "relid IS NULL");
+        assert_eq!(
+            apply_worker_filter(160_000),
+            "relid IS NULL AND leader_pid IS NULL"
+        );
+        assert_eq!(apply_worker_filter(170_000), "worker_type = 'apply'");
+    }
+
+    #[test]
+    fn query_only_uses_worker_type_on_pg17() {
+        assert!(!subscription_query(160_000).contains("worker_type"));
+        assert!(subscription_query(170_000).contains("worker_type = 'apply'"));
+        assert!(!subscription_query(150_000).contains("leader_pid"));
+    }
+
+    #[test]
+    fn register_metrics_succeeds_with_unique_names() {
+        let registry = Registry::new();
+        assert!(
+            SubscriptionCollector::new()
+                .register_metrics(&registry)
+                .is_ok()
+        );
+    }
+}
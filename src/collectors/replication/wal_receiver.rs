@@ -0,0 +1,230 @@
+//! Standby upstream health from `pg_stat_wal_receiver`.
+//!
+//! The view holds at most one row: the WAL receiver of a standby streaming from its
+//! upstream. A primary has no WAL receiver, so the view is empty and no series are
+//! exported. A standby whose receiver is not running (upstream down, restoring from
+//! the archive only) reports `pg_stat_wal_receiver_status` 0 and nothing else.
+
+use crate::collectors::{
+    Collector,
+    util::{acquire_connection, get_in_recovery},
+};
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{GaugeVec, IntGaugeVec, Opts, Registry};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use tracing::{debug, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+const WAL_RECEIVER_QUERY: &str = r"
+SELECT
+    status::text AS status,
+    received_tli::bigint AS received_tli,
+    (latest_end_lsn - '0/0'::pg_lsn)::double precision AS latest_end_lsn,
+    EXTRACT(EPOCH FROM (now() - last_msg_receipt_time))::double precision
+        AS last_msg_receipt_age_seconds
+FROM pg_stat_wal_receiver
+";
+
+/// Exposes the standby's WAL receiver state from `pg_stat_wal_receiver`.
+///
+/// **Metrics (no labels; absent on a primary):**
+/// - `pg_stat_wal_receiver_status`: 1 while streaming, 0 otherwise (including a standby
+///   without a running WAL receiver)
+/// - `pg_stat_wal_receiver_latest_end_lsn`: last WAL location reported to the upstream
+/// - `pg_stat_wal_receiver_received_tli`: timeline of the last WAL location received
+/// - `pg_stat_wal_receiver_last_msg_receipt_age_seconds`: seconds since the last message
+///   from the upstream
+#[derive(Clone)]
+pub struct WalReceiverCollector {
+    status: IntGaugeVec,
+    latest_end_lsn: GaugeVec,
+    received_tli: IntGaugeVec,
+    last_msg_receipt_age_seconds: GaugeVec,
+}
+
+impl Default for WalReceiverCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WalReceiverCollector {
+    /// Creates a new `WalReceiverCollector` with all metrics initialized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails, which only happens with an invalid
+    /// metric name or label set and therefore never at runtime.
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let status = IntGaugeVec::new(
+            Opts::new(
+                "pg_stat_wal_receiver_status",
+                "Whether the standby's WAL receiver is streaming from its upstream (1) or not (0)",
+            ),
+            &[],
+        )
+        .expect("pg_stat_wal_receiver_status metric");
+
+        let latest_end_lsn = GaugeVec::new(
+            Opts::new(
+                "pg_stat_wal_receiver_latest_end_lsn",
+                "Last write-ahead log location reported to the upstream by the WAL receiver, in bytes",
+            ),
+            &[],
+        )
+        .expect("pg_stat_wal_receiver_latest_end_lsn metric");
+
+        let received_tli = IntGaugeVec::new(
+            Opts::new(
+                "pg_stat_wal_receiver_received_tli",
+                "Timeline of the last write-ahead log location received by the WAL receiver",
+            ),
+            &[],
+        )
+        .expect("pg_stat_wal_receiver_received_tli metric");
+
+        let last_msg_receipt_age_seconds = GaugeVec::new(
+            Opts::new(
+                "pg_stat_wal_receiver_last_msg_receipt_age_seconds",
+                "Seconds since the WAL receiver last received a message from the upstream",
+            ),
+            &[],
+        )
+        .expect("pg_stat_wal_receiver_last_msg_receipt_age_seconds metric");
+
+        Self {
+            status,
+            latest_end_lsn,
+            received_tli,
+            last_msg_receipt_age_seconds,
+        }
+    }
+
+    fn reset_all(&self) {
+        self.status.reset();
+        self.latest_end_lsn.reset();
+        self.received_tli.reset();
+        self.last_msg_receipt_age_seconds.reset();
+    }
+
+    fn apply_row(&self, row: &PgRow) {
+        let status: Option<String> = row.try_get("status").unwrap_or_default();
+        self.status
+            .with_label_values(&[] as &[&str])
+            .set(i64::from(is_streaming(status.as_deref())));
+
+        // Without pg_read_all_stats only the status is visible; the rest stays absent.
+        if let Ok(Some(tli)) = row.try_get::<Option<i64>, _>("received_tli") {
+            self.received_tli.with_label_values(&[] as &[&str]).set(tli);
+        }
+        for (gauge, column) in [
+            (&self.latest_end_lsn, "latest_end_lsn"),
+            (
+                &self.last_msg_receipt_age_seconds,
+                "last_msg_receipt_age_seconds",
+            ),
+        ] {
+            if let Ok(Some(value)) = row.try_get::<Option<f64>, _>(column) {
+                gauge.with_label_values(&[] as &[&str]).set(value);
+            }
+        }
+
+        debug!(status = ?status, "collected pg_stat_wal_receiver metric");
+    }
+}
+
+/// Returns whether a `pg_stat_wal_receiver.status` value means WAL is being streamed.
+#[must_use]
+fn is_streaming(status: Option<&str>) -> bool {
+    status == Some("streaming")
+}
+
+impl Collector for WalReceiverCollector {
+    fn name(&self) -> &'static str {
+        "wal_receiver"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "wal_receiver")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.status.clone()))?;
+        registry.register(Box::new(self.latest_end_lsn.clone()))?;
+        registry.register(Box::new(self.received_tli.clone()))?;
+        registry.register(Box::new(self.last_msg_receipt_age_seconds.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector = "wal_receiver", otel.kind = "internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let query_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement = "SELECT status, received_tli, latest_end_lsn, last_msg_receipt_time FROM pg_stat_wal_receiver",
+                db.sql.table = "pg_stat_wal_receiver"
+            );
+
+            let row = sqlx::query(WAL_RECEIVER_QUERY)
+                .fetch_optional(&mut *acquire_connection(pool).await?)
+                .instrument(query_span)
+                .await?;
+
+            self.reset_all();
+
+            match row {
+                Some(row) => self.apply_row(&row),
+                // A standby without a running WAL receiver is not streaming; a primary
+                // has no receiver at all and exports nothing.
+                None if get_in_recovery() => {
+                    self.status.with_label_values(&[] as &[&str]).set(0);
+                    debug!("standby has no running WAL receiver");
+                }
+                None => debug!("no WAL receiver (primary); skipping pg_stat_wal_receiver metrics"),
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collector_name_is_wal_receiver() {
+        assert_eq!(WalReceiverCollector::new().name(), "wal_receiver");
+    }
+
+    #[test]
+    fn only_streaming_status_counts_as_streaming() {
+        assert!(is_streaming(Some("streaming")));
+        assert!(!is_streaming(Some("waiting")));
+        assert!(!is_streaming(Some("catchup")));
+        assert!(!is_streaming(None));
+    }
+
+    #[test]
+    fn register_metrics_succeeds_with_unique_names() {
+        let registry = Registry::new();
+        assert!(
+            WalReceiverCollector::new()
+                .register_metrics(&registry)
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use crate::collectors::Collector;
+use crate::collectors::util::acquire_connection;
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{IntGauge, Opts, Registry};
+use sqlx::{PgPool, Row};
+use tracing::{debug, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// Tracks WAL sender saturation against `max_wal_senders`:
+/// - `pg_wal_senders_active` (`IntGauge`): walsender processes in `pg_stat_replication`
+/// - `pg_wal_senders_max` (`IntGauge`): `current_setting('max_wal_senders')`
+///
+/// Streaming replicas and base backups (`pg_basebackup`) each hold a walsender, so once
+/// `active` reaches `max` new replicas and backups are refused. On a standby the count
+/// covers cascading walsenders only; its own WAL receiver (`pg_stat_wal_receiver`) does not
+/// use a `max_wal_senders` slot and is not counted.
+#[derive(Clone)]
+pub struct WalSendersCollector {
+    active: IntGauge, // pg_wal_senders_active
+    max: IntGauge,    // pg_wal_senders_max
+}
+
+impl Default for WalSendersCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WalSendersCollector {
+    /// Creates a new `WalSendersCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let active = IntGauge::with_opts(Opts::new(
+            "pg_wal_senders_active",
+            "Number of WAL sender processes currently running (replicas and base backups)",
+        ))
+        .expect("Failed to create pg_wal_senders_active metric");
+
+        let max = IntGauge::with_opts(Opts::new(
+            "pg_wal_senders_max",
+            "Maximum number of concurrent WAL sender processes (max_wal_senders)",
+        ))
+        .expect("Failed to create pg_wal_senders_max metric");
+
+        Self { active, max }
+    }
+}
+
+impl Collector for WalSendersCollector {
+    fn name(&self) -> &'static str {
+        "wal_senders"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "wal_senders")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.active.clone()))?;
+        registry.register(Box::new(self.max.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector="wal_senders", otel.kind="internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let query_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement = "SELECT COUNT(*) FROM pg_stat_replication, max_wal_senders",
+                db.sql.table = "pg_stat_replication"
+            );
+
+            let row = sqlx::query(
+                r"
+                SELECT
+                    (SELECT COUNT(*) FROM pg_stat_replication)::bigint AS active,
+                    current_setting('max_wal_senders')::bigint AS max
+                ",
+            )
+            .fetch_one(&mut *acquire_connection(pool).await?)
+            .instrument(query_span)
+            .await?;
+
+            let active: i64 = row.try_get("active").unwrap_or(0);
+            let max: i64 = row.try_get("max").unwrap_or(0);
+
+            self.active.set(active);
+            self.max.set(max);
+
+            debug!(active, max, "updated wal sender metrics");
+
+            Ok(())
+        })
+    }
+}
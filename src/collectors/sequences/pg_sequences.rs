@@ -1,5 +1,6 @@
 use crate::collectors::util::{
-    acquire_db_query_permit, get_default_database, get_excluded_databases, open_db_connection,
+    acquire_connection, acquire_db_query_permit, get_default_database, in_acquire_scope,
+    list_target_databases, open_db_connection,
 };
 use crate::collectors::{Collector, all_databases_failed};
 use anyhow::{Result, anyhow};
@@ -125,29 +126,7 @@ impl Collector for PgSequencesCollector {
     )]
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
         Box::pin(async move {
-            let excluded = get_excluded_databases().to_vec();
-            let db_list_span = info_span!(
-                "db.query",
-                otel.kind = "client",
-                db.system = "postgresql",
-                db.operation = "SELECT",
-                db.statement = "SELECT datname FROM pg_database WHERE datallowconn ...",
-                db.sql.table = "pg_database"
-            );
-            let dbs: Vec<String> = sqlx::query_scalar(
-                r"
-                SELECT datname
-                FROM pg_database
-                WHERE datallowconn
-                  AND NOT datistemplate
-                  AND NOT (datname = ANY($1))
-                ORDER BY datname
-                ",
-            )
-            .bind(&excluded)
-            .fetch_all(pool)
-            .instrument(db_list_span)
-            .await?;
+            let dbs = list_target_databases(pool).await?;
 
             let shared_pool = pool.clone();
             let default_db = get_default_database().map(std::string::ToString::to_string);
@@ -158,7 +137,7 @@ impl Collector for PgSequencesCollector {
                 let shared_pool = shared_pool.clone();
                 let default_db = default_db.clone();
 
-                tasks.spawn(async move {
+                tasks.spawn(in_acquire_scope(async move {
                     let use_shared = default_db.as_deref() == Some(datname.as_str());
 
                     let query_span = info_span!(
@@ -182,7 +161,7 @@ impl Collector for PgSequencesCollector {
 
                     let rows_res: anyhow::Result<Vec<PgRow>> = if use_shared {
                         sqlx::query(PG_SEQUENCES_QUERY)
-                            .fetch_all(&shared_pool)
+                            .fetch_all(&mut *acquire_connection(&shared_pool).await?)
                             .instrument(query_span)
                             .await
                             .map_err(Into::into)
@@ -204,7 +183,7 @@ impl Collector for PgSequencesCollector {
                         .iter()
                         .map(Self::sample_from_row)
                         .collect::<Vec<_>>())
-                });
+                }));
             }
 
             let mut all_samples = Vec::new();
@@ -299,6 +278,10 @@ mod tests {
     #[test]
     fn register_metrics_succeeds() {
         let registry = Registry::new();
-        assert!(PgSequencesCollector::new().register_metrics(&registry).is_ok());
+        assert!(
+            PgSequencesCollector::new()
+                .register_metrics(&registry)
+                .is_ok()
+        );
     }
 }
@@ -17,7 +17,10 @@
 //! collector skips cleanly (no error, no populated series) and logs a single
 //! warning that `PostgreSQL` 13+ is required.
 
-use crate::collectors::{Collector, util::get_pg_version};
+use crate::collectors::{
+    Collector,
+    util::{acquire_connection, resolve_server_version},
+};
 use anyhow::Result;
 use futures::future::BoxFuture;
 use prometheus::{IntGaugeVec, Opts, Registry};
@@ -30,7 +33,7 @@ use tracing::{debug, info_span, instrument, warn};
 use tracing_futures::Instrument as _;
 
 /// `pg_stat_slru` was introduced in `PostgreSQL` 13.
-const MIN_PG_STAT_SLRU_VERSION: i32 = 130_000;
+pub(crate) const MIN_PG_STAT_SLRU_VERSION: i32 = 130_000;
 
 /// Labels shared by every `pg_stat_slru` metric.
 const SLRU_LABELS: [&str; 1] = ["name"];
@@ -216,26 +219,17 @@ impl PgStatSlruCollector {
     }
 }
 
-/// Resolves the server version, preferring the cached value set at startup and
-/// falling back to a direct query.
-async fn resolve_server_version(pool: &PgPool) -> Result<i32> {
-    let cached = get_pg_version();
-    if cached > 0 {
-        return Ok(cached);
-    }
-
-    let row = sqlx::query("SELECT current_setting('server_version_num')::int AS v")
-        .fetch_one(pool)
-        .await?;
-    Ok(row.try_get::<i32, _>("v")?)
-}
-
 impl Collector for PgStatSlruCollector {
     fn name(&self) -> &'static str {
         "pg_stat_slru"
     }
 
-    #[instrument(skip(self, registry), level = "info", err, fields(collector = "pg_stat_slru"))]
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "pg_stat_slru")
+    )]
     fn register_metrics(&self, registry: &Registry) -> Result<()> {
         registry.register(Box::new(self.blks_zeroed.clone()))?;
         registry.register(Box::new(self.blks_hit.clone()))?;
@@ -279,7 +273,7 @@ impl Collector for PgStatSlruCollector {
             );
 
             let rows = sqlx::query(PG_STAT_SLRU_QUERY)
-                .fetch_all(pool)
+                .fetch_all(&mut *acquire_connection(pool).await?)
                 .instrument(query_span)
                 .await?;
 
@@ -350,6 +344,10 @@ mod tests {
     #[test]
     fn register_metrics_succeeds_with_unique_names() {
         let registry = Registry::new();
-        assert!(PgStatSlruCollector::new().register_metrics(&registry).is_ok());
+        assert!(
+            PgStatSlruCollector::new()
+                .register_metrics(&registry)
+                .is_ok()
+        );
     }
 }
@@ -1,17 +1,20 @@
-use crate::collectors::Collector;
+use crate::collectors::{Collector, util::collect_subs};
 use anyhow::Result;
 use futures::future::BoxFuture;
-use futures::stream::{FuturesUnordered, StreamExt};
 use prometheus::Registry;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tracing::{debug, info_span, instrument, warn};
-use tracing_futures::Instrument as _;
 
 // Sub-collectors under the "stat" umbrella.
+// Cluster-wide I/O from `pg_stat_io` (PostgreSQL 16+) is the separate `stat_io` collector,
+// not a sub-collector here, so enabling both never registers its metrics twice.
 pub mod user_tables;
 use user_tables::StatUserTablesCollector;
 
+pub mod user_functions;
+use user_functions::StatUserFunctionsCollector;
+
 #[derive(Clone, Default)]
 pub struct StatCollector {
     subs: Vec<Arc<dyn Collector + Send + Sync>>,
@@ -20,8 +23,28 @@ pub struct StatCollector {
 impl StatCollector {
     #[must_use]
     pub fn new() -> Self {
+        Self::with_seconds_ago(false)
+    }
+
+    /// Build the stat collector, optionally exporting the server-computed `*_seconds_ago`
+    /// gauges (`--stat.seconds-ago`).
+    #[must_use]
+    pub fn with_seconds_ago(seconds_ago: bool) -> Self {
+        Self::with_options(seconds_ago, false)
+    }
+
+    /// Build the stat collector with the `--stat.*` options: the server-computed
+    /// `*_seconds_ago` gauges and system catalog tables (`--stat.include-system`).
+    #[must_use]
+    pub fn with_options(seconds_ago: bool, include_system: bool) -> Self {
         Self {
-            subs: vec![Arc::new(StatUserTablesCollector::new())],
+            subs: vec![
+                Arc::new(StatUserTablesCollector::with_options(
+                    seconds_ago,
+                    include_system,
+                )),
+                Arc::new(StatUserFunctionsCollector::new()),
+            ],
         }
     }
 }
@@ -55,21 +78,7 @@ impl Collector for StatCollector {
         fields(collector = "stat", otel.kind = "internal")
     )]
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
-        Box::pin(async move {
-            // Collect sub-collectors concurrently (unordered join)
-            let mut tasks = FuturesUnordered::new();
-
-            for sub in &self.subs {
-                let span = info_span!("collector.collect", sub_collector = %sub.name(), otel.kind="internal");
-                tasks.push(sub.collect(pool).instrument(span));
-            }
-
-            while let Some(res) = tasks.next().await {
-                res?;
-            }
-
-            Ok(())
-        })
+        Box::pin(async move { collect_subs(&self.subs, pool).await })
     }
 
     fn enabled_by_default(&self) -> bool {
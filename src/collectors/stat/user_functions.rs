@@ -0,0 +1,290 @@
+use crate::collectors::util::{
+    acquire_connection, acquire_db_query_permit, datname_label, get_default_database,
+    in_acquire_scope, list_target_databases, open_db_connection,
+};
+use crate::collectors::{Collector, all_databases_failed};
+use anyhow::{Result, anyhow};
+use futures::future::BoxFuture;
+use prometheus::{GaugeVec, IntGaugeVec, Opts, Registry};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use tokio::task::JoinSet;
+use tracing::{debug, error, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+const USER_FUNCTION_LABELS: [&str; 3] = ["datname", "schemaname", "funcname"];
+
+/// Collector for `pg_stat_user_functions`
+///
+/// **What it measures:**
+/// With `track_functions = pl` (or `all`), `PostgreSQL` counts calls of every tracked
+/// function and the time spent in it, with and without the functions it calls. This
+/// is where hot or slow PL/pgSQL shows up, which `pg_stat_statements` only reports as
+/// the statement that called it.
+///
+/// **Key metrics:**
+/// - `pg_stat_user_functions_calls{datname,schemaname,funcname}`
+/// - `pg_stat_user_functions_total_time_seconds{datname,schemaname,funcname}`: time in
+///   the function including the functions it calls
+/// - `pg_stat_user_functions_self_time_seconds{datname,schemaname,funcname}`: time in the
+///   function itself
+///
+/// Overloads of a function share a name and are summed into one series.
+///
+/// With `track_functions = none` (the default) the view is empty and nothing is exported.
+///
+/// **Multi-database:**
+/// `pg_stat_user_functions` only covers the current database, so this collector iterates
+/// every connectable, non-excluded database like the other per-database collectors.
+#[derive(Clone)]
+pub struct StatUserFunctionsCollector {
+    calls: IntGaugeVec,
+    total_time_seconds: GaugeVec,
+    self_time_seconds: GaugeVec,
+}
+
+impl Default for StatUserFunctionsCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One row per function name of the current database; times are converted from
+/// milliseconds.
+const STAT_USER_FUNCTIONS_QUERY: &str = r"
+    SELECT
+        current_database() AS datname,
+        schemaname::text AS schemaname,
+        funcname::text AS funcname,
+        COALESCE(SUM(calls), 0)::bigint AS calls,
+        (COALESCE(SUM(total_time), 0) / 1000.0)::double precision AS total_time_seconds,
+        (COALESCE(SUM(self_time), 0) / 1000.0)::double precision AS self_time_seconds
+    FROM pg_stat_user_functions
+    GROUP BY schemaname, funcname
+    ";
+
+/// Statistics of one function name in one database.
+struct UserFunctionSample {
+    datname: String,
+    schemaname: String,
+    funcname: String,
+    calls: i64,
+    total_time_seconds: f64,
+    self_time_seconds: f64,
+}
+
+impl StatUserFunctionsCollector {
+    /// Creates a new `StatUserFunctionsCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        Self {
+            calls: IntGaugeVec::new(
+                Opts::new(
+                    "pg_stat_user_functions_calls",
+                    "Number of times the function has been called (requires track_functions)",
+                ),
+                &USER_FUNCTION_LABELS,
+            )
+            .expect("Failed to create pg_stat_user_functions_calls"),
+            total_time_seconds: GaugeVec::new(
+                Opts::new(
+                    "pg_stat_user_functions_total_time_seconds",
+                    "Time spent in the function and all functions it calls, in seconds",
+                ),
+                &USER_FUNCTION_LABELS,
+            )
+            .expect("Failed to create pg_stat_user_functions_total_time_seconds"),
+            self_time_seconds: GaugeVec::new(
+                Opts::new(
+                    "pg_stat_user_functions_self_time_seconds",
+                    "Time spent in the function itself, excluding functions it calls, in seconds",
+                ),
+                &USER_FUNCTION_LABELS,
+            )
+            .expect("Failed to create pg_stat_user_functions_self_time_seconds"),
+        }
+    }
+
+    fn sample_from_row(row: &PgRow) -> Result<UserFunctionSample> {
+        Ok(UserFunctionSample {
+            datname: datname_label(row.try_get("datname")?),
+            schemaname: row.try_get("schemaname")?,
+            funcname: row.try_get("funcname")?,
+            calls: row.try_get("calls").unwrap_or(0),
+            total_time_seconds: row.try_get("total_time_seconds").unwrap_or(0.0),
+            self_time_seconds: row.try_get("self_time_seconds").unwrap_or(0.0),
+        })
+    }
+}
+
+impl Collector for StatUserFunctionsCollector {
+    fn name(&self) -> &'static str {
+        "stat_user_functions"
+    }
+
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.calls.clone()))?;
+        registry.register(Box::new(self.total_time_seconds.clone()))?;
+        registry.register(Box::new(self.self_time_seconds.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector = "stat_user_functions", otel.kind = "internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            // 1) Discover connectable, non-excluded databases via the shared pool.
+            let dbs = list_target_databases(pool).await?;
+
+            let shared_pool = pool.clone();
+            let default_db = get_default_database().map(std::string::ToString::to_string);
+
+            // 2) One task per DB. The default DB reuses the shared pool; every other database
+            // must pass through the global per-database connection limiter.
+            let mut tasks: JoinSet<Result<Vec<UserFunctionSample>>> = JoinSet::new();
+
+            let num_dbs = dbs.len();
+            for datname in dbs {
+                let shared_pool = shared_pool.clone();
+                let default_db = default_db.clone();
+
+                tasks.spawn(in_acquire_scope(async move {
+                    let use_shared = default_db.as_deref() == Some(datname.as_str());
+
+                    let query_span = info_span!(
+                        "db.query",
+                        otel.kind = "client",
+                        db.system = "postgresql",
+                        db.operation = "SELECT",
+                        db.statement = "SELECT ... FROM pg_stat_user_functions",
+                        db.sql.table = "pg_stat_user_functions",
+                        datname = %datname,
+                        reuse_pool = use_shared
+                    );
+
+                    let db_query_permit = if use_shared {
+                        None
+                    } else {
+                        Some(acquire_db_query_permit().await.map_err(|e| {
+                            anyhow!(
+                                "stat_user_functions: failed to acquire database query permit: {e}"
+                            )
+                        })?)
+                    };
+
+                    let rows: Vec<PgRow> = if use_shared {
+                        sqlx::query(STAT_USER_FUNCTIONS_QUERY)
+                            .fetch_all(&mut *acquire_connection(&shared_pool).await?)
+                            .instrument(query_span)
+                            .await?
+                    } else {
+                        let Some(permit) = db_query_permit.as_ref() else {
+                            return Err(anyhow!(
+                                "stat_user_functions: missing database query permit"
+                            ));
+                        };
+                        let mut conn = open_db_connection(&datname, permit).await?;
+                        sqlx::query(STAT_USER_FUNCTIONS_QUERY)
+                            .fetch_all(&mut conn)
+                            .instrument(query_span)
+                            .await?
+                    };
+
+                    rows.iter().map(Self::sample_from_row).collect()
+                }));
+            }
+
+            let mut all_samples = Vec::new();
+            let mut failures = Vec::new();
+            let mut failed_db_count = 0;
+            while let Some(joined) = tasks.join_next().await {
+                match joined {
+                    Ok(Ok(samples)) => all_samples.extend(samples),
+                    Ok(Err(e)) => {
+                        error!(error=?e, "stat_user_functions: task returned error");
+                        failures.push(e.to_string());
+                        failed_db_count += 1;
+                    }
+                    Err(e) => {
+                        error!(error=?e, "stat_user_functions: task join error");
+                        failures.push(e.to_string());
+                        failed_db_count += 1;
+                    }
+                }
+            }
+
+            if all_databases_failed(num_dbs, failed_db_count) {
+                return Err(anyhow!(
+                    "stat_user_functions collection failed for ALL {failed_db_count} database task(s): {}",
+                    failures.join("; ")
+                ));
+            }
+
+            if !failures.is_empty() {
+                error!(
+                    failed_databases = failed_db_count,
+                    errors = %failures.join("; "),
+                    "stat_user_functions: continuing with partial snapshot after per-database failures"
+                );
+            }
+
+            self.calls.reset();
+            self.total_time_seconds.reset();
+            self.self_time_seconds.reset();
+
+            for sample in &all_samples {
+                let labels = [
+                    sample.datname.as_str(),
+                    sample.schemaname.as_str(),
+                    sample.funcname.as_str(),
+                ];
+                self.calls.with_label_values(&labels).set(sample.calls);
+                self.total_time_seconds
+                    .with_label_values(&labels)
+                    .set(sample.total_time_seconds);
+                self.self_time_seconds
+                    .with_label_values(&labels)
+                    .set(sample.self_time_seconds);
+            }
+
+            debug!(
+                functions = all_samples.len(),
+                "updated pg_stat_user_functions metrics"
+            );
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_functions_collector_name() {
+        let collector = StatUserFunctionsCollector::new();
+        assert_eq!(collector.name(), "stat_user_functions");
+    }
+
+    #[test]
+    fn test_user_functions_collector_registers() {
+        let registry = Registry::new();
+        let collector = StatUserFunctionsCollector::new();
+        assert!(collector.register_metrics(&registry).is_ok());
+    }
+
+    #[test]
+    fn test_user_functions_query_sums_overloads() {
+        assert!(STAT_USER_FUNCTIONS_QUERY.contains("GROUP BY schemaname, funcname"));
+        assert!(STAT_USER_FUNCTIONS_QUERY.contains("SUM(self_time)"));
+    }
+}
@@ -1,17 +1,35 @@
 use crate::collectors::util::{
-    acquire_db_query_permit, get_default_database, get_excluded_databases, open_db_connection,
+    acquire_connection, acquire_db_query_permit, datname_label, get_default_database,
+    in_acquire_scope, list_target_databases, open_db_connection,
 };
 use crate::collectors::{Collector, all_databases_failed, i64_to_f64};
 use anyhow::{Result, anyhow};
 use futures::future::BoxFuture;
-use prometheus::{GaugeVec, IntGaugeVec, Opts, Registry};
+use once_cell::sync::Lazy;
+use prometheus::{GaugeVec, IntCounterVec, IntGaugeVec, Opts, Registry};
 use sqlx::{PgPool, Row, postgres::PgRow};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 use tokio::task::JoinSet;
 use tracing::{debug, error, info_span, instrument};
 use tracing_futures::Instrument as _;
 
 /// Mirrors `postgres_exporter`'s `pg_stat_user_tables` collector:
 /// Metrics are exported as `pg_stat_user_tables`_* with labels {`datname`, schemaname, relname}.
+///
+/// Maintenance times are exported as raw epoch `*_timestamp_seconds` gauges so ages can be
+/// computed in `PromQL` (`time() - ..._timestamp_seconds`) without scrape-timing drift. The
+/// server-computed `*_seconds_ago` gauges are only exported with `--stat.seconds-ago`.
+///
+/// `pg_autovacuum_launches_total{datname}` is derived across scrapes: `PostgreSQL` does not
+/// count autovacuum launches, so the collector remembers each table's `autovacuum_count`
+/// and adds the increases since the previous scrape to the table's database.
+///
+/// `pg_stat_user_tables_dead_tuples_reclaimed` is derived the same way, per table: when
+/// `autovacuum_count` increased since the previous scrape, it is set to the drop in
+/// `n_dead_tup` over that interval and kept until the next autovacuum.
 #[derive(Clone)]
 pub struct StatUserTablesCollector {
     // Scan counts (cumulative)
@@ -31,12 +49,21 @@ pub struct StatUserTablesCollector {
     n_dead_tup: IntGaugeVec,
     n_mod_since_analyze: IntGaugeVec,
 
+    // Planner row estimate (pg_class.reltuples), absent until first vacuum/analyze
+    reltuples_estimate: GaugeVec,
+
     // Last maintenance times as epoch seconds (gauges)
     last_vacuum: IntGaugeVec,
     last_autovacuum: IntGaugeVec,
     last_analyze: IntGaugeVec,
     last_autoanalyze: IntGaugeVec,
 
+    // Last maintenance times as full-precision epoch seconds (absent when never run)
+    last_vacuum_timestamp: GaugeVec,
+    last_autovacuum_timestamp: GaugeVec,
+    last_analyze_timestamp: GaugeVec,
+    last_autoanalyze_timestamp: GaugeVec,
+
     // Maintenance counters (cumulative)
     vacuum_count: IntGaugeVec,
     autovacuum_count: IntGaugeVec,
@@ -61,21 +88,38 @@ pub struct StatUserTablesCollector {
     bloat_ratio: GaugeVec,
     dead_tuple_size_bytes: GaugeVec,
 
+    // HOT update efficiency (n_tup_hot_upd / n_tup_upd, 0 when no updates)
+    hot_update_ratio: GaugeVec,
+
     // Autovacuum-specific metrics (Phase 1 enhancement)
     // These metrics enable predictive alerting and prevent wraparound disasters
-    
-    // Time-based metrics (easier for alerting than epoch timestamps)
-    last_autovacuum_seconds_ago: GaugeVec,   // Alert when >86400 (24h) - table not being maintained
-    last_autoanalyze_seconds_ago: GaugeVec,  // Track analyze freshness
-    never_autovacuumed: IntGaugeVec,         // 1 when the table has never been autovacuumed
-    never_autoanalyzed: IntGaugeVec,         // 1 when the table has never been autoanalyzed
+
+    // Server-computed ages, only exported with --stat.seconds-ago
+    seconds_ago: bool,
+    // Read pg_stat_all_tables (system catalogs and TOAST included), --stat.include-system
+    include_system: bool,
+    last_autovacuum_seconds_ago: GaugeVec, // Alert when >86400 (24h) - table not being maintained
+    last_autoanalyze_seconds_ago: GaugeVec, // Track analyze freshness
+    never_autovacuumed: IntGaugeVec,       // 1 when the table has never been autovacuumed
+    never_autoanalyzed: IntGaugeVec,       // 1 when the table has never been autoanalyzed
 
     // GOLD METRICS - Predict autovacuum triggers BEFORE they happen
     // Ratio: n_dead_tup / (threshold + scale_factor * n_live_tup)
     // Values: 0.0=clean, 0.8=warning, 1.0=trigger point, >1.0=overdue
     // Use these to prevent transaction ID wraparound emergencies!
-    autovacuum_threshold_ratio: GaugeVec,    // THE critical metric for autovacuum monitoring
-    autoanalyze_threshold_ratio: GaugeVec,   // Predict when autoanalyze will trigger
+    autovacuum_threshold_ratio: GaugeVec, // THE critical metric for autovacuum monitoring
+    autoanalyze_threshold_ratio: GaugeVec, // Predict when autoanalyze will trigger
+
+    // Per-database count of tables whose autovacuum_threshold_ratio is >= 1.0 (due now)
+    vacuum_due_count: IntGaugeVec,
+
+    // Autovacuum runs per database, derived from per-table autovacuum_count deltas across scrapes
+    autovacuum_launches: IntCounterVec,
+    autovacuum_counts: Arc<Mutex<HashMap<TableKey, i64>>>,
+
+    // Dead tuples removed by the last autovacuum seen between two scrapes, per table
+    dead_tuples_reclaimed: IntGaugeVec,
+    dead_tuple_samples: Arc<Mutex<HashMap<TableKey, DeadTupleSample>>>,
 }
 
 impl Default for StatUserTablesCollector {
@@ -91,8 +135,32 @@ impl StatUserTablesCollector {
     ///
     /// Panics if metric creation fails (should never happen with valid metric names)
     #[must_use]
-    #[allow(clippy::expect_used)]
     pub fn new() -> Self {
+        Self::with_seconds_ago(false)
+    }
+
+    /// Creates a `UserTablesCollector`, optionally exporting the legacy server-computed
+    /// `*_seconds_ago` gauges next to the `*_timestamp_seconds` ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn with_seconds_ago(seconds_ago: bool) -> Self {
+        Self::with_options(seconds_ago, false)
+    }
+
+    /// Creates a `UserTablesCollector` with the `--stat.*` options. With `include_system`
+    /// the stats are read from `pg_stat_all_tables`, so system catalogs and TOAST tables are
+    /// exported too (same metric names, `schemaname` tells them apart).
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn with_options(seconds_ago: bool, include_system: bool) -> Self {
         Self {
             seq_scan: int_metric("pg_stat_user_tables_seq_scan", "Number of sequential scans initiated on this table"),
             seq_tup_read: int_metric("pg_stat_user_tables_seq_tup_read", "Number of live rows fetched by sequential scans"),
@@ -105,10 +173,15 @@ impl StatUserTablesCollector {
             n_live_tup: int_metric("pg_stat_user_tables_n_live_tup", "Estimated number of live rows"),
             n_dead_tup: int_metric("pg_stat_user_tables_n_dead_tup", "Estimated number of dead rows"),
             n_mod_since_analyze: int_metric("pg_stat_user_tables_n_mod_since_analyze", "Estimated number of rows changed since last analyze"),
+            reltuples_estimate: gauge_metric("pg_stat_user_tables_reltuples_estimate", "Planner row estimate from pg_class.reltuples"),
             last_vacuum: int_metric("pg_stat_user_tables_last_vacuum", "Last manual vacuum time (epoch seconds)"),
             last_autovacuum: int_metric("pg_stat_user_tables_last_autovacuum", "Last autovacuum time (epoch seconds)"),
             last_analyze: int_metric("pg_stat_user_tables_last_analyze", "Last manual analyze time (epoch seconds)"),
             last_autoanalyze: int_metric("pg_stat_user_tables_last_autoanalyze", "Last autoanalyze time (epoch seconds)"),
+            last_vacuum_timestamp: gauge_metric("pg_stat_user_tables_last_vacuum_timestamp_seconds", "Last manual vacuum time as a Unix timestamp with sub-second precision"),
+            last_autovacuum_timestamp: gauge_metric("pg_stat_user_tables_last_autovacuum_timestamp_seconds", "Last autovacuum time as a Unix timestamp with sub-second precision"),
+            last_analyze_timestamp: gauge_metric("pg_stat_user_tables_last_analyze_timestamp_seconds", "Last manual analyze time as a Unix timestamp with sub-second precision"),
+            last_autoanalyze_timestamp: gauge_metric("pg_stat_user_tables_last_autoanalyze_timestamp_seconds", "Last autoanalyze time as a Unix timestamp with sub-second precision"),
             vacuum_count: int_metric("pg_stat_user_tables_vacuum_count", "Number of times manually vacuumed"),
             autovacuum_count: int_metric("pg_stat_user_tables_autovacuum_count", "Number of times vacuumed by autovacuum"),
             analyze_count: int_metric("pg_stat_user_tables_analyze_count", "Number of times manually analyzed"),
@@ -117,12 +190,28 @@ impl StatUserTablesCollector {
             table_size_bytes: int_metric("pg_stat_user_tables_table_size_bytes", "Total disk space used by this table, in bytes"),
             bloat_ratio: gauge_metric("pg_stat_user_tables_bloat_ratio", "Estimated bloat ratio (dead tuples / total tuples)"),
             dead_tuple_size_bytes: gauge_metric("pg_stat_user_tables_dead_tuple_size_bytes", "Estimated disk space used by dead tuples"),
+            hot_update_ratio: gauge_metric("pg_stat_user_tables_hot_update_ratio", "Fraction of row updates that were HOT (n_tup_hot_upd / n_tup_upd, 0 when no updates)"),
+            seconds_ago,
+            include_system,
             last_autovacuum_seconds_ago: gauge_metric("pg_stat_user_tables_last_autovacuum_seconds_ago", "Seconds since last autovacuum (alert when > 86400)"),
             last_autoanalyze_seconds_ago: gauge_metric("pg_stat_user_tables_last_autoanalyze_seconds_ago", "Seconds since last autoanalyze (alert when > 86400)"),
             never_autovacuumed: int_metric("pg_stat_user_tables_never_autovacuumed", "Whether the table has never been autovacuumed (1 = never autovacuumed)"),
             never_autoanalyzed: int_metric("pg_stat_user_tables_never_autoanalyzed", "Whether the table has never been autoanalyzed (1 = never autoanalyzed)"),
             autovacuum_threshold_ratio: gauge_metric("pg_stat_user_tables_autovacuum_threshold_ratio", "Ratio of dead tuples to autovacuum threshold (0.0 clean, 1.0 trigger, >1.0 overdue)"),
             autoanalyze_threshold_ratio: gauge_metric("pg_stat_user_tables_autoanalyze_threshold_ratio", "Ratio of modified tuples to autoanalyze threshold (0.0 clean, 1.0 trigger, >1.0 overdue)"),
+            vacuum_due_count: IntGaugeVec::new(
+                Opts::new("pg_stat_user_tables_vacuum_due_count", "Number of tables at or past their autovacuum trigger point (autovacuum_threshold_ratio >= 1.0)"),
+                &["datname"],
+            )
+            .expect("pg_stat_user_tables_vacuum_due_count metric"),
+            autovacuum_launches: IntCounterVec::new(
+                Opts::new("pg_autovacuum_launches_total", "Autovacuum runs on tables of this database, derived from increases of each table's pg_stat_user_tables.autovacuum_count between scrapes"),
+                &["datname"],
+            )
+            .expect("pg_autovacuum_launches_total metric"),
+            autovacuum_counts: Arc::new(Mutex::new(HashMap::new())),
+            dead_tuples_reclaimed: int_metric("pg_stat_user_tables_dead_tuples_reclaimed", "Drop in n_dead_tup across the scrape interval in which autovacuum last ran on this table (0 = it ran but reclaimed nothing)"),
+            dead_tuple_samples: Arc::new(Mutex::new(HashMap::new())),
             heap_blks_read: int_metric("pg_stat_user_tables_heap_blks_read_total", "Number of disk blocks read from this table"),
             heap_blks_hit: int_metric("pg_stat_user_tables_heap_blks_hit_total", "Number of buffer hits in this table"),
             idx_blks_read: int_metric("pg_stat_user_tables_idx_blks_read_total", "Number of disk blocks read from all indexes on this table"),
@@ -146,10 +235,15 @@ impl StatUserTablesCollector {
         self.n_live_tup.reset();
         self.n_dead_tup.reset();
         self.n_mod_since_analyze.reset();
+        self.reltuples_estimate.reset();
         self.last_vacuum.reset();
         self.last_autovacuum.reset();
         self.last_analyze.reset();
         self.last_autoanalyze.reset();
+        self.last_vacuum_timestamp.reset();
+        self.last_autovacuum_timestamp.reset();
+        self.last_analyze_timestamp.reset();
+        self.last_autoanalyze_timestamp.reset();
         self.vacuum_count.reset();
         self.autovacuum_count.reset();
         self.analyze_count.reset();
@@ -158,12 +252,15 @@ impl StatUserTablesCollector {
         self.table_size_bytes.reset();
         self.bloat_ratio.reset();
         self.dead_tuple_size_bytes.reset();
+        self.hot_update_ratio.reset();
         self.last_autovacuum_seconds_ago.reset();
         self.last_autoanalyze_seconds_ago.reset();
         self.never_autovacuumed.reset();
         self.never_autoanalyzed.reset();
         self.autovacuum_threshold_ratio.reset();
         self.autoanalyze_threshold_ratio.reset();
+        self.vacuum_due_count.reset();
+        self.dead_tuples_reclaimed.reset();
         self.heap_blks_read.reset();
         self.heap_blks_hit.reset();
         self.idx_blks_read.reset();
@@ -173,10 +270,121 @@ impl StatUserTablesCollector {
         self.tidx_blks_read.reset();
         self.tidx_blks_hit.reset();
     }
+
+    /// Add each table's `autovacuum_count` increase since the previous scrape to its
+    /// database's `pg_autovacuum_launches_total`. A table seen for the first time only
+    /// records a baseline, and tables missing from `samples` (dropped, or their database
+    /// failed this scrape) are forgotten, so neither shows up as a spike.
+    fn record_autovacuum_launches(&self, databases: &[String], samples: &[UserTableSample]) {
+        let mut previous = match self.autovacuum_counts.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        // Every scraped database gets a series, even before its first autovacuum
+        for datname in databases {
+            self.autovacuum_launches
+                .with_label_values(&[datname.as_str()])
+                .inc_by(0);
+        }
+
+        let mut current = HashMap::with_capacity(samples.len());
+        for sample in samples {
+            let key = (
+                sample.datname.clone(),
+                sample.schemaname.clone(),
+                sample.relname.clone(),
+            );
+            let launches =
+                autovacuum_launch_delta(previous.get(&key).copied(), sample.autovacuum_count);
+            self.autovacuum_launches
+                .with_label_values(&[sample.datname.as_str()])
+                .inc_by(launches);
+            current.insert(key, sample.autovacuum_count);
+        }
+
+        *previous = current;
+    }
+}
+
+impl StatUserTablesCollector {
+    /// Set `pg_stat_user_tables_dead_tuples_reclaimed` for every table autovacuumed since
+    /// the previous scrape and carry the last value of the others. Tables not in `samples`
+    /// (dropped, or their database failed this scrape) start from a new baseline.
+    fn record_dead_tuples_reclaimed(&self, samples: &[UserTableSample]) {
+        let mut previous = match self.dead_tuple_samples.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        let mut current = HashMap::with_capacity(samples.len());
+        for sample in samples {
+            let key = (
+                sample.datname.clone(),
+                sample.schemaname.clone(),
+                sample.relname.clone(),
+            );
+            let mut next = DeadTupleSample {
+                autovacuum_count: sample.autovacuum_count,
+                n_dead_tup: sample.n_dead_tup,
+                reclaimed: None,
+            };
+            if let Some(before) = previous.get(&key) {
+                next.reclaimed = dead_tuples_reclaimed(before, &next).or(before.reclaimed);
+            }
+            if let Some(reclaimed) = next.reclaimed {
+                self.dead_tuples_reclaimed
+                    .with_label_values(&[&sample.datname, &sample.schemaname, &sample.relname])
+                    .set(reclaimed);
+            }
+            current.insert(key, next);
+        }
+
+        *previous = current;
+    }
+}
+
+/// `(datname, schemaname, relname)`
+type TableKey = (String, String, String);
+
+/// What the previous scrape saw of a table, to spot autovacuum runs in between.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct DeadTupleSample {
+    autovacuum_count: i64,
+    n_dead_tup: i64,
+    /// Dead tuples reclaimed by the last autovacuum seen, `None` until one is seen.
+    reclaimed: Option<i64>,
+}
+
+/// Dead tuples that disappeared between two samples of a table during which autovacuum
+/// ran, or `None` when it did not run (or the statistics were reset). Tuples that died
+/// after the run within the same interval are netted out, so this is a lower bound.
+fn dead_tuples_reclaimed(previous: &DeadTupleSample, current: &DeadTupleSample) -> Option<i64> {
+    (current.autovacuum_count > previous.autovacuum_count)
+        .then(|| (previous.n_dead_tup - current.n_dead_tup).max(0))
+}
+
+/// Autovacuum runs between two samples of a table's `autovacuum_count`.
+///
+/// A lower count means the table's statistics were reset; the new count is then the best
+/// estimate of runs since, mirroring Prometheus counter-reset handling.
+fn autovacuum_launch_delta(previous: Option<i64>, current: i64) -> u64 {
+    let Some(previous) = previous else {
+        return 0;
+    };
+    let delta = if current >= previous {
+        current - previous
+    } else {
+        current
+    };
+    u64::try_from(delta).unwrap_or(0)
 }
 
 const USER_TABLE_LABELS: [&str; 3] = ["datname", "schemaname", "relname"];
 
+/// `autovacuum_threshold_ratio` at which autovacuum considers a table due.
+const VACUUM_DUE_RATIO: f64 = 1.0;
+
 const STAT_USER_TABLES_QUERY: &str = r"
     SELECT
         current_database() AS datname,
@@ -193,10 +401,15 @@ const STAT_USER_TABLES_QUERY: &str = r"
         s.n_live_tup::bigint,
         s.n_dead_tup::bigint,
         s.n_mod_since_analyze::bigint,
+        CASE WHEN c.reltuples >= 0 THEN c.reltuples::double precision END AS reltuples_estimate,
         COALESCE(EXTRACT(EPOCH FROM s.last_vacuum)::bigint, 0)       AS last_vacuum_epoch,
         COALESCE(EXTRACT(EPOCH FROM s.last_autovacuum)::bigint, 0)  AS last_autovacuum_epoch,
         COALESCE(EXTRACT(EPOCH FROM s.last_analyze)::bigint, 0)     AS last_analyze_epoch,
         COALESCE(EXTRACT(EPOCH FROM s.last_autoanalyze)::bigint, 0) AS last_autoanalyze_epoch,
+        EXTRACT(EPOCH FROM s.last_vacuum)::double precision       AS last_vacuum_timestamp,
+        EXTRACT(EPOCH FROM s.last_autovacuum)::double precision   AS last_autovacuum_timestamp,
+        EXTRACT(EPOCH FROM s.last_analyze)::double precision      AS last_analyze_timestamp,
+        EXTRACT(EPOCH FROM s.last_autoanalyze)::double precision  AS last_autoanalyze_timestamp,
         s.vacuum_count::bigint,
         s.autovacuum_count::bigint,
         s.analyze_count::bigint,
@@ -266,6 +479,17 @@ const STAT_USER_TABLES_QUERY: &str = r"
     LEFT JOIN pg_statio_user_tables io ON io.relid = s.relid
     ";
 
+/// [`STAT_USER_TABLES_QUERY`] over `pg_stat_all_tables`/`pg_statio_all_tables`, used with
+/// `--stat.include-system` to also expose system catalog and TOAST table churn.
+static STAT_ALL_TABLES_QUERY: Lazy<String> = Lazy::new(|| {
+    STAT_USER_TABLES_QUERY
+        .replace("FROM pg_stat_user_tables s", "FROM pg_stat_all_tables s")
+        .replace(
+            "JOIN pg_statio_user_tables io",
+            "JOIN pg_statio_all_tables io",
+        )
+});
+
 #[derive(Clone, Debug)]
 struct UserTableSample {
     datname: String,
@@ -282,10 +506,15 @@ struct UserTableSample {
     n_live_tup: i64,
     n_dead_tup: i64,
     n_mod_since_analyze: i64,
+    reltuples_estimate: Option<f64>,
     last_vacuum_epoch: i64,
     last_autovacuum_epoch: i64,
     last_analyze_epoch: i64,
     last_autoanalyze_epoch: i64,
+    last_vacuum_timestamp: Option<f64>,
+    last_autovacuum_timestamp: Option<f64>,
+    last_analyze_timestamp: Option<f64>,
+    last_autoanalyze_timestamp: Option<f64>,
     vacuum_count: i64,
     autovacuum_count: i64,
     analyze_count: i64,
@@ -308,16 +537,34 @@ struct UserTableSample {
     tidx_blks_hit: i64,
 }
 
+/// Fraction of updates that were heap-only (HOT). Persistently low values on a
+/// frequently-updated table point at fillfactor tuning or an index on a hot column.
+fn hot_update_ratio(n_tup_hot_upd: i64, n_tup_upd: i64) -> f64 {
+    if n_tup_upd > 0 {
+        (i64_to_f64(n_tup_hot_upd) / i64_to_f64(n_tup_upd)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+/// Number of tables that autovacuum should be processing right now. A count that
+/// keeps growing means the workers cannot keep up with the dead-tuple churn.
+fn vacuum_due_count(samples: &[UserTableSample]) -> i64 {
+    let due = samples
+        .iter()
+        .filter(|sample| sample.autovacuum_threshold_ratio >= VACUUM_DUE_RATIO)
+        .count();
+    i64::try_from(due).unwrap_or(i64::MAX)
+}
+
 #[allow(clippy::expect_used)]
 fn int_metric(name: &str, help: &str) -> IntGaugeVec {
-    IntGaugeVec::new(Opts::new(name, help), &USER_TABLE_LABELS)
-        .expect("pg_stat_user_tables metric")
+    IntGaugeVec::new(Opts::new(name, help), &USER_TABLE_LABELS).expect("pg_stat_user_tables metric")
 }
 
 #[allow(clippy::expect_used)]
 fn gauge_metric(name: &str, help: &str) -> GaugeVec {
-    GaugeVec::new(Opts::new(name, help), &USER_TABLE_LABELS)
-        .expect("pg_stat_user_tables metric")
+    GaugeVec::new(Opts::new(name, help), &USER_TABLE_LABELS).expect("pg_stat_user_tables metric")
 }
 
 impl Collector for StatUserTablesCollector {
@@ -336,11 +583,17 @@ impl Collector for StatUserTablesCollector {
         registry.register(Box::new(self.n_tup_hot_upd.clone()))?;
         registry.register(Box::new(self.n_live_tup.clone()))?;
         registry.register(Box::new(self.n_dead_tup.clone()))?;
+        registry.register(Box::new(self.dead_tuples_reclaimed.clone()))?;
         registry.register(Box::new(self.n_mod_since_analyze.clone()))?;
+        registry.register(Box::new(self.reltuples_estimate.clone()))?;
         registry.register(Box::new(self.last_vacuum.clone()))?;
         registry.register(Box::new(self.last_autovacuum.clone()))?;
         registry.register(Box::new(self.last_analyze.clone()))?;
         registry.register(Box::new(self.last_autoanalyze.clone()))?;
+        registry.register(Box::new(self.last_vacuum_timestamp.clone()))?;
+        registry.register(Box::new(self.last_autovacuum_timestamp.clone()))?;
+        registry.register(Box::new(self.last_analyze_timestamp.clone()))?;
+        registry.register(Box::new(self.last_autoanalyze_timestamp.clone()))?;
         registry.register(Box::new(self.vacuum_count.clone()))?;
         registry.register(Box::new(self.autovacuum_count.clone()))?;
         registry.register(Box::new(self.analyze_count.clone()))?;
@@ -349,12 +602,17 @@ impl Collector for StatUserTablesCollector {
         registry.register(Box::new(self.table_size_bytes.clone()))?;
         registry.register(Box::new(self.bloat_ratio.clone()))?;
         registry.register(Box::new(self.dead_tuple_size_bytes.clone()))?;
-        registry.register(Box::new(self.last_autovacuum_seconds_ago.clone()))?;
-        registry.register(Box::new(self.last_autoanalyze_seconds_ago.clone()))?;
+        registry.register(Box::new(self.hot_update_ratio.clone()))?;
+        if self.seconds_ago {
+            registry.register(Box::new(self.last_autovacuum_seconds_ago.clone()))?;
+            registry.register(Box::new(self.last_autoanalyze_seconds_ago.clone()))?;
+        }
         registry.register(Box::new(self.never_autovacuumed.clone()))?;
         registry.register(Box::new(self.never_autoanalyzed.clone()))?;
         registry.register(Box::new(self.autovacuum_threshold_ratio.clone()))?;
         registry.register(Box::new(self.autoanalyze_threshold_ratio.clone()))?;
+        registry.register(Box::new(self.vacuum_due_count.clone()))?;
+        registry.register(Box::new(self.autovacuum_launches.clone()))?;
         registry.register(Box::new(self.heap_blks_read.clone()))?;
         registry.register(Box::new(self.heap_blks_hit.clone()))?;
         registry.register(Box::new(self.idx_blks_read.clone()))?;
@@ -370,29 +628,7 @@ impl Collector for StatUserTablesCollector {
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
         Box::pin(async move {
             // 1) Discover databases (exclude templates and configured exclusions)
-            let excluded = get_excluded_databases().to_vec();
-            let db_list_span = info_span!(
-                "db.query",
-                otel.kind = "client",
-                db.system = "postgresql",
-                db.operation = "SELECT",
-                db.statement = "SELECT datname FROM pg_database WHERE datallowconn ...",
-                db.sql.table = "pg_database"
-            );
-            let dbs: Vec<String> = sqlx::query_scalar(
-                r"
-                SELECT datname
-                FROM pg_database
-                WHERE datallowconn
-                  AND NOT datistemplate
-                  AND NOT (datname = ANY($1))
-                ORDER BY datname
-                ",
-            )
-            .bind(&excluded)
-            .fetch_all(pool)
-            .instrument(db_list_span)
-            .await?;
+            let dbs = list_target_databases(pool).await?;
 
             let shared_pool = pool.clone();
             let default_db = get_default_database().map(std::string::ToString::to_string);
@@ -401,12 +637,18 @@ impl Collector for StatUserTablesCollector {
             // database must pass through the global per-database connection limiter.
             let mut tasks = JoinSet::new();
 
+            let query: &'static str = if self.include_system {
+                STAT_ALL_TABLES_QUERY.as_str()
+            } else {
+                STAT_USER_TABLES_QUERY
+            };
+
             let num_dbs = dbs.len();
             for datname in dbs {
                 let shared_pool = shared_pool.clone();
                 let default_db = default_db.clone();
 
-                tasks.spawn(async move {
+                tasks.spawn(in_acquire_scope(async move {
                     let use_shared = default_db.as_deref() == Some(datname.as_str());
 
                     let query_span = info_span!(
@@ -431,8 +673,8 @@ impl Collector for StatUserTablesCollector {
                     };
 
                     let rows_res: anyhow::Result<Vec<PgRow>> = if use_shared {
-                        sqlx::query(STAT_USER_TABLES_QUERY)
-                            .fetch_all(&shared_pool)
+                        sqlx::query(query)
+                            .fetch_all(&mut *acquire_connection(&shared_pool).await?)
                             .instrument(query_span)
                             .await
                             .map_err(Into::into)
@@ -441,7 +683,7 @@ impl Collector for StatUserTablesCollector {
                             return Err(anyhow!("stat_user_tables: missing database query permit"));
                         };
                         match open_db_connection(&datname, permit).await {
-                            Ok(mut conn) => sqlx::query(STAT_USER_TABLES_QUERY)
+                            Ok(mut conn) => sqlx::query(query)
                                 .fetch_all(&mut conn)
                                 .instrument(query_span)
                                 .await
@@ -455,9 +697,7 @@ impl Collector for StatUserTablesCollector {
 
                     for row in rows {
                         samples.push(UserTableSample {
-                            datname: row
-                                .try_get::<Option<String>, _>("datname")?
-                                .unwrap_or_else(|| "[unknown]".to_string()),
+                            datname: datname_label(row.try_get("datname")?),
                             schemaname: row.try_get("schemaname")?,
                             relname: row.try_get("relname")?,
                             seq_scan: row.try_get("seq_scan").unwrap_or(0),
@@ -471,6 +711,7 @@ impl Collector for StatUserTablesCollector {
                             n_live_tup: row.try_get("n_live_tup").unwrap_or(0),
                             n_dead_tup: row.try_get("n_dead_tup").unwrap_or(0),
                             n_mod_since_analyze: row.try_get("n_mod_since_analyze").unwrap_or(0),
+                            reltuples_estimate: row.try_get("reltuples_estimate")?,
                             last_vacuum_epoch: row.try_get("last_vacuum_epoch").unwrap_or(0),
                             last_autovacuum_epoch: row
                                 .try_get("last_autovacuum_epoch")
@@ -479,6 +720,11 @@ impl Collector for StatUserTablesCollector {
                             last_autoanalyze_epoch: row
                                 .try_get("last_autoanalyze_epoch")
                                 .unwrap_or(0),
+                            last_vacuum_timestamp: row.try_get("last_vacuum_timestamp")?,
+                            last_autovacuum_timestamp: row.try_get("last_autovacuum_timestamp")?,
+                            last_analyze_timestamp: row.try_get("last_analyze_timestamp")?,
+                            last_autoanalyze_timestamp: row
+                                .try_get("last_autoanalyze_timestamp")?,
                             vacuum_count: row.try_get("vacuum_count").unwrap_or(0),
                             autovacuum_count: row.try_get("autovacuum_count").unwrap_or(0),
                             analyze_count: row.try_get("analyze_count").unwrap_or(0),
@@ -508,16 +754,20 @@ impl Collector for StatUserTablesCollector {
                         });
                     }
 
-                    Ok::<Vec<UserTableSample>, anyhow::Error>(samples)
-                });
+                    Ok::<(String, Vec<UserTableSample>), anyhow::Error>((datname, samples))
+                }));
             }
 
             let mut all_samples = Vec::new();
+            let mut due_counts = Vec::new();
+            let mut scraped_databases = Vec::new();
             let mut failures = Vec::new();
             let mut failed_db_count = 0;
             while let Some(joined) = tasks.join_next().await {
                 match joined {
-                    Ok(Ok(samples)) => {
+                    Ok(Ok((datname, samples))) => {
+                        scraped_databases.push(datname.clone());
+                        due_counts.push((datname, vacuum_due_count(&samples)));
                         all_samples.extend(samples);
                     }
                     Ok(Err(e)) => {
@@ -549,36 +799,108 @@ impl Collector for StatUserTablesCollector {
             }
 
             self.reset_metrics();
+            self.record_autovacuum_launches(&scraped_databases, &all_samples);
+            self.record_dead_tuples_reclaimed(&all_samples);
+
+            for (datname, due) in &due_counts {
+                self.vacuum_due_count
+                    .with_label_values(&[datname.as_str()])
+                    .set(*due);
+            }
 
             for sample in &all_samples {
                 let labels = [&sample.datname, &sample.schemaname, &sample.relname];
 
-                self.seq_scan.with_label_values(&labels).set(sample.seq_scan);
-                self.seq_tup_read.with_label_values(&labels).set(sample.seq_tup_read);
-                self.idx_scan.with_label_values(&labels).set(sample.idx_scan);
-                self.idx_tup_fetch.with_label_values(&labels).set(sample.idx_tup_fetch);
+                self.seq_scan
+                    .with_label_values(&labels)
+                    .set(sample.seq_scan);
+                self.seq_tup_read
+                    .with_label_values(&labels)
+                    .set(sample.seq_tup_read);
+                self.idx_scan
+                    .with_label_values(&labels)
+                    .set(sample.idx_scan);
+                self.idx_tup_fetch
+                    .with_label_values(&labels)
+                    .set(sample.idx_tup_fetch);
+
+                self.n_tup_ins
+                    .with_label_values(&labels)
+                    .set(sample.n_tup_ins);
+                self.n_tup_upd
+                    .with_label_values(&labels)
+                    .set(sample.n_tup_upd);
+                self.n_tup_del
+                    .with_label_values(&labels)
+                    .set(sample.n_tup_del);
+                self.n_tup_hot_upd
+                    .with_label_values(&labels)
+                    .set(sample.n_tup_hot_upd);
 
-                self.n_tup_ins.with_label_values(&labels).set(sample.n_tup_ins);
-                self.n_tup_upd.with_label_values(&labels).set(sample.n_tup_upd);
-                self.n_tup_del.with_label_values(&labels).set(sample.n_tup_del);
-                self.n_tup_hot_upd.with_label_values(&labels).set(sample.n_tup_hot_upd);
+                self.n_live_tup
+                    .with_label_values(&labels)
+                    .set(sample.n_live_tup);
+                self.n_dead_tup
+                    .with_label_values(&labels)
+                    .set(sample.n_dead_tup);
+                self.n_mod_since_analyze
+                    .with_label_values(&labels)
+                    .set(sample.n_mod_since_analyze);
+                if let Some(reltuples) = sample.reltuples_estimate {
+                    self.reltuples_estimate
+                        .with_label_values(&labels)
+                        .set(reltuples);
+                }
 
-                self.n_live_tup.with_label_values(&labels).set(sample.n_live_tup);
-                self.n_dead_tup.with_label_values(&labels).set(sample.n_dead_tup);
-                self.n_mod_since_analyze.with_label_values(&labels).set(sample.n_mod_since_analyze);
+                self.last_vacuum
+                    .with_label_values(&labels)
+                    .set(sample.last_vacuum_epoch);
+                self.last_autovacuum
+                    .with_label_values(&labels)
+                    .set(sample.last_autovacuum_epoch);
+                self.last_analyze
+                    .with_label_values(&labels)
+                    .set(sample.last_analyze_epoch);
+                self.last_autoanalyze
+                    .with_label_values(&labels)
+                    .set(sample.last_autoanalyze_epoch);
 
-                self.last_vacuum.with_label_values(&labels).set(sample.last_vacuum_epoch);
-                self.last_autovacuum.with_label_values(&labels).set(sample.last_autovacuum_epoch);
-                self.last_analyze.with_label_values(&labels).set(sample.last_analyze_epoch);
-                self.last_autoanalyze.with_label_values(&labels).set(sample.last_autoanalyze_epoch);
+                for (gauge, timestamp) in [
+                    (&self.last_vacuum_timestamp, sample.last_vacuum_timestamp),
+                    (
+                        &self.last_autovacuum_timestamp,
+                        sample.last_autovacuum_timestamp,
+                    ),
+                    (&self.last_analyze_timestamp, sample.last_analyze_timestamp),
+                    (
+                        &self.last_autoanalyze_timestamp,
+                        sample.last_autoanalyze_timestamp,
+                    ),
+                ] {
+                    if let Some(seconds) = timestamp {
+                        gauge.with_label_values(&labels).set(seconds);
+                    }
+                }
 
-                self.vacuum_count.with_label_values(&labels).set(sample.vacuum_count);
-                self.autovacuum_count.with_label_values(&labels).set(sample.autovacuum_count);
-                self.analyze_count.with_label_values(&labels).set(sample.analyze_count);
-                self.autoanalyze_count.with_label_values(&labels).set(sample.autoanalyze_count);
+                self.vacuum_count
+                    .with_label_values(&labels)
+                    .set(sample.vacuum_count);
+                self.autovacuum_count
+                    .with_label_values(&labels)
+                    .set(sample.autovacuum_count);
+                self.analyze_count
+                    .with_label_values(&labels)
+                    .set(sample.analyze_count);
+                self.autoanalyze_count
+                    .with_label_values(&labels)
+                    .set(sample.autoanalyze_count);
 
-                self.index_size_bytes.with_label_values(&labels).set(sample.index_size_bytes);
-                self.table_size_bytes.with_label_values(&labels).set(sample.table_size_bytes);
+                self.index_size_bytes
+                    .with_label_values(&labels)
+                    .set(sample.index_size_bytes);
+                self.table_size_bytes
+                    .with_label_values(&labels)
+                    .set(sample.table_size_bytes);
 
                 let total_tuples = sample.n_live_tup + sample.n_dead_tup;
                 let bloat_ratio = if total_tuples > 0 {
@@ -593,15 +915,30 @@ impl Collector for StatUserTablesCollector {
                 };
 
                 self.bloat_ratio.with_label_values(&labels).set(bloat_ratio);
-                self.dead_tuple_size_bytes.with_label_values(&labels).set(dead_size_estimate);
-                self.never_autovacuumed.with_label_values(&labels).set(sample.never_autovacuumed);
-                self.never_autoanalyzed.with_label_values(&labels).set(sample.never_autoanalyzed);
+                self.dead_tuple_size_bytes
+                    .with_label_values(&labels)
+                    .set(dead_size_estimate);
+                self.hot_update_ratio
+                    .with_label_values(&labels)
+                    .set(hot_update_ratio(sample.n_tup_hot_upd, sample.n_tup_upd));
+                self.never_autovacuumed
+                    .with_label_values(&labels)
+                    .set(sample.never_autovacuumed);
+                self.never_autoanalyzed
+                    .with_label_values(&labels)
+                    .set(sample.never_autoanalyzed);
 
-                if let Some(seconds) = sample.last_autovacuum_seconds_ago {
-                    self.last_autovacuum_seconds_ago.with_label_values(&labels).set(seconds);
-                }
-                if let Some(seconds) = sample.last_autoanalyze_seconds_ago {
-                    self.last_autoanalyze_seconds_ago.with_label_values(&labels).set(seconds);
+                if self.seconds_ago {
+                    if let Some(seconds) = sample.last_autovacuum_seconds_ago {
+                        self.last_autovacuum_seconds_ago
+                            .with_label_values(&labels)
+                            .set(seconds);
+                    }
+                    if let Some(seconds) = sample.last_autoanalyze_seconds_ago {
+                        self.last_autoanalyze_seconds_ago
+                            .with_label_values(&labels)
+                            .set(seconds);
+                    }
                 }
 
                 self.autovacuum_threshold_ratio
@@ -611,14 +948,30 @@ impl Collector for StatUserTablesCollector {
                     .with_label_values(&labels)
                     .set(sample.autoanalyze_threshold_ratio);
 
-                self.heap_blks_read.with_label_values(&labels).set(sample.heap_blks_read);
-                self.heap_blks_hit.with_label_values(&labels).set(sample.heap_blks_hit);
-                self.idx_blks_read.with_label_values(&labels).set(sample.idx_blks_read);
-                self.idx_blks_hit.with_label_values(&labels).set(sample.idx_blks_hit);
-                self.toast_blks_read.with_label_values(&labels).set(sample.toast_blks_read);
-                self.toast_blks_hit.with_label_values(&labels).set(sample.toast_blks_hit);
-                self.tidx_blks_read.with_label_values(&labels).set(sample.tidx_blks_read);
-                self.tidx_blks_hit.with_label_values(&labels).set(sample.tidx_blks_hit);
+                self.heap_blks_read
+                    .with_label_values(&labels)
+                    .set(sample.heap_blks_read);
+                self.heap_blks_hit
+                    .with_label_values(&labels)
+                    .set(sample.heap_blks_hit);
+                self.idx_blks_read
+                    .with_label_values(&labels)
+                    .set(sample.idx_blks_read);
+                self.idx_blks_hit
+                    .with_label_values(&labels)
+                    .set(sample.idx_blks_hit);
+                self.toast_blks_read
+                    .with_label_values(&labels)
+                    .set(sample.toast_blks_read);
+                self.toast_blks_hit
+                    .with_label_values(&labels)
+                    .set(sample.toast_blks_hit);
+                self.tidx_blks_read
+                    .with_label_values(&labels)
+                    .set(sample.tidx_blks_read);
+                self.tidx_blks_hit
+                    .with_label_values(&labels)
+                    .set(sample.tidx_blks_hit);
 
                 debug!(
                     datname=%sample.datname,
@@ -635,7 +988,156 @@ impl Collector for StatUserTablesCollector {
 
 #[cfg(test)]
 mod tests {
-    use super::STAT_USER_TABLES_QUERY;
+    use super::{
+        DeadTupleSample, STAT_ALL_TABLES_QUERY, STAT_USER_TABLES_QUERY, StatUserTablesCollector,
+        UserTableSample, autovacuum_launch_delta, dead_tuples_reclaimed, hot_update_ratio,
+        vacuum_due_count,
+    };
+
+    #[test]
+    fn test_hot_update_ratio() {
+        assert!((hot_update_ratio(75, 100) - 0.75).abs() < f64::EPSILON);
+        assert!((hot_update_ratio(100, 100) - 1.0).abs() < f64::EPSILON);
+        assert!(hot_update_ratio(0, 0).abs() < f64::EPSILON);
+        assert!(hot_update_ratio(5, 0).abs() < f64::EPSILON);
+    }
+
+    fn sample_with_ratio(autovacuum_threshold_ratio: f64) -> UserTableSample {
+        UserTableSample {
+            datname: "db".to_string(),
+            schemaname: "public".to_string(),
+            relname: "t".to_string(),
+            seq_scan: 0,
+            seq_tup_read: 0,
+            idx_scan: 0,
+            idx_tup_fetch: 0,
+            n_tup_ins: 0,
+            n_tup_upd: 0,
+            n_tup_del: 0,
+            n_tup_hot_upd: 0,
+            n_live_tup: 0,
+            n_dead_tup: 0,
+            n_mod_since_analyze: 0,
+            reltuples_estimate: None,
+            last_vacuum_epoch: 0,
+            last_autovacuum_epoch: 0,
+            last_analyze_epoch: 0,
+            last_autoanalyze_epoch: 0,
+            last_vacuum_timestamp: None,
+            last_autovacuum_timestamp: None,
+            last_analyze_timestamp: None,
+            last_autoanalyze_timestamp: None,
+            vacuum_count: 0,
+            autovacuum_count: 0,
+            analyze_count: 0,
+            autoanalyze_count: 0,
+            index_size_bytes: 0,
+            table_size_bytes: 0,
+            last_autovacuum_seconds_ago: None,
+            last_autoanalyze_seconds_ago: None,
+            never_autovacuumed: 0,
+            never_autoanalyzed: 0,
+            autovacuum_threshold_ratio,
+            autoanalyze_threshold_ratio: 0.0,
+            heap_blks_read: 0,
+            heap_blks_hit: 0,
+            idx_blks_read: 0,
+            idx_blks_hit: 0,
+            toast_blks_read: 0,
+            toast_blks_hit: 0,
+            tidx_blks_read: 0,
+            tidx_blks_hit: 0,
+        }
+    }
+
+    #[test]
+    fn test_stat_all_tables_query_reads_all_tables_views() {
+        assert!(STAT_ALL_TABLES_QUERY.contains("FROM pg_stat_all_tables s"));
+        assert!(STAT_ALL_TABLES_QUERY.contains("LEFT JOIN pg_statio_all_tables io"));
+        assert!(!STAT_ALL_TABLES_QUERY.contains("_user_tables"));
+    }
+
+    #[test]
+    fn test_dead_tuples_reclaimed_only_after_autovacuum() {
+        let sample = |autovacuum_count, n_dead_tup| DeadTupleSample {
+            autovacuum_count,
+            n_dead_tup,
+            reclaimed: None,
+        };
+
+        assert_eq!(
+            dead_tuples_reclaimed(&sample(3, 5000), &sample(4, 200)),
+            Some(4800)
+        );
+        assert_eq!(
+            dead_tuples_reclaimed(&sample(3, 5000), &sample(4, 6000)),
+            Some(0),
+            "autovacuum ran but dead tuples kept growing (xmin horizon held back)"
+        );
+        assert_eq!(
+            dead_tuples_reclaimed(&sample(3, 5000), &sample(3, 200)),
+            None,
+            "manual vacuum or pruning"
+        );
+        assert_eq!(
+            dead_tuples_reclaimed(&sample(3, 5000), &sample(0, 0)),
+            None,
+            "stats reset"
+        );
+    }
+
+    #[test]
+    fn test_autovacuum_launch_delta() {
+        assert_eq!(
+            autovacuum_launch_delta(None, 42),
+            0,
+            "first scrape is a baseline"
+        );
+        assert_eq!(autovacuum_launch_delta(Some(40), 42), 2);
+        assert_eq!(autovacuum_launch_delta(Some(42), 42), 0);
+        assert_eq!(
+            autovacuum_launch_delta(Some(42), 3),
+            3,
+            "stats reset counts from zero"
+        );
+    }
+
+    #[test]
+    fn test_autovacuum_launches_ignore_dropped_and_new_tables() {
+        let table = |relname: &str, autovacuum_count| UserTableSample {
+            relname: relname.to_string(),
+            autovacuum_count,
+            ..sample_with_ratio(0.0)
+        };
+        let databases = ["db".to_string()];
+        let collector = StatUserTablesCollector::new();
+        let launches = || {
+            collector
+                .autovacuum_launches
+                .with_label_values(&["db"])
+                .get()
+        };
+
+        collector.record_autovacuum_launches(&databases, &[table("a", 10), table("big", 500)]);
+        assert_eq!(launches(), 0, "first scrape is a baseline");
+
+        // "big" was dropped and "new" created: only a's two runs count
+        collector.record_autovacuum_launches(&databases, &[table("a", 12), table("new", 7)]);
+        assert_eq!(launches(), 2);
+
+        collector.record_autovacuum_launches(&databases, &[table("a", 13), table("new", 8)]);
+        assert_eq!(launches(), 4);
+    }
+
+    #[test]
+    fn test_vacuum_due_count_includes_tables_at_trigger_point() {
+        let samples: Vec<UserTableSample> = [0.0, 0.99, 1.0, 3.5]
+            .into_iter()
+            .map(sample_with_ratio)
+            .collect();
+        assert_eq!(vacuum_due_count(&samples), 2);
+        assert_eq!(vacuum_due_count(&[]), 0);
+    }
 
     #[test]
     fn test_stat_user_tables_query_honors_reloptions() {
@@ -677,10 +1179,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_stat_user_tables_query_keeps_full_precision_timestamps() {
+        for column in [
+            "last_vacuum",
+            "last_autovacuum",
+            "last_analyze",
+            "last_autoanalyze",
+        ] {
+            let expected = format!("EXTRACT(EPOCH FROM s.{column})::double precision");
+            assert!(
+                STAT_USER_TABLES_QUERY.contains(&expected),
+                "query should expose {column} as a double precision epoch"
+            );
+        }
+    }
+
     #[test]
     fn test_stat_user_tables_query_includes_block_io() {
         assert!(
-            STAT_USER_TABLES_QUERY.contains("LEFT JOIN pg_statio_user_tables io ON io.relid = s.relid"),
+            STAT_USER_TABLES_QUERY
+                .contains("LEFT JOIN pg_statio_user_tables io ON io.relid = s.relid"),
             "query should left-join pg_statio_user_tables so tables without I/O rows are kept"
         );
         for column in [
@@ -694,7 +1213,8 @@ mod tests {
             "tidx_blks_hit",
         ] {
             assert!(
-                STAT_USER_TABLES_QUERY.contains(&format!("COALESCE(io.{column}::bigint, 0) AS {column}")),
+                STAT_USER_TABLES_QUERY
+                    .contains(&format!("COALESCE(io.{column}::bigint, 0) AS {column}")),
                 "query should expose {column} as a COALESCE'd ::bigint aggregate"
             );
         }
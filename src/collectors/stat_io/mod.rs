@@ -8,15 +8,13 @@
 //! reads only the shared pool and never fans out per database. It is disabled
 //! by default to keep the extra label cardinality opt-in.
 
-use crate::collectors::Collector;
+use crate::collectors::{Collector, util::collect_subs};
 use anyhow::Result;
 use futures::future::BoxFuture;
-use futures::stream::{FuturesUnordered, StreamExt};
 use prometheus::Registry;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tracing::{debug, info_span, instrument, warn};
-use tracing_futures::Instrument as _;
 
 pub mod pg_stat_io;
 use pg_stat_io::PgStatIoCollector;
@@ -52,7 +50,12 @@ impl Collector for StatIoCollector {
         "stat_io"
     }
 
-    #[instrument(skip(self, registry), level = "info", err, fields(collector = "stat_io"))]
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "stat_io")
+    )]
     fn register_metrics(&self, registry: &Registry) -> Result<()> {
         for sub in &self.subs {
             let span = info_span!("collector.register_metrics", sub_collector = %sub.name());
@@ -76,24 +79,7 @@ impl Collector for StatIoCollector {
         fields(collector = "stat_io", otel.kind = "internal")
     )]
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
-        Box::pin(async move {
-            let mut tasks = FuturesUnordered::new();
-
-            for sub in &self.subs {
-                let span = info_span!(
-                    "collector.collect",
-                    sub_collector = %sub.name(),
-                    otel.kind = "internal"
-                );
-                tasks.push(sub.collect(pool).instrument(span));
-            }
-
-            while let Some(res) = tasks.next().await {
-                res?;
-            }
-
-            Ok(())
-        })
+        Box::pin(async move { collect_subs(&self.subs, pool).await })
     }
 
     fn enabled_by_default(&self) -> bool {
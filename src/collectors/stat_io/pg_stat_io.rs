@@ -33,7 +33,7 @@
 
 use crate::collectors::{
     Collector,
-    util::{MS_TO_SEC, get_pg_version},
+    util::{MS_TO_SEC, acquire_connection, resolve_server_version},
 };
 use anyhow::Result;
 use futures::future::BoxFuture;
@@ -47,7 +47,7 @@ use tracing::{debug, info_span, instrument, warn};
 use tracing_futures::Instrument as _;
 
 /// `pg_stat_io` was introduced in `PostgreSQL` 16.
-const MIN_PG_STAT_IO_VERSION: i32 = 160_000;
+pub(crate) const MIN_PG_STAT_IO_VERSION: i32 = 160_000;
 
 /// `PostgreSQL` 18 replaced `op_bytes` with native `read_bytes`/`write_bytes`/`extend_bytes`.
 const PG_NATIVE_BYTES_VERSION: i32 = 180_000;
@@ -300,9 +300,10 @@ impl PgStatIoCollector {
         self.write_time
             .with_label_values(&labels)
             .set(row.try_get::<f64, _>("write_time_seconds").unwrap_or(0.0));
-        self.writeback_time
-            .with_label_values(&labels)
-            .set(row.try_get::<f64, _>("writeback_time_seconds").unwrap_or(0.0));
+        self.writeback_time.with_label_values(&labels).set(
+            row.try_get::<f64, _>("writeback_time_seconds")
+                .unwrap_or(0.0),
+        );
         self.extend_time
             .with_label_values(&labels)
             .set(row.try_get::<f64, _>("extend_time_seconds").unwrap_or(0.0));
@@ -312,21 +313,6 @@ impl PgStatIoCollector {
     }
 }
 
-/// Resolves the server version, preferring the cached value set at startup and
-/// falling back to a direct query (the cache is not initialized in unit tests
-/// that call `collect` without going through the exporter bootstrap).
-async fn resolve_server_version(pool: &PgPool) -> Result<i32> {
-    let cached = get_pg_version();
-    if cached > 0 {
-        return Ok(cached);
-    }
-
-    let row = sqlx::query("SELECT current_setting('server_version_num')::int AS v")
-        .fetch_one(pool)
-        .await?;
-    Ok(row.try_get::<i32, _>("v")?)
-}
-
 /// Builds the `pg_stat_io` query for a given server version.
 ///
 /// `PostgreSQL` 18+ exposes native byte columns; earlier versions derive byte
@@ -371,7 +357,12 @@ impl Collector for PgStatIoCollector {
         "pg_stat_io"
     }
 
-    #[instrument(skip(self, registry), level = "info", err, fields(collector = "pg_stat_io"))]
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "pg_stat_io")
+    )]
     fn register_metrics(&self, registry: &Registry) -> Result<()> {
         registry.register(Box::new(self.reads.clone()))?;
         registry.register(Box::new(self.writes.clone()))?;
@@ -429,7 +420,7 @@ impl Collector for PgStatIoCollector {
             );
 
             let rows = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
-                .fetch_all(pool)
+                .fetch_all(&mut *acquire_connection(pool).await?)
                 .instrument(query_span)
                 .await?;
 
@@ -492,15 +483,21 @@ mod tests {
     fn pg16_and_pg17_use_derived_bytes() {
         assert_eq!(
             stat_io_support(160_000),
-            StatIoSupport::Supported { native_bytes: false }
+            StatIoSupport::Supported {
+                native_bytes: false
+            }
         );
         assert_eq!(
             stat_io_support(170_000),
-            StatIoSupport::Supported { native_bytes: false }
+            StatIoSupport::Supported {
+                native_bytes: false
+            }
         );
         assert_eq!(
             stat_io_support(179_999),
-            StatIoSupport::Supported { native_bytes: false }
+            StatIoSupport::Supported {
+                native_bytes: false
+            }
         );
     }
 
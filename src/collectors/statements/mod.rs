@@ -1,12 +1,10 @@
-use crate::collectors::Collector;
+use crate::collectors::{Collector, util::collect_subs};
 use anyhow::Result;
 use futures::future::BoxFuture;
-use futures::stream::{FuturesUnordered, StreamExt};
 use prometheus::Registry;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tracing::{debug, info_span, instrument, warn};
-use tracing_futures::Instrument as _;
 
 pub mod pg_statements;
 use pg_statements::PgStatementsCollector;
@@ -65,8 +63,19 @@ impl StatementsCollector {
 
     #[must_use]
     pub fn with_top_n(top_n: usize) -> Self {
+        Self::with_options(top_n, false, 0.0)
+    }
+
+    /// Build with a top-N limit, `--statements.normalize` literal replacement and the
+    /// `--statements.min-mean-time-ms` latency floor.
+    #[must_use]
+    pub fn with_options(top_n: usize, normalize: bool, min_mean_time_ms: f64) -> Self {
         Self {
-            subs: vec![Arc::new(PgStatementsCollector::with_top_n(top_n))],
+            subs: vec![Arc::new(
+                PgStatementsCollector::with_top_n(top_n)
+                    .with_normalize(normalize)
+                    .with_min_mean_time(min_mean_time_ms),
+            )],
         }
     }
 }
@@ -101,26 +110,7 @@ impl Collector for StatementsCollector {
     }
 
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
-        Box::pin(async move {
-            // Collect sub-collectors concurrently (unordered join)
-            let mut tasks = FuturesUnordered::new();
-
-            for sub in &self.subs {
-                let span = info_span!(
-                    "collector.collect",
-                    sub_collector = %sub.name(),
-                    otel.kind = "internal"
-                );
-
-                tasks.push(sub.collect(pool).instrument(span));
-            }
-
-            while let Some(res) = tasks.next().await {
-                res?;
-            }
-
-            Ok(())
-        })
+        Box::pin(async move { collect_subs(&self.subs, pool).await })
     }
 
     fn enabled_by_default(&self) -> bool {
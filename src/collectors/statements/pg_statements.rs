@@ -1,11 +1,11 @@
 use crate::collectors::{
     Collector, i64_to_f64,
-    util::{MS_TO_SEC, TEMPLATE0, TEMPLATE1},
+    util::{MS_TO_SEC, TEMPLATE0, TEMPLATE1, acquire_connection},
 };
 use anyhow::Result;
 use futures::future::BoxFuture;
 use prometheus::{GaugeVec, IntGaugeVec, Opts, Registry};
-use sqlx::{postgres::PgRow, PgPool, Row};
+use sqlx::{PgPool, Row, postgres::PgRow};
 use std::{
     sync::{Arc, Mutex, MutexGuard},
     time::{Duration, Instant},
@@ -16,7 +16,7 @@ use tracing_futures::Instrument as _;
 /// `PgStatementsCollector` tracks `pg_stat_statements` metrics
 ///
 /// Collects `query` performance statistics including:
-/// - Execution time (total, mean, max, stddev)
+/// - Execution time (total, mean, max, stddev, coefficient of variation)
 /// - Call frequency and row counts
 /// - I/O metrics (cache hits/misses, disk reads/writes)
 /// - Temp file usage (queries spilling to disk)
@@ -28,54 +28,195 @@ use tracing_futures::Instrument as _;
 #[derive(Clone)]
 pub struct PgStatementsCollector {
     // Execution time metrics (most important for DBREs)
-    total_exec_time: GaugeVec,       // {queryid, datname, usename, query_short}
-    mean_exec_time: GaugeVec,        // {queryid, datname, usename, query_short}
-    max_exec_time: GaugeVec,         // {queryid, datname, usename, query_short}
-    stddev_exec_time: GaugeVec,      // {queryid, datname, usename, query_short}
-    
+    total_exec_time: GaugeVec,  // {queryid, datname, usename, query_short}
+    mean_exec_time: GaugeVec,   // {queryid, datname, usename, query_short}
+    max_exec_time: GaugeVec,    // {queryid, datname, usename, query_short}
+    stddev_exec_time: GaugeVec, // {queryid, datname, usename, query_short}
+    exec_time_cv: GaugeVec,     // {queryid, datname, usename, query_short} - stddev / mean
+
     // Call frequency metrics
-    calls: IntGaugeVec,              // {queryid, datname, usename, query_short}
-    rows: IntGaugeVec,               // {queryid, datname, usename, query_short}
-    
+    calls: IntGaugeVec, // {queryid, datname, usename, query_short}
+    rows: IntGaugeVec,  // {queryid, datname, usename, query_short}
+
     // I/O metrics (critical for performance analysis)
-    shared_blks_hit: IntGaugeVec,    // {queryid, datname, usename, query_short} - cache hits
-    shared_blks_read: IntGaugeVec,   // {queryid, datname, usename, query_short} - disk reads
+    shared_blks_hit: IntGaugeVec, // {queryid, datname, usename, query_short} - cache hits
+    shared_blks_read: IntGaugeVec, // {queryid, datname, usename, query_short} - disk reads
     shared_blks_dirtied: IntGaugeVec, // {queryid, datname, usename, query_short}
     shared_blks_written: IntGaugeVec, // {queryid, datname, usename, query_short}
-    
+
     // Local I/O (temp tables)
-    local_blks_hit: IntGaugeVec,     // {queryid, datname, usename, query_short}
-    local_blks_read: IntGaugeVec,    // {queryid, datname, usename, query_short}
+    local_blks_hit: IntGaugeVec, // {queryid, datname, usename, query_short}
+    local_blks_read: IntGaugeVec, // {queryid, datname, usename, query_short}
     local_blks_dirtied: IntGaugeVec, // {queryid, datname, usename, query_short}
     local_blks_written: IntGaugeVec, // {queryid, datname, usename, query_short}
-    
+
     // Temp file usage (queries spilling to disk - often indicates memory issues)
-    temp_blks_read: IntGaugeVec,     // {queryid, datname, usename, query_short}
-    temp_blks_written: IntGaugeVec,  // {queryid, datname, usename, query_short}
-    
+    temp_blks_read: IntGaugeVec, // {queryid, datname, usename, query_short}
+    temp_blks_written: IntGaugeVec, // {queryid, datname, usename, query_short}
+
     // WAL generation (write-heavy queries)
-    wal_bytes: IntGaugeVec,          // {queryid, datname, usename, query_short}
-    
+    wal_bytes: IntGaugeVec, // {queryid, datname, usename, query_short}
+
     // Cache hit ratio (derived metric)
-    cache_hit_ratio: GaugeVec,       // {queryid, datname, usename, query_short}
-    
+    cache_hit_ratio: GaugeVec, // {queryid, datname, usename, query_short}
+
     // Top N tracking limit
     top_n: usize,
 
+    // Replace literals in query text with `?` before building `query_short`
+    normalize: bool,
+
+    // Minimum mean execution time (ms) a statement needs to be ranked at all
+    min_mean_time_ms: f64,
+
     // Cached extension detection to avoid re-querying pg_extension every scrape.
     extension_state: Arc<Mutex<ExtensionState>>,
+
+    // 1 when pg_stat_statements is preloaded and created, {collector="statements"}
+    available: IntGaugeVec,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum ExtensionState {
     Unknown,
-    Installed,
+    Installed(StatementColumns),
     Missing { last_checked: Instant },
 }
 
+/// Column layout of the installed `pg_stat_statements` extension version.
+///
+/// Only the columns this collector reads are tracked. The server can run a newer
+/// `PostgreSQL` than the extension version (e.g. after `pg_upgrade` without
+/// `ALTER EXTENSION pg_stat_statements UPDATE`), so the layout is read from the view
+/// itself rather than inferred from `server_version_num`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct StatementColumns {
+    /// `total_exec_time`, `mean_exec_time`, ... (1.8+); older versions name them
+    /// `total_time`, `mean_time`, ...
+    exec_time: bool,
+    /// `wal_bytes` (1.8+); exported as 0 when absent.
+    wal_bytes: bool,
+}
+
+impl StatementColumns {
+    /// Layout of every extension version shipped with a supported `PostgreSQL`.
+    const CURRENT: Self = Self {
+        exec_time: true,
+        wal_bytes: true,
+    };
+
+    /// Layout from the attribute names of the `pg_stat_statements` view. An empty
+    /// list (view not visible) keeps the current layout so the query error names
+    /// the real problem.
+    fn from_columns(columns: &[String]) -> Self {
+        if columns.is_empty() {
+            return Self::CURRENT;
+        }
+        let has = |name: &str| columns.iter().any(|c| c == name);
+        Self {
+            exec_time: has("total_exec_time"),
+            wal_bytes: has("wal_bytes"),
+        }
+    }
+}
+
+/// Why `pg_stat_statements` cannot be collected, as an actionable log message.
+///
+/// `preloaded` is `None` when the role cannot read `shared_preload_libraries` (it is only
+/// visible to superusers and `pg_read_all_settings`); the preload check is skipped then.
+const fn unavailable_reason(preloaded: Option<bool>, created: bool) -> Option<&'static str> {
+    match (preloaded, created) {
+        (Some(false), false) => Some(
+            "pg_stat_statements is neither preloaded nor created - add it to \
+             shared_preload_libraries, restart PostgreSQL and run CREATE EXTENSION \
+             pg_stat_statements; skipping collection",
+        ),
+        (Some(false), true) => Some(
+            "pg_stat_statements extension is created but not preloaded - add it to \
+             shared_preload_libraries and restart PostgreSQL; skipping collection",
+        ),
+        (_, false) => Some(
+            "pg_stat_statements is not created - run CREATE EXTENSION pg_stat_statements \
+             in the database the exporter connects to; skipping collection",
+        ),
+        (_, true) => None,
+    }
+}
+
+/// Whether a `shared_preload_libraries` value lists `pg_stat_statements`.
+fn preloads_pg_stat_statements(setting: &str) -> bool {
+    setting.split(',').any(|library| {
+        let library = library.trim().trim_matches('"');
+        let library = library.strip_prefix("$libdir/").unwrap_or(library);
+        library.strip_suffix(".so").unwrap_or(library) == "pg_stat_statements"
+    })
+}
+
 const MISSING_EXTENSION_RECHECK_AFTER: Duration = Duration::from_mins(1);
 // Reuse this value for the query start and self-filter so formatting cannot drift.
 const SELF_QUERY_PREFIX: &str = "SELECT queryid::text, d.datname,";
+// Query text fetched per row when --statements.normalize is on.
+const NORMALIZE_FETCH_CHARS: usize = 1024;
+
+const fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$' || !c.is_ascii()
+}
+
+/// Index just past the closing `quote` starting the scan at `start`; a doubled quote is
+/// an escaped quote. Returns `chars.len()` when the literal is unterminated.
+fn find_closing(chars: &[char], start: usize, quote: char, backslash_escapes: bool) -> usize {
+    let mut i = start;
+    while let Some(&c) = chars.get(i) {
+        if backslash_escapes && c == '\\' {
+            i += 2;
+        } else if c == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                i += 2;
+            } else {
+                return i + 1;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    chars.len()
+}
+
+/// Drop a standalone `E`/`B`/`X`/`N` string prefix already copied to `out`; returns
+/// whether the literal uses backslash escapes (`E''`).
+fn strip_string_prefix(out: &mut String) -> bool {
+    let mut rev = out.chars().rev();
+    let Some(prefix) = rev.next() else {
+        return false;
+    };
+    let standalone = !rev.next().is_some_and(is_ident_char);
+    if standalone && matches!(prefix.to_ascii_uppercase(), 'E' | 'B' | 'X' | 'N') {
+        out.pop();
+        return prefix.eq_ignore_ascii_case(&'E');
+    }
+    false
+}
+
+/// For a `$tag$...$tag$` literal starting at `start`, the index just past its end.
+/// Returns `None` when `start` does not open a dollar quote (e.g. a `$1` placeholder).
+fn skip_dollar_quoted(chars: &[char], start: usize) -> Option<usize> {
+    let mut tag_end = start + 1;
+    while let Some(&c) = chars.get(tag_end) {
+        if c == '$' {
+            break;
+        }
+        if !(c.is_ascii_alphabetic() || c == '_' || (tag_end > start + 1 && c.is_ascii_digit())) {
+            return None;
+        }
+        tag_end += 1;
+    }
+    let tag = chars.get(start..=tag_end)?;
+    let body_start = tag_end + 1;
+    let end = (body_start..chars.len())
+        .find(|&i| chars.get(i..i + tag.len()) == Some(tag))
+        .map_or(chars.len(), |i| i + tag.len());
+    Some(end)
+}
 
 impl PgStatementsCollector {
     /// Create a new `pg_statements` collector
@@ -170,6 +311,7 @@ impl PgStatementsCollector {
             mean_exec_time,
             max_exec_time,
             stddev_exec_time,
+            exec_time_cv: exec_time_cv_gauge(),
             calls,
             rows,
             shared_blks_hit,
@@ -185,8 +327,79 @@ impl PgStatementsCollector {
             wal_bytes,
             cache_hit_ratio,
             top_n,
+            normalize: false,
+            min_mean_time_ms: 0.0,
             extension_state: Arc::new(Mutex::new(ExtensionState::Unknown)),
+            available: collector_available_gauge(),
+        }
+    }
+
+    /// Replace numeric and string literals in `query_short` with `?`.
+    ///
+    /// `PostgreSQL` 14+ already normalizes `pg_stat_statements.query` server-side; older
+    /// servers may keep literal constants for some statement types, leaking them into labels.
+    #[must_use]
+    pub const fn with_normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Only rank statements whose mean execution time is at least `min_mean_time_ms`, so
+    /// fast but frequent statements do not take top-N slots. 0 keeps every statement.
+    #[must_use]
+    pub const fn with_min_mean_time(mut self, min_mean_time_ms: f64) -> Self {
+        self.min_mean_time_ms = min_mean_time_ms;
+        self
+    }
+
+    /// Replace literal constants in `query` with `?`: numbers that do not belong to an
+    /// identifier or `$n` placeholder, single-quoted strings (including `E''`, `B''`,
+    /// `X''` and `N''` prefixes) and dollar-quoted strings. Quoted identifiers are kept.
+    /// An unterminated literal (cut off by `LEFT()`) is replaced up to the end.
+    fn normalize_query(query: &str) -> String {
+        let chars: Vec<char> = query.chars().collect();
+        let mut out = String::with_capacity(query.len());
+        let mut i = 0;
+
+        while let Some(&c) = chars.get(i) {
+            let prev_is_ident = out.chars().next_back().is_some_and(is_ident_char);
+
+            if c == '"' {
+                let end = find_closing(&chars, i + 1, '"', false);
+                out.extend(chars.get(i..end).unwrap_or_default());
+                i = end;
+            } else if c == '\'' {
+                let backslash_escapes = strip_string_prefix(&mut out);
+                out.push('?');
+                i = find_closing(&chars, i + 1, '\'', backslash_escapes);
+            } else if c == '$' && !prev_is_ident {
+                if let Some(end) = skip_dollar_quoted(&chars, i) {
+                    out.push('?');
+                    i = end;
+                } else {
+                    out.push(c);
+                    i += 1;
+                }
+            } else if c.is_ascii_digit() && !prev_is_ident {
+                out.push('?');
+                i += 1;
+                while let Some(&n) = chars.get(i) {
+                    let exponent_sign = (n == '+' || n == '-')
+                        && chars
+                            .get(i.wrapping_sub(1))
+                            .is_some_and(|p| p.eq_ignore_ascii_case(&'e'));
+                    if !(n.is_ascii_alphanumeric() || n == '.' || n == '_' || exponent_sign) {
+                        break;
+                    }
+                    i += 1;
+                }
+            } else {
+                out.push(c);
+                i += 1;
+            }
         }
+
+        out
     }
 
     /// Truncate `query` text for labels (avoid high cardinality)
@@ -197,7 +410,7 @@ impl PgStatementsCollector {
             .map(str::trim)
             .collect::<Vec<_>>()
             .join(" ");
-        
+
         if cleaned.len() <= max_len {
             cleaned
         } else {
@@ -206,17 +419,29 @@ impl PgStatementsCollector {
         }
     }
 
-    fn build_pg_statements_query(&self) -> String {
+    fn build_pg_statements_query(&self, columns: StatementColumns) -> String {
+        // Pre-1.8 extension versions call the execution time columns total_time & co.
+        let time = if columns.exec_time {
+            "exec_time"
+        } else {
+            "time"
+        };
+        let wal_bytes = if columns.wal_bytes {
+            "COALESCE(wal_bytes, 0)::bigint"
+        } else {
+            "0::bigint"
+        };
+
         // IMPORTANT: keep casts to avoid NUMERIC/i64 mismatches.
         format!(
             r"{SELF_QUERY_PREFIX}
                 COALESCE(r.rolname, '<unknown>') as usename,
-                LEFT(query, 80) as query_short,
+                LEFT(query, {}) as query_short,
                 calls::bigint,
-                (total_exec_time / {MS_TO_SEC})::double precision as total_exec_time_sec,
-                (mean_exec_time / {MS_TO_SEC})::double precision as mean_exec_time_sec,
-                (max_exec_time / {MS_TO_SEC})::double precision as max_exec_time_sec,
-                (stddev_exec_time / {MS_TO_SEC})::double precision as stddev_exec_time_sec,
+                (total_{time} / {MS_TO_SEC})::double precision as total_exec_time_sec,
+                (mean_{time} / {MS_TO_SEC})::double precision as mean_exec_time_sec,
+                (max_{time} / {MS_TO_SEC})::double precision as max_exec_time_sec,
+                (stddev_{time} / {MS_TO_SEC})::double precision as stddev_exec_time_sec,
                 rows::bigint,
                 shared_blks_hit::bigint,
                 shared_blks_read::bigint,
@@ -228,17 +453,24 @@ impl PgStatementsCollector {
                 local_blks_written::bigint,
                 temp_blks_read::bigint,
                 temp_blks_written::bigint,
-                COALESCE(wal_bytes, 0)::bigint as wal_bytes
+                {wal_bytes} as wal_bytes
             FROM pg_stat_statements s
             JOIN pg_database d ON d.oid = s.dbid
             LEFT JOIN pg_roles r ON r.oid = s.userid
             WHERE queryid IS NOT NULL
-              AND total_exec_time > 0
+              AND total_{time} > 0
+              AND mean_{time} >= $1
               AND d.datname NOT IN ('{TEMPLATE0}', '{TEMPLATE1}')
               AND query NOT LIKE '{SELF_QUERY_PREFIX}%'
-            ORDER BY total_exec_time DESC
+            ORDER BY total_{time} DESC
             LIMIT {}
             ",
+            // Normalizing shortens literals, so fetch more text to fill the 80-char label.
+            if self.normalize {
+                NORMALIZE_FETCH_CHARS
+            } else {
+                80
+            },
             self.top_n
         )
     }
@@ -250,45 +482,71 @@ impl PgStatementsCollector {
         }
     }
 
-    fn cached_extension_availability(&self) -> Option<bool> {
+    /// The cached detection result, or `None` when detection has to run (first scrape,
+    /// or a missing extension whose cache entry expired).
+    fn cached_extension_state(&self) -> Option<ExtensionState> {
         match *self.extension_state_lock() {
-            ExtensionState::Installed => Some(true),
-            ExtensionState::Missing { last_checked }
+            state @ ExtensionState::Installed(_) => Some(state),
+            state @ ExtensionState::Missing { last_checked }
                 if last_checked.elapsed() < MISSING_EXTENSION_RECHECK_AFTER =>
             {
-                Some(false)
+                Some(state)
             }
             ExtensionState::Unknown | ExtensionState::Missing { .. } => None,
         }
     }
 
-    fn update_extension_state(&self, installed: bool) {
+    fn update_extension_state(&self, columns: Option<StatementColumns>) {
         let mut state = self.extension_state_lock();
-        *state = if installed {
-            ExtensionState::Installed
-        } else {
-            ExtensionState::Missing {
+        *state = columns.map_or_else(
+            || ExtensionState::Missing {
                 last_checked: Instant::now(),
-            }
-        };
+            },
+            ExtensionState::Installed,
+        );
+    }
+
+    /// Forget the detected layout so the next scrape re-reads it, e.g. after
+    /// `ALTER EXTENSION pg_stat_statements UPDATE` renamed columns.
+    fn reset_extension_state(&self) {
+        *self.extension_state_lock() = ExtensionState::Unknown;
     }
 
-    async fn pg_statements_available(&self, pool: &PgPool) -> Result<bool> {
-        if let Some(installed) = self.cached_extension_availability() {
-            return Ok(installed);
+    async fn pg_statements_available(&self, pool: &PgPool) -> Result<Option<StatementColumns>> {
+        match self.cached_extension_state() {
+            Some(ExtensionState::Installed(columns)) => return Ok(Some(columns)),
+            Some(_) => return Ok(None),
+            None => {}
         }
 
-        let installed = pg_statements_installed(pool).await?;
-        self.update_extension_state(installed);
+        let preloaded = pg_statements_preloaded(pool).await?;
+        let columns = pg_statements_columns(pool).await?;
+        let reason = unavailable_reason(preloaded, columns.is_some());
+        // Querying the view without the preloaded library only fails every scrape
+        let columns = columns.filter(|_| reason.is_none());
+        self.update_extension_state(columns);
+        self.available
+            .with_label_values(&["statements"])
+            .set(i64::from(columns.is_some()));
 
-        if !installed {
-            warn!(
+        match columns {
+            None => warn!(
                 collector = "pg_statements",
-                "pg_stat_statements extension not installed - skipping collection"
-            );
+                preloaded = ?preloaded,
+                "{}",
+                reason.unwrap_or("pg_stat_statements unavailable - skipping collection")
+            ),
+            Some(columns) if columns != StatementColumns::CURRENT => warn!(
+                collector = "pg_statements",
+                exec_time_columns = columns.exec_time,
+                wal_bytes_column = columns.wal_bytes,
+                "pg_stat_statements extension is outdated - run ALTER EXTENSION \
+                 pg_stat_statements UPDATE; collecting with the legacy column names"
+            ),
+            Some(_) => {}
         }
 
-        Ok(installed)
+        Ok(columns)
     }
 
     fn record_statement_row(&self, row: &PgRow) {
@@ -302,8 +560,16 @@ impl PgStatementsCollector {
             .try_get("usename")
             .unwrap_or_else(|_| "unknown".to_string());
         let query_text: Option<String> = row.try_get("query_short").ok();
-        let query_short =
-            query_text.map_or_else(|| "<utility>".to_string(), |q| Self::truncate_query(&q, 80));
+        let query_short = query_text.map_or_else(
+            || "<utility>".to_string(),
+            |q| {
+                if self.normalize {
+                    Self::truncate_query(&Self::normalize_query(&q), 80)
+                } else {
+                    Self::truncate_query(&q, 80)
+                }
+            },
+        );
 
         let labels = [
             queryid.as_str(),
@@ -327,6 +593,9 @@ impl PgStatementsCollector {
         self.stddev_exec_time
             .with_label_values(&labels)
             .set(stddev_time);
+        self.exec_time_cv
+            .with_label_values(&labels)
+            .set(exec_time_cv(mean_time, stddev_time));
 
         let calls: i64 = row.try_get("calls").unwrap_or(0);
         let rows_returned: i64 = row.try_get("rows").unwrap_or(0);
@@ -393,6 +662,23 @@ impl PgStatementsCollector {
     }
 }
 
+fn exec_time_cv_gauge() -> GaugeVec {
+    statement_gauge(
+        "pg_stat_statements_exec_time_cv",
+        "Coefficient of variation of execution time (stddev / mean) - high value flags \
+         unstable performance such as plan flips that the mean hides",
+    )
+}
+
+/// Coefficient of variation of execution time, `stddev / mean`; 0 when the mean is 0.
+fn exec_time_cv(mean_time: f64, stddev_time: f64) -> f64 {
+    if mean_time > 0.0 {
+        stddev_time / mean_time
+    } else {
+        0.0
+    }
+}
+
 const STATEMENT_LABELS: [&str; 4] = ["queryid", "datname", "usename", "query_short"];
 
 #[allow(clippy::expect_used)]
@@ -413,11 +699,56 @@ fn statement_int_gauge(name: &str, help: &str) -> IntGaugeVec {
     .expect("pg_stat_statements int metric")
 }
 
-async fn pg_statements_installed(pool: &PgPool) -> Result<bool> {
-    Ok(sqlx::query("SELECT 1 FROM pg_extension WHERE extname = 'pg_stat_statements'")
-        .fetch_optional(pool)
+#[allow(clippy::expect_used)]
+fn collector_available_gauge() -> IntGaugeVec {
+    IntGaugeVec::new(
+        Opts::new(
+            "pg_exporter_collector_available",
+            "Whether the collector's data source is usable (1) or missing (0); for statements, \
+             pg_stat_statements must be preloaded and created",
+        ),
+        &["collector"],
+    )
+    .expect("pg_exporter_collector_available metric")
+}
+
+/// Whether `shared_preload_libraries` lists `pg_stat_statements`, or `None` when the
+/// setting is hidden from this role.
+async fn pg_statements_preloaded(pool: &PgPool) -> Result<Option<bool>> {
+    let setting: Option<String> = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT setting FROM pg_settings WHERE name = 'shared_preload_libraries'",
+    )
+    .fetch_optional(&mut *acquire_connection(pool).await?)
+    .await?
+    .flatten();
+
+    Ok(setting.map(|setting| preloads_pg_stat_statements(&setting)))
+}
+
+/// Column layout of the installed extension, or `None` when it is not installed.
+async fn pg_statements_columns(pool: &PgPool) -> Result<Option<StatementColumns>> {
+    let installed = sqlx::query("SELECT 1 FROM pg_extension WHERE extname = 'pg_stat_statements'")
+        .fetch_optional(&mut *acquire_connection(pool).await?)
         .await?
-        .is_some())
+        .is_some();
+    if !installed {
+        return Ok(None);
+    }
+
+    // Resolve the view through search_path, exactly like the collection query does.
+    let columns: Vec<String> = sqlx::query_scalar(
+        r"
+        SELECT attname::text
+        FROM pg_attribute
+        WHERE attrelid = to_regclass('pg_stat_statements')
+          AND attnum > 0
+          AND NOT attisdropped
+        ",
+    )
+    .fetch_all(&mut *acquire_connection(pool).await?)
+    .await?;
+
+    Ok(Some(StatementColumns::from_columns(&columns)))
 }
 
 impl Collector for PgStatementsCollector {
@@ -436,6 +767,7 @@ impl Collector for PgStatementsCollector {
         registry.register(Box::new(self.mean_exec_time.clone()))?;
         registry.register(Box::new(self.max_exec_time.clone()))?;
         registry.register(Box::new(self.stddev_exec_time.clone()))?;
+        registry.register(Box::new(self.exec_time_cv.clone()))?;
         registry.register(Box::new(self.calls.clone()))?;
         registry.register(Box::new(self.rows.clone()))?;
         registry.register(Box::new(self.shared_blks_hit.clone()))?;
@@ -450,6 +782,7 @@ impl Collector for PgStatementsCollector {
         registry.register(Box::new(self.temp_blks_written.clone()))?;
         registry.register(Box::new(self.wal_bytes.clone()))?;
         registry.register(Box::new(self.cache_hit_ratio.clone()))?;
+        registry.register(Box::new(self.available.clone()))?;
 
         debug!(collector = "pg_statements", "registered metrics");
         Ok(())
@@ -458,13 +791,22 @@ impl Collector for PgStatementsCollector {
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
         Box::pin(
             async move {
-                if !self.pg_statements_available(pool).await? {
+                let Some(columns) = self.pg_statements_available(pool).await? else {
                     return Ok(());
-                }
+                };
 
-                let query = self.build_pg_statements_query();
-                let rows: Vec<PgRow> =
-                    sqlx::query(sqlx::AssertSqlSafe(query.as_str())).fetch_all(pool).await?;
+                let query = self.build_pg_statements_query(columns);
+                let rows: Vec<PgRow> = match sqlx::query(sqlx::AssertSqlSafe(query.as_str()))
+                    .bind(self.min_mean_time_ms)
+                    .fetch_all(&mut *acquire_connection(pool).await?)
+                    .await
+                {
+                    Ok(rows) => rows,
+                    Err(e) => {
+                        self.reset_extension_state();
+                        return Err(e.into());
+                    }
+                };
                 let row_count = rows.len();
 
                 // Only clear previous series after we have fresh replacement rows.
@@ -472,6 +814,7 @@ impl Collector for PgStatementsCollector {
                 self.mean_exec_time.reset();
                 self.max_exec_time.reset();
                 self.stddev_exec_time.reset();
+                self.exec_time_cv.reset();
                 self.calls.reset();
                 self.rows.reset();
                 self.shared_blks_hit.reset();
@@ -550,10 +893,138 @@ mod tests {
         assert_eq!(result, format!("{prefix}..."));
     }
 
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_exec_time_cv_guards_zero_mean() {
+        assert_eq!(exec_time_cv(0.5, 1.0), 2.0);
+        assert_eq!(exec_time_cv(2.0, 0.0), 0.0);
+        assert_eq!(exec_time_cv(0.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_normalize_query_replaces_literals() {
+        let cases = [
+            (
+                "SELECT * FROM users WHERE id = 42 AND email = 'a@b.c'",
+                "SELECT * FROM users WHERE id = ? AND email = ?",
+            ),
+            (
+                "UPDATE t1 SET v = 1.5e-3, n = 'it''s' WHERE k = $1",
+                "UPDATE t1 SET v = ?, n = ? WHERE k = $1",
+            ),
+            (
+                "SELECT \"col2\" FROM t WHERE x IN (1, 2, 0x1F) AND y = E'a\\'b'",
+                "SELECT \"col2\" FROM t WHERE x IN (?, ?, ?) AND y = ?",
+            ),
+            ("SELECT $fn$ body 1 $fn$, $$x$$", "SELECT ?, ?"),
+            (
+                "INSERT INTO t VALUES ('secret cut off by LEF",
+                "INSERT INTO t VALUES (?",
+            ),
+        ];
+
+        for (query, expected) in cases {
+            assert_eq!(PgStatementsCollector::normalize_query(query), expected);
+        }
+    }
+
+    #[test]
+    fn test_statements_query_fetches_more_text_when_normalizing() {
+        let plain = PgStatementsCollector::with_top_n(25)
+            .build_pg_statements_query(StatementColumns::CURRENT);
+        assert!(plain.contains("LEFT(query, 80)"));
+
+        let normalized = PgStatementsCollector::with_top_n(25)
+            .with_normalize(true)
+            .build_pg_statements_query(StatementColumns::CURRENT);
+        assert!(normalized.contains(&format!("LEFT(query, {NORMALIZE_FETCH_CHARS})")));
+    }
+
+    #[test]
+    fn test_build_pg_statements_query_maps_legacy_columns() {
+        let legacy = StatementColumns {
+            exec_time: false,
+            wal_bytes: false,
+        };
+        let query = PgStatementsCollector::with_top_n(25).build_pg_statements_query(legacy);
+
+        assert!(query.contains(&format!(
+            "(total_time / {MS_TO_SEC})::double precision as total_exec_time_sec"
+        )));
+        assert!(query.contains(&format!(
+            "(stddev_time / {MS_TO_SEC})::double precision as stddev_exec_time_sec"
+        )));
+        assert!(query.contains("ORDER BY total_time DESC"));
+        assert!(query.contains("AND mean_time >= $1"));
+        assert!(query.contains("0::bigint as wal_bytes"));
+        assert!(!query.contains("total_exec_time "));
+        assert!(!query.contains("COALESCE(wal_bytes"));
+    }
+
+    #[test]
+    fn test_statement_columns_from_view_attributes() {
+        let names = |cols: &[&str]| cols.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        assert_eq!(
+            StatementColumns::from_columns(&names(&["queryid", "total_exec_time", "wal_bytes"])),
+            StatementColumns::CURRENT
+        );
+        assert_eq!(
+            StatementColumns::from_columns(&names(&["queryid", "total_time", "mean_time"])),
+            StatementColumns {
+                exec_time: false,
+                wal_bytes: false,
+            }
+        );
+        assert_eq!(
+            StatementColumns::from_columns(&[]),
+            StatementColumns::CURRENT
+        );
+    }
+
+    #[test]
+    fn test_preloads_pg_stat_statements() {
+        assert!(preloads_pg_stat_statements("pg_stat_statements"));
+        assert!(preloads_pg_stat_statements(
+            "auto_explain, pg_stat_statements"
+        ));
+        assert!(preloads_pg_stat_statements(
+            "\"$libdir/pg_stat_statements.so\""
+        ));
+        assert!(!preloads_pg_stat_statements(""));
+        assert!(!preloads_pg_stat_statements("auto_explain,pg_stat_kcache"));
+    }
+
+    #[test]
+    fn test_unavailable_reason_distinguishes_preload_from_create() {
+        let not_preloaded = unavailable_reason(Some(false), true);
+        let not_created = unavailable_reason(Some(true), false);
+
+        assert!(not_preloaded.is_some_and(|r| r.contains("not preloaded")));
+        assert!(not_created.is_some_and(|r| r.contains("CREATE EXTENSION")));
+        assert!(
+            unavailable_reason(Some(false), false)
+                .is_some_and(|r| r.contains("shared_preload_libraries") && r.contains("CREATE"))
+        );
+        // Hidden setting: only the extension itself can be checked
+        assert_eq!(unavailable_reason(None, false), not_created);
+        assert_eq!(unavailable_reason(None, true), None);
+        assert_eq!(unavailable_reason(Some(true), true), None);
+    }
+
+    #[test]
+    fn test_reset_extension_state_forces_redetection() {
+        let collector = PgStatementsCollector::with_top_n(25);
+        collector.update_extension_state(Some(StatementColumns::CURRENT));
+        collector.reset_extension_state();
+
+        assert_eq!(collector.cached_extension_state(), None);
+    }
+
     #[test]
     fn test_build_pg_statements_query_uses_roles_left_join() {
         let collector = PgStatementsCollector::with_top_n(25);
-        let query = collector.build_pg_statements_query();
+        let query = collector.build_pg_statements_query(StatementColumns::CURRENT);
 
         assert!(query.contains("LEFT JOIN pg_roles r ON r.oid = s.userid"));
         assert!(query.contains("COALESCE(r.rolname, '<unknown>') as usename"));
@@ -562,7 +1033,7 @@ mod tests {
     #[test]
     fn test_build_pg_statements_query_uses_fast_self_filter() {
         let collector = PgStatementsCollector::with_top_n(25);
-        let query = collector.build_pg_statements_query();
+        let query = collector.build_pg_statements_query(StatementColumns::CURRENT);
         let expected_filter = format!("AND query NOT LIKE '{SELF_QUERY_PREFIX}%'");
 
         assert!(query.starts_with(SELF_QUERY_PREFIX));
@@ -574,9 +1045,12 @@ mod tests {
     #[test]
     fn test_cached_extension_availability_uses_installed_cache() {
         let collector = PgStatementsCollector::with_top_n(25);
-        collector.update_extension_state(true);
+        collector.update_extension_state(Some(StatementColumns::CURRENT));
 
-        assert_eq!(collector.cached_extension_availability(), Some(true));
+        assert_eq!(
+            collector.cached_extension_state(),
+            Some(ExtensionState::Installed(StatementColumns::CURRENT))
+        );
     }
 
     #[test]
@@ -589,7 +1063,10 @@ mod tests {
             };
         }
 
-        assert_eq!(collector.cached_extension_availability(), Some(false));
+        assert!(matches!(
+            collector.cached_extension_state(),
+            Some(ExtensionState::Missing { .. })
+        ));
     }
 
     #[test]
@@ -605,6 +1082,6 @@ mod tests {
             };
         }
 
-        assert_eq!(collector.cached_extension_availability(), None);
+        assert_eq!(collector.cached_extension_state(), None);
     }
 }
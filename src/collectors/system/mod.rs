@@ -20,15 +20,13 @@
 //! CPU/memory numbers describe the exporter's host, not the database server, and
 //! would be misleading.
 
-use crate::collectors::Collector;
+use crate::collectors::{Collector, util::collect_subs};
 use anyhow::Result;
 use futures::future::BoxFuture;
-use futures::stream::{FuturesUnordered, StreamExt};
 use prometheus::Registry;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tracing::{debug, info_span, instrument, warn};
-use tracing_futures::Instrument as _;
 
 pub mod cpu;
 pub mod memory;
@@ -76,7 +74,12 @@ impl Collector for SystemCollector {
         "system"
     }
 
-    #[instrument(skip(self, registry), level = "info", err, fields(collector = "system"))]
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "system")
+    )]
     fn register_metrics(&self, registry: &Registry) -> Result<()> {
         for sub in &self.subs {
             let span = info_span!("collector.register_metrics", sub_collector = %sub.name());
@@ -100,24 +103,7 @@ impl Collector for SystemCollector {
         fields(collector = "system", otel.kind = "internal")
     )]
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
-        Box::pin(async move {
-            let mut tasks = FuturesUnordered::new();
-
-            for sub in &self.subs {
-                let span = info_span!(
-                    "collector.collect",
-                    sub_collector = %sub.name(),
-                    otel.kind = "internal"
-                );
-                tasks.push(sub.collect(pool).instrument(span));
-            }
-
-            while let Some(res) = tasks.next().await {
-                res?;
-            }
-
-            Ok(())
-        })
+        Box::pin(async move { collect_subs(&self.subs, pool).await })
     }
 
     fn enabled_by_default(&self) -> bool {
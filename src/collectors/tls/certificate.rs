@@ -1,3 +1,4 @@
+use crate::collectors::util::acquire_connection;
 use crate::collectors::{Collector, i64_to_f64};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -5,19 +6,34 @@ use futures::future::BoxFuture;
 use prometheus::{Gauge, IntGauge, Opts, Registry};
 use sqlx::PgPool;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::{debug, info_span, warn};
 use tracing_futures::Instrument;
 use x509_parser::prelude::*;
 
+/// Where the certificate collector reads the server certificate from
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum CertificateSource {
+    /// The `ssl_cert_file` path reported by the server, read from the exporter's filesystem
+    #[default]
+    ServerFile,
+    /// Certificate PEM supplied inline (`--tls.cert-pem`)
+    Pem(String),
+    /// Certificate PEM file on the exporter host (`--tls.cert-pem-file`), re-read on every
+    /// scrape so a renewed certificate is picked up without a restart
+    PemFile(PathBuf),
+}
+
 /// Collector for SSL/TLS certificate expiration and validity
 ///
-/// This collector reads certificate files from the filesystem to check expiration dates.
-/// It requires filesystem access to the certificate files specified in `ssl_cert_file`.
+/// By default this collector reads the certificate file named by `ssl_cert_file` from the
+/// filesystem, which requires the exporter to run on the database host.
 ///
 /// **Remote Installations:** When the exporter runs on a different machine than `PostgreSQL`,
-/// certificate files will not be accessible, and no certificate metrics will be exported.
-/// This is expected behavior - the collector will log a debug message and continue gracefully.
+/// certificate files will not be accessible and no certificate metrics are exported unless
+/// the certificate is supplied with `--tls.cert-pem` or `--tls.cert-pem-file`
+/// (see [`CertificateSource`]). Without one, the collector logs a debug message and
+/// continues gracefully.
 #[derive(Clone)]
 #[allow(clippy::struct_field_names)]
 pub struct CertificateCollector {
@@ -25,6 +41,7 @@ pub struct CertificateCollector {
     pg_ssl_certificate_valid: IntGauge,
     pg_ssl_certificate_not_before_timestamp: Gauge,
     pg_ssl_certificate_not_after_timestamp: Gauge,
+    source: CertificateSource,
 }
 
 impl CertificateCollector {
@@ -36,8 +53,18 @@ impl CertificateCollector {
     /// This should only happen during development if metric names conflict.
     #[must_use]
     #[allow(clippy::new_without_default)]
-    #[allow(clippy::expect_used)]
     pub fn new() -> Self {
+        Self::with_source(CertificateSource::default())
+    }
+
+    /// Creates a new `CertificateCollector` reading the certificate from `source`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn with_source(source: CertificateSource) -> Self {
         let pg_ssl_certificate_expiry_seconds = Gauge::with_opts(Opts::new(
             "pg_ssl_certificate_expiry_seconds",
             "Seconds until SSL/TLS certificate expires (negative if expired)",
@@ -67,6 +94,7 @@ impl CertificateCollector {
             pg_ssl_certificate_valid,
             pg_ssl_certificate_not_before_timestamp,
             pg_ssl_certificate_not_after_timestamp,
+            source,
         }
     }
 
@@ -93,16 +121,21 @@ impl CertificateCollector {
             }
         };
 
+        self.apply_certificate(&cert_data)
+    }
+
+    /// Parse PEM or DER certificate data and set the validity metrics
+    fn apply_certificate(&self, cert_data: &[u8]) -> Result<()> {
         // Try to parse as PEM first
         let der_data = if cert_data.starts_with(b"-----BEGIN") {
             // Parse PEM format
-            let pem = parse_x509_pem(&cert_data)
+            let pem = parse_x509_pem(cert_data)
                 .map_err(|e| anyhow::anyhow!("Failed to parse PEM certificate: {e:?}"))?
                 .1;
             pem.contents
         } else {
             // Assume DER format
-            cert_data
+            cert_data.to_vec()
         };
 
         // Parse the certificate
@@ -127,8 +160,7 @@ impl CertificateCollector {
             .set(i64_to_f64(not_before));
         self.pg_ssl_certificate_not_after_timestamp
             .set(i64_to_f64(not_after));
-        self.pg_ssl_certificate_valid
-            .set(i64::from(is_valid));
+        self.pg_ssl_certificate_valid.set(i64::from(is_valid));
 
         // Log certificate info
         debug!(
@@ -162,6 +194,29 @@ impl Collector for CertificateCollector {
 
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
         Box::pin(async move {
+            match &self.source {
+                CertificateSource::ServerFile => {}
+                CertificateSource::Pem(pem) => {
+                    if let Err(e) = self.apply_certificate(pem.as_bytes()) {
+                        warn!("Failed to parse --tls.cert-pem certificate: {e}");
+                    }
+                    return Ok(());
+                }
+                CertificateSource::PemFile(path) => {
+                    match fs::read(path) {
+                        Ok(data) => {
+                            if let Err(e) = self.apply_certificate(&data) {
+                                warn!("Failed to parse certificate file '{}': {e}", path.display());
+                            }
+                        }
+                        Err(e) => {
+                            warn!("Cannot read certificate file '{}': {e}", path.display());
+                        }
+                    }
+                    return Ok(());
+                }
+            }
+
             let span = info_span!(
                 "db.query",
                 db.system = "postgresql",
@@ -172,7 +227,7 @@ impl Collector for CertificateCollector {
 
             // Query the certificate file path from PostgreSQL
             match sqlx::query_scalar::<_, String>("SHOW ssl_cert_file")
-                .fetch_one(pool)
+                .fetch_one(&mut *acquire_connection(pool).await?)
                 .instrument(span)
                 .await
             {
@@ -1,4 +1,7 @@
-use crate::collectors::{util::is_pg_version_at_least, Collector, i64_to_f64};
+use crate::collectors::{
+    Collector, i64_to_f64,
+    util::{acquire_connection, is_pg_version_at_least},
+};
 use anyhow::Result;
 use futures::future::BoxFuture;
 use prometheus::{Gauge, GaugeVec, Opts, Registry};
@@ -85,9 +88,7 @@ impl Collector for ConnectionTlsCollector {
         Box::pin(async move {
             // pg_stat_ssl is available on all supported PostgreSQL versions (14+)
             if !is_pg_version_at_least(90_500) {
-                warn!(
-                    "pg_stat_ssl view requires PostgreSQL 14+, skipping connection TLS stats"
-                );
+                warn!("pg_stat_ssl view requires PostgreSQL 14+, skipping connection TLS stats");
                 return Ok(());
             }
 
@@ -110,7 +111,7 @@ impl Collector for ConnectionTlsCollector {
             ";
 
             match sqlx::query_as::<_, (bool, Option<String>, Option<String>, Option<i32>)>(query)
-                .fetch_all(pool)
+                .fetch_all(&mut *acquire_connection(pool).await?)
                 .instrument(span)
                 .await
             {
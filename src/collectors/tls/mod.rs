@@ -1,18 +1,19 @@
 pub mod certificate;
 pub mod connection_stats;
 pub mod server_config;
+pub mod transport;
 
-use crate::collectors::Collector;
+use crate::collectors::{Collector, util::collect_subs};
 use anyhow::Result;
-use certificate::CertificateCollector;
+use certificate::{CertificateCollector, CertificateSource};
 use connection_stats::ConnectionTlsCollector;
-use futures::{future::BoxFuture, stream::FuturesUnordered, StreamExt};
+use futures::future::BoxFuture;
 use prometheus::Registry;
 use server_config::ServerTlsConfigCollector;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tracing::{info_span, warn};
-use tracing_futures::Instrument;
+use transport::ConnectionTransportCollector;
 
 /// Main TLS collector that orchestrates all TLS-related sub-collectors
 ///
@@ -20,10 +21,12 @@ use tracing_futures::Instrument;
 /// - Server SSL/TLS configuration (works remotely)
 /// - Certificate expiration and validity monitoring (requires local access to cert files)
 /// - Per-connection SSL/TLS statistics (`PostgreSQL` 14+, works remotely)
+/// - Client connections by transport (Unix socket, plaintext TCP, TLS)
 ///
-/// **Note:** The certificate collector requires filesystem access to the certificate files.
-/// When running the exporter remotely, certificate metrics will not be available unless
-/// the certificate files are accessible via a mounted filesystem.
+/// **Note:** By default the certificate collector requires filesystem access to the
+/// certificate files. When running the exporter remotely, supply the certificate with
+/// `--tls.cert-pem` or `--tls.cert-pem-file` instead
+/// (see [`TlsCollector::with_certificate_source`]).
 ///
 /// This collector is disabled by default and must be explicitly enabled with `--collector.tls`
 #[derive(Clone, Default)]
@@ -34,11 +37,19 @@ pub struct TlsCollector {
 impl TlsCollector {
     #[must_use]
     pub fn new() -> Self {
+        Self::with_certificate_source(CertificateSource::default())
+    }
+
+    /// Build the TLS collector reading the server certificate from `source` instead of
+    /// the `ssl_cert_file` path on the exporter's filesystem.
+    #[must_use]
+    pub fn with_certificate_source(source: CertificateSource) -> Self {
         Self {
             subs: vec![
                 Arc::new(ServerTlsConfigCollector::new()),
-                Arc::new(CertificateCollector::new()),
+                Arc::new(CertificateCollector::with_source(source)),
                 Arc::new(ConnectionTlsCollector::new()),
+                Arc::new(ConnectionTransportCollector::new()),
             ],
         }
     }
@@ -68,25 +79,9 @@ impl Collector for TlsCollector {
 
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
         Box::pin(async move {
-            let mut tasks = FuturesUnordered::new();
-
-            for sub in &self.subs {
-                let span = info_span!(
-                    "collector.collect",
-                    sub_collector = %sub.name(),
-                    otel.kind = "internal"
-                );
-                tasks.push(sub.collect(pool).instrument(span));
-            }
-
-            while let Some(res) = tasks.next().await {
-                if let Err(e) = res {
-                    warn!("Sub-collector failed: {}", e);
-                    return Err(e);
-                }
-            }
-
-            Ok(())
+            collect_subs(&self.subs, pool).await.inspect_err(|e| {
+                warn!("Sub-collector failed: {}", e);
+            })
         })
     }
 
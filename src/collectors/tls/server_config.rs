@@ -1,4 +1,5 @@
 use crate::collectors::Collector;
+use crate::collectors::util::acquire_connection;
 use anyhow::Result;
 use futures::future::BoxFuture;
 use prometheus::{IntGauge, Opts, Registry};
@@ -55,13 +56,12 @@ impl Collector for ServerTlsConfigCollector {
 
             // Query SSL enabled status
             match sqlx::query_scalar::<_, String>("SHOW ssl")
-                .fetch_one(pool)
+                .fetch_one(&mut *acquire_connection(pool).await?)
                 .instrument(span)
                 .await
             {
                 Ok(ssl_status) => {
-                    let enabled =
-                        i64::from(ssl_status.eq_ignore_ascii_case("on"));
+                    let enabled = i64::from(ssl_status.eq_ignore_ascii_case("on"));
                     self.pg_ssl_enabled.set(enabled);
                 }
                 Err(e) => {
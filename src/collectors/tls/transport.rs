@@ -0,0 +1,104 @@
+use crate::collectors::Collector;
+use crate::collectors::util::acquire_connection;
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{IntGaugeVec, Opts, Registry};
+use sqlx::PgPool;
+use tracing::info_span;
+use tracing_futures::Instrument;
+
+/// Every transport a client connection can use; all are exported so a `tcp` series of 0
+/// is present to alert on.
+pub const TRANSPORTS: [&str; 3] = ["local", "tcp", "tls"];
+
+// `client_port` is -1 for Unix sockets and NULL for rows the role may not see in full
+// (and for background processes), so only connections whose transport is known count.
+const CONNECTIONS_BY_TRANSPORT_QUERY: &str = r"
+    SELECT
+        CASE
+            WHEN a.client_port = -1 THEN 'local'
+            WHEN COALESCE(s.ssl, false) THEN 'tls'
+            ELSE 'tcp'
+        END AS transport,
+        COUNT(*)::bigint AS connections
+    FROM pg_stat_activity a
+    LEFT JOIN pg_stat_ssl s ON s.pid = a.pid
+    WHERE a.backend_type = 'client backend'
+      AND a.client_port IS NOT NULL
+    GROUP BY 1
+";
+
+/// Client connections split by transport: Unix socket, plaintext TCP and TCP with TLS
+#[derive(Clone)]
+pub struct ConnectionTransportCollector {
+    pg_connections_by_transport: IntGaugeVec,
+}
+
+impl ConnectionTransportCollector {
+    /// Creates a new `ConnectionTransportCollector`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::new_without_default)]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let pg_connections_by_transport = IntGaugeVec::new(
+            Opts::new(
+                "pg_connections_by_transport",
+                "Client connections by transport: local (Unix socket), tcp (plaintext) or tls",
+            ),
+            &["transport"],
+        )
+        .expect("Failed to create pg_connections_by_transport metric");
+
+        Self {
+            pg_connections_by_transport,
+        }
+    }
+}
+
+impl Collector for ConnectionTransportCollector {
+    fn name(&self) -> &'static str {
+        "tls.transport"
+    }
+
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.pg_connections_by_transport.clone()))?;
+        Ok(())
+    }
+
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let span = info_span!(
+                "db.query",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement = "SELECT FROM pg_stat_activity JOIN pg_stat_ssl",
+                otel.kind = "client"
+            );
+
+            let rows = sqlx::query_as::<_, (String, i64)>(CONNECTIONS_BY_TRANSPORT_QUERY)
+                .fetch_all(&mut *acquire_connection(pool).await?)
+                .instrument(span)
+                .await?;
+
+            for transport in TRANSPORTS {
+                let connections = rows
+                    .iter()
+                    .find(|(name, _)| name == transport)
+                    .map_or(0, |(_, connections)| *connections);
+                self.pg_connections_by_transport
+                    .with_label_values(&[transport])
+                    .set(connections);
+            }
+
+            Ok(())
+        })
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+}
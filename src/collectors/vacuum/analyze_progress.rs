@@ -7,7 +7,7 @@
 
 use crate::collectors::{
     Collector,
-    util::{get_pg_version, is_pg_version_at_least},
+    util::{acquire_connection, get_pg_version, is_pg_version_at_least, resolve_server_version},
 };
 use anyhow::Result;
 use futures::future::BoxFuture;
@@ -160,18 +160,6 @@ impl AnalyzeProgressCollector {
     }
 }
 
-async fn resolve_server_version(pool: &PgPool) -> Result<i32> {
-    let cached = get_pg_version();
-    if cached > 0 {
-        return Ok(cached);
-    }
-
-    let row = sqlx::query("SELECT current_setting('server_version_num')::int AS v")
-        .fetch_one(pool)
-        .await?;
-    Ok(row.try_get::<i32, _>("v")?)
-}
-
 async fn resolve_analyze_progress_support(pool: &PgPool) -> Result<(i32, AnalyzeProgressSupport)> {
     if is_pg_version_at_least(MIN_ANALYZE_PROGRESS_VERSION) {
         return Ok((get_pg_version(), AnalyzeProgressSupport::Supported));
@@ -227,7 +215,7 @@ impl Collector for AnalyzeProgressCollector {
                 db.sql.table = "pg_stat_progress_analyze"
             );
             let rows = sqlx::query(ANALYZE_PROGRESS_QUERY)
-                .fetch_all(pool)
+                .fetch_all(&mut *acquire_connection(pool).await?)
                 .instrument(query_span)
                 .await?;
 
@@ -257,14 +245,19 @@ mod tests {
     #[test]
     fn register_metrics_succeeds_with_unique_names() {
         let registry = Registry::new();
-        assert!(AnalyzeProgressCollector::new()
-            .register_metrics(&registry)
-            .is_ok());
+        assert!(
+            AnalyzeProgressCollector::new()
+                .register_metrics(&registry)
+                .is_ok()
+        );
     }
 
     #[test]
     fn versions_before_pg13_are_unsupported() {
-        assert_eq!(analyze_progress_support(0), AnalyzeProgressSupport::Unsupported);
+        assert_eq!(
+            analyze_progress_support(0),
+            AnalyzeProgressSupport::Unsupported
+        );
         assert_eq!(
             analyze_progress_support(120_000),
             AnalyzeProgressSupport::Unsupported
@@ -292,8 +285,12 @@ mod tests {
         assert!(ANALYZE_PROGRESS_QUERY.contains("pg_stat_progress_analyze"));
         assert!(!ANALYZE_PROGRESS_QUERY.contains("WHERE"));
         assert!(ANALYZE_PROGRESS_QUERY.contains("current_database()"));
-        assert!(ANALYZE_PROGRESS_QUERY.contains("p.sample_blks_total::bigint AS sample_blks_total"));
-        assert!(ANALYZE_PROGRESS_QUERY.contains("p.sample_blks_scanned::bigint AS sample_blks_scanned"));
+        assert!(
+            ANALYZE_PROGRESS_QUERY.contains("p.sample_blks_total::bigint AS sample_blks_total")
+        );
+        assert!(
+            ANALYZE_PROGRESS_QUERY.contains("p.sample_blks_scanned::bigint AS sample_blks_scanned")
+        );
     }
 
     #[test]
@@ -314,7 +311,10 @@ mod tests {
             .find(|family| family.name() == "pg_stat_progress_analyze_sample_blks_scanned")
             .is_some_and(|family| !family.get_metric().is_empty());
 
-        assert!(!stale_series, "analyze progress series should reset cleanly");
+        assert!(
+            !stale_series,
+            "analyze progress series should reset cleanly"
+        );
         Ok(())
     }
 }
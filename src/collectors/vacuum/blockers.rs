@@ -5,6 +5,7 @@
 //! cluster-wide, so no per-database fan-out is needed.
 
 use crate::collectors::Collector;
+use crate::collectors::util::acquire_connection;
 use anyhow::Result;
 use futures::future::BoxFuture;
 use prometheus::{Gauge, IntGauge, IntGaugeVec, Opts, Registry};
@@ -16,10 +17,15 @@ const BACKEND_HOLDER: &str = "backend";
 const PREPARED_XACT_HOLDER: &str = "prepared_xact";
 const REPLICATION_SLOT_HOLDER: &str = "replication_slot";
 
+/// A backend is named by `application_name` plus its pid, so the offender can be passed
+/// to `pg_terminate_backend()`; unnamed backends (psql without a name, cron jobs) by pid only.
 const BACKEND_WORST_QUERY: &str = r"
     SELECT
         age(backend_xmin)::bigint AS age_xids,
-        COALESCE(application_name, '') AS identity
+        CASE
+            WHEN COALESCE(application_name, '') = '' THEN 'pid ' || pid
+            ELSE application_name || ' (pid ' || pid || ')'
+        END AS identity
     FROM pg_stat_activity
     WHERE backend_xmin IS NOT NULL
     ORDER BY age(backend_xmin) DESC
@@ -66,18 +72,36 @@ struct PreparedXactsAggregate {
     oldest_age_seconds: f64,
 }
 
+/// Pick the holder that pins the cluster-wide xmin horizon, i.e. the oldest
+/// sample across all holder types. Ties keep the first holder in input order.
+fn oldest_holder<'a>(
+    samples: &[(&'static str, Option<&'a HolderSample>)],
+) -> Option<(&'static str, &'a HolderSample)> {
+    samples
+        .iter()
+        .filter_map(|(holder, sample)| sample.map(|sample| (*holder, sample)))
+        .filter(|(_, sample)| sample.age_xids > 0)
+        .fold(None, |oldest, candidate| match oldest {
+            Some((_, current)) if current.age_xids >= candidate.1.age_xids => oldest,
+            _ => Some(candidate),
+        })
+}
+
 /// Exposes cluster-wide vacuum blocker metrics.
 ///
 /// The fixed holder horizon metric, `pg_xmin_horizon_age_xids`, always emits
 /// `backend`, `prepared_xact`, and `replication_slot` holder labels. The
 /// offender metric, `pg_xmin_horizon_holder_age_xids`, is reset every scrape and
 /// emits at most the single oldest identity for each holder type.
+/// `pg_oldest_xmin_age` emits at most one series: the holder and identity that
+/// currently pin the cluster-wide horizon.
 #[derive(Clone)]
 pub struct VacuumBlockersCollector {
     xmin_horizon_age_xids: IntGaugeVec,
     prepared_xacts_count: IntGauge,
     prepared_xacts_oldest_age_seconds: Gauge,
     xmin_horizon_holder_age_xids: IntGaugeVec,
+    oldest_xmin_age: IntGaugeVec,
 }
 
 impl Default for VacuumBlockersCollector {
@@ -126,11 +150,21 @@ impl VacuumBlockersCollector {
         )
         .expect("valid pg_xmin_horizon_holder_age_xids opts");
 
+        let oldest_xmin_age = IntGaugeVec::new(
+            Opts::new(
+                "pg_oldest_xmin_age",
+                "Age in transaction IDs of the oldest xmin pinning the cluster-wide horizon, labeled by the holder that pins it",
+            ),
+            &["holder", "identity"],
+        )
+        .expect("valid pg_oldest_xmin_age opts");
+
         Self {
             xmin_horizon_age_xids,
             prepared_xacts_count,
             prepared_xacts_oldest_age_seconds,
             xmin_horizon_holder_age_xids,
+            oldest_xmin_age,
         }
     }
 
@@ -178,6 +212,7 @@ impl Collector for VacuumBlockersCollector {
         registry.register(Box::new(self.prepared_xacts_count.clone()))?;
         registry.register(Box::new(self.prepared_xacts_oldest_age_seconds.clone()))?;
         registry.register(Box::new(self.xmin_horizon_holder_age_xids.clone()))?;
+        registry.register(Box::new(self.oldest_xmin_age.clone()))?;
         Ok(())
     }
 
@@ -198,7 +233,7 @@ impl Collector for VacuumBlockersCollector {
                 db.sql.table = "pg_stat_activity"
             );
             let backend_worst = sqlx::query(BACKEND_WORST_QUERY)
-                .fetch_optional(pool)
+                .fetch_optional(&mut *acquire_connection(pool).await?)
                 .instrument(backend_span)
                 .await?
                 .as_ref()
@@ -213,11 +248,14 @@ impl Collector for VacuumBlockersCollector {
                 db.sql.table = "pg_prepared_xacts"
             );
             let prepared_aggregate = sqlx::query(PREPARED_XACTS_AGGREGATE_QUERY)
-                .fetch_optional(pool)
+                .fetch_optional(&mut *acquire_connection(pool).await?)
                 .instrument(prepared_aggregate_span)
                 .await?
                 .as_ref()
-                .map_or_else(PreparedXactsAggregate::default, Self::prepared_aggregate_from_row);
+                .map_or_else(
+                    PreparedXactsAggregate::default,
+                    Self::prepared_aggregate_from_row,
+                );
 
             let prepared_worst_span = info_span!(
                 "db.query",
@@ -228,7 +266,7 @@ impl Collector for VacuumBlockersCollector {
                 db.sql.table = "pg_prepared_xacts"
             );
             let prepared_worst = sqlx::query(PREPARED_XACT_WORST_QUERY)
-                .fetch_optional(pool)
+                .fetch_optional(&mut *acquire_connection(pool).await?)
                 .instrument(prepared_worst_span)
                 .await?
                 .as_ref()
@@ -243,13 +281,14 @@ impl Collector for VacuumBlockersCollector {
                 db.sql.table = "pg_replication_slots"
             );
             let replication_slot_worst = sqlx::query(REPLICATION_SLOT_WORST_QUERY)
-                .fetch_optional(pool)
+                .fetch_optional(&mut *acquire_connection(pool).await?)
                 .instrument(replication_slot_span)
                 .await?
                 .as_ref()
                 .map(Self::holder_sample_from_row);
 
             self.xmin_horizon_holder_age_xids.reset();
+            self.oldest_xmin_age.reset();
 
             self.set_holder_age(
                 BACKEND_HOLDER,
@@ -277,6 +316,16 @@ impl Collector for VacuumBlockersCollector {
                 self.set_worst_holder(REPLICATION_SLOT_HOLDER, sample);
             }
 
+            if let Some((holder, sample)) = oldest_holder(&[
+                (BACKEND_HOLDER, backend_worst.as_ref()),
+                (PREPARED_XACT_HOLDER, prepared_worst.as_ref()),
+                (REPLICATION_SLOT_HOLDER, replication_slot_worst.as_ref()),
+            ]) {
+                self.oldest_xmin_age
+                    .with_label_values(&[holder, sample.identity.as_str()])
+                    .set(sample.age_xids);
+            }
+
             debug!(
                 prepared_xacts = prepared_aggregate.count,
                 "updated vacuum blocker metrics"
@@ -299,9 +348,11 @@ mod tests {
     #[test]
     fn register_metrics_succeeds_with_unique_names() {
         let registry = Registry::new();
-        assert!(VacuumBlockersCollector::new()
-            .register_metrics(&registry)
-            .is_ok());
+        assert!(
+            VacuumBlockersCollector::new()
+                .register_metrics(&registry)
+                .is_ok()
+        );
     }
 
     #[test]
@@ -326,12 +377,62 @@ mod tests {
         Ok(())
     }
 
+    fn sample(identity: &str, age_xids: i64) -> HolderSample {
+        HolderSample {
+            identity: identity.to_string(),
+            age_xids,
+        }
+    }
+
+    #[test]
+    fn oldest_holder_picks_the_highest_age_across_holder_types() {
+        let backend = sample("app", 10);
+        let slot = sample("standby_slot", 500);
+        let prepared = sample("gid-1", 200);
+
+        let oldest = oldest_holder(&[
+            (BACKEND_HOLDER, Some(&backend)),
+            (PREPARED_XACT_HOLDER, Some(&prepared)),
+            (REPLICATION_SLOT_HOLDER, Some(&slot)),
+        ]);
+
+        assert_eq!(
+            oldest.map(|(holder, sample)| (holder, sample.identity.as_str(), sample.age_xids)),
+            Some((REPLICATION_SLOT_HOLDER, "standby_slot", 500))
+        );
+    }
+
+    #[test]
+    fn oldest_holder_is_none_without_holders_or_with_zero_ages() {
+        let idle = sample("", 0);
+
+        assert!(
+            oldest_holder(&[(BACKEND_HOLDER, None), (REPLICATION_SLOT_HOLDER, None)]).is_none()
+        );
+        assert!(oldest_holder(&[(BACKEND_HOLDER, Some(&idle))]).is_none());
+    }
+
+    #[test]
+    fn oldest_holder_ties_keep_the_first_holder() {
+        let backend = sample("app", 42);
+        let prepared = sample("gid", 42);
+
+        let oldest = oldest_holder(&[
+            (BACKEND_HOLDER, Some(&backend)),
+            (PREPARED_XACT_HOLDER, Some(&prepared)),
+        ]);
+
+        assert_eq!(oldest.map(|(holder, _)| holder), Some(BACKEND_HOLDER));
+    }
+
     #[test]
     fn blocker_queries_cast_numeric_outputs() {
         assert!(BACKEND_WORST_QUERY.contains("age(backend_xmin)::bigint AS age_xids"));
         assert!(PREPARED_XACTS_AGGREGATE_QUERY.contains("count(*)::bigint AS cnt"));
         assert!(PREPARED_XACTS_AGGREGATE_QUERY.contains("0)::bigint AS max_age_xids"));
-        assert!(PREPARED_XACTS_AGGREGATE_QUERY.contains("0)::double precision AS oldest_age_seconds"));
+        assert!(
+            PREPARED_XACTS_AGGREGATE_QUERY.contains("0)::double precision AS oldest_age_seconds")
+        );
         assert!(PREPARED_XACT_WORST_QUERY.contains("age(transaction)::bigint AS age_xids"));
         assert!(REPLICATION_SLOT_WORST_QUERY.contains(")::bigint AS age_xids"));
     }
@@ -7,7 +7,7 @@
 
 use crate::collectors::{
     Collector,
-    util::{get_pg_version, is_pg_version_at_least},
+    util::{acquire_connection, get_pg_version, is_pg_version_at_least, resolve_server_version},
 };
 use anyhow::Result;
 use futures::future::BoxFuture;
@@ -238,18 +238,6 @@ impl CreateIndexProgressCollector {
     }
 }
 
-async fn resolve_server_version(pool: &PgPool) -> Result<i32> {
-    let cached = get_pg_version();
-    if cached > 0 {
-        return Ok(cached);
-    }
-
-    let row = sqlx::query("SELECT current_setting('server_version_num')::int AS v")
-        .fetch_one(pool)
-        .await?;
-    Ok(row.try_get::<i32, _>("v")?)
-}
-
 async fn resolve_create_index_progress_support(
     pool: &PgPool,
 ) -> Result<(i32, CreateIndexProgressSupport)> {
@@ -311,7 +299,7 @@ impl Collector for CreateIndexProgressCollector {
                 db.sql.table = "pg_stat_progress_create_index"
             );
             let rows = sqlx::query(CREATE_INDEX_PROGRESS_QUERY)
-                .fetch_all(pool)
+                .fetch_all(&mut *acquire_connection(pool).await?)
                 .instrument(query_span)
                 .await?;
 
@@ -323,7 +311,10 @@ impl Collector for CreateIndexProgressCollector {
                 self.apply_sample(sample);
             }
 
-            debug!(rows = samples.len(), "updated create index progress metrics");
+            debug!(
+                rows = samples.len(),
+                "updated create index progress metrics"
+            );
             Ok(())
         })
     }
@@ -344,9 +335,11 @@ mod tests {
     #[test]
     fn register_metrics_succeeds_with_unique_names() {
         let registry = Registry::new();
-        assert!(CreateIndexProgressCollector::new()
-            .register_metrics(&registry)
-            .is_ok());
+        assert!(
+            CreateIndexProgressCollector::new()
+                .register_metrics(&registry)
+                .is_ok()
+        );
     }
 
     #[test]
@@ -404,7 +397,10 @@ mod tests {
             .find(|family| family.name() == "pg_stat_progress_create_index_blocks_done")
             .is_some_and(|family| !family.get_metric().is_empty());
 
-        assert!(!stale_series, "create index progress series should reset cleanly");
+        assert!(
+            !stale_series,
+            "create index progress series should reset cleanly"
+        );
         Ok(())
     }
 }
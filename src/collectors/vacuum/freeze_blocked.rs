@@ -0,0 +1,324 @@
+//! Tables whose anti-wraparound autovacuum is stuck behind a lock.
+//!
+//! Since `PostgreSQL` 12 a regular autovacuum skips a table it cannot lock, but an
+//! anti-wraparound autovacuum (`age(relfrozenxid)` past `autovacuum_freeze_max_age`) waits
+//! for the lock instead. An autovacuum worker with an ungranted relation lock in `pg_locks`
+//! is therefore a freeze that cannot proceed. `pg_locks` is cluster-wide, so blocked tables
+//! are found with one query on the shared pool; only when one shows up does the collector
+//! connect to that database to resolve the table name and its `relfrozenxid` age.
+
+use crate::collectors::{
+    Collector,
+    util::{
+        acquire_connection, acquire_db_query_permit, get_default_database, get_excluded_databases,
+        open_db_connection,
+    },
+};
+use anyhow::Result;
+use futures::future::BoxFuture;
+use prometheus::{IntGaugeVec, Opts, Registry};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info_span, instrument};
+use tracing_futures::Instrument as _;
+
+/// Relations an autovacuum worker is waiting to lock, cluster-wide.
+const WAITING_AUTOVACUUM_QUERY: &str = r"
+    SELECT DISTINCT
+        d.datname,
+        l.relation::bigint AS relid
+    FROM pg_locks l
+    JOIN pg_stat_activity a ON a.pid = l.pid
+    JOIN pg_database d ON d.oid = l.database
+    WHERE a.backend_type = 'autovacuum worker'
+      AND l.locktype = 'relation'
+      AND NOT l.granted
+      AND NOT (d.datname = ANY($1))
+    ORDER BY 1, 2
+";
+
+/// Name and freeze age of the given relations in the connected database. The per-table
+/// `autovacuum_freeze_max_age` reloption overrides the server setting.
+const RELATION_FREEZE_AGE_QUERY: &str = r"
+    SELECT
+        c.oid::bigint AS relid,
+        n.nspname AS schemaname,
+        c.relname,
+        age(c.relfrozenxid)::bigint AS age_xids,
+        COALESCE(
+            (SELECT option_value::bigint
+             FROM pg_options_to_table(c.reloptions)
+             WHERE option_name = 'autovacuum_freeze_max_age'),
+            current_setting('autovacuum_freeze_max_age')::bigint
+        ) AS freeze_max_age
+    FROM pg_class c
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    WHERE c.oid = ANY(($1::bigint[])::oid[])
+";
+
+/// A blocked table, identified across scrapes by database and relation OID.
+type TableKey = (String, i64);
+
+#[derive(Clone, Debug)]
+struct BlockedTable {
+    schemaname: String,
+    relname: String,
+    age_xids: i64,
+}
+
+/// Exposes tables that need freezing but whose autovacuum is waiting on a lock:
+/// - `pg_tables_freeze_blocked{datname,schemaname,relname}`: consecutive collections in
+///   which an autovacuum worker was waiting for a lock on the table while its
+///   `age(relfrozenxid)` was at or past `autovacuum_freeze_max_age`
+/// - `pg_tables_freeze_blocked_age_xids{datname,schemaname,relname}`: the table's
+///   `age(relfrozenxid)`
+///
+/// Series exist only while the table is blocked, and the streak restarts once a collection
+/// no longer sees the worker waiting. One sighting can be a short `ALTER TABLE`; a growing
+/// value is a session holding the lock for good, which stops the database's
+/// `datfrozenxid` from advancing and heads the cluster towards a wraparound shutdown.
+#[derive(Clone)]
+pub struct FreezeBlockedCollector {
+    blocked: IntGaugeVec,  // pg_tables_freeze_blocked{datname,schemaname,relname}
+    age_xids: IntGaugeVec, // pg_tables_freeze_blocked_age_xids{datname,schemaname,relname}
+    streaks: Arc<Mutex<HashMap<TableKey, i64>>>,
+}
+
+impl Default for FreezeBlockedCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FreezeBlockedCollector {
+    /// Creates a new `FreezeBlockedCollector`
+    ///
+    /// # Panics
+    ///
+    /// Panics if metric creation fails (should never happen with valid metric names)
+    #[must_use]
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let blocked = IntGaugeVec::new(
+            Opts::new(
+                "pg_tables_freeze_blocked",
+                "Consecutive collections in which an anti-wraparound autovacuum of the table was waiting on a lock",
+            ),
+            &["datname", "schemaname", "relname"],
+        )
+        .expect("create pg_tables_freeze_blocked");
+
+        let age_xids = IntGaugeVec::new(
+            Opts::new(
+                "pg_tables_freeze_blocked_age_xids",
+                "age(relfrozenxid) of a table whose anti-wraparound autovacuum is waiting on a lock",
+            ),
+            &["datname", "schemaname", "relname"],
+        )
+        .expect("create pg_tables_freeze_blocked_age_xids");
+
+        Self {
+            blocked,
+            age_xids,
+            streaks: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Resolves the waiting relations of one database, keeping those due for freezing.
+    ///
+    /// Best-effort like the vacuum progress name lookup: a database that cannot be reached
+    /// (dropped meanwhile, not connectable) yields no tables instead of failing the scrape.
+    async fn resolve_blocked_tables(
+        pool: &PgPool,
+        datname: &str,
+        relids: &[i64],
+    ) -> Vec<(i64, BlockedTable)> {
+        let rows = if get_default_database() == Some(datname) {
+            match acquire_connection(pool).await {
+                Ok(mut conn) => sqlx::query(RELATION_FREEZE_AGE_QUERY)
+                    .bind(relids)
+                    .fetch_all(&mut *conn)
+                    .await
+                    .map_err(anyhow::Error::from),
+                Err(e) => Err(e),
+            }
+        } else {
+            match acquire_db_query_permit().await {
+                Ok(permit) => match open_db_connection(datname, &permit).await {
+                    Ok(mut conn) => sqlx::query(RELATION_FREEZE_AGE_QUERY)
+                        .bind(relids)
+                        .fetch_all(&mut conn)
+                        .await
+                        .map_err(anyhow::Error::from),
+                    Err(e) => Err(e),
+                },
+                Err(e) => Err(e),
+            }
+        };
+
+        match rows {
+            Ok(rows) => rows.iter().filter_map(due_for_freeze).collect(),
+            Err(e) => {
+                debug!(database = %datname, error = %e, "freeze_blocked: cannot resolve waiting relations");
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// The table of `row` when its freeze age has reached its `autovacuum_freeze_max_age`.
+fn due_for_freeze(row: &PgRow) -> Option<(i64, BlockedTable)> {
+    let relid: i64 = row.try_get("relid").ok()?;
+    let age_xids: i64 = row.try_get("age_xids").ok()?;
+    let freeze_max_age: i64 = row.try_get("freeze_max_age").ok()?;
+    if age_xids < freeze_max_age {
+        return None;
+    }
+
+    Some((
+        relid,
+        BlockedTable {
+            schemaname: row.try_get("schemaname").ok()?,
+            relname: row.try_get("relname").ok()?,
+            age_xids,
+        },
+    ))
+}
+
+/// Extend the streak of every table blocked in this collection and forget the others.
+fn advance_streaks(streaks: &mut HashMap<TableKey, i64>, blocked: &HashSet<TableKey>) {
+    streaks.retain(|key, _| blocked.contains(key));
+    for key in blocked {
+        let streak = streaks.entry(key.clone()).or_insert(0);
+        *streak = streak.saturating_add(1);
+    }
+}
+
+fn lock_streaks(
+    streaks: &Mutex<HashMap<TableKey, i64>>,
+) -> std::sync::MutexGuard<'_, HashMap<TableKey, i64>> {
+    match streaks.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+impl Collector for FreezeBlockedCollector {
+    fn name(&self) -> &'static str {
+        "vacuum_freeze_blocked"
+    }
+
+    #[instrument(
+        skip(self, registry),
+        level = "info",
+        err,
+        fields(collector = "vacuum_freeze_blocked")
+    )]
+    fn register_metrics(&self, registry: &Registry) -> Result<()> {
+        registry.register(Box::new(self.blocked.clone()))?;
+        registry.register(Box::new(self.age_xids.clone()))?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, pool),
+        level = "info",
+        err,
+        fields(collector="vacuum_freeze_blocked", otel.kind="internal")
+    )]
+    fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let excluded: Vec<String> = get_excluded_databases().to_vec();
+
+            let query_span = info_span!(
+                "db.query",
+                otel.kind = "client",
+                db.system = "postgresql",
+                db.operation = "SELECT",
+                db.statement = "SELECT datname, relation FROM pg_locks JOIN pg_stat_activity (autovacuum workers waiting)",
+                db.sql.table = "pg_locks"
+            );
+            let rows = sqlx::query(WAITING_AUTOVACUUM_QUERY)
+                .bind(&excluded)
+                .fetch_all(&mut *acquire_connection(pool).await?)
+                .instrument(query_span)
+                .await?;
+
+            let mut waiting: BTreeMap<String, Vec<i64>> = BTreeMap::new();
+            for row in &rows {
+                let datname: String = row.try_get("datname")?;
+                let relid: i64 = row.try_get("relid")?;
+                waiting.entry(datname).or_default().push(relid);
+            }
+
+            // Rare: only databases with a waiting autovacuum worker are visited.
+            let mut tables: Vec<(TableKey, BlockedTable)> = Vec::new();
+            for (datname, relids) in &waiting {
+                for (relid, table) in Self::resolve_blocked_tables(pool, datname, relids).await {
+                    tables.push(((datname.clone(), relid), table));
+                }
+            }
+
+            let blocked_keys: HashSet<TableKey> =
+                tables.iter().map(|(key, _)| key.clone()).collect();
+            let streaks = {
+                let mut streaks = lock_streaks(&self.streaks);
+                advance_streaks(&mut streaks, &blocked_keys);
+                streaks.clone()
+            };
+
+            self.blocked.reset();
+            self.age_xids.reset();
+
+            for ((datname, relid), table) in &tables {
+                let labels = [
+                    datname.as_str(),
+                    table.schemaname.as_str(),
+                    table.relname.as_str(),
+                ];
+                let streak = streaks
+                    .get(&(datname.clone(), *relid))
+                    .copied()
+                    .unwrap_or(1);
+                self.blocked.with_label_values(&labels).set(streak);
+                self.age_xids.with_label_values(&labels).set(table.age_xids);
+            }
+
+            debug!(
+                waiting_relations = rows.len(),
+                freeze_blocked = tables.len(),
+                "updated freeze blocked metrics"
+            );
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(datname: &str, relid: i64) -> TableKey {
+        (datname.to_string(), relid)
+    }
+
+    #[test]
+    fn test_advance_streaks_counts_consecutive_sightings() {
+        let mut streaks = HashMap::new();
+
+        advance_streaks(&mut streaks, &HashSet::from([key("app", 1), key("app", 2)]));
+        advance_streaks(&mut streaks, &HashSet::from([key("app", 1)]));
+        assert_eq!(streaks.get(&key("app", 1)), Some(&2));
+        assert_eq!(streaks.get(&key("app", 2)), None);
+
+        // A table seen again after a gap starts over
+        advance_streaks(&mut streaks, &HashSet::from([key("app", 1), key("app", 2)]));
+        assert_eq!(streaks.get(&key("app", 1)), Some(&3));
+        assert_eq!(streaks.get(&key("app", 2)), Some(&1));
+
+        advance_streaks(&mut streaks, &HashSet::new());
+        assert!(streaks.is_empty());
+    }
+}
@@ -1,12 +1,10 @@
-use crate::collectors::Collector;
+use crate::collectors::{Collector, util::collect_subs};
 use anyhow::Result;
 use futures::future::BoxFuture;
-use futures::stream::{FuturesUnordered, StreamExt};
 use prometheus::Registry;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tracing::{debug, info_span, instrument, warn};
-use tracing_futures::Instrument as _;
 
 pub mod analyze_progress;
 use analyze_progress::AnalyzeProgressCollector;
@@ -17,6 +15,9 @@ use blockers::VacuumBlockersCollector;
 pub mod create_index_progress;
 use create_index_progress::CreateIndexProgressCollector;
 
+pub mod freeze_blocked;
+use freeze_blocked::FreezeBlockedCollector;
+
 pub mod progress;
 use progress::VacuumProgressCollector;
 
@@ -36,6 +37,7 @@ impl VacuumCollector {
                 Arc::new(VacuumStatsCollector::new()),
                 Arc::new(VacuumProgressCollector::new()),
                 Arc::new(VacuumBlockersCollector::new()),
+                Arc::new(FreezeBlockedCollector::new()),
                 Arc::new(CreateIndexProgressCollector::new()),
                 Arc::new(AnalyzeProgressCollector::new()),
             ],
@@ -82,21 +84,7 @@ impl Collector for VacuumCollector {
         fields(collector = "vacuum", otel.kind = "internal")
     )]
     fn collect<'a>(&'a self, pool: &'a PgPool) -> BoxFuture<'a, Result<()>> {
-        Box::pin(async move {
-            let mut tasks = FuturesUnordered::new();
-
-            for sub in &self.subs {
-                let span = info_span!("collector.collect", sub_collector = %sub.name(), otel.kind = "internal");
-
-                tasks.push(sub.collect(pool).instrument(span));
-            }
-
-            while let Some(res) = tasks.next().await {
-                res?;
-            }
-
-            Ok(())
-        })
+        Box::pin(async move { collect_subs(&self.subs, pool).await })
     }
 
     fn enabled_by_default(&self) -> bool {
@@ -1,14 +1,14 @@
 use crate::collectors::{
-    i64_to_f64,
+    Collector, i64_to_f64,
     util::{
-        acquire_db_query_permit, get_default_database, get_excluded_databases, open_db_connection,
+        acquire_connection, acquire_db_query_permit, get_default_database, get_excluded_databases,
+        open_db_connection,
     },
-    Collector,
 };
 use anyhow::Result;
 use futures::future::BoxFuture;
 use prometheus::{GaugeVec, IntGauge, IntGaugeVec, Opts, Registry};
-use sqlx::{postgres::PgRow, PgPool, Row};
+use sqlx::{PgPool, Row, postgres::PgRow};
 use tracing::{debug, info_span, instrument};
 use tracing_futures::Instrument as _;
 
@@ -28,6 +28,7 @@ const VACUUM_PROGRESS_QUERY: &str = r"
              THEN n.nspname || '.' || c.relname
              ELSE NULL
         END AS local_table_name,
+        COALESCE(p.phase, 'unknown') AS phase,
         p.heap_blks_total,
         p.heap_blks_scanned,
         p.heap_blks_vacuumed,
@@ -42,6 +43,10 @@ const VACUUM_PROGRESS_QUERY: &str = r"
     WHERE (d.datname IS NULL OR NOT (d.datname = ANY($1)))
 ";
 
+/// Phases in which vacuum works through the indexes. The heap scan is paused, so the
+/// scanned/total heap block ratio does not say how far along the vacuum is.
+const INDEX_PHASES: [&str; 2] = ["vacuuming indexes", "cleaning up indexes"];
+
 /// Resolves a single relation OID to `schema.table` within the connected database.
 const RESOLVE_RELID_QUERY: &str = r"
     SELECT n.nspname || '.' || c.relname AS table_name
@@ -57,6 +62,7 @@ struct VacuumSample {
     /// Resolved `schema.table`. `None` until resolved (locally in-query or lazily);
     /// falls back to the numeric relid when a name cannot be resolved.
     table_name: Option<String>,
+    phase: String,
     heap_blks_total: i64,
     heap_blks_scanned: i64,
     heap_blks_vacuumed: i64,
@@ -69,16 +75,18 @@ struct VacuumSample {
 #[derive(Clone)]
 pub struct VacuumProgressCollector {
     in_progress: IntGaugeVec,
-    heap_progress: GaugeVec,  // Changed to GaugeVec for 0.0-1.0 ratio
+    heap_progress: GaugeVec, // Changed to GaugeVec for 0.0-1.0 ratio
     heap_vacuumed: IntGaugeVec,
     index_vacuum_count: IntGaugeVec,
     global_active: IntGauge,
-    
+    phase: IntGaugeVec,         // pg_vacuum_phase{database,table,phase}, always 1
+    percent_complete: GaugeVec, // pg_stat_progress_vacuum_percent_complete{database,table}
+
     // Autovacuum-specific metrics (Phase 1 enhancement)
     // These metrics help DBREs distinguish autovacuum from manual vacuum
     // and detect stuck/long-running autovacuum processes
-    is_autovacuum: IntGaugeVec,      // 1=autovacuum, 0=manual vacuum
-    duration_seconds: IntGaugeVec,   // How long the vacuum has been running (detect stuck processes)
+    is_autovacuum: IntGaugeVec,    // 1=autovacuum, 0=manual vacuum
+    duration_seconds: IntGaugeVec, // How long the vacuum has been running (detect stuck processes)
 }
 
 impl Default for VacuumProgressCollector {
@@ -106,7 +114,10 @@ impl VacuumProgressCollector {
         .expect("valid pg_vacuum_in_progress opts");
 
         let heap_progress = GaugeVec::new(
-            Opts::new("pg_vacuum_heap_progress", "Progress of heap blocks scanned (0.0-1.0 ratio)"),
+            Opts::new(
+                "pg_vacuum_heap_progress",
+                "Progress of heap blocks scanned (0.0-1.0 ratio)",
+            ),
             &["database", "table"],
         )
         .expect("valid pg_vacuum_heap_progress opts");
@@ -132,6 +143,25 @@ impl VacuumProgressCollector {
         ))
         .expect("valid pg_vacuum_active opts");
 
+        let phase = IntGaugeVec::new(
+            Opts::new(
+                "pg_vacuum_phase",
+                "Current phase of a running vacuum, as reported by pg_stat_progress_vacuum (always 1)",
+            ),
+            &["database", "table", "phase"],
+        )
+        .expect("valid pg_vacuum_phase opts");
+
+        let percent_complete = GaugeVec::new(
+            Opts::new(
+                "pg_stat_progress_vacuum_percent_complete",
+                "Heap blocks scanned as a percentage (0-100) of the table's heap blocks; absent \
+                 while the total is unknown or vacuum is processing indexes",
+            ),
+            &["database", "table"],
+        )
+        .expect("valid pg_stat_progress_vacuum_percent_complete opts");
+
         let is_autovacuum = IntGaugeVec::new(
             Opts::new(
                 "pg_vacuum_is_autovacuum",
@@ -156,16 +186,30 @@ impl VacuumProgressCollector {
             heap_vacuumed,
             index_vacuum_count,
             global_active,
+            phase,
+            percent_complete,
             is_autovacuum,
             duration_seconds,
         }
     }
 
+    /// `heap_blks_scanned / heap_blks_total` as a percentage, or `None` when it does not
+    /// measure progress: the total is still unknown (`initializing`) or vacuum is in an
+    /// index phase.
+    fn percent_complete(phase: &str, heap_scanned: i64, heap_total: i64) -> Option<f64> {
+        if heap_total <= 0 || INDEX_PHASES.contains(&phase) {
+            return None;
+        }
+        Some((i64_to_f64(heap_scanned) * 100.0 / i64_to_f64(heap_total)).clamp(0.0, 100.0))
+    }
+
     fn reset_progress_metrics(&self) {
         self.in_progress.reset();
         self.heap_progress.reset();
         self.heap_vacuumed.reset();
         self.index_vacuum_count.reset();
+        self.phase.reset();
+        self.percent_complete.reset();
         self.is_autovacuum.reset();
         self.duration_seconds.reset();
     }
@@ -180,6 +224,9 @@ impl VacuumProgressCollector {
                 .try_get::<Option<String>, _>("local_table_name")
                 .ok()
                 .flatten(),
+            phase: row
+                .try_get("phase")
+                .unwrap_or_else(|_| "unknown".to_string()),
             heap_blks_total: row.try_get("heap_blks_total").unwrap_or(0),
             heap_blks_scanned: row.try_get("heap_blks_scanned").unwrap_or(0),
             heap_blks_vacuumed: row.try_get("heap_blks_vacuumed").unwrap_or(0),
@@ -249,6 +296,8 @@ impl Collector for VacuumProgressCollector {
         registry.register(Box::new(self.heap_vacuumed.clone()))?;
         registry.register(Box::new(self.index_vacuum_count.clone()))?;
         registry.register(Box::new(self.global_active.clone()))?;
+        registry.register(Box::new(self.phase.clone()))?;
+        registry.register(Box::new(self.percent_complete.clone()))?;
         registry.register(Box::new(self.is_autovacuum.clone()))?;
         registry.register(Box::new(self.duration_seconds.clone()))?;
         Ok(())
@@ -278,7 +327,7 @@ impl Collector for VacuumProgressCollector {
             );
             let rows = sqlx::query(VACUUM_PROGRESS_QUERY)
                 .bind(&excluded)
-                .fetch_all(pool)
+                .fetch_all(&mut *acquire_connection(pool).await?)
                 .instrument(query_span)
                 .await?;
 
@@ -294,8 +343,10 @@ impl Collector for VacuumProgressCollector {
                 }
             }
 
-            let update_span =
-                info_span!("vacuum_progress.update_metrics", active_rows = all_samples.len());
+            let update_span = info_span!(
+                "vacuum_progress.update_metrics",
+                active_rows = all_samples.len()
+            );
             let _g = update_span.enter();
 
             self.reset_progress_metrics();
@@ -327,7 +378,19 @@ impl Collector for VacuumProgressCollector {
                         0.0
                     };
 
-                    self.in_progress.with_label_values(&[database, table]).set(1);
+                    self.in_progress
+                        .with_label_values(&[database, table])
+                        .set(1);
+                    self.phase
+                        .with_label_values(&[database, table, sample.phase.as_str()])
+                        .set(1);
+                    if let Some(percent) =
+                        Self::percent_complete(&sample.phase, heap_scanned, heap_total)
+                    {
+                        self.percent_complete
+                            .with_label_values(&[database, table])
+                            .set(percent);
+                    }
                     self.heap_progress
                         .with_label_values(&[database, table])
                         .set(progress_ratio);
@@ -347,6 +410,7 @@ impl Collector for VacuumProgressCollector {
                     debug!(
                         database = %database,
                         table = %table,
+                        phase = %sample.phase,
                         heap_total,
                         heap_scanned,
                         heap_vacuumed = heap_vac,
@@ -409,6 +473,30 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_percent_complete_skips_unknown_total_and_index_phases() {
+        assert_eq!(
+            VacuumProgressCollector::percent_complete("scanning heap", 60, 100),
+            Some(60.0)
+        );
+        assert_eq!(
+            VacuumProgressCollector::percent_complete("truncating heap", 100, 100),
+            Some(100.0)
+        );
+        assert_eq!(
+            VacuumProgressCollector::percent_complete("initializing", 0, 0),
+            None
+        );
+        assert_eq!(
+            VacuumProgressCollector::percent_complete("vacuuming indexes", 40, 100),
+            None
+        );
+        assert_eq!(
+            VacuumProgressCollector::percent_complete("cleaning up indexes", 100, 100),
+            None
+        );
+    }
+
     #[test]
     fn vacuum_progress_query_is_cluster_wide_with_local_name_resolution() {
         // Hybrid design: a single cluster-wide query (must NOT be scoped to
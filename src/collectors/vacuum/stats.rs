@@ -1,4 +1,7 @@
-use crate::collectors::{Collector, util::get_excluded_databases};
+use crate::collectors::{
+    Collector,
+    util::{acquire_connection, datname_label, get_excluded_databases},
+};
 use anyhow::Result;
 use futures::future::BoxFuture;
 use prometheus::{IntGauge, IntGaugeVec, Opts, Registry};
@@ -12,6 +15,7 @@ use tracing_futures::Instrument as _;
 /// - `pg_vacuum_freeze_max_age_xids`
 /// - `pg_vacuum_database_freeze_age_pct_of_max`{`datname`}
 /// - `pg_vacuum_autovacuum_workers`{`datname`}
+/// - `pg_autovacuum_workers_blocked` (autovacuum workers waiting on a heavyweight lock)
 #[derive(Clone)]
 pub struct VacuumStatsCollector {
     // Per-database freeze age (age(datfrozenxid) in xids)
@@ -22,6 +26,8 @@ pub struct VacuumStatsCollector {
     db_freeze_age_pct_of_max: IntGaugeVec, // pg_vacuum_database_freeze_age_pct_of_max{datname}
     // Per-database autovacuum workers currently running
     autovac_workers: IntGaugeVec, // pg_vacuum_autovacuum_workers{datname}
+    // Autovacuum workers stuck behind a conflicting lock (e.g. ALTER TABLE)
+    autovac_workers_blocked: IntGauge, // pg_autovacuum_workers_blocked
 }
 
 impl Default for VacuumStatsCollector {
@@ -72,11 +78,18 @@ impl VacuumStatsCollector {
         )
         .expect("create pg_vacuum_autovacuum_workers");
 
+        let autovac_workers_blocked = IntGauge::with_opts(Opts::new(
+            "pg_autovacuum_workers_blocked",
+            "Number of autovacuum workers waiting on a lock (wait_event_type = 'Lock').",
+        ))
+        .expect("create pg_autovacuum_workers_blocked");
+
         Self {
             db_freeze_age_xids,
             freeze_max_age_xids,
             db_freeze_age_pct_of_max,
             autovac_workers,
+            autovac_workers_blocked,
         }
     }
 }
@@ -97,6 +110,7 @@ impl Collector for VacuumStatsCollector {
         registry.register(Box::new(self.freeze_max_age_xids.clone()))?;
         registry.register(Box::new(self.db_freeze_age_pct_of_max.clone()))?;
         registry.register(Box::new(self.autovac_workers.clone()))?;
+        registry.register(Box::new(self.autovac_workers_blocked.clone()))?;
         Ok(())
     }
 
@@ -118,12 +132,11 @@ impl Collector for VacuumStatsCollector {
                 db.operation = "SELECT",
                 db.statement = "SELECT current_setting('autovacuum_freeze_max_age')",
             );
-            let freeze_max_age_xids: i64 = sqlx::query_scalar(
-                r"SELECT current_setting('autovacuum_freeze_max_age')::bigint",
-            )
-            .fetch_one(pool)
-            .instrument(q_freeze_max)
-            .await?;
+            let freeze_max_age_xids: i64 =
+                sqlx::query_scalar(r"SELECT current_setting('autovacuum_freeze_max_age')::bigint")
+                    .fetch_one(&mut *acquire_connection(pool).await?)
+                    .instrument(q_freeze_max)
+                    .await?;
 
             // Query 2: per-database freeze age (xids) from pg_database
             let q_db_freeze_age = info_span!(
@@ -147,7 +160,7 @@ impl Collector for VacuumStatsCollector {
                 ",
             )
             .bind(&excluded)
-            .fetch_all(pool)
+            .fetch_all(&mut *acquire_connection(pool).await?)
             .instrument(q_db_freeze_age)
             .await?;
 
@@ -156,9 +169,7 @@ impl Collector for VacuumStatsCollector {
             let mut freeze_pct_values: HashMap<String, i64> = HashMap::new();
 
             for row in &rows {
-                let datname: String = row
-                    .try_get::<Option<String>, _>("datname")?
-                    .unwrap_or_else(|| "[unknown]".to_string());
+                let datname: String = datname_label(row.try_get("datname")?);
                 let age_xids: i64 = row.try_get::<i64, _>("freeze_age").unwrap_or(0);
 
                 seen_dbs.insert(datname.clone());
@@ -202,7 +213,8 @@ impl Collector for VacuumStatsCollector {
                 r"
                 SELECT
                     datname,
-                    COUNT(*)::bigint AS workers
+                    COUNT(*)::bigint AS workers,
+                    COUNT(*) FILTER (WHERE wait_event_type = 'Lock')::bigint AS blocked
                 FROM pg_stat_activity
                 WHERE backend_type = 'autovacuum worker'
                   AND NOT (COALESCE(datname,'') = ANY($1))
@@ -211,18 +223,18 @@ impl Collector for VacuumStatsCollector {
                 ",
             )
             .bind(&excluded)
-            .fetch_all(pool)
+            .fetch_all(&mut *acquire_connection(pool).await?)
             .instrument(q_workers)
             .await?;
 
             let mut worker_map: HashMap<String, i64> = HashMap::new();
+            let mut blocked_workers: i64 = 0;
             for row in &worker_rows {
-                let datname: String = row
-                    .try_get::<Option<String>, _>("datname")?
-                    .unwrap_or_else(|| "[unknown]".to_string());
+                let datname: String = datname_label(row.try_get("datname")?);
                 let workers: i64 = row.try_get::<i64, _>("workers").unwrap_or(0);
+                let blocked: i64 = row.try_get::<i64, _>("blocked").unwrap_or(0);
                 worker_map.insert(datname.clone(), workers);
-
+                blocked_workers = blocked_workers.saturating_add(blocked);
             }
 
             // Ensure we emit zeros for DBs with no workers visible this scrape
@@ -235,6 +247,7 @@ impl Collector for VacuumStatsCollector {
             self.db_freeze_age_pct_of_max.reset();
             self.autovac_workers.reset();
             self.freeze_max_age_xids.set(freeze_max_age_xids);
+            self.autovac_workers_blocked.set(blocked_workers);
 
             for (datname, age_xids) in freeze_age_values {
                 self.db_freeze_age_xids
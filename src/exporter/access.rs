@@ -0,0 +1,186 @@
+use axum::{
+    body::Body,
+    http::{
+        HeaderMap, HeaderValue, Request, StatusCode,
+        header::{AUTHORIZATION, USER_AGENT, WWW_AUTHENTICATE},
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use once_cell::sync::OnceCell;
+use regex::Regex;
+use secrecy::{ExposeSecret, SecretString};
+use tracing::warn;
+
+// Optional anchored regex the User-Agent header must match to scrape /metrics
+static ALLOWED_USER_AGENTS: OnceCell<Regex> = OnceCell::new();
+
+// Bearer token guarding /admin/* and /debug/config; those routes exist only when this is set
+static ADMIN_TOKEN: OnceCell<SecretString> = OnceCell::new();
+
+/// Set the `/metrics` User-Agent allowlist once at startup (`None` allows every client).
+pub fn set_allowed_user_agents(allowlist: Option<Regex>) {
+    if let Some(regex) = allowlist {
+        let _ = ALLOWED_USER_AGENTS.set(regex);
+    }
+}
+
+/// Get the configured `/metrics` User-Agent allowlist, if any.
+pub fn get_allowed_user_agents() -> Option<&'static Regex> {
+    ALLOWED_USER_AGENTS.get()
+}
+
+/// Returns `true` when the request may scrape: no allowlist is configured, or the
+/// `User-Agent` header is present, valid UTF-8, and matches the allowlist.
+fn user_agent_allowed(allowlist: Option<&Regex>, headers: &HeaderMap) -> bool {
+    let Some(regex) = allowlist else {
+        return true;
+    };
+
+    headers
+        .get(USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|user_agent| regex.is_match(user_agent))
+}
+
+/// Middleware guarding `/metrics`: rejects clients whose `User-Agent` does not match
+/// `--web.allowed-user-agents` with 403 before any collector runs.
+pub async fn require_allowed_user_agent(req: Request<Body>, next: Next) -> Response {
+    if user_agent_allowed(get_allowed_user_agents(), req.headers()) {
+        return next.run(req).await;
+    }
+
+    let user_agent = req
+        .headers()
+        .get(USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("none");
+    warn!(user_agent, "rejected scrape from disallowed user agent");
+
+    (
+        StatusCode::FORBIDDEN,
+        "# Forbidden: user agent not allowed\n",
+    )
+        .into_response()
+}
+
+/// Enable the admin endpoints with the bearer token they require (set once at startup).
+pub fn set_admin_token(token: SecretString) {
+    let _ = ADMIN_TOKEN.set(token);
+}
+
+/// Whether `--enable-admin-endpoints` was given (and therefore a token configured).
+pub fn admin_endpoints_enabled() -> bool {
+    ADMIN_TOKEN.get().is_some()
+}
+
+/// Returns `true` when the `Authorization: Bearer <token>` header matches `expected`.
+/// The comparison does not short-circuit on the first differing byte.
+fn bearer_token_matches(expected: &str, headers: &HeaderMap) -> bool {
+    let Some(presented) = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+
+    let (presented, expected) = (presented.trim().as_bytes(), expected.as_bytes());
+    presented.len() == expected.len()
+        && presented
+            .iter()
+            .zip(expected)
+            .fold(0_u8, |diff, (left, right)| diff | (left ^ right))
+            == 0
+}
+
+/// Middleware guarding `/admin/*` and `/debug/config`: rejects requests without the configured bearer token
+/// with 401 before the handler touches the database.
+pub async fn require_admin_token(req: Request<Body>, next: Next) -> Response {
+    let authorized = ADMIN_TOKEN
+        .get()
+        .is_some_and(|token| bearer_token_matches(token.expose_secret(), req.headers()));
+    if authorized {
+        return next.run(req).await;
+    }
+
+    let request_id = req
+        .headers()
+        .get("x-request-id")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("none");
+    warn!(request_id, path = %req.uri().path(), "rejected unauthorized admin request");
+
+    let mut res = (StatusCode::UNAUTHORIZED, "unauthorized\n").into_response();
+    res.headers_mut()
+        .insert(WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers_with(user_agent: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_static(user_agent));
+        headers
+    }
+
+    #[test]
+    fn test_user_agent_allowed_without_allowlist() {
+        assert!(user_agent_allowed(None, &HeaderMap::new()));
+        assert!(user_agent_allowed(None, &headers_with("curl/8.5.0")));
+    }
+
+    #[test]
+    fn test_user_agent_allowed_matches_allowlist() -> Result<(), regex::Error> {
+        let regex = Regex::new("^(?:Prometheus/.*)$")?;
+
+        assert!(user_agent_allowed(
+            Some(&regex),
+            &headers_with("Prometheus/3.1.0")
+        ));
+        assert!(!user_agent_allowed(
+            Some(&regex),
+            &headers_with("curl/8.5.0")
+        ));
+        Ok(())
+    }
+
+    fn headers_with_authorization(value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn test_bearer_token_matches() {
+        assert!(bearer_token_matches(
+            "s3cret",
+            &headers_with_authorization("Bearer s3cret")
+        ));
+        assert!(!bearer_token_matches(
+            "s3cret",
+            &headers_with_authorization("Bearer s3cre")
+        ));
+        assert!(!bearer_token_matches(
+            "s3cret",
+            &headers_with_authorization("Bearer s3creT")
+        ));
+        assert!(!bearer_token_matches(
+            "s3cret",
+            &headers_with_authorization("Basic s3cret")
+        ));
+        assert!(!bearer_token_matches("s3cret", &HeaderMap::new()));
+    }
+
+    #[test]
+    fn test_user_agent_allowed_rejects_missing_header() -> Result<(), regex::Error> {
+        let regex = Regex::new("^(?:.*)$")?;
+
+        assert!(!user_agent_allowed(Some(&regex), &HeaderMap::new()));
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use crate::collectors::shared_pool::SharedPool;
+use axum::{
+    extract::Extension,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::{error, instrument, warn};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AdminResult {
+    action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+// pg_stat_statements_reset() returns void before PostgreSQL 17 and the reset
+// timestamp afterwards; casting to text covers both.
+const RESET_STATEMENTS_QUERY: &str = "SELECT pg_stat_statements_reset()::text";
+const RESET_STATS_QUERY: &str = "SELECT pg_stat_reset()::text";
+
+fn request_id(headers: &HeaderMap) -> &str {
+    headers
+        .get("x-request-id")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("none")
+}
+
+async fn run_reset(
+    pool: &PgPool,
+    headers: &HeaderMap,
+    action: &str,
+    query: &'static str,
+) -> Response {
+    let request_id = request_id(headers);
+    warn!(request_id, action, "admin endpoint invoked");
+
+    match sqlx::query_scalar::<_, Option<String>>(query)
+        .fetch_one(pool)
+        .await
+    {
+        Ok(result) => {
+            warn!(request_id, action, "admin reset completed");
+            let body = AdminResult {
+                action: action.to_string(),
+                result: result.filter(|value| !value.is_empty()),
+                error: None,
+            };
+            (StatusCode::OK, Json(body)).into_response()
+        }
+        Err(e) => {
+            error!(request_id, action, error = %e, "admin reset failed");
+            let body = AdminResult {
+                action: action.to_string(),
+                result: None,
+                error: Some(e.to_string()),
+            };
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(body)).into_response()
+        }
+    }
+}
+
+// POST /admin/reset-statements
+#[instrument(skip(pool, headers), fields(http.route="/admin/reset-statements"))]
+pub async fn reset_statements(
+    Extension(pool): Extension<SharedPool>,
+    headers: HeaderMap,
+) -> Response {
+    run_reset(
+        &pool.get(),
+        &headers,
+        "pg_stat_statements_reset",
+        RESET_STATEMENTS_QUERY,
+    )
+    .await
+}
+
+// POST /admin/reset-stats
+#[instrument(skip(pool, headers), fields(http.route="/admin/reset-stats"))]
+pub async fn reset_stats(Extension(pool): Extension<SharedPool>, headers: HeaderMap) -> Response {
+    run_reset(&pool.get(), &headers, "pg_stat_reset", RESET_STATS_QUERY).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_request_id_defaults_to_none() {
+        assert_eq!(request_id(&HeaderMap::new()), "none");
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", HeaderValue::from_static("01ABC"));
+        assert_eq!(request_id(&headers), "01ABC");
+    }
+
+    #[test]
+    fn test_admin_result_skips_empty_fields() -> Result<(), serde_json::Error> {
+        let body = AdminResult {
+            action: "pg_stat_reset".to_string(),
+            result: None,
+            error: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&body)?,
+            r#"{"action":"pg_stat_reset"}"#
+        );
+        Ok(())
+    }
+}
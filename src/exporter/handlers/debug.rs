@@ -0,0 +1,139 @@
+use axum::{
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+use tracing::{instrument, warn};
+
+// CLI-equivalent flags of the running exporter, recorded once at startup
+static EFFECTIVE_FLAGS: OnceCell<Vec<EffectiveFlag>> = OnceCell::new();
+
+/// One resolved command-line flag of the running exporter.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct EffectiveFlag {
+    /// Long flag including the leading dashes, e.g. `--port`
+    pub flag: String,
+    /// Flag value with secrets redacted; `None` for switches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Where the value came from: `cli`, `env`, `file` or `default`
+    pub source: &'static str,
+}
+
+/// Record the effective flags served by `GET /debug/config` (set once at startup).
+pub fn set_effective_flags(flags: Vec<EffectiveFlag>) {
+    let _ = EFFECTIVE_FLAGS.set(flags);
+}
+
+#[derive(Serialize, Debug)]
+struct DebugConfig<'a> {
+    version: &'static str,
+    command_line: String,
+    flags: &'a [EffectiveFlag],
+}
+
+/// Quote `value` for a POSIX shell unless it only holds characters that need no quoting.
+fn shell_quote(value: &str) -> String {
+    let safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:,=@%+".contains(c));
+    if safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+/// Render the flags as a copy-pasteable `pg_exporter ...` invocation.
+fn command_line(flags: &[EffectiveFlag]) -> String {
+    let mut line = String::from(env!("CARGO_PKG_NAME"));
+    for flag in flags {
+        line.push(' ');
+        line.push_str(&flag.flag);
+        if let Some(value) = &flag.value {
+            line.push(' ');
+            line.push_str(&shell_quote(value));
+        }
+    }
+    line
+}
+
+// GET /debug/config
+#[instrument(skip(headers), fields(http.route="/debug/config"))]
+pub async fn config(headers: HeaderMap) -> Response {
+    let request_id = headers
+        .get("x-request-id")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("none");
+    warn!(request_id, "debug config endpoint invoked");
+
+    let flags = EFFECTIVE_FLAGS.get().map_or(&[][..], Vec::as_slice);
+    let body = DebugConfig {
+        version: env!("CARGO_PKG_VERSION"),
+        command_line: command_line(flags),
+        flags,
+    };
+
+    (StatusCode::OK, Json(body)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flag(flag: &str, value: Option<&str>) -> EffectiveFlag {
+        EffectiveFlag {
+            flag: flag.to_string(),
+            value: value.map(str::to_string),
+            source: "cli",
+        }
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("9432"), "9432");
+        assert_eq!(shell_quote("db1,db2"), "db1,db2");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("Prometheus/.*"), "'Prometheus/.*'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+
+    #[test]
+    fn test_command_line_renders_switches_and_values() {
+        let flags = [
+            flag("--port", Some("9432")),
+            flag("--dsn", Some("postgresql:///postgres?host=/run/postgresql")),
+            flag("--collector.locks", None),
+            flag("--no-collector.vacuum", None),
+        ];
+
+        assert_eq!(
+            command_line(&flags),
+            "pg_exporter --port 9432 --dsn 'postgresql:///postgres?host=/run/postgresql' \
+             --collector.locks --no-collector.vacuum"
+        );
+    }
+
+    #[test]
+    fn test_debug_config_serializes_switches_without_value() -> Result<(), serde_json::Error> {
+        let flags = [flag("--collector.locks", None)];
+        let json = serde_json::to_value(DebugConfig {
+            version: "1.0.0",
+            command_line: command_line(&flags),
+            flags: &flags,
+        })?;
+
+        assert_eq!(
+            json.pointer("/flags/0"),
+            Some(&serde_json::json!({"flag": "--collector.locks", "source": "cli"}))
+        );
+        assert_eq!(
+            json.pointer("/command_line")
+                .and_then(serde_json::Value::as_str),
+            Some("pg_exporter --collector.locks")
+        );
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::exporter::GIT_COMMIT_HASH;
+use crate::{collectors::shared_pool::SharedPool, exporter::GIT_COMMIT_HASH};
 use axum::{
     body::Body,
     extract::Extension,
@@ -95,8 +95,8 @@ fn create_app_headers(health: &Health) -> HeaderMap {
 
 // Main axum handler for health
 #[instrument(skip(pool), fields(http.route="/health"))]
-pub async fn health(method: Method, pool: Extension<PgPool>) -> impl IntoResponse {
-    let db_result = check_database_health(&pool.0).await;
+pub async fn health(method: Method, pool: Extension<SharedPool>) -> impl IntoResponse {
+    let db_result = check_database_health(&pool.0.get()).await;
     let health = create_health_response(db_result);
     let body = create_response_body(&method, &health);
     let headers = create_app_headers(&health);
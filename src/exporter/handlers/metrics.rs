@@ -1,15 +1,17 @@
-use crate::collectors::registry::{CollectorRegistry, ScrapeError};
+use crate::collectors::{
+    registry::{CollectorRegistry, ScrapeError},
+    shared_pool::SharedPool,
+};
 use axum::{
     extract::Extension,
     http::{HeaderMap, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
 };
-use sqlx::PgPool;
 use tracing::{debug, error, instrument};
 
 #[instrument(skip(pool, registry), fields(http.route="/metrics"))]
 pub async fn metrics(
-    Extension(pool): Extension<PgPool>,
+    Extension(pool): Extension<SharedPool>,
     Extension(registry): Extension<CollectorRegistry>,
 ) -> Response {
     let mut headers = HeaderMap::new();
@@ -18,11 +20,16 @@ pub async fn metrics(
         HeaderValue::from_static("text/plain; charset=utf-8"),
     );
 
-    match registry.collect_all_bytes(&pool).await {
+    match registry.collect_all_bytes(&pool.get()).await {
         Ok(metrics) => {
             debug!("Successfully collected metrics");
             (StatusCode::OK, headers, metrics).into_response()
         }
+        // --fail-scrape-on-db-down: keep the pg_up 0 body so metric-based alerts still fire
+        Err(ScrapeError::DatabaseDown(metrics)) => {
+            debug!("PostgreSQL unreachable; answering 503 with pg_up 0");
+            (StatusCode::SERVICE_UNAVAILABLE, headers, metrics).into_response()
+        }
         Err(e) => {
             error!("Failed to collect metrics: {}", e);
             let status = match e {
@@ -30,7 +37,8 @@ pub async fn metrics(
                 ScrapeError::Busy
                 | ScrapeError::CollectorFailed(_)
                 | ScrapeError::Encode(_)
-                | ScrapeError::Utf8(_) => StatusCode::SERVICE_UNAVAILABLE,
+                | ScrapeError::Utf8(_)
+                | ScrapeError::DatabaseDown(_) => StatusCode::SERVICE_UNAVAILABLE,
             };
 
             (
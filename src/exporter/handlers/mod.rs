@@ -1,3 +1,7 @@
+pub mod admin;
+
+pub mod debug;
+
 pub mod health;
 pub use self::health::health;
 
@@ -384,3 +384,139 @@ async fn test_binary_uses_dsn_file() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that `--web.allowed-user-agents` rejects other scrapers but never guards /health
+#[tokio::test]
+async fn test_binary_allowed_user_agents_guards_metrics_only() -> Result<()> {
+    let port = common::get_available_port();
+    let dsn = common::get_test_dsn();
+
+    let child = Command::new(get_binary_path())
+        .args([
+            "--port",
+            &port.to_string(),
+            "--dsn",
+            &dsn,
+            "--web.allowed-user-agents",
+            "Prometheus/.*",
+        ])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let _guard = ChildGuard::new(child);
+
+    if !common::wait_for_server(port, 100).await {
+        anyhow::bail!("Server failed to start on port {port}");
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+    let url = format!("http://localhost:{port}");
+
+    let denied = client
+        .get(format!("{url}/metrics"))
+        .header("user-agent", "curl/8.5.0")
+        .send()
+        .await?;
+    assert_eq!(denied.status(), reqwest::StatusCode::FORBIDDEN);
+
+    let missing = client.get(format!("{url}/metrics")).send().await?;
+    assert_eq!(missing.status(), reqwest::StatusCode::FORBIDDEN);
+
+    let allowed = client
+        .get(format!("{url}/metrics"))
+        .header("user-agent", "Prometheus/3.1.0")
+        .send()
+        .await?;
+    assert!(allowed.status().is_success());
+    assert!(allowed.text().await?.contains("pg_up"));
+
+    let health = client
+        .get(format!("{url}/health"))
+        .header("user-agent", "kube-probe/1.30")
+        .send()
+        .await?;
+    assert!(health.status().is_success());
+
+    Ok(())
+}
+
+/// Test that admin endpoints are absent by default and require the bearer token when enabled
+#[tokio::test]
+async fn test_binary_admin_endpoints_require_enablement_and_token() -> Result<()> {
+    let dsn = common::get_test_dsn();
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let disabled_port = common::get_available_port();
+    let _disabled = start_and_wait(disabled_port, &dsn).await?;
+    let not_mounted = client
+        .post(format!(
+            "http://localhost:{disabled_port}/admin/reset-stats"
+        ))
+        .bearer_auth("s3cret")
+        .send()
+        .await?;
+    assert_eq!(not_mounted.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let port = common::get_available_port();
+    let child = Command::new(get_binary_path())
+        .env("PG_EXPORTER_WEB_ADMIN_TOKEN", "s3cret")
+        .args([
+            "--port",
+            &port.to_string(),
+            "--dsn",
+            &dsn,
+            "--enable-admin-endpoints",
+        ])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let _guard = ChildGuard::new(child);
+
+    if !common::wait_for_server(port, 100).await {
+        anyhow::bail!("Server failed to start on port {port}");
+    }
+    let url = format!("http://localhost:{port}/admin/reset-stats");
+
+    let anonymous = client.post(&url).send().await?;
+    assert_eq!(anonymous.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let wrong = client.post(&url).bearer_auth("nope").send().await?;
+    assert_eq!(wrong.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let authorized = client.post(&url).bearer_auth("s3cret").send().await?;
+    assert_eq!(authorized.status(), reqwest::StatusCode::OK);
+    assert!(authorized.text().await?.contains("pg_stat_reset"));
+
+    let config_url = format!("http://localhost:{port}/debug/config");
+    let anonymous = client.get(&config_url).send().await?;
+    assert_eq!(anonymous.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let config = client.get(&config_url).bearer_auth("s3cret").send().await?;
+    assert_eq!(config.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = config.json().await?;
+    let command_line = body["command_line"].as_str().unwrap_or_default();
+    assert!(command_line.starts_with("pg_exporter "));
+    assert!(command_line.contains(&format!("--port {port}")));
+    assert!(command_line.contains("--enable-admin-endpoints"));
+    assert!(command_line.contains("--web.admin-token '<redacted>'"));
+    assert!(!body.to_string().contains("s3cret"));
+
+    Ok(())
+}
+
+/// Test that enabling admin endpoints without a token is a startup error
+#[test]
+fn test_binary_admin_endpoints_without_token_fails() {
+    let output = Command::new(get_binary_path())
+        .env_remove("PG_EXPORTER_WEB_ADMIN_TOKEN")
+        .args(["--enable-admin-endpoints"])
+        .output()
+        .expect("Failed to execute binary");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--web.admin-token"));
+}
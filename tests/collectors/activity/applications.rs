@@ -0,0 +1,100 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{
+    Collector,
+    activity::applications::{ApplicationBackendsCollector, OTHER_APPLICATIONS},
+};
+use prometheus::Registry;
+use sqlx::{Connection, PgConnection, postgres::PgConnectOptions};
+use std::str::FromStr;
+
+const METRIC: &str = "pg_stat_activity_backends_by_application";
+
+/// `(application_name, state, value)` of every exported series
+fn series(registry: &Registry) -> Vec<(String, String, i64)> {
+    registry
+        .gather()
+        .iter()
+        .filter(|m| m.name() == METRIC)
+        .flat_map(|m| m.get_metric().iter())
+        .map(|m| {
+            let label = |name: &str| {
+                m.get_label()
+                    .iter()
+                    .find(|l| l.name() == name)
+                    .map(|l| l.value().to_string())
+                    .unwrap_or_default()
+            };
+            (
+                label("application_name"),
+                label("state"),
+                common::metric_value_to_i64(m.get_gauge().value()),
+            )
+        })
+        .collect()
+}
+
+async fn connect_as(application_name: &str) -> Result<PgConnection> {
+    let opts =
+        PgConnectOptions::from_str(&common::get_test_dsn())?.application_name(application_name);
+    Ok(PgConnection::connect_with(&opts).await?)
+}
+
+#[tokio::test]
+async fn test_backends_by_application_counts_idle_sessions() -> Result<()> {
+    const APP: &str = "pg_exporter_by_application_test";
+
+    let pool = common::create_test_pool().await?;
+    let first = connect_as(APP).await?;
+    let second = connect_as(APP).await?;
+
+    let collector = ApplicationBackendsCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let idle = series(&registry)
+        .into_iter()
+        .find(|(application, state, _)| application == APP && state == "idle")
+        .map(|(_, _, value)| value);
+
+    first.close().await?;
+    second.close().await?;
+    pool.close().await;
+
+    assert_eq!(idle, Some(2), "both idle sessions of {APP} are counted");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_backends_by_application_limit_folds_other_applications() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let first = connect_as("pg_exporter_by_application_limit_a").await?;
+    let second = connect_as("pg_exporter_by_application_limit_b").await?;
+
+    let collector = ApplicationBackendsCollector::with_limit(1);
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let exported = series(&registry);
+
+    first.close().await?;
+    second.close().await?;
+    pool.close().await;
+
+    let mut applications: Vec<&str> = exported
+        .iter()
+        .map(|(application, _, _)| application.as_str())
+        .collect();
+    applications.sort_unstable();
+    applications.dedup();
+
+    assert_eq!(
+        applications.len(),
+        2,
+        "one kept application plus {OTHER_APPLICATIONS}, got {applications:?}"
+    );
+    assert!(applications.contains(&OTHER_APPLICATIONS));
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, activity::churn::ConnectionChurnCollector};
+use prometheus::Registry;
+use sqlx::Connection;
+
+fn opened_total(registry: &Registry, datname: &str) -> Option<f64> {
+    registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == "pg_stat_activity_connections_opened_total")
+        .and_then(|family| {
+            family
+                .get_metric()
+                .iter()
+                .find(|m| {
+                    m.get_label()
+                        .iter()
+                        .any(|l| l.name() == "datname" && l.value() == datname)
+                })
+                .map(|m| m.get_counter().value())
+        })
+}
+
+#[tokio::test]
+async fn test_connection_churn_registers_without_error() -> Result<()> {
+    let collector = ConnectionChurnCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    assert_eq!(collector.name(), "connection_churn");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_connection_churn_counts_new_sessions() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let collector = ConnectionChurnCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let version: i32 = sqlx::query_scalar("SELECT current_setting('server_version_num')::int")
+        .fetch_one(&pool)
+        .await?;
+    if version >= 140_000 {
+        // pg_stat_database_sessions_total carries the exact count; no second series
+        assert_eq!(opened_total(&registry, "postgres"), None);
+        pool.close().await;
+        return Ok(());
+    }
+
+    let before = opened_total(&registry, "postgres");
+    assert!(before.is_some(), "postgres should get a series");
+    assert!(
+        registry
+            .gather()
+            .iter()
+            .filter(|f| f.name() == "pg_stat_activity_connections_opened_total")
+            .all(|f| f.get_field_type() == prometheus::proto::MetricType::COUNTER),
+        "a _total series must be a counter"
+    );
+
+    // Open and close a fresh session
+    let conn = sqlx::PgConnection::connect(&common::get_test_dsn()).await?;
+    conn.close().await?;
+
+    collector.collect(&pool).await?;
+    let after = opened_total(&registry, "postgres");
+    assert!(after.unwrap_or(0.0) >= before.unwrap_or(0.0));
+
+    pool.close().await;
+    Ok(())
+}
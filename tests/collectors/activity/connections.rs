@@ -34,6 +34,7 @@ async fn test_connections_collector_has_all_metrics() -> Result<()> {
         "pg_stat_activity_waiting_connections",
         "pg_stat_activity_blocked_connections",
         "pg_stat_activity_on_cpu_backends",
+        "pg_stat_activity_writing_backends",
     ];
 
     for metric_name in expected_metrics {
@@ -354,3 +355,202 @@ async fn test_connections_collector_detects_on_cpu_backend() -> Result<()> {
     );
     Ok(())
 }
+
+// A transaction that was assigned a real xid (it wrote) counts as a writing backend.
+#[tokio::test]
+async fn test_connections_collector_counts_writing_backends() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let collector = ConnectionsCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+
+    let writing_total = |registry: &Registry| -> f64 {
+        registry
+            .gather()
+            .iter()
+            .find(|m| m.name() == "pg_stat_activity_writing_backends")
+            .map_or(0.0, |f| {
+                f.get_metric().iter().map(|m| m.get_gauge().value()).sum()
+            })
+    };
+
+    // txid_current() forces xid assignment, just like the first write of a transaction
+    let mut tx = pool.begin().await?;
+    sqlx::query("SELECT txid_current()")
+        .execute(&mut *tx)
+        .await?;
+
+    collector.collect(&pool).await?;
+    let during = writing_total(&registry);
+
+    tx.rollback().await?;
+    pool.close().await;
+
+    assert!(
+        during >= 1.0,
+        "expected the open write transaction to be counted, got {during}"
+    );
+    Ok(())
+}
+
+// Sessions of a role listed in --exclude-users must not show up in activity metrics.
+#[tokio::test]
+async fn test_connections_collector_skips_excluded_users() -> Result<()> {
+    use pg_exporter::collectors::util::set_excluded_users;
+    use sqlx::{Connection, PgConnection, postgres::PgConnectOptions};
+    use std::str::FromStr;
+
+    const ROLE: &str = "exporter_excluded_user_test";
+    const APP: &str = "pg_exporter_exclude_users_test";
+
+    let pool = common::create_test_pool().await?;
+    sqlx::query(sqlx::AssertSqlSafe(format!(
+        "DO $$ BEGIN
+             IF NOT EXISTS (SELECT 1 FROM pg_roles WHERE rolname = '{ROLE}') THEN
+                 CREATE ROLE {ROLE} LOGIN PASSWORD '{ROLE}';
+             END IF;
+         END $$"
+    )))
+    .execute(&pool)
+    .await?;
+
+    set_excluded_users(vec![ROLE.to_string()]);
+
+    let opts = PgConnectOptions::from_str(&common::get_test_dsn())?
+        .username(ROLE)
+        .password(ROLE)
+        .application_name(APP);
+    let conn = PgConnection::connect_with(&opts).await?;
+
+    let collector = ConnectionsCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let seen = registry
+        .gather()
+        .iter()
+        .filter(|m| m.name() == "pg_stat_activity_connections_by_application")
+        .flat_map(|m| m.get_metric().iter())
+        .any(|m| {
+            m.get_label()
+                .iter()
+                .any(|l| l.name() == "application_name" && l.value() == APP)
+        });
+
+    conn.close().await?;
+    pool.close().await;
+
+    assert!(!seen, "sessions of an excluded role must not be counted");
+    Ok(())
+}
+
+// Without pg_read_all_stats, other roles' sessions have a NULL state. They must still be
+// counted (as state="unknown") so the per-state series add up to the used connections.
+#[tokio::test]
+async fn test_connections_collector_counts_sessions_with_hidden_state() -> Result<()> {
+    use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+    use std::str::FromStr;
+
+    const ROLE: &str = "exporter_hidden_state_test";
+
+    let pool = common::create_test_pool().await?;
+    sqlx::query(sqlx::AssertSqlSafe(format!(
+        "DO $$ BEGIN
+             IF NOT EXISTS (SELECT 1 FROM pg_roles WHERE rolname = '{ROLE}') THEN
+                 CREATE ROLE {ROLE} LOGIN PASSWORD '{ROLE}';
+             END IF;
+         END $$"
+    )))
+    .execute(&pool)
+    .await?;
+
+    // A superuser session the unprivileged role cannot inspect
+    let mut hidden = pool.acquire().await?;
+    sqlx::query("SELECT 1").execute(&mut *hidden).await?;
+
+    let opts = PgConnectOptions::from_str(&common::get_test_dsn())?
+        .username(ROLE)
+        .password(ROLE);
+    let unprivileged = PgPoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await?;
+
+    let collector = ConnectionsCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&unprivileged).await?;
+
+    let families = registry.gather();
+    let by_state: Vec<(String, f64)> = families
+        .iter()
+        .filter(|m| m.name() == "pg_stat_activity_count")
+        .flat_map(|m| m.get_metric().iter())
+        .map(|m| {
+            let state = m
+                .get_label()
+                .iter()
+                .find(|l| l.name() == "state")
+                .map(|l| l.value().to_string())
+                .unwrap_or_default();
+            (state, m.get_gauge().value())
+        })
+        .collect();
+    let used = families
+        .iter()
+        .find(|m| m.name() == "pg_stat_activity_used_connections")
+        .and_then(|m| m.get_metric().first())
+        .map_or(0.0, |m| m.get_gauge().value());
+
+    drop(hidden);
+    unprivileged.close().await;
+    pool.close().await;
+
+    assert!(
+        by_state
+            .iter()
+            .any(|(state, count)| state == "unknown" && *count >= 1.0),
+        "sessions with a hidden state should be counted as unknown: {by_state:?}"
+    );
+    let sum: f64 = by_state.iter().map(|(_, count)| count).sum();
+    assert!(
+        (sum - used).abs() < f64::EPSILON,
+        "per-state counts ({sum}) should add up to used connections ({used})"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_connections_collector_counts_backends_by_type() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let collector = ConnectionsCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let metric_families = registry.gather();
+    let backends = |backend_type: &str| {
+        metric_families
+            .iter()
+            .filter(|m| m.name() == "pg_stat_activity_backends")
+            .flat_map(prometheus::proto::MetricFamily::get_metric)
+            .find(|m| {
+                m.get_label()
+                    .iter()
+                    .any(|l| l.name() == "backend_type" && l.value() == backend_type)
+            })
+            .map_or(0.0, |m| m.get_gauge().value())
+    };
+
+    // Unlike pg_stat_activity_count, the exporter's own session and background
+    // processes are counted: they use memory like any other backend.
+    assert!(backends("client backend") >= 1.0);
+    assert!(backends("checkpointer") >= 1.0);
+
+    pool.close().await;
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{
+    Collector, activity::idle_in_transaction::IdleInTransactionLocksCollector,
+};
+use prometheus::Registry;
+use sqlx::{Connection, PgConnection};
+use std::time::Duration;
+
+fn value(registry: &Registry, metric: &str, datname: &str) -> Option<f64> {
+    registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == metric)
+        .and_then(|family| {
+            family
+                .get_metric()
+                .iter()
+                .find(|m| {
+                    m.get_label()
+                        .iter()
+                        .any(|l| l.name() == "datname" && l.value() == datname)
+                })
+                .map(|m| m.get_gauge().value())
+        })
+}
+
+#[tokio::test]
+async fn test_idle_in_transaction_locks_exports_every_database() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let registry = Registry::new();
+    let collector = IdleInTransactionLocksCollector::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let holding = value(
+        &registry,
+        "pg_stat_activity_idle_in_transaction_holding_locks",
+        "postgres",
+    );
+    let age = value(
+        &registry,
+        "pg_stat_activity_idle_in_transaction_holding_locks_max_age_seconds",
+        "postgres",
+    );
+
+    pool.close().await;
+
+    assert!(holding.is_some_and(|v| v >= 0.0));
+    assert!(age.is_some_and(|v| v >= 0.0));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_idle_in_transaction_locks_counts_session_holding_relation_lock() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    // Reading a catalog inside an open transaction keeps its AccessShareLock until the end
+    let mut holder = PgConnection::connect(&common::get_test_dsn()).await?;
+    sqlx::query("BEGIN").execute(&mut holder).await?;
+    sqlx::query("SELECT count(*) FROM pg_class")
+        .execute(&mut holder)
+        .await?;
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+
+    let registry = Registry::new();
+    let collector = IdleInTransactionLocksCollector::new();
+    collector.register_metrics(&registry)?;
+    let result = collector.collect(&pool).await;
+
+    sqlx::query("ROLLBACK").execute(&mut holder).await?;
+    holder.close().await?;
+    pool.close().await;
+    result?;
+
+    let holding = value(
+        &registry,
+        "pg_stat_activity_idle_in_transaction_holding_locks",
+        "postgres",
+    )
+    .unwrap_or(0.0);
+    let age = value(
+        &registry,
+        "pg_stat_activity_idle_in_transaction_holding_locks_max_age_seconds",
+        "postgres",
+    )
+    .unwrap_or(0.0);
+
+    assert!(
+        holding >= 1.0,
+        "expected the open transaction to count, got {holding}"
+    );
+    assert!(
+        age >= 1.0,
+        "expected the transaction age to be tracked, got {age}"
+    );
+    Ok(())
+}
@@ -1,5 +1,11 @@
 //! Tests for activity collector and its sub-collectors
 
+mod applications;
+mod churn;
 mod connections;
+mod idle_in_transaction;
+mod parallel;
 mod queries;
+mod query_age;
+mod threshold;
 mod wait;
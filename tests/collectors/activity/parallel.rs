@@ -0,0 +1,87 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, activity::parallel::ParallelQueriesCollector};
+use prometheus::Registry;
+
+fn gauge(registry: &Registry, name: &str) -> Option<f64> {
+    registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == name)
+        .and_then(|family| family.get_metric().first().map(|m| m.get_gauge().value()))
+}
+
+#[tokio::test]
+async fn test_parallel_queries_collector_exports_gauges() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let registry = Registry::new();
+    let collector = ParallelQueriesCollector::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let groups = gauge(&registry, "pg_stat_activity_parallel_groups");
+    let max_workers = gauge(&registry, "pg_stat_activity_parallel_max_workers_per_group");
+    assert!(groups.is_some_and(|v| v >= 0.0));
+    assert!(max_workers.is_some_and(|v| v >= 0.0));
+
+    pool.close().await;
+    Ok(())
+}
+
+// Force a parallel plan with a slow, parallel-safe filter so workers are still running
+// while the collector samples pg_stat_activity.
+#[tokio::test]
+async fn test_parallel_queries_collector_detects_running_workers() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS test_parallel_groups AS SELECT g FROM generate_series(1, 2000) g",
+    )
+    .execute(&pool)
+    .await?;
+
+    let worker_pool = pool.clone();
+    let parallel_query = tokio::spawn(async move {
+        let Ok(mut conn) = worker_pool.acquire().await else {
+            return;
+        };
+        for setting in [
+            "SET parallel_setup_cost = 0",
+            "SET parallel_tuple_cost = 0",
+            "SET min_parallel_table_scan_size = 0",
+            "SET max_parallel_workers_per_gather = 2",
+        ] {
+            let _ = sqlx::query(setting).execute(&mut *conn).await;
+        }
+        let _ = sqlx::query(
+            "SELECT count(*) FROM test_parallel_groups WHERE pg_sleep(0.001) IS NOT NULL",
+        )
+        .fetch_one(&mut *conn)
+        .await;
+        let _ = sqlx::query("RESET ALL").execute(&mut *conn).await;
+    });
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(800)).await;
+
+    let registry = Registry::new();
+    let collector = ParallelQueriesCollector::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let groups = gauge(&registry, "pg_stat_activity_parallel_groups").unwrap_or(0.0);
+    let max_workers =
+        gauge(&registry, "pg_stat_activity_parallel_max_workers_per_group").unwrap_or(0.0);
+
+    let _ = parallel_query.await;
+    sqlx::query("DROP TABLE IF EXISTS test_parallel_groups")
+        .execute(&pool)
+        .await?;
+    pool.close().await;
+
+    assert!(groups >= 1.0, "expected a parallel group, got {groups}");
+    assert!(
+        max_workers >= 1.0,
+        "expected parallel workers, got {max_workers}"
+    );
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, activity::query_age::QueryAgeCollector};
+use prometheus::Registry;
+
+fn bucket(registry: &Registry, le: &str, state: &str) -> Option<f64> {
+    registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == "pg_stat_activity_query_age_bucket")
+        .and_then(|family| {
+            family.get_metric().iter().find(|m| {
+                let labels = m.get_label();
+                labels.iter().any(|l| l.name() == "le" && l.value() == le)
+                    && labels
+                        .iter()
+                        .any(|l| l.name() == "state" && l.value() == state)
+            })
+        })
+        .map(|m| m.get_gauge().value())
+}
+
+#[tokio::test]
+async fn test_query_age_collector_exports_active_buckets() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let registry = Registry::new();
+    let collector = QueryAgeCollector::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    // state="active" is always exported, with every default bucket plus +Inf.
+    for le in ["1", "10", "60", "+Inf"] {
+        assert!(
+            bucket(&registry, le, "active").is_some_and(|v| v >= 0.0),
+            "missing active bucket le={le}"
+        );
+    }
+
+    pool.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_query_age_collector_counts_running_query() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let sleeper_pool = pool.clone();
+    let sleeper = tokio::spawn(async move {
+        let _ = sqlx::query("SELECT pg_sleep(2)")
+            .execute(&sleeper_pool)
+            .await;
+    });
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(1200)).await;
+
+    let registry = Registry::new();
+    let collector = QueryAgeCollector::with_buckets(&[1.0, 60.0]);
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let _ = sleeper.await;
+    pool.close().await;
+
+    let over_one_second = bucket(&registry, "+Inf", "active").unwrap_or(0.0)
+        - bucket(&registry, "1", "active").unwrap_or(0.0);
+    assert!(
+        over_one_second >= 1.0,
+        "pg_sleep(2) should be counted above the 1s bucket, got {over_one_second}"
+    );
+
+    // Buckets are cumulative.
+    assert!(
+        bucket(&registry, "1", "active").unwrap_or(0.0)
+            <= bucket(&registry, "60", "active").unwrap_or(0.0)
+    );
+
+    Ok(())
+}
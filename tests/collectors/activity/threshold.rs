@@ -0,0 +1,101 @@
+//! `pg_stat_activity_slow_queries` and `pg_stat_activity_stuck_backends` share one query
+//! shape, so both are checked by the same cases.
+
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{
+    Collector,
+    activity::{slow::SlowQueriesCollector, stuck::StuckBackendsCollector},
+};
+use prometheus::Registry;
+use std::time::Duration;
+
+type Build = fn(Duration) -> Box<dyn Collector + Send + Sync>;
+
+// pg_sleep waits on a Timeout event, not a lock, so a long sleep is both a slow query and
+// a wedged active backend once the threshold has passed.
+const CASES: [(&str, Build); 2] = [
+    ("pg_stat_activity_slow_queries", |threshold| {
+        Box::new(SlowQueriesCollector::with_threshold(threshold))
+    }),
+    ("pg_stat_activity_stuck_backends", |threshold| {
+        Box::new(StuckBackendsCollector::with_threshold(threshold))
+    }),
+];
+
+fn backends(registry: &Registry, metric: &str, datname: &str) -> Option<f64> {
+    registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == metric)
+        .and_then(|family| {
+            family
+                .get_metric()
+                .iter()
+                .find(|m| {
+                    m.get_label()
+                        .iter()
+                        .any(|l| l.name() == "datname" && l.value() == datname)
+                })
+                .map(|m| m.get_gauge().value())
+        })
+}
+
+#[tokio::test]
+async fn test_threshold_gauges_export_zero_per_database() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    for (metric, build) in CASES {
+        let registry = Registry::new();
+        // A threshold no test query can reach
+        let collector = build(Duration::from_hours(24));
+        collector.register_metrics(&registry)?;
+        collector.collect(&pool).await?;
+
+        assert_eq!(
+            backends(&registry, metric, "postgres"),
+            Some(0.0),
+            "{metric}"
+        );
+    }
+
+    pool.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_threshold_gauges_count_long_running_query() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let sleeper_pool = pool.clone();
+    let sleeper = tokio::spawn(async move {
+        let _ = sqlx::query("SELECT pg_sleep(3)")
+            .execute(&sleeper_pool)
+            .await;
+    });
+
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+
+    let mut counts = Vec::new();
+    for (metric, build) in CASES {
+        let registry = Registry::new();
+        let collector = build(Duration::from_secs(1));
+        collector.register_metrics(&registry)?;
+        collector.collect(&pool).await?;
+        counts.push((
+            metric,
+            backends(&registry, metric, "postgres").unwrap_or(0.0),
+        ));
+    }
+
+    let _ = sleeper.await;
+    pool.close().await;
+
+    for (metric, count) in counts {
+        assert!(
+            count >= 1.0,
+            "expected the sleeping query to be counted in {metric}, got {count}"
+        );
+    }
+    Ok(())
+}
@@ -10,7 +10,8 @@
 use super::common;
 use anyhow::{Result, anyhow};
 use pg_exporter::collectors::util::{
-    acquire_db_query_permit, get_max_db_concurrency, open_db_connection,
+    acquire_db_query_permit, get_max_db_concurrency, is_database_excluded, list_target_databases,
+    open_db_connection,
 };
 use std::sync::{
     Arc,
@@ -162,3 +163,27 @@ async fn per_database_connections_never_exceed_concurrency_limit() -> Result<()>
     test_db.cleanup().await?;
     Ok(())
 }
+
+/// Every multi-database collector discovers targets through `list_target_databases`, so
+/// template databases are never visited and exclusions apply uniformly.
+#[tokio::test]
+async fn list_target_databases_skips_templates() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let dbs = list_target_databases(&pool).await?;
+    assert!(!dbs.iter().any(|db| db == "template0" || db == "template1"));
+    assert!(
+        dbs.windows(2).all(|pair| pair[0] < pair[1]),
+        "sorted: {dbs:?}"
+    );
+
+    let current: String = sqlx::query_scalar("SELECT current_database()")
+        .fetch_one(&pool)
+        .await?;
+    if !is_database_excluded(&current) {
+        assert!(dbs.contains(&current), "{current} missing from {dbs:?}");
+    }
+
+    pool.close().await;
+    Ok(())
+}
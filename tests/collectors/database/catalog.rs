@@ -131,6 +131,106 @@ async fn test_database_size_and_connection_limit_values_reasonable() -> Result<(
     Ok(())
 }
 
+#[tokio::test]
+async fn test_database_catalog_flags_template_databases() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let collector = DatabaseSubCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let current: String = sqlx::query_scalar("SELECT current_database()")
+        .fetch_one(&pool)
+        .await?;
+
+    let families = registry.gather();
+    let is_template = |datname: &str| {
+        families
+            .iter()
+            .find(|m| m.name() == "pg_database_is_template")
+            .and_then(|fam| {
+                fam.get_metric().iter().find(|m| {
+                    m.get_label()
+                        .iter()
+                        .any(|l| l.name() == "datname" && l.value() == datname)
+                })
+            })
+            .map(|m| m.get_gauge().value())
+    };
+
+    // Either may be excluded via PG_EXPORTER_EXCLUDE_DATABASES; only check what is exported.
+    if let Some(value) = is_template("template1") {
+        assert!(
+            (value - 1.0).abs() < f64::EPSILON,
+            "template1 should be a template"
+        );
+    }
+    if let Some(value) = is_template(&current) {
+        assert!(
+            value.abs() < f64::EPSILON,
+            "{current} should not be a template"
+        );
+    }
+
+    pool.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_database_catalog_exports_encoding_and_locale() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let collector = DatabaseSubCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let (current, encoding, collate): (String, String, String) = sqlx::query_as(
+        "SELECT current_database()::text, pg_encoding_to_char(encoding)::text, datcollate::text \
+         FROM pg_database WHERE datname = current_database()",
+    )
+    .fetch_one(&pool)
+    .await?;
+
+    let families = registry.gather();
+    let label = |metric: &prometheus::proto::Metric, name: &str| {
+        metric
+            .get_label()
+            .iter()
+            .find(|l| l.name() == name)
+            .map(|l| l.value().to_string())
+    };
+
+    // The current database may be excluded via PG_EXPORTER_EXCLUDE_DATABASES.
+    if let Some(info) = families
+        .iter()
+        .find(|m| m.name() == "pg_database_encoding_info")
+        .and_then(|fam| {
+            fam.get_metric()
+                .iter()
+                .find(|m| label(m, "datname").as_deref() == Some(current.as_str()))
+        })
+    {
+        assert_eq!(label(info, "encoding"), Some(encoding));
+        assert_eq!(label(info, "collate"), Some(collate.clone()));
+        assert!(label(info, "icu_locale").is_some());
+        assert!((info.get_gauge().value() - 1.0).abs() < f64::EPSILON);
+
+        let server = families
+            .iter()
+            .find(|m| m.name() == "pg_server_locale_info")
+            .and_then(|fam| fam.get_metric().first())
+            .expect("pg_server_locale_info should be exported");
+        assert_eq!(label(server, "lc_collate"), Some(collate));
+    }
+
+    pool.close().await;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_database_collector_runs_both_subcollectors() -> Result<()> {
     let pool = common::create_test_pool().await?;
@@ -0,0 +1,74 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, database::collation::DatabaseCollationCollector};
+use prometheus::Registry;
+
+fn mismatch_for(registry: &Registry, datname: &str, collname: &str) -> Option<f64> {
+    registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == "pg_collation_version_mismatch")
+        .and_then(|family| {
+            family.get_metric().iter().find(|m| {
+                let labels = m.get_label();
+                labels
+                    .iter()
+                    .any(|l| l.name() == "datname" && l.value() == datname)
+                    && labels
+                        .iter()
+                        .any(|l| l.name() == "collname" && l.value() == collname)
+            })
+        })
+        .map(|m| m.get_gauge().value())
+}
+
+#[tokio::test]
+async fn test_collation_collector_reports_recorded_version_mismatch() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let version: i32 = sqlx::query_scalar("SELECT current_setting('server_version_num')::int")
+        .fetch_one(&pool)
+        .await?;
+    let datname: String = sqlx::query_scalar("SELECT current_database()")
+        .fetch_one(&pool)
+        .await?;
+    let collname = format!("test_collation_mismatch_{}", std::process::id());
+
+    // Record a version the ICU library will never report; skip on builds without ICU.
+    if version < 150_000
+        || sqlx::query(sqlx::AssertSqlSafe(format!(
+            "CREATE COLLATION {collname} (provider = icu, locale = 'und', version = '0.0')"
+        )))
+        .execute(&pool)
+        .await
+        .is_err()
+    {
+        pool.close().await;
+        return Ok(());
+    }
+
+    let registry = Registry::new();
+    let collector = DatabaseCollationCollector::new();
+    collector.register_metrics(&registry)?;
+    let collected = collector.collect(&pool).await;
+    let mismatch = mismatch_for(&registry, &datname, &collname);
+
+    sqlx::query(sqlx::AssertSqlSafe(format!("DROP COLLATION {collname}")))
+        .execute(&pool)
+        .await?;
+
+    collector.collect(&pool).await?;
+    let after_drop = mismatch_for(&registry, &datname, &collname);
+    pool.close().await;
+
+    collected?;
+    assert_eq!(
+        mismatch,
+        Some(1.0),
+        "{collname} should be reported as mismatched"
+    );
+    assert_eq!(
+        after_drop, None,
+        "dropped collation should no longer be reported"
+    );
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, database::disk::DatabaseDiskCollector};
+use prometheus::Registry;
+use sqlx::{Executor, postgres::PgPoolOptions};
+
+fn gauge(registry: &Registry, name: &str) -> Option<f64> {
+    registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == name)
+        .and_then(|fam| fam.get_metric().first().map(|m| m.get_gauge().value()))
+}
+
+#[tokio::test]
+async fn test_database_disk_registers_without_error() -> Result<()> {
+    let collector = DatabaseDiskCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_database_disk_reports_sizes_for_privileged_role() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let privileged: bool = sqlx::query_scalar(
+        "SELECT rolsuper OR pg_has_role(current_user, 'pg_monitor', 'USAGE') FROM pg_roles WHERE rolname = current_user",
+    )
+    .fetch_one(&pool)
+    .await?;
+
+    let collector = DatabaseDiskCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let pg_default = registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == "pg_tablespace_size_bytes")
+        .and_then(|fam| {
+            fam.get_metric().iter().find(|m| {
+                m.get_label()
+                    .iter()
+                    .any(|l| l.name() == "tablespace" && l.value() == "pg_default")
+            })
+        })
+        .map(|m| m.get_gauge().value());
+    assert!(
+        pg_default.is_some_and(|v| v > 0.0),
+        "pg_default tablespace size should always be exported"
+    );
+
+    if privileged {
+        let wal = gauge(&registry, "pg_wal_directory_size_bytes");
+        let total = gauge(&registry, "pg_data_directory_size_bytes");
+        assert!(wal.is_some_and(|v| v > 0.0), "pg_wal should not be empty");
+        assert!(
+            total.zip(wal).is_some_and(|(total, wal)| total > wal),
+            "data directory size should include WAL and tablespaces"
+        );
+        assert!(
+            (gauge(&registry, "pg_data_directory_info").unwrap_or(0.0) - 1.0).abs() < f64::EPSILON,
+            "pg_data_directory_info should be 1"
+        );
+    }
+
+    pool.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_database_disk_skips_sources_without_privilege() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let can_create_role: bool = sqlx::query_scalar(
+        "SELECT rolsuper OR rolcreaterole FROM pg_roles WHERE rolname = current_user",
+    )
+    .fetch_one(&pool)
+    .await?;
+    if !can_create_role {
+        pool.close().await;
+        return Ok(());
+    }
+
+    pool.execute(
+        "DO $$ BEGIN
+            IF NOT EXISTS (SELECT 1 FROM pg_roles WHERE rolname = 'exporter_disk_test') THEN
+                CREATE ROLE exporter_disk_test NOLOGIN;
+            END IF;
+        END $$",
+    )
+    .await?;
+
+    // One connection that runs everything as the unprivileged role.
+    let restricted = PgPoolOptions::new()
+        .max_connections(1)
+        .after_connect(|conn, _meta| {
+            Box::pin(async move {
+                conn.execute("SET ROLE exporter_disk_test").await?;
+                Ok(())
+            })
+        })
+        .connect(&common::get_test_dsn())
+        .await?;
+
+    let collector = DatabaseDiskCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+    let result = collector.collect(&restricted).await;
+
+    restricted.close().await;
+    pool.execute("DROP ROLE IF EXISTS exporter_disk_test")
+        .await?;
+    pool.close().await;
+
+    result?;
+
+    assert!(
+        gauge(&registry, "pg_tablespace_size_bytes").is_some(),
+        "pg_default is readable by every role"
+    );
+    assert_eq!(gauge(&registry, "pg_wal_directory_size_bytes"), None);
+    assert_eq!(gauge(&registry, "pg_data_directory_size_bytes"), None);
+    assert_eq!(gauge(&registry, "pg_data_directory_info"), None);
+
+    Ok(())
+}
@@ -1,4 +1,8 @@
 //! Tests for database collector and its sub-collectors
 
 mod catalog;
+mod collation;
+mod disk;
 mod stat;
+mod temp_files;
+mod temp_tables;
@@ -106,6 +106,9 @@ async fn test_database_stats_has_all_metrics_after_collection() -> Result<()> {
         "pg_stat_database_blks_read",
         "pg_stat_database_blks_hit",
         "pg_stat_database_blks_hit_ratio", // NEW: cache hit ratio
+        "pg_stat_database_scan_efficiency_ratio",
+        "pg_stat_database_rollback_ratio",
+        "pg_stat_database_deadlocks_per_million_xacts",
         "pg_stat_database_tup_returned",
         "pg_stat_database_tup_fetched",
         "pg_stat_database_tup_inserted",
@@ -417,6 +420,67 @@ async fn test_database_stats_cache_hit_ratio_range() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_database_stats_scan_efficiency_ratio_matches_tuple_counters() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let collector = DatabaseStatCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let families = registry.gather();
+    let value_for = |name: &str, datid: &str| {
+        families
+            .iter()
+            .find(|m| m.name() == name)
+            .and_then(|family| {
+                family.get_metric().iter().find(|metric| {
+                    metric
+                        .get_label()
+                        .iter()
+                        .any(|l| l.name() == "datid" && l.value() == datid)
+                })
+            })
+            .map(|metric| metric.get_gauge().value())
+    };
+
+    let ratio_family = find_metric_family(&families, "pg_stat_database_scan_efficiency_ratio")?;
+    assert!(!ratio_family.get_metric().is_empty());
+
+    for metric in ratio_family.get_metric() {
+        let ratio = metric.get_gauge().value();
+        assert!(
+            ratio >= 0.0,
+            "scan efficiency ratio must not be negative: {ratio}"
+        );
+
+        let Some(datid) = metric
+            .get_label()
+            .iter()
+            .find(|l| l.name() == "datid")
+            .map(|l| l.value().to_string())
+        else {
+            continue;
+        };
+        let returned = value_for("pg_stat_database_tup_returned", &datid).unwrap_or(0.0);
+        let fetched = value_for("pg_stat_database_tup_fetched", &datid).unwrap_or(0.0);
+        let expected = if fetched > 0.0 {
+            returned / fetched
+        } else {
+            0.0
+        };
+        assert!(
+            (ratio - expected).abs() < 1e-9,
+            "datid {datid}: ratio {ratio} != {returned} / {fetched}"
+        );
+    }
+
+    pool.close().await;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_database_stats_cache_hit_ratio_calculation() -> Result<()> {
     let pool = common::create_test_pool().await?;
@@ -555,3 +619,132 @@ async fn test_database_stats_cache_hit_ratio_labels() -> Result<()> {
     pool.close().await;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_database_stats_rollback_ratio_matches_xact_counters() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    // Make sure the current database has finished (and rolled back) transactions
+    let mut tx = pool.begin().await?;
+    sqlx::query("SELECT 1").execute(&mut *tx).await?;
+    tx.rollback().await?;
+
+    let collector = DatabaseStatCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let families = registry.gather();
+    let value_for = |name: &str, datid: &str| {
+        families
+            .iter()
+            .find(|m| m.name() == name)
+            .and_then(|family| {
+                family.get_metric().iter().find(|metric| {
+                    metric
+                        .get_label()
+                        .iter()
+                        .any(|l| l.name() == "datid" && l.value() == datid)
+                })
+            })
+            .map(|metric| metric.get_gauge().value())
+    };
+
+    let ratio_family = find_metric_family(&families, "pg_stat_database_rollback_ratio")?;
+    assert!(!ratio_family.get_metric().is_empty());
+
+    for metric in ratio_family.get_metric() {
+        let ratio = metric.get_gauge().value();
+        assert!(
+            (0.0..=1.0).contains(&ratio),
+            "rollback ratio must be between 0.0 and 1.0: {ratio}"
+        );
+
+        let Some(datid) = metric
+            .get_label()
+            .iter()
+            .find(|l| l.name() == "datid")
+            .map(|l| l.value().to_string())
+        else {
+            continue;
+        };
+        let commit = value_for("pg_stat_database_xact_commit", &datid).unwrap_or(0.0);
+        let rollback = value_for("pg_stat_database_xact_rollback", &datid).unwrap_or(0.0);
+        let expected = if commit + rollback > 0.0 {
+            rollback / (commit + rollback)
+        } else {
+            0.0
+        };
+        assert!(
+            (ratio - expected).abs() < 1e-9,
+            "datid {datid}: ratio {ratio} != {rollback} / ({commit} + {rollback})"
+        );
+    }
+
+    pool.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_database_stats_deadlocks_per_million_xacts_matches_counters() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let collector = DatabaseStatCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let families = registry.gather();
+    let value_for = |name: &str, datid: &str| {
+        families
+            .iter()
+            .find(|m| m.name() == name)
+            .and_then(|family| {
+                family.get_metric().iter().find(|metric| {
+                    metric
+                        .get_label()
+                        .iter()
+                        .any(|l| l.name() == "datid" && l.value() == datid)
+                })
+            })
+            .map(|metric| metric.get_gauge().value())
+    };
+
+    let family = find_metric_family(&families, "pg_stat_database_deadlocks_per_million_xacts")?;
+    assert!(!family.get_metric().is_empty());
+
+    for metric in family.get_metric() {
+        let rate = metric.get_gauge().value();
+        assert!(
+            rate.is_finite() && rate >= 0.0,
+            "rate must be finite: {rate}"
+        );
+
+        let Some(datid) = metric
+            .get_label()
+            .iter()
+            .find(|l| l.name() == "datid")
+            .map(|l| l.value().to_string())
+        else {
+            continue;
+        };
+        let deadlocks = value_for("pg_stat_database_deadlocks", &datid).unwrap_or(0.0);
+        let commit = value_for("pg_stat_database_xact_commit", &datid).unwrap_or(0.0);
+        let rollback = value_for("pg_stat_database_xact_rollback", &datid).unwrap_or(0.0);
+        // Zero-transaction databases (e.g. the shared-objects row) report 0, never NaN
+        let expected = if commit + rollback > 0.0 {
+            deadlocks * 1_000_000.0 / (commit + rollback)
+        } else {
+            0.0
+        };
+        assert!(
+            (rate - expected).abs() < 1e-6,
+            "datid {datid}: {rate} != {deadlocks} * 1e6 / ({commit} + {rollback})"
+        );
+    }
+
+    pool.close().await;
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use super::super::common;
+use anyhow::{Result, anyhow};
+use pg_exporter::collectors::{Collector, database::temp_files::DatabaseTempFilesCollector};
+use prometheus::Registry;
+use sqlx::{Connection, Executor, PgConnection, postgres::PgPoolOptions};
+
+fn gauge(registry: &Registry, name: &str, tablespace: Option<&str>) -> Option<f64> {
+    registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == name)
+        .and_then(|fam| {
+            fam.get_metric().iter().find(|m| {
+                tablespace.is_none_or(|spcname| {
+                    m.get_label()
+                        .iter()
+                        .any(|l| l.name() == "tablespace" && l.value() == spcname)
+                })
+            })
+        })
+        .map(|m| m.get_gauge().value())
+}
+
+async fn is_privileged(pool: &sqlx::PgPool) -> Result<bool> {
+    Ok(sqlx::query_scalar(
+        "SELECT rolsuper OR pg_has_role(current_user, 'pg_monitor', 'USAGE') FROM pg_roles WHERE rolname = current_user",
+    )
+    .fetch_one(pool)
+    .await?)
+}
+
+#[tokio::test]
+async fn test_database_temp_files_exports_pg_default() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let privileged = is_privileged(&pool).await?;
+
+    let collector = DatabaseTempFilesCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+    pool.close().await;
+
+    assert!(
+        gauge(&registry, "pg_temp_files_io_backends", None).is_some_and(|v| v >= 0.0),
+        "pg_temp_files_io_backends should always be exported"
+    );
+    if privileged {
+        assert!(gauge(&registry, "pg_temp_files_current", Some("pg_default")).is_some());
+        assert!(gauge(&registry, "pg_temp_files_current_bytes", Some("pg_default")).is_some());
+        assert_eq!(
+            gauge(&registry, "pg_temp_files_current", Some("pg_global")),
+            None,
+            "pg_global never holds temporary files"
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_database_temp_files_sees_a_running_spill() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    if !is_privileged(&pool).await? {
+        pool.close().await;
+        return Ok(());
+    }
+
+    // A sort far larger than work_mem spills to pgsql_tmp until the query ends.
+    let mut conn = PgConnection::connect(&common::get_test_dsn()).await?;
+    let spill = tokio::spawn(async move {
+        conn.execute("SET work_mem = '64kB'").await?;
+        conn.execute(
+            "SELECT count(*) FROM (SELECT g FROM generate_series(1, 3000000) g ORDER BY g DESC) s",
+        )
+        .await?;
+        conn.close().await?;
+        Result::<()>::Ok(())
+    });
+
+    let collector = DatabaseTempFilesCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+
+    let mut observed = false;
+    while !spill.is_finished() {
+        collector.collect(&pool).await?;
+        if gauge(&registry, "pg_temp_files_current", Some("pg_default")).is_some_and(|v| v > 0.0) {
+            observed = gauge(&registry, "pg_temp_files_current_bytes", Some("pg_default"))
+                .is_some_and(|v| v > 0.0);
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+
+    spill
+        .await
+        .map_err(|error| anyhow!("spill task failed to join: {error}"))??;
+    pool.close().await;
+
+    assert!(
+        observed,
+        "expected temporary files while the sort was spilling"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_database_temp_files_skips_files_without_privilege() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let can_create_role: bool = sqlx::query_scalar(
+        "SELECT rolsuper OR rolcreaterole FROM pg_roles WHERE rolname = current_user",
+    )
+    .fetch_one(&pool)
+    .await?;
+    if !can_create_role {
+        pool.close().await;
+        return Ok(());
+    }
+
+    pool.execute(
+        "DO $$ BEGIN
+            IF NOT EXISTS (SELECT 1 FROM pg_roles WHERE rolname = 'exporter_temp_files_test') THEN
+                CREATE ROLE exporter_temp_files_test NOLOGIN;
+            END IF;
+        END $$",
+    )
+    .await?;
+
+    // One connection that runs everything as the unprivileged role.
+    let restricted = PgPoolOptions::new()
+        .max_connections(1)
+        .after_connect(|conn, _meta| {
+            Box::pin(async move {
+                conn.execute("SET ROLE exporter_temp_files_test").await?;
+                Ok(())
+            })
+        })
+        .connect(&common::get_test_dsn())
+        .await?;
+
+    let collector = DatabaseTempFilesCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+    let result = collector.collect(&restricted).await;
+
+    restricted.close().await;
+    pool.execute("DROP ROLE IF EXISTS exporter_temp_files_test")
+        .await?;
+    pool.close().await;
+
+    result?;
+
+    assert_eq!(gauge(&registry, "pg_temp_files_current", None), None);
+    assert_eq!(gauge(&registry, "pg_temp_files_current_bytes", None), None);
+    assert!(
+        gauge(&registry, "pg_temp_files_io_backends", None).is_some(),
+        "pg_stat_activity wait events are readable by every role"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, database::temp_tables::DatabaseTempTablesCollector};
+use prometheus::Registry;
+
+fn temp_tables_for(registry: &Registry, datname: &str) -> Option<f64> {
+    registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == "pg_temp_tables_count")
+        .and_then(|family| {
+            family.get_metric().iter().find(|m| {
+                m.get_label()
+                    .iter()
+                    .any(|l| l.name() == "datname" && l.value() == datname)
+            })
+        })
+        .map(|m| m.get_gauge().value())
+}
+
+#[tokio::test]
+async fn test_temp_tables_collector_counts_session_temp_tables() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let datname: String = sqlx::query_scalar("SELECT current_database()")
+        .fetch_one(&pool)
+        .await?;
+
+    let registry = Registry::new();
+    let collector = DatabaseTempTablesCollector::new();
+    collector.register_metrics(&registry)?;
+
+    collector.collect(&pool).await?;
+    let before = temp_tables_for(&registry, &datname).unwrap_or(0.0);
+
+    // Temporary tables live as long as the session, so keep a dedicated connection open.
+    let mut conn = pool.acquire().await?;
+    for i in 0..3 {
+        sqlx::query(sqlx::AssertSqlSafe(format!(
+            "CREATE TEMP TABLE test_temp_tables_{i} (id int)"
+        )))
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    collector.collect(&pool).await?;
+    let during = temp_tables_for(&registry, &datname).unwrap_or(0.0);
+
+    sqlx::query("DISCARD TEMP").execute(&mut *conn).await?;
+    drop(conn);
+    pool.close().await;
+
+    assert!(
+        during - before >= 3.0,
+        "expected at least 3 more temp tables, before={before} during={during}"
+    );
+    Ok(())
+}
@@ -27,6 +27,7 @@ async fn test_archiver_collector_has_all_metrics() -> Result<()> {
         "pg_stat_archiver_failed_total",
         "pg_stat_archiver_last_archived_age_seconds",
         "pg_stat_archiver_last_failed_age_seconds",
+        "pg_stat_archiver_lag_bytes",
     ];
 
     for metric_name in expected_metrics {
@@ -110,6 +111,7 @@ async fn test_archiver_collector_counter_and_gauge_types() -> Result<()> {
     let gauge_metrics = vec![
         "pg_stat_archiver_last_archived_age_seconds",
         "pg_stat_archiver_last_failed_age_seconds",
+        "pg_stat_archiver_lag_bytes",
     ];
 
     for metric_name in gauge_metrics {
@@ -26,6 +26,7 @@ async fn test_bgwriter_collector_has_all_metrics() -> Result<()> {
         "pg_stat_bgwriter_buffers_clean_total",
         "pg_stat_bgwriter_maxwritten_clean_total",
         "pg_stat_bgwriter_buffers_alloc_total",
+        "pg_stat_bgwriter_stats_reset_seconds",
     ];
 
     for metric_name in expected_metrics {
@@ -71,6 +72,42 @@ async fn test_bgwriter_collector_values_non_negative() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_bgwriter_collector_stats_reset_matches_server() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let registry = Registry::new();
+    let collector = BgwriterCollector::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let expected: f64 = sqlx::query_scalar(
+        "SELECT EXTRACT(EPOCH FROM stats_reset)::double precision FROM pg_stat_bgwriter",
+    )
+    .fetch_one(&pool)
+    .await?;
+
+    let families = registry.gather();
+    let stats_reset = families
+        .iter()
+        .find(|m| m.name() == "pg_stat_bgwriter_stats_reset_seconds")
+        .and_then(|f| f.get_metric().first())
+        .map(|m| m.get_gauge().value());
+
+    assert_eq!(
+        stats_reset.map(common::metric_value_to_i64),
+        Some(common::metric_value_to_i64(expected)),
+        "stats_reset should be exported as a Unix timestamp"
+    );
+    assert!(
+        expected > 0.0,
+        "pg_stat_bgwriter.stats_reset is set at initdb"
+    );
+
+    pool.close().await;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_bgwriter_collector_buffers_alloc_increases() -> Result<()> {
     let pool = common::create_test_pool().await?;
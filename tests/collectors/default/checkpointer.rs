@@ -28,6 +28,7 @@ async fn test_checkpointer_collector_has_all_metrics() -> Result<()> {
         "pg_stat_checkpointer_buffers_written_total",
         "pg_stat_checkpointer_write_time_seconds_total",
         "pg_stat_checkpointer_sync_time_seconds_total",
+        "pg_stat_checkpointer_stats_reset_seconds",
     ];
 
     for metric_name in expected_metrics {
@@ -564,3 +565,38 @@ async fn test_checkpointer_control_checkpoint_idempotent() -> Result<()> {
     pool.close().await;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_checkpointer_wal_since_checkpoint_ratio_uses_max_wal_size() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let registry = Registry::new();
+    let collector = CheckpointerCollector::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let max_wal_size: i64 =
+        sqlx::query_scalar("SELECT pg_size_bytes(current_setting('max_wal_size'))::bigint")
+            .fetch_one(&pool)
+            .await?;
+    pool.close().await;
+
+    let families = registry.gather();
+    let gauge = |name: &str| {
+        families
+            .iter()
+            .find(|m| m.name() == name)
+            .map(|m| m.get_metric()[0].get_gauge().value())
+    };
+
+    let wal_bytes = gauge("pg_wal_bytes_since_last_checkpoint").expect("wal bytes should exist");
+    let ratio = gauge("pg_wal_bytes_since_last_checkpoint_ratio").expect("ratio should exist");
+    #[allow(clippy::cast_precision_loss)]
+    let expected = wal_bytes / max_wal_size as f64;
+    assert!(
+        (ratio - expected).abs() < 1e-9,
+        "ratio {ratio} should be wal bytes {wal_bytes} / max_wal_size {max_wal_size}"
+    );
+
+    Ok(())
+}
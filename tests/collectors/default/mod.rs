@@ -3,6 +3,8 @@
 mod archiver;
 mod bgwriter;
 mod checkpointer;
+mod notify;
+mod pending_restart;
 mod postmaster;
 mod settings;
 mod version;
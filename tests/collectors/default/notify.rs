@@ -0,0 +1,32 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, default::notify::NotifyCollector};
+use prometheus::Registry;
+
+#[tokio::test]
+async fn test_notify_collector_exports_queue_usage_ratio() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let collector = NotifyCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let metric_families = registry.gather();
+    let usage = metric_families
+        .iter()
+        .find(|m| m.name() == "pg_notification_queue_usage_ratio")
+        .expect("pg_notification_queue_usage_ratio should exist");
+
+    assert_eq!(usage.get_field_type(), prometheus::proto::MetricType::GAUGE);
+
+    let value = usage.get_metric()[0].get_gauge().value();
+    assert!(
+        (0.0..=1.0).contains(&value),
+        "queue usage should be a ratio, got: {value}"
+    );
+
+    pool.close().await;
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, default::pending_restart::PendingRestartCollector};
+use prometheus::Registry;
+
+#[tokio::test]
+async fn test_pending_restart_collector_matches_pg_settings() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let collector = PendingRestartCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let mut expected: Vec<String> = sqlx::query_scalar(
+        "SELECT name::text FROM pg_settings WHERE pending_restart ORDER BY name",
+    )
+    .fetch_all(&pool)
+    .await?;
+    expected.sort();
+    pool.close().await;
+
+    let metric_families = registry.gather();
+    let count = metric_families
+        .iter()
+        .find(|m| m.name() == "pg_settings_pending_restart_count")
+        .expect("pg_settings_pending_restart_count should exist");
+    assert_eq!(
+        common::metric_value_to_i64(count.get_metric()[0].get_gauge().value()),
+        i64::try_from(expected.len())?
+    );
+
+    // One series per pending setting, none when the configuration is applied
+    let mut names: Vec<String> = metric_families
+        .iter()
+        .filter(|m| m.name() == "pg_settings_pending_restart")
+        .flat_map(|m| m.get_metric().iter())
+        .map(|m| {
+            assert_eq!(common::metric_value_to_i64(m.get_gauge().value()), 1);
+            m.get_label()
+                .iter()
+                .find(|l| l.name() == "name")
+                .map(|l| l.value().to_string())
+                .unwrap_or_default()
+        })
+        .collect();
+    names.sort();
+    assert_eq!(names, expected);
+
+    Ok(())
+}
@@ -60,6 +60,43 @@ async fn test_postmaster_collector_returns_start_time() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_postmaster_collector_returns_uptime() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let collector = PostmasterCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let metric_families = registry.gather();
+    let gauge = |name: &str| {
+        metric_families
+            .iter()
+            .find(|m| m.name() == name)
+            .map(|m| m.get_metric()[0].get_gauge().value())
+    };
+
+    let uptime = gauge("pg_postmaster_uptime_seconds").expect("uptime should exist");
+    let start_time = gauge("pg_postmaster_start_time_seconds").expect("start time should exist");
+
+    assert!(uptime > 0.0, "uptime should be positive, got: {uptime}");
+
+    // start_time + uptime is the server's now(), which should be close to the exporter's clock
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs_f64();
+    assert!(
+        (start_time + uptime - now).abs() < 60.0,
+        "start_time + uptime should be close to now. start_time: {start_time}, uptime: {uptime}, now: {now}"
+    );
+
+    pool.close().await;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_postmaster_collector_is_idempotent() -> Result<()> {
     let pool = common::create_test_pool().await?;
@@ -206,3 +206,164 @@ async fn test_settings_collector_exposes_wal_size_settings() -> Result<()> {
     pool.close().await;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_settings_collector_exposes_huge_pages_context() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let collector = SettingsCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let metric_families = registry.gather();
+    let value_of = |name: &str| {
+        metric_families
+            .iter()
+            .find(|m| m.name() == name)
+            .map_or_else(
+                || panic!("{name} should exist"),
+                |fam| common::metric_value_to_i64(fam.get_metric()[0].get_gauge().value()),
+            )
+    };
+
+    let huge_pages: String = sqlx::query_scalar("SHOW huge_pages")
+        .fetch_one(&pool)
+        .await?;
+    let expected = match huge_pages.as_str() {
+        "on" => 1,
+        "try" => 2,
+        _ => 0,
+    };
+    assert_eq!(value_of("pg_settings_huge_pages"), expected);
+
+    let active = value_of("pg_settings_huge_pages_active");
+    assert!(
+        (-1..=1).contains(&active),
+        "huge_pages_active should be -1, 0 or 1, got {active}"
+    );
+    if huge_pages == "off" {
+        assert_ne!(active, 1, "huge pages cannot be active with huge_pages=off");
+    }
+
+    let version: i32 = sqlx::query_scalar("SELECT current_setting('server_version_num')::int")
+        .fetch_one(&pool)
+        .await?;
+    if version >= 150_000 {
+        assert!(
+            value_of("pg_settings_shared_memory_size_bytes") > 0,
+            "shared_memory_size should be reported in bytes on PG15+"
+        );
+    }
+
+    pool.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_settings_collector_exposes_planner_cost_settings() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let collector = SettingsCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let metric_families = registry.gather();
+    let value_of = |name: &str| {
+        metric_families
+            .iter()
+            .find(|m| m.name() == name)
+            .map_or_else(
+                || panic!("{name} should exist"),
+                |fam| fam.get_metric()[0].get_gauge().value(),
+            )
+    };
+
+    // Cost settings are fractional and must not be truncated to integers.
+    for name in [
+        "random_page_cost",
+        "seq_page_cost",
+        "cpu_tuple_cost",
+        "cpu_index_tuple_cost",
+        "cpu_operator_cost",
+    ] {
+        let expected: f64 =
+            sqlx::query_scalar("SELECT setting::double precision FROM pg_settings WHERE name = $1")
+                .bind(name)
+                .fetch_one(&pool)
+                .await?;
+        let got = value_of(&format!("pg_settings_{name}"));
+        assert!(
+            (got - expected).abs() < 1e-9,
+            "{name}: expected {expected}, got {got}"
+        );
+    }
+
+    let cache_bytes: i64 =
+        sqlx::query_scalar("SELECT pg_size_bytes(current_setting('effective_cache_size'))")
+            .fetch_one(&pool)
+            .await?;
+    assert_eq!(
+        common::metric_value_to_i64(value_of("pg_settings_effective_cache_size_bytes")),
+        cache_bytes
+    );
+    assert!(value_of("pg_settings_default_statistics_target") > 0.0);
+
+    pool.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_settings_collector_exposes_memory_sizing_inputs() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let collector = SettingsCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let metric_families = registry.gather();
+    let value_of = |name: &str| {
+        metric_families
+            .iter()
+            .find(|m| m.name() == name)
+            .map_or_else(
+                || panic!("{name} should exist"),
+                |fam| fam.get_metric()[0].get_gauge().value(),
+            )
+    };
+
+    let temp_buffers: i64 =
+        sqlx::query_scalar("SELECT pg_size_bytes(current_setting('temp_buffers'))")
+            .fetch_one(&pool)
+            .await?;
+    assert_eq!(
+        common::metric_value_to_i64(value_of("pg_settings_temp_buffers_bytes")),
+        temp_buffers
+    );
+
+    // -1 (fall back to maintenance_work_mem) must not be scaled by the kB unit
+    let autovacuum_work_mem: i64 = sqlx::query_scalar(
+        "SELECT CASE WHEN current_setting('autovacuum_work_mem') = '-1' THEN -1 \
+         ELSE pg_size_bytes(current_setting('autovacuum_work_mem')) END",
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(
+        common::metric_value_to_i64(value_of("pg_settings_autovacuum_work_mem_bytes")),
+        autovacuum_work_mem
+    );
+
+    let hash_mem_multiplier: f64 =
+        sqlx::query_scalar("SELECT current_setting('hash_mem_multiplier')::double precision")
+            .fetch_one(&pool)
+            .await?;
+    assert!((value_of("pg_settings_hash_mem_multiplier") - hash_mem_multiplier).abs() < 1e-9);
+
+    assert!(value_of("pg_settings_max_worker_processes") > 0.0);
+    assert!(value_of("pg_settings_max_parallel_workers") >= 0.0);
+
+    pool.close().await;
+    Ok(())
+}
@@ -27,6 +27,10 @@ async fn test_wal_collector_has_all_metrics() -> Result<()> {
         "pg_stat_wal_fpi_total",
         "pg_stat_wal_bytes_total",
         "pg_stat_wal_buffers_full_total",
+        "pg_stat_wal_write_total",
+        "pg_stat_wal_sync_total",
+        "pg_stat_wal_write_time_seconds_total",
+        "pg_stat_wal_sync_time_seconds_total",
     ];
 
     for metric_name in expected_metrics {
@@ -87,6 +91,10 @@ async fn test_wal_collector_metrics_are_counters() -> Result<()> {
         "pg_stat_wal_fpi_total",
         "pg_stat_wal_bytes_total",
         "pg_stat_wal_buffers_full_total",
+        "pg_stat_wal_write_total",
+        "pg_stat_wal_sync_total",
+        "pg_stat_wal_write_time_seconds_total",
+        "pg_stat_wal_sync_time_seconds_total",
     ];
 
     for metric_name in wal_metrics {
@@ -240,6 +248,10 @@ async fn test_wal_collector_metric_help_text() -> Result<()> {
         "pg_stat_wal_fpi_total",
         "pg_stat_wal_bytes_total",
         "pg_stat_wal_buffers_full_total",
+        "pg_stat_wal_write_total",
+        "pg_stat_wal_sync_total",
+        "pg_stat_wal_write_time_seconds_total",
+        "pg_stat_wal_sync_time_seconds_total",
     ];
 
     for metric_name in wal_metrics {
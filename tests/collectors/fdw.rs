@@ -0,0 +1,152 @@
+use super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, fdw::FdwCollector};
+use prometheus::Registry;
+use sqlx::PgPool;
+
+const SERVER: &str = "exporter_test_fdw_server";
+const DOWN_SERVER: &str = "exporter_test_fdw_down";
+const UNPROBED_SERVER: &str = "exporter_test_fdw_unprobed";
+
+fn label<'a>(metric: &'a prometheus::proto::Metric, name: &str) -> Option<&'a str> {
+    metric
+        .get_label()
+        .iter()
+        .find(|l| l.name() == name)
+        .map(prometheus::proto::LabelPair::value)
+}
+
+#[tokio::test]
+async fn test_fdw_collector_name() {
+    let collector = FdwCollector::new();
+    assert_eq!(collector.name(), "fdw");
+    assert!(!collector.enabled_by_default());
+}
+
+async fn execute(pool: &PgPool, sql: String) -> Result<()> {
+    sqlx::query(sqlx::AssertSqlSafe(sql)).execute(pool).await?;
+    Ok(())
+}
+
+async fn drop_servers(pool: &PgPool) -> Result<()> {
+    for server in [SERVER, DOWN_SERVER, UNPROBED_SERVER] {
+        execute(pool, format!("DROP SERVER IF EXISTS {server} CASCADE")).await?;
+    }
+    Ok(())
+}
+
+/// Foreign servers pointing back at the test database, one at a port nobody listens on,
+/// and one without foreign tables.
+async fn create_servers(pool: &PgPool) -> Result<()> {
+    let dsn = url::Url::parse(&common::get_test_dsn())?;
+    let host = dsn.host_str().unwrap_or("localhost");
+    let port = dsn.port().unwrap_or(5432);
+    let dbname = dsn.path().trim_start_matches('/');
+    let dbname = if dbname.is_empty() {
+        "postgres"
+    } else {
+        dbname
+    };
+    let user = if dsn.username().is_empty() {
+        "postgres"
+    } else {
+        dsn.username()
+    };
+    let password = dsn.password().unwrap_or_default();
+
+    execute(
+        pool,
+        "CREATE EXTENSION IF NOT EXISTS postgres_fdw".to_string(),
+    )
+    .await?;
+    drop_servers(pool).await?;
+    for (server, server_port) in [(SERVER, port), (DOWN_SERVER, 1), (UNPROBED_SERVER, port)] {
+        execute(
+            pool,
+            format!(
+                "CREATE SERVER {server} FOREIGN DATA WRAPPER postgres_fdw \
+                 OPTIONS (host '{host}', port '{server_port}', dbname '{dbname}')"
+            ),
+        )
+        .await?;
+        execute(
+            pool,
+            format!(
+                "CREATE USER MAPPING FOR CURRENT_USER SERVER {server} \
+                 OPTIONS (user '{user}', password '{password}')"
+            ),
+        )
+        .await?;
+    }
+    for server in [SERVER, DOWN_SERVER] {
+        execute(
+            pool,
+            format!(
+                "CREATE FOREIGN TABLE {server}_table () SERVER {server} \
+                 OPTIONS (schema_name 'pg_catalog', table_name 'pg_database')"
+            ),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fdw_collector_lists_and_probes_foreign_servers() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    create_servers(&pool).await?;
+
+    let collector = FdwCollector::with_probe(true);
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+    let result = collector.collect(&pool).await;
+
+    drop_servers(&pool).await?;
+    pool.close().await;
+    result?;
+
+    let families = registry.gather();
+    let find = |name: &str, server: &str| {
+        families.iter().find(|m| m.name() == name).and_then(|f| {
+            f.get_metric()
+                .iter()
+                .find(|m| label(m, "server") == Some(server))
+                .cloned()
+        })
+    };
+
+    let info = find("pg_foreign_server_info", SERVER).expect("server info should be exported");
+    assert_eq!(label(&info, "wrapper"), Some("postgres_fdw"));
+    // Server options carry host and dbname, so they are not exported
+    assert_eq!(label(&info, "options"), None);
+
+    let mappings = find("pg_foreign_server_user_mappings", SERVER).expect("user mappings exported");
+    assert!((mappings.get_gauge().value() - 1.0).abs() < f64::EPSILON);
+
+    // The server points at the test database itself, so the query through it succeeds.
+    let up = find("pg_foreign_server_up", SERVER).expect("probe result exported");
+    assert!((up.get_gauge().value() - 1.0).abs() < f64::EPSILON);
+    assert!(find("pg_foreign_server_probe_duration_seconds", SERVER).is_some());
+
+    let down = find("pg_foreign_server_up", DOWN_SERVER).expect("probe result exported");
+    assert!(down.get_gauge().value().abs() < f64::EPSILON);
+
+    // Without a foreign table there is nothing to query through
+    assert!(find("pg_foreign_server_info", UNPROBED_SERVER).is_some());
+    assert!(find("pg_foreign_server_up", UNPROBED_SERVER).is_none());
+
+    let count: f64 = families
+        .iter()
+        .find(|m| m.name() == "pg_foreign_servers_count")
+        .expect("servers count exported")
+        .get_metric()
+        .iter()
+        .map(|m| m.get_gauge().value())
+        .sum();
+    assert!(
+        count >= 3.0,
+        "expected at least three foreign servers, got {count}"
+    );
+
+    Ok(())
+}
@@ -3,3 +3,4 @@
 mod regression;
 mod stats;
 mod unused;
+mod validity;
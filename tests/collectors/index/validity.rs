@@ -0,0 +1,117 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, index::IndexValidityCollector};
+use prometheus::Registry;
+
+#[tokio::test]
+async fn test_index_validity_collector_has_count_metrics_after_collection() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let registry = Registry::new();
+    let collector = IndexValidityCollector::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let families = registry.gather();
+    for metric in ["pg_invalid_indexes_count", "pg_invalid_constraints_count"] {
+        let family = families
+            .iter()
+            .find(|m| m.name() == metric)
+            .ok_or_else(|| anyhow::anyhow!("{metric} should exist"))?;
+        for m in family.get_metric() {
+            assert!(m.get_label().iter().any(|l| l.name() == "datname"));
+            assert!(m.get_gauge().value() >= 0.0);
+        }
+    }
+
+    pool.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_index_validity_collector_reports_not_valid_constraint() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let table_name = format!("test_not_valid_constraint_{}", std::process::id());
+
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "DROP TABLE IF EXISTS {table_name}"
+    )))
+    .execute(&pool)
+    .await?;
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "CREATE TABLE {table_name} (id INT)"
+    )))
+    .execute(&pool)
+    .await?;
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "ALTER TABLE {table_name} ADD CONSTRAINT {table_name}_positive CHECK (id > 0) NOT VALID"
+    )))
+    .execute(&pool)
+    .await?;
+
+    // Mark an index invalid the same way a failed CREATE INDEX CONCURRENTLY leaves it.
+    let index_name = format!("{table_name}_id_idx");
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "CREATE INDEX {index_name} ON {table_name} (id)"
+    )))
+    .execute(&pool)
+    .await?;
+    let marked_invalid = sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "UPDATE pg_index SET indisvalid = false WHERE indexrelid = '{index_name}'::regclass"
+    )))
+    .execute(&pool)
+    .await
+    .is_ok();
+
+    let datname: String = sqlx::query_scalar("SELECT current_database()")
+        .fetch_one(&pool)
+        .await?;
+
+    let registry = Registry::new();
+    let collector = IndexValidityCollector::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let families = registry.gather();
+    let value_for = |metric: &str| {
+        families
+            .iter()
+            .find(|f| f.name() == metric)
+            .and_then(|f| {
+                f.get_metric().iter().find(|m| {
+                    m.get_label()
+                        .iter()
+                        .any(|l| l.name() == "datname" && l.value() == datname)
+                })
+            })
+            .map(|m| m.get_gauge().value())
+    };
+
+    assert!(value_for("pg_invalid_constraints_count").is_some_and(|v| v >= 1.0));
+
+    // Updating pg_index requires superuser; only assert when the fixture could be applied.
+    if marked_invalid {
+        assert!(value_for("pg_invalid_indexes_count").is_some_and(|v| v >= 1.0));
+        let is_valid = families
+            .iter()
+            .find(|f| f.name() == "pg_index_is_valid")
+            .and_then(|f| {
+                f.get_metric().iter().find(|m| {
+                    m.get_label()
+                        .iter()
+                        .any(|l| l.name() == "indexrelname" && l.value() == index_name)
+                })
+            })
+            .map(|m| m.get_gauge().value());
+        assert_eq!(is_valid, Some(0.0));
+    }
+
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "DROP TABLE IF EXISTS {table_name}"
+    )))
+    .execute(&pool)
+    .await?;
+
+    pool.close().await;
+    Ok(())
+}
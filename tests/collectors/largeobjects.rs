@@ -0,0 +1,74 @@
+use super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, largeobjects::LargeObjectsCollector};
+use prometheus::Registry;
+
+fn gauge_for(registry: &Registry, name: &str, datname: &str) -> Option<f64> {
+    registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == name)
+        .and_then(|family| {
+            family.get_metric().iter().find(|m| {
+                m.get_label()
+                    .iter()
+                    .any(|l| l.name() == "datname" && l.value() == datname)
+            })
+        })
+        .map(|m| m.get_gauge().value())
+}
+
+#[tokio::test]
+async fn test_largeobjects_collector_name() {
+    let collector = LargeObjectsCollector::new();
+    assert_eq!(collector.name(), "largeobjects");
+    assert!(!collector.enabled_by_default());
+}
+
+#[tokio::test]
+async fn test_largeobjects_collector_counts_and_sizes_objects() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let datname: String = sqlx::query_scalar("SELECT current_database()")
+        .fetch_one(&pool)
+        .await?;
+
+    let registry = Registry::new();
+    let collector = LargeObjectsCollector::new();
+    collector.register_metrics(&registry)?;
+
+    collector.collect(&pool).await?;
+    let count_before = gauge_for(&registry, "pg_largeobjects_count", &datname).unwrap_or(0.0);
+    let size_before = gauge_for(&registry, "pg_largeobjects_size_bytes", &datname);
+
+    // 10000 bytes spread over several 2 kB pages
+    let oid: sqlx::postgres::types::Oid =
+        sqlx::query_scalar("SELECT lo_from_bytea(0, convert_to(repeat('x', 10000), 'UTF8'))")
+            .fetch_one(&pool)
+            .await?;
+
+    let result = collector.collect(&pool).await;
+    let count_during = gauge_for(&registry, "pg_largeobjects_count", &datname).unwrap_or(0.0);
+    let size_during = gauge_for(&registry, "pg_largeobjects_size_bytes", &datname);
+
+    sqlx::query("SELECT lo_unlink($1)")
+        .bind(oid)
+        .execute(&pool)
+        .await?;
+    pool.close().await;
+    result?;
+
+    assert!(
+        count_during - count_before >= 1.0,
+        "expected one more large object, before={count_before} during={count_during}"
+    );
+
+    // The size needs read access to pg_largeobject (superuser or an explicit grant)
+    if let (Some(before), Some(during)) = (size_before, size_during) {
+        assert!(
+            during - before >= 10_000.0,
+            "expected 10000 more bytes, before={before} during={during}"
+        );
+    }
+
+    Ok(())
+}
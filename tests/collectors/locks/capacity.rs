@@ -0,0 +1,88 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, locks::capacity::LockCapacityCollector};
+use prometheus::Registry;
+
+fn gauge_value(registry: &Registry, name: &str) -> Option<f64> {
+    registry
+        .gather()
+        .iter()
+        .find(|f| f.name() == name)
+        .and_then(|f| f.get_metric().first().map(|m| m.get_gauge().value()))
+}
+
+#[tokio::test]
+async fn test_lock_capacity_reports_table_size() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let registry = Registry::new();
+    let collector = LockCapacityCollector::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let (max_locks, max_conn, max_prepared): (f64, f64, f64) = sqlx::query_as(
+        "SELECT current_setting('max_locks_per_transaction')::float8, \
+                current_setting('max_connections')::float8, \
+                current_setting('max_prepared_transactions')::float8",
+    )
+    .fetch_one(&pool)
+    .await?;
+    let expected = max_locks * (max_conn + max_prepared);
+    let capacity = gauge_value(&registry, "pg_locks_table_capacity").unwrap_or(0.0);
+
+    assert!(expected > 0.0, "lock table settings should be positive");
+    assert!(
+        (capacity - expected).abs() < f64::EPSILON,
+        "capacity {capacity} should equal {max_locks} * ({max_conn} + {max_prepared})"
+    );
+
+    // The sizing settings are exported by the settings collector, not duplicated here.
+    for name in [
+        "pg_locks_max_locks_per_transaction",
+        "pg_locks_max_connections",
+        "pg_locks_max_prepared_transactions",
+    ] {
+        assert!(
+            gauge_value(&registry, name).is_none(),
+            "{name} duplicates pg_settings_* and should not be exported"
+        );
+    }
+
+    pool.close().await;
+    Ok(())
+}
+
+// An open transaction holding locks on several relations must be counted.
+#[tokio::test]
+async fn test_lock_capacity_counts_locked_relations() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    for i in 0..3 {
+        sqlx::query(sqlx::AssertSqlSafe(format!(
+            "CREATE TABLE IF NOT EXISTS test_lock_capacity_rel_{i} (id INT)"
+        )))
+        .execute(&pool)
+        .await?;
+    }
+
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        "LOCK TABLE test_lock_capacity_rel_0, test_lock_capacity_rel_1, test_lock_capacity_rel_2 \
+         IN ACCESS SHARE MODE",
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let registry = Registry::new();
+    let collector = LockCapacityCollector::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let locked = gauge_value(&registry, "pg_locks_locked_relations").unwrap_or(0.0);
+    assert!(
+        locked >= 3.0,
+        "expected at least the 3 locked test relations, got {locked}"
+    );
+
+    tx.rollback().await?;
+    pool.close().await;
+    Ok(())
+}
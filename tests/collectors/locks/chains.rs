@@ -0,0 +1,83 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, locks::chains::LockChainsCollector};
+use prometheus::Registry;
+
+#[tokio::test]
+async fn test_lock_chains_registers_metric() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let registry = Registry::new();
+    let collector = LockChainsCollector::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let families = registry.gather();
+    assert!(
+        families
+            .iter()
+            .any(|m| m.name() == "pg_locks_max_blocking_chain_depth")
+    );
+
+    pool.close().await;
+    Ok(())
+}
+
+// tx1 holds ACCESS EXCLUSIVE; a second session queues for ACCESS EXCLUSIVE behind it and
+// a third session's SELECT queues behind both: tx1 -> second -> third is a chain of 2.
+#[tokio::test]
+async fn test_lock_chains_detect_two_level_chain() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    sqlx::query("CREATE TABLE IF NOT EXISTS test_lock_chain_rel (id INT PRIMARY KEY)")
+        .execute(&pool)
+        .await?;
+
+    let mut tx1 = pool.begin().await?;
+    sqlx::query("LOCK TABLE test_lock_chain_rel IN ACCESS EXCLUSIVE MODE")
+        .execute(&mut *tx1)
+        .await?;
+
+    let second_pool = pool.clone();
+    let second = tokio::spawn(async move {
+        if let Ok(mut tx2) = second_pool.begin().await {
+            let _ = sqlx::query("LOCK TABLE test_lock_chain_rel IN ACCESS EXCLUSIVE MODE")
+                .execute(&mut *tx2)
+                .await;
+            let _ = tx2.commit().await;
+        }
+    });
+    tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+
+    let third_pool = pool.clone();
+    let third = tokio::spawn(async move {
+        let _ = sqlx::query("SELECT * FROM test_lock_chain_rel")
+            .fetch_optional(&third_pool)
+            .await;
+    });
+    tokio::time::sleep(tokio::time::Duration::from_millis(600)).await;
+
+    let registry = Registry::new();
+    let collector = LockChainsCollector::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let depth = registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == "pg_locks_max_blocking_chain_depth")
+        .and_then(|f| f.get_metric().first().map(|m| m.get_gauge().value()))
+        .unwrap_or(0.0);
+
+    tx1.commit().await?;
+    let _ = second.await;
+    let _ = third.await;
+    sqlx::query("DROP TABLE IF EXISTS test_lock_chain_rel")
+        .execute(&pool)
+        .await?;
+    pool.close().await;
+
+    assert!(
+        (depth - 2.0).abs() < f64::EPSILON,
+        "expected chain depth 2, got {depth}"
+    );
+    Ok(())
+}
@@ -1,6 +1,10 @@
 use super::super::common;
 use anyhow::Result;
-use pg_exporter::collectors::{Collector, locks::LocksCollector, util::set_excluded_databases};
+use pg_exporter::collectors::{
+    Collector,
+    locks::LocksCollector,
+    util::{get_null_datname_label, set_excluded_databases},
+};
 use prometheus::Registry;
 
 #[tokio::test]
@@ -318,21 +322,30 @@ async fn test_locks_count_empty_database_name() -> Result<()> {
     collector.register_metrics(&registry)?;
     collector.collect(&pool).await?;
 
-    // Locks without a database (system locks) should have empty datname
+    // Locks without a database (virtualxid, transactionid) use the NULL datname sentinel
     let families = registry.gather();
     let locks_family = families
         .iter()
         .find(|m| m.name() == "pg_locks_count")
         .expect("pg_locks_count metric should exist");
 
-    // Verify that all metrics have the datname label (even if empty)
+    let mut saw_sentinel = false;
     for metric in locks_family.get_metric() {
-        let has_datname = metric.get_label().iter().any(|l| l.name() == "datname");
+        let datname = metric
+            .get_label()
+            .iter()
+            .find(|l| l.name() == "datname")
+            .map(prometheus::proto::LabelPair::value);
         assert!(
-            has_datname,
-            "All lock metrics should have datname label (even if empty)"
+            datname.is_some_and(|d| !d.is_empty()),
+            "All lock metrics should have a non-empty datname label"
         );
+        saw_sentinel |= datname == Some(get_null_datname_label());
     }
+    assert!(
+        saw_sentinel,
+        "The collector's own virtualxid lock should be reported under the sentinel"
+    );
 
     pool.close().await;
     Ok(())
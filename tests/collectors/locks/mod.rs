@@ -1,3 +1,5 @@
 //! Tests for locks collector
 
+mod capacity;
+mod chains;
 mod count;
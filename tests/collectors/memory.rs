@@ -0,0 +1,73 @@
+use super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, memory::MemoryCollector};
+use prometheus::Registry;
+use sqlx::Row;
+
+#[tokio::test]
+async fn test_memory_collector_name() {
+    let collector = MemoryCollector::new();
+    assert_eq!(collector.name(), "memory");
+    assert!(!collector.enabled_by_default());
+}
+
+#[tokio::test]
+async fn test_memory_collector_exports_backend_memory_by_parent() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let row = sqlx::query(
+        "SELECT current_setting('server_version_num')::int AS v, \
+         (SELECT rolsuper FROM pg_roles WHERE rolname = current_user) AS su",
+    )
+    .fetch_one(&pool)
+    .await?;
+    let version: i32 = row.try_get("v")?;
+    let superuser: bool = row.try_get::<Option<bool>, _>("su")?.unwrap_or(false);
+
+    let collector = MemoryCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+
+    if version < 140_000 || (version < 150_000 && !superuser) {
+        // The view is missing (or superuser-only on PG14); nothing to assert.
+        pool.close().await;
+        return Ok(());
+    }
+
+    collector.collect(&pool).await?;
+
+    let families = registry.gather();
+    let family = families
+        .iter()
+        .find(|m| m.name() == "pg_backend_memory_total_bytes")
+        .expect("pg_backend_memory_total_bytes should be exported");
+
+    let parents: Vec<&str> = family
+        .get_metric()
+        .iter()
+        .filter_map(|m| {
+            m.get_label()
+                .iter()
+                .find(|l| l.name() == "parent")
+                .map(prometheus::proto::LabelPair::value)
+        })
+        .collect();
+    assert!(parents.contains(&"[root]"), "parents: {parents:?}");
+    assert!(
+        parents.contains(&"TopMemoryContext"),
+        "parents: {parents:?}"
+    );
+
+    let total: f64 = family
+        .get_metric()
+        .iter()
+        .map(|m| m.get_gauge().value())
+        .sum();
+    assert!(
+        total > 0.0,
+        "backend memory should be positive, got {total}"
+    );
+
+    pool.close().await;
+    Ok(())
+}
@@ -10,8 +10,11 @@ pub mod connection;
 pub mod connection_hardening;
 pub mod database;
 pub mod default;
+pub mod fdw;
 pub mod index;
+pub mod largeobjects;
 pub mod locks;
+pub mod memory;
 pub mod replication;
 pub mod sequences;
 pub mod slru;
@@ -3,3 +3,7 @@ pub mod replica_topology;
 pub mod slots;
 pub mod stat_replication;
 pub mod stat_replication_slots;
+pub mod stat_subscription_stats;
+pub mod subscription;
+pub mod wal_receiver;
+pub mod wal_senders;
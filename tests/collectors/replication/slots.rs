@@ -129,3 +129,209 @@ async fn test_replication_slots_collector_handles_concurrent_collection() -> Res
     pool.close().await;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_replication_slots_lag_seconds_sentinel_for_inactive_slot() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let slot_name = "pg_exporter_test_lag_slot";
+    sqlx::query(
+        "SELECT pg_drop_replication_slot(slot_name) FROM pg_replication_slots WHERE slot_name = $1",
+    )
+    .bind(slot_name)
+    .execute(&pool)
+    .await?;
+    sqlx::query("SELECT pg_create_physical_replication_slot($1)")
+        .bind(slot_name)
+        .execute(&pool)
+        .await?;
+
+    let registry = Registry::new();
+    let collector = ReplicationSlotsCollector::new();
+    collector.register_metrics(&registry)?;
+    let result = collector.collect(&pool).await;
+
+    sqlx::query("SELECT pg_drop_replication_slot($1)")
+        .bind(slot_name)
+        .execute(&pool)
+        .await?;
+    pool.close().await;
+    result?;
+
+    let lag = registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == "pg_replication_slots_lag_seconds")
+        .and_then(|family| {
+            family
+                .get_metric()
+                .iter()
+                .find(|m| {
+                    m.get_label()
+                        .iter()
+                        .any(|l| l.name() == "slot_name" && l.value() == slot_name)
+                })
+                .map(|m| m.get_gauge().value())
+        });
+
+    assert_eq!(lag, Some(-1.0), "inactive slot should report the sentinel");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replication_slots_active_pid_absent_for_inactive_slot() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let slot_name = "pg_exporter_test_active_pid_slot";
+    sqlx::query(
+        "SELECT pg_drop_replication_slot(slot_name) FROM pg_replication_slots WHERE slot_name = $1",
+    )
+    .bind(slot_name)
+    .execute(&pool)
+    .await?;
+    sqlx::query("SELECT pg_create_physical_replication_slot($1)")
+        .bind(slot_name)
+        .execute(&pool)
+        .await?;
+
+    let registry = Registry::new();
+    let collector = ReplicationSlotsCollector::new();
+    collector.register_metrics(&registry)?;
+    let result = collector.collect(&pool).await;
+
+    sqlx::query("SELECT pg_drop_replication_slot($1)")
+        .bind(slot_name)
+        .execute(&pool)
+        .await?;
+    pool.close().await;
+    result?;
+
+    let families = registry.gather();
+    let has_slot = |name: &str| {
+        families
+            .iter()
+            .filter(|m| m.name() == name)
+            .flat_map(|family| family.get_metric().iter())
+            .any(|m| {
+                m.get_label()
+                    .iter()
+                    .any(|l| l.name() == "slot_name" && l.value() == slot_name)
+            })
+    };
+    assert!(has_slot("pg_replication_slots_active"));
+    assert!(
+        !has_slot("pg_replication_slots_active_pid"),
+        "a slot nobody consumes has no consumer pid"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replication_slots_info_inventories_physical_slot() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let slot_name = "pg_exporter_test_info_slot";
+    sqlx::query(
+        "SELECT pg_drop_replication_slot(slot_name) FROM pg_replication_slots WHERE slot_name = $1",
+    )
+    .bind(slot_name)
+    .execute(&pool)
+    .await?;
+    sqlx::query("SELECT pg_create_physical_replication_slot($1)")
+        .bind(slot_name)
+        .execute(&pool)
+        .await?;
+
+    let registry = Registry::new();
+    let collector = ReplicationSlotsCollector::new();
+    collector.register_metrics(&registry)?;
+    let result = collector.collect(&pool).await;
+
+    sqlx::query("SELECT pg_drop_replication_slot($1)")
+        .bind(slot_name)
+        .execute(&pool)
+        .await?;
+    pool.close().await;
+    result?;
+
+    let families = registry.gather();
+    let metric = families
+        .iter()
+        .find(|m| m.name() == "pg_replication_slots_info")
+        .and_then(|family| {
+            family.get_metric().iter().find(|m| {
+                m.get_label()
+                    .iter()
+                    .any(|l| l.name() == "slot_name" && l.value() == slot_name)
+            })
+        })
+        .expect("physical slot should be inventoried");
+
+    let label = |name: &str| {
+        metric
+            .get_label()
+            .iter()
+            .find(|l| l.name() == name)
+            .map(prometheus::proto::LabelPair::value)
+    };
+    assert_eq!(label("slot_type"), Some("physical"));
+    assert_eq!(
+        label("plugin"),
+        Some(""),
+        "physical slots have no output plugin"
+    );
+    assert!((metric.get_gauge().value() - 1.0).abs() < f64::EPSILON);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replication_slots_invalidated_reports_wal_status() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let slot_name = "pg_exporter_test_invalidated_slot";
+    sqlx::query(
+        "SELECT pg_drop_replication_slot(slot_name) FROM pg_replication_slots WHERE slot_name = $1",
+    )
+    .bind(slot_name)
+    .execute(&pool)
+    .await?;
+    // immediately_reserve = true, so the slot holds WAL and reports wal_status = 'reserved'
+    sqlx::query("SELECT pg_create_physical_replication_slot($1, true)")
+        .bind(slot_name)
+        .execute(&pool)
+        .await?;
+
+    let registry = Registry::new();
+    let collector = ReplicationSlotsCollector::new();
+    collector.register_metrics(&registry)?;
+    let result = collector.collect(&pool).await;
+
+    sqlx::query("SELECT pg_drop_replication_slot($1)")
+        .bind(slot_name)
+        .execute(&pool)
+        .await?;
+    pool.close().await;
+    result?;
+
+    let families = registry.gather();
+    let metric = families
+        .iter()
+        .find(|m| m.name() == "pg_replication_slots_invalidated")
+        .and_then(|family| {
+            family.get_metric().iter().find(|m| {
+                m.get_label()
+                    .iter()
+                    .any(|l| l.name() == "slot_name" && l.value() == slot_name)
+            })
+        })
+        .expect("slot should export pg_replication_slots_invalidated");
+
+    let wal_status = metric
+        .get_label()
+        .iter()
+        .find(|l| l.name() == "wal_status")
+        .map(prometheus::proto::LabelPair::value);
+    assert_eq!(wal_status, Some("reserved"));
+    assert_eq!(
+        common::metric_value_to_i64(metric.get_gauge().value()),
+        0,
+        "a slot holding its WAL is not invalidated"
+    );
+    Ok(())
+}
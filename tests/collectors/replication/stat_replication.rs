@@ -109,3 +109,62 @@ async fn test_stat_replication_collector_handles_concurrent_collection() -> Resu
     pool.close().await;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_stat_replication_collector_reports_sync_standbys() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let registry = Registry::new();
+    let collector = StatReplicationCollector::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let sync_standby_names: String =
+        sqlx::query_scalar("SELECT current_setting('synchronous_standby_names')")
+            .fetch_one(&pool)
+            .await?;
+    let sync_walsenders: f64 = sqlx::query_scalar(
+        "SELECT COUNT(*)::double precision FROM pg_stat_replication WHERE sync_state IN ('sync', 'quorum')",
+    )
+    .fetch_one(&pool)
+    .await?;
+
+    let families = registry.gather();
+    let gauge = |name: &str| {
+        families
+            .iter()
+            .find(|family| family.name() == name)
+            .and_then(|family| family.get_metric().first())
+            .map(|metric| metric.get_gauge().value())
+    };
+
+    assert_eq!(
+        gauge("pg_stat_replication_sync_standbys"),
+        Some(sync_walsenders)
+    );
+    let expected = gauge("pg_stat_replication_sync_standbys_expected");
+    if sync_standby_names.trim().is_empty() {
+        assert_eq!(expected, Some(0.0), "synchronous replication is off");
+    } else {
+        assert!(expected.is_some_and(|n| n >= 1.0));
+    }
+
+    // Every walsender carries its sync_state and sync_priority
+    if let Some(family) = families
+        .iter()
+        .find(|family| family.name() == "pg_stat_replication_sync_state")
+    {
+        for metric in family.get_metric() {
+            let labels: Vec<_> = metric
+                .get_label()
+                .iter()
+                .map(prometheus::proto::LabelPair::name)
+                .collect();
+            assert_eq!(labels, ["application_name", "sync_priority", "sync_state"]);
+            assert!((metric.get_gauge().value() - 1.0).abs() < f64::EPSILON);
+        }
+    }
+
+    pool.close().await;
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{
+    Collector, replication::stat_subscription_stats::StatSubscriptionStatsCollector,
+};
+use prometheus::Registry;
+use sqlx::{PgPool, Row};
+
+const MIN_STAT_SUBSCRIPTION_STATS_VERSION: i32 = 150_000;
+
+async fn server_version_num(pool: &PgPool) -> Result<i32> {
+    let row = sqlx::query("SELECT current_setting('server_version_num')::int AS v")
+        .fetch_one(pool)
+        .await?;
+    Ok(row.try_get::<i32, _>("v")?)
+}
+
+fn gauge_value(registry: &Registry, metric: &str, subname: &str) -> Option<i64> {
+    registry
+        .gather()
+        .iter()
+        .find(|family| family.name() == metric)
+        .and_then(|family| {
+            family.get_metric().iter().find(|m| {
+                m.get_label()
+                    .iter()
+                    .any(|label| label.name() == "subname" && label.value() == subname)
+            })
+        })
+        .map(|m| {
+            #[allow(clippy::cast_possible_truncation)]
+            let value = m.get_gauge().value() as i64;
+            value
+        })
+}
+
+#[tokio::test]
+async fn test_stat_subscription_stats_collector_name() {
+    let collector = StatSubscriptionStatsCollector::new();
+    assert_eq!(collector.name(), "stat_subscription_stats");
+}
+
+#[tokio::test]
+async fn test_stat_subscription_stats_collect_succeeds_on_any_version() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let registry = Registry::new();
+    let collector = StatSubscriptionStatsCollector::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    pool.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stat_subscription_stats_exports_disconnected_subscription() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    if server_version_num(&pool).await? < MIN_STAT_SUBSCRIPTION_STATS_VERSION {
+        pool.close().await;
+        return Ok(());
+    }
+
+    let subname = "test_sub_stats_sub";
+    // `connect = false` creates the subscription without contacting a publisher
+    let created = sqlx::query(
+        "CREATE SUBSCRIPTION test_sub_stats_sub CONNECTION 'host=127.0.0.1 dbname=postgres' \
+         PUBLICATION test_sub_stats_pub \
+         WITH (connect = false, enabled = false, create_slot = false, slot_name = NONE)",
+    )
+    .execute(&pool)
+    .await;
+    if created.is_err() {
+        // Requires superuser or pg_create_subscription
+        pool.close().await;
+        return Ok(());
+    }
+
+    let registry = Registry::new();
+    let collector = StatSubscriptionStatsCollector::new();
+    collector.register_metrics(&registry)?;
+    let result = collector.collect(&pool).await;
+
+    sqlx::query("DROP SUBSCRIPTION test_sub_stats_sub")
+        .execute(&pool)
+        .await?;
+    result?;
+
+    assert_eq!(
+        gauge_value(&registry, "pg_stat_subscription_apply_error_count", subname),
+        Some(0)
+    );
+    assert_eq!(
+        gauge_value(&registry, "pg_stat_subscription_sync_error_count", subname),
+        Some(0)
+    );
+
+    pool.close().await;
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, replication::subscription::SubscriptionCollector};
+use prometheus::Registry;
+
+fn gauge_value(registry: &Registry, metric: &str, subname: &str) -> Option<f64> {
+    registry
+        .gather()
+        .iter()
+        .find(|family| family.name() == metric)
+        .and_then(|family| {
+            family.get_metric().iter().find(|m| {
+                m.get_label()
+                    .iter()
+                    .any(|label| label.name() == "subname" && label.value() == subname)
+            })
+        })
+        .map(|m| m.get_gauge().value())
+}
+
+#[tokio::test]
+async fn test_subscription_collector_name() {
+    let collector = SubscriptionCollector::new();
+    assert_eq!(collector.name(), "subscription");
+}
+
+#[tokio::test]
+async fn test_subscription_collect_succeeds_on_any_version() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let registry = Registry::new();
+    let collector = SubscriptionCollector::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    pool.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_subscription_exports_disabled_subscription_without_workers() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let subname = "test_sub_progress_sub";
+    // `connect = false` creates the subscription without contacting a publisher
+    let created = sqlx::query(
+        "CREATE SUBSCRIPTION test_sub_progress_sub CONNECTION 'host=127.0.0.1 dbname=postgres' \
+         PUBLICATION test_sub_progress_pub \
+         WITH (connect = false, enabled = false, create_slot = false, slot_name = NONE)",
+    )
+    .execute(&pool)
+    .await;
+    if created.is_err() {
+        // Requires superuser or pg_create_subscription
+        pool.close().await;
+        return Ok(());
+    }
+
+    let registry = Registry::new();
+    let collector = SubscriptionCollector::new();
+    collector.register_metrics(&registry)?;
+    let result = collector.collect(&pool).await;
+
+    sqlx::query("DROP SUBSCRIPTION test_sub_progress_sub")
+        .execute(&pool)
+        .await?;
+    result?;
+
+    assert_eq!(
+        gauge_value(&registry, "pg_stat_subscription_worker_count", subname),
+        Some(0.0)
+    );
+    // No apply worker runs for a disabled subscription, so there is no position to report
+    assert_eq!(
+        gauge_value(&registry, "pg_stat_subscription_received_lsn", subname),
+        None
+    );
+
+    pool.close().await;
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, replication::wal_receiver::WalReceiverCollector};
+use prometheus::Registry;
+
+#[tokio::test]
+async fn test_wal_receiver_collector_name() {
+    let collector = WalReceiverCollector::new();
+    assert_eq!(collector.name(), "wal_receiver");
+}
+
+#[tokio::test]
+async fn test_wal_receiver_exports_nothing_without_receiver() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let has_receiver =
+        sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM pg_stat_wal_receiver)")
+            .fetch_one(&pool)
+            .await?;
+    let in_recovery = sqlx::query_scalar::<_, bool>("SELECT pg_is_in_recovery()")
+        .fetch_one(&pool)
+        .await?;
+
+    let registry = Registry::new();
+    let collector = WalReceiverCollector::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    if !has_receiver && !in_recovery {
+        // A primary has no WAL receiver: collection succeeds but no samples are emitted
+        let samples: usize = registry
+            .gather()
+            .iter()
+            .filter(|family| family.name().starts_with("pg_stat_wal_receiver_"))
+            .map(|family| family.get_metric().len())
+            .sum();
+        assert_eq!(samples, 0);
+    }
+
+    pool.close().await;
+    Ok(())
+}
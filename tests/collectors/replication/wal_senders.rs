@@ -0,0 +1,37 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, replication::wal_senders::WalSendersCollector};
+use prometheus::Registry;
+
+fn gauge(registry: &Registry, name: &str) -> Option<f64> {
+    registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == name)
+        .and_then(|family| family.get_metric().first().map(|m| m.get_gauge().value()))
+}
+
+#[tokio::test]
+async fn test_wal_senders_collector_exports_active_and_max() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let registry = Registry::new();
+    let collector = WalSendersCollector::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let active = gauge(&registry, "pg_wal_senders_active");
+    let max = gauge(&registry, "pg_wal_senders_max");
+
+    let expected_max: i64 = sqlx::query_scalar("SELECT current_setting('max_wal_senders')::bigint")
+        .fetch_one(&pool)
+        .await?;
+
+    assert!(active.is_some_and(|v| v >= 0.0));
+    #[allow(clippy::cast_precision_loss)]
+    let expected_max = expected_max as f64;
+    assert_eq!(max, Some(expected_max));
+
+    pool.close().await;
+    Ok(())
+}
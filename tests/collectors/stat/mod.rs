@@ -1,3 +1,4 @@
 //! Tests for stat collector and its sub-collectors
 
+mod user_functions;
 mod user_tables;
@@ -0,0 +1,85 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, stat::user_functions::StatUserFunctionsCollector};
+use prometheus::Registry;
+use std::time::Duration;
+
+fn calls_for(registry: &Registry, funcname: &str) -> Option<f64> {
+    registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == "pg_stat_user_functions_calls")
+        .and_then(|family| {
+            family.get_metric().iter().find(|m| {
+                m.get_label()
+                    .iter()
+                    .any(|l| l.name() == "funcname" && l.value() == funcname)
+            })
+        })
+        .map(|m| m.get_gauge().value())
+}
+
+#[tokio::test]
+async fn test_stat_user_functions_collector_registers_without_error() -> Result<()> {
+    let collector = StatUserFunctionsCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stat_user_functions_collector_counts_tracked_calls() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let funcname = format!("test_user_functions_{}", std::process::id());
+
+    sqlx::query(sqlx::AssertSqlSafe(format!(
+        "CREATE OR REPLACE FUNCTION {funcname}() RETURNS int LANGUAGE plpgsql AS $$ BEGIN RETURN 1; END $$"
+    )))
+    .execute(&pool)
+    .await?;
+
+    // track_functions is superuser-only; tracking it for this session is enough.
+    let mut conn = pool.acquire().await?;
+    let tracked = sqlx::query("SET track_functions = 'pl'")
+        .execute(&mut *conn)
+        .await
+        .is_ok();
+    if tracked {
+        for _ in 0..3 {
+            sqlx::query(sqlx::AssertSqlSafe(format!("SELECT {funcname}()")))
+                .execute(&mut *conn)
+                .await?;
+        }
+        // Pending function stats are flushed when the session goes idle, at most once a second.
+        if sqlx::query("SELECT pg_stat_force_next_flush()")
+            .execute(&mut *conn)
+            .await
+            .is_err()
+        {
+            tokio::time::sleep(Duration::from_millis(1100)).await;
+        }
+        sqlx::query("SELECT 1").execute(&mut *conn).await?;
+    }
+    drop(conn);
+
+    let registry = Registry::new();
+    let collector = StatUserFunctionsCollector::new();
+    collector.register_metrics(&registry)?;
+    let collected = collector.collect(&pool).await;
+    let calls = calls_for(&registry, &funcname);
+
+    sqlx::query(sqlx::AssertSqlSafe(format!("DROP FUNCTION {funcname}()")))
+        .execute(&pool)
+        .await?;
+    pool.close().await;
+
+    collected?;
+    if tracked {
+        assert!(
+            calls.is_some_and(|calls| calls >= 3.0),
+            "expected at least 3 calls of {funcname}, got {calls:?}"
+        );
+    }
+    Ok(())
+}
@@ -3,6 +3,7 @@ use anyhow::Result;
 use pg_exporter::collectors::{
     Collector,
     stat::{StatCollector, user_tables::StatUserTablesCollector},
+    util::timestamps_to_milliseconds,
 };
 use prometheus::Registry;
 use sqlx::postgres::PgPoolOptions;
@@ -11,6 +12,9 @@ use std::{
     time::Duration as StdDuration,
 };
 
+// Per-database rollup; every other pg_stat_user_tables_* metric is per table.
+const PER_DATABASE_METRIC: &str = "pg_stat_user_tables_vacuum_due_count";
+
 static TEST_TABLE_COUNTER: AtomicU64 = AtomicU64::new(1);
 
 fn unique_table_name(prefix: &str) -> String {
@@ -95,7 +99,9 @@ async fn test_stat_user_tables_collector_with_created_table() -> Result<()> {
     if has_metrics {
         // If metrics exist, they should have the correct structure
         for family in &metric_families {
-            if family.name().starts_with("pg_stat_user_tables_") {
+            if family.name().starts_with("pg_stat_user_tables_")
+                && family.name() != PER_DATABASE_METRIC
+            {
                 for metric in family.get_metric() {
                     let label_names: Vec<String> = metric
                         .get_label()
@@ -239,7 +245,8 @@ async fn test_stat_user_tables_collector_metrics_have_correct_labels() -> Result
 
     // All metrics should have datname, schemaname, and relname labels
     for family in &metric_families {
-        if family.name().starts_with("pg_stat_user_tables_") {
+        if family.name().starts_with("pg_stat_user_tables_") && family.name() != PER_DATABASE_METRIC
+        {
             for metric in family.get_metric() {
                 let label_names: Vec<String> = metric
                     .get_label()
@@ -537,6 +544,75 @@ async fn test_stat_user_tables_collector_timestamp_values_are_reasonable() -> Re
     Ok(())
 }
 
+#[tokio::test]
+async fn test_stat_user_tables_timestamps_rescale_to_milliseconds() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let table_name = unique_table_name("test_timestamp_unit");
+
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "CREATE TABLE IF NOT EXISTS {table_name} (id INT)"
+    )))
+    .execute(&pool)
+    .await?;
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "VACUUM ANALYZE {table_name}"
+    )))
+    .execute(&pool)
+    .await?;
+
+    let collector = StatUserTablesCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+    let result = collector.collect(&pool).await;
+
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "DROP TABLE IF EXISTS {table_name}"
+    )))
+    .execute(&pool)
+    .await?;
+    pool.close().await;
+    result?;
+
+    let seconds = registry.gather();
+    let mut milliseconds = registry.gather();
+    timestamps_to_milliseconds(&mut milliseconds);
+
+    for (name, renamed) in [
+        (
+            "pg_stat_user_tables_last_vacuum",
+            "pg_stat_user_tables_last_vacuum_milliseconds",
+        ),
+        (
+            "pg_stat_user_tables_last_analyze",
+            "pg_stat_user_tables_last_analyze_milliseconds",
+        ),
+        (
+            "pg_stat_user_tables_last_vacuum_timestamp_seconds",
+            "pg_stat_user_tables_last_vacuum_timestamp_milliseconds",
+        ),
+        (
+            "pg_stat_user_tables_last_analyze_timestamp_seconds",
+            "pg_stat_user_tables_last_analyze_timestamp_milliseconds",
+        ),
+    ] {
+        let before = find_metric_for_table(&seconds, name, &table_name)
+            .map(|metric| metric.get_gauge().value())
+            .ok_or_else(|| anyhow::anyhow!("{name} missing for {table_name}"))?;
+        let after = find_metric_for_table(&milliseconds, renamed, &table_name)
+            .map(|metric| metric.get_gauge().value())
+            .ok_or_else(|| anyhow::anyhow!("{renamed} missing for {table_name}"))?;
+
+        assert!(before > 0.0, "{name} should be set after VACUUM ANALYZE");
+        assert!(
+            (after - before * 1000.0).abs() < 1.0,
+            "{renamed} should be {name} in milliseconds: {before} -> {after}"
+        );
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_stat_user_tables_collector_tracks_table_size() -> Result<()> {
     let pool = common::create_test_pool().await?;
@@ -691,6 +767,136 @@ async fn test_stat_user_tables_collector_bloat_metrics() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_stat_user_tables_collector_hot_update_ratio() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let table_name = unique_table_name("test_hot_ratio");
+
+    // Leave free space on each page and only update an unindexed column so
+    // PostgreSQL can keep every update on the same heap page (HOT).
+    let mut conn = pool.acquire().await?;
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "CREATE TABLE {table_name} (id INT PRIMARY KEY, counter INT) WITH (fillfactor = 50)"
+    )))
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "INSERT INTO {table_name} SELECT g, 0 FROM generate_series(1, 10) g"
+    )))
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "UPDATE {table_name} SET counter = counter + 1"
+    )))
+    .execute(&mut *conn)
+    .await?;
+    let _ = sqlx::query("SELECT pg_stat_force_next_flush()")
+        .execute(&mut *conn)
+        .await;
+    drop(conn);
+
+    let collector = StatUserTablesCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+
+    let mut ratio = 0.0;
+    for _ in 0..20 {
+        collector.collect(&pool).await?;
+        let metric_families = registry.gather();
+        ratio = find_metric_for_table(
+            &metric_families,
+            "pg_stat_user_tables_hot_update_ratio",
+            &table_name,
+        )
+        .map_or(0.0, |metric| metric.get_gauge().value());
+
+        if ratio > 0.0 {
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+
+    assert!(
+        ratio > 0.0 && ratio <= 1.0,
+        "HOT update ratio should be in (0, 1] after in-page updates, got {ratio}"
+    );
+
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "DROP TABLE IF EXISTS {table_name}"
+    )))
+    .execute(&pool)
+    .await?;
+
+    pool.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stat_user_tables_collector_reltuples_estimate() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let table_name = unique_table_name("test_reltuples");
+
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "CREATE TABLE {table_name} (id INT)"
+    )))
+    .execute(&pool)
+    .await?;
+
+    let version_num: i32 = sqlx::query_scalar("SELECT current_setting('server_version_num')::int")
+        .fetch_one(&pool)
+        .await?;
+
+    let collector = StatUserTablesCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+
+    // PostgreSQL 14+ stores reltuples = -1 until the first vacuum or analyze
+    if version_num >= 140_000 {
+        collector.collect(&pool).await?;
+        assert!(
+            find_metric_for_table(
+                &registry.gather(),
+                "pg_stat_user_tables_reltuples_estimate",
+                &table_name,
+            )
+            .is_none(),
+            "never analyzed table should not expose a reltuples estimate"
+        );
+    }
+
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "INSERT INTO {table_name} SELECT generate_series(1, 250)"
+    )))
+    .execute(&pool)
+    .await?;
+    sqlx::query(sqlx::AssertSqlSafe(&*format!("ANALYZE {table_name}")))
+        .execute(&pool)
+        .await?;
+
+    collector.collect(&pool).await?;
+    let estimate = find_metric_for_table(
+        &registry.gather(),
+        "pg_stat_user_tables_reltuples_estimate",
+        &table_name,
+    )
+    .map(|metric| metric.get_gauge().value());
+
+    assert_eq!(
+        estimate.map(common::metric_value_to_i64),
+        Some(250),
+        "ANALYZE of a small table counts every row"
+    );
+
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "DROP TABLE IF EXISTS {table_name}"
+    )))
+    .execute(&pool)
+    .await?;
+
+    pool.close().await;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_stat_user_tables_collector_captures_all_tuple_operations() -> Result<()> {
     let pool = common::create_test_pool().await?;
@@ -965,16 +1171,16 @@ async fn test_stat_user_tables_collector_marks_never_autovacuumed_tables() -> Re
         "new table should be marked as never autovacuumed"
     );
 
-    let has_last_autovacuum_age = find_metric_for_table(
+    let has_last_autovacuum_timestamp = find_metric_for_table(
         &metric_families,
-        "pg_stat_user_tables_last_autovacuum_seconds_ago",
+        "pg_stat_user_tables_last_autovacuum_timestamp_seconds",
         &table_name,
     )
     .is_some();
 
     assert!(
-        !has_last_autovacuum_age,
-        "table that was never autovacuumed should not expose last_autovacuum_seconds_ago"
+        !has_last_autovacuum_timestamp,
+        "table that was never autovacuumed should not expose last_autovacuum_timestamp_seconds"
     );
 
     sqlx::query(sqlx::AssertSqlSafe(&*format!(
@@ -1058,27 +1264,33 @@ async fn test_stat_user_tables_collector_emits_automatic_maintenance_ages() -> R
     .execute(&pool)
     .await?;
 
-    let database_name: String = sqlx::query_scalar("SELECT current_database()")
-        .fetch_one(&pool)
-        .await?;
-    let collector = StatCollector::new();
+    let (database_name, now_epoch): (String, f64) = sqlx::query_as(
+        "SELECT current_database()::text, EXTRACT(EPOCH FROM now())::double precision",
+    )
+    .fetch_one(&pool)
+    .await?;
+    let collector = StatCollector::with_seconds_ago(true);
     let registry = Registry::new();
     collector.register_metrics(&registry)?;
     collector.collect(&pool).await?;
 
     let metric_families = registry.gather();
-    for (metric_name, expected_seconds) in [
-        ("pg_stat_user_tables_last_autovacuum_seconds_ago", 300.0),
-        ("pg_stat_user_tables_last_autoanalyze_seconds_ago", 600.0),
+    for (metric_name, expected_value) in [
+        ("last_autovacuum_seconds_ago", 300.0),
+        ("last_autoanalyze_seconds_ago", 600.0),
+        ("last_autovacuum_timestamp_seconds", now_epoch - 300.0),
+        ("last_autoanalyze_timestamp_seconds", now_epoch - 600.0),
     ] {
+        let metric_name = format!("pg_stat_user_tables_{metric_name}");
+        let metric_name = metric_name.as_str();
         let metric = find_metric_for_table(&metric_families, metric_name, &table_name)
             .ok_or_else(|| anyhow::anyhow!("{metric_name} was not collected for {table_name}"))?;
         let value = metric.get_gauge().value();
 
         assert!(value.is_finite(), "{metric_name} should be finite");
         assert!(
-            (value - expected_seconds).abs() < 1.0,
-            "{metric_name} should be approximately {expected_seconds}, got {value}"
+            (value - expected_value).abs() < 1.0,
+            "{metric_name} should be approximately {expected_value}, got {value}"
         );
         assert!(
             metric
@@ -1095,6 +1307,27 @@ async fn test_stat_user_tables_collector_emits_automatic_maintenance_ages() -> R
     Ok(())
 }
 
+#[tokio::test]
+async fn test_stat_user_tables_collector_omits_seconds_ago_by_default() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let collector = StatUserTablesCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    for family in registry.gather() {
+        assert!(
+            !family.name().ends_with("_seconds_ago"),
+            "{} should only be exported with --stat.seconds-ago",
+            family.name()
+        );
+    }
+
+    pool.close().await;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_stat_user_tables_collector_preserves_last_good_snapshot_on_query_failure()
 -> Result<()> {
@@ -1163,3 +1396,153 @@ async fn test_stat_user_tables_collector_preserves_last_good_snapshot_on_query_f
     pool.close().await;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_stat_user_tables_collector_counts_tables_due_for_vacuum() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let table_name = unique_table_name("test_vacuum_due");
+
+    // A threshold of one dead tuple with no scale factor makes any update push the table
+    // past its trigger point; autovacuum is disabled so it cannot clean up mid-test.
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "CREATE TABLE {table_name} (id INT PRIMARY KEY, data TEXT) WITH (autovacuum_enabled = false, autovacuum_vacuum_threshold = 1, autovacuum_vacuum_scale_factor = 0)"
+    )))
+    .execute(&pool)
+    .await?;
+
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "INSERT INTO {table_name} SELECT g, 'row' FROM generate_series(1, 20) g"
+    )))
+    .execute(&pool)
+    .await?;
+
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "UPDATE {table_name} SET data = 'modified'"
+    )))
+    .execute(&pool)
+    .await?;
+
+    let collector = StatUserTablesCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+
+    let datname: String = sqlx::query_scalar("SELECT current_database()")
+        .fetch_one(&pool)
+        .await?;
+
+    let mut due = 0.0;
+    for _ in 0..20 {
+        let _ = sqlx::query("SELECT pg_stat_force_next_flush()")
+            .execute(&pool)
+            .await;
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+        collector.collect(&pool).await?;
+
+        due = registry
+            .gather()
+            .iter()
+            .find(|m| m.name() == "pg_stat_user_tables_vacuum_due_count")
+            .and_then(|family| {
+                family.get_metric().iter().find(|metric| {
+                    metric
+                        .get_label()
+                        .iter()
+                        .any(|label| label.name() == "datname" && label.value() == datname)
+                })
+            })
+            .map_or(0.0, |metric| metric.get_gauge().value());
+
+        if due >= 1.0 {
+            break;
+        }
+    }
+
+    sqlx::query(sqlx::AssertSqlSafe(&*format!(
+        "DROP TABLE IF EXISTS {table_name}"
+    )))
+    .execute(&pool)
+    .await?;
+    pool.close().await;
+
+    assert!(
+        due >= 1.0,
+        "expected at least one table due for vacuum in {datname}, got {due}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stat_user_tables_collector_derives_autovacuum_launches() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let collector = StatUserTablesCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+
+    let launches = |registry: &Registry| -> Option<f64> {
+        registry
+            .gather()
+            .iter()
+            .find(|m| m.name() == "pg_autovacuum_launches_total")?
+            .get_metric()
+            .iter()
+            .find(|m| {
+                m.get_label()
+                    .iter()
+                    .any(|l| l.name() == "datname" && l.value() == "postgres")
+            })
+            .map(|m| m.get_counter().value())
+    };
+
+    // The first scrape only records the baseline
+    collector.collect(&pool).await?;
+    assert_eq!(launches(&registry), Some(0.0));
+
+    collector.collect(&pool).await?;
+    assert!(launches(&registry).is_some_and(|value| value >= 0.0));
+
+    pool.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stat_user_tables_collector_include_system_exports_catalogs() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let has_catalog = |registry: &Registry| {
+        registry
+            .gather()
+            .iter()
+            .find(|family| family.name() == "pg_stat_user_tables_seq_scan")
+            .is_some_and(|family| {
+                family.get_metric().iter().any(|metric| {
+                    let labels = metric.get_label();
+                    labels
+                        .iter()
+                        .any(|l| l.name() == "schemaname" && l.value() == "pg_catalog")
+                        && labels
+                            .iter()
+                            .any(|l| l.name() == "relname" && l.value() == "pg_class")
+                })
+            })
+    };
+
+    let registry = Registry::new();
+    let collector = StatUserTablesCollector::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+    assert!(!has_catalog(&registry), "catalogs are excluded by default");
+
+    let registry = Registry::new();
+    let collector = StatUserTablesCollector::with_options(false, true);
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+    assert!(
+        has_catalog(&registry),
+        "--stat.include-system should export pg_catalog.pg_class"
+    );
+
+    pool.close().await;
+    Ok(())
+}
@@ -54,6 +54,23 @@ async fn timed_filter_count(
     Ok((count, started.elapsed()))
 }
 
+/// `pg_exporter_collector_available{collector="statements"}`, if exported
+fn statements_available(registry: &Registry) -> Option<f64> {
+    registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == "pg_exporter_collector_available")
+        .and_then(|family| {
+            family.get_metric().iter().find(|metric| {
+                metric
+                    .get_label()
+                    .iter()
+                    .any(|l| l.name() == "collector" && l.value() == "statements")
+            })
+        })
+        .map(|metric| metric.get_gauge().value())
+}
+
 fn median_duration(samples: &mut [StdDuration]) -> StdDuration {
     samples.sort_unstable();
     samples.get(samples.len() / 2).copied().unwrap_or_default()
@@ -97,11 +114,13 @@ async fn test_pg_statements_collector_has_all_metrics_after_collection() -> Resu
         "postgres_pg_stat_statements_mean_exec_time_seconds",
         "postgres_pg_stat_statements_max_exec_time_seconds",
         "postgres_pg_stat_statements_stddev_exec_time_seconds",
+        "postgres_pg_stat_statements_exec_time_cv",
         "postgres_pg_stat_statements_calls_total",
         "postgres_pg_stat_statements_rows_total",
         "postgres_pg_stat_statements_shared_blks_hit_total",
         "postgres_pg_stat_statements_shared_blks_read_total",
         "postgres_pg_stat_statements_cache_hit_ratio",
+        "pg_exporter_collector_available",
     ];
 
     for metric_name in expected_metrics {
@@ -116,6 +135,7 @@ async fn test_pg_statements_collector_has_all_metrics_after_collection() -> Resu
                 .collect::<Vec<_>>()
         );
     }
+    assert_eq!(statements_available(&registry), Some(1.0));
 
     test_db.cleanup().await?;
     Ok(())
@@ -138,6 +158,11 @@ async fn test_pg_statements_collector_gracefully_handles_missing_extension() ->
         result.is_ok(),
         "Collector should handle missing extension gracefully"
     );
+    assert_eq!(
+        statements_available(&registry),
+        Some(0.0),
+        "a missing extension is reported as unavailable"
+    );
 
     test_db.cleanup().await?;
     Ok(())
@@ -165,6 +190,46 @@ async fn test_pg_statements_collector_with_top_n_configuration() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_pg_statements_collector_min_mean_time_filters_fast_statements() -> Result<()> {
+    let Some(test_db) = setup_pg_statements_test_db().await? else {
+        println!("pg_stat_statements extension not installed, skipping test");
+        return Ok(());
+    };
+    let pool = test_db.pool();
+
+    for _ in 0..5 {
+        sqlx::query("SELECT 1").execute(pool).await?;
+    }
+
+    let series = |registry: &Registry| {
+        registry
+            .gather()
+            .iter()
+            .find(|m| m.name() == "postgres_pg_stat_statements_mean_exec_time_seconds")
+            .map_or(0, |family| family.get_metric().len())
+    };
+
+    let unfiltered = PgStatementsCollector::with_top_n(25);
+    let registry = Registry::new();
+    unfiltered.register_metrics(&registry)?;
+    unfiltered.collect(pool).await?;
+    assert!(
+        series(&registry) > 0,
+        "unfiltered collection should export statements"
+    );
+
+    // No statement averages over a day per execution
+    let filtered = PgStatementsCollector::with_top_n(25).with_min_mean_time(86_400_000.0);
+    let registry = Registry::new();
+    filtered.register_metrics(&registry)?;
+    filtered.collect(pool).await?;
+    assert_eq!(series(&registry), 0);
+
+    test_db.cleanup().await?;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_pg_statements_collector_excludes_own_query() -> Result<()> {
     let Some(test_db) = setup_pg_statements_test_db().await? else {
@@ -4,9 +4,32 @@ mod common;
 
 use anyhow::Result;
 use pg_exporter::collectors::Collector;
-use pg_exporter::collectors::tls::certificate::CertificateCollector;
+use pg_exporter::collectors::tls::certificate::{CertificateCollector, CertificateSource};
 use prometheus::Registry;
 
+// Self-signed EC certificate, CN=pg-exporter-test, valid until 2126-09-23T03:57:14Z
+const TEST_CERT_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIBjjCCATOgAwIBAgIUdFJgcHc5J10mPufnl+l+zXcz/LEwCgYIKoZIzj0EAwIw
+GzEZMBcGA1UEAwwQcGctZXhwb3J0ZXItdGVzdDAgFw0yNjEwMTcwMzU3MTRaGA8y
+MTI2MDkyMzAzNTcxNFowGzEZMBcGA1UEAwwQcGctZXhwb3J0ZXItdGVzdDBZMBMG
+ByqGSM49AgEGCCqGSM49AwEHA0IABOK4DOpAhrTZ6ThyNWHRb+WFC4x7pKj8ANW1
+p3WyYD9Z245cK6MYG8j2+sYnDymKszoG5h0u9lMSErOtTM6CU1ujUzBRMB0GA1Ud
+DgQWBBQxve4kx4LBHYWShRQXMeRbvldIGzAfBgNVHSMEGDAWgBQxve4kx4LBHYWS
+hRQXMeRbvldIGzAPBgNVHRMBAf8EBTADAQH/MAoGCCqGSM49BAMCA0kAMEYCIQDP
+FSimZ1AJPucnu2EQl9S7oC5Jt2G3PK/c23cMxjAadAIhAOrv0JXXKnuyzp3sCJgJ
+0YeN2y/2QUadQxwFc7eFqLgx
+-----END CERTIFICATE-----
+";
+const TEST_CERT_NOT_AFTER: f64 = 4_945_809_434.0;
+
+fn gauge(registry: &Registry, name: &str) -> Option<f64> {
+    registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == name)
+        .and_then(|fam| fam.get_metric().first().map(|m| m.get_gauge().value()))
+}
+
 #[tokio::test]
 async fn test_certificate_collector_registers_without_error() -> Result<()> {
     let collector = CertificateCollector::new();
@@ -91,3 +114,72 @@ async fn test_certificate_collector_collects_from_database() -> Result<()> {
     pool.close().await;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_certificate_collector_reads_inline_pem() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let collector =
+        CertificateCollector::with_source(CertificateSource::Pem(TEST_CERT_PEM.to_string()));
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    assert_eq!(
+        gauge(&registry, "pg_ssl_certificate_not_after_timestamp"),
+        Some(TEST_CERT_NOT_AFTER)
+    );
+    assert_eq!(gauge(&registry, "pg_ssl_certificate_valid"), Some(1.0));
+    assert!(gauge(&registry, "pg_ssl_certificate_expiry_seconds").is_some_and(|v| v > 0.0));
+
+    pool.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_certificate_collector_reads_pem_file() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let path =
+        std::env::temp_dir().join(format!("pg_exporter_test_cert_{}.pem", std::process::id()));
+    std::fs::write(&path, TEST_CERT_PEM)?;
+
+    let collector = CertificateCollector::with_source(CertificateSource::PemFile(path.clone()));
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    let result = collector.collect(&pool).await;
+    std::fs::remove_file(&path)?;
+    result?;
+
+    assert_eq!(
+        gauge(&registry, "pg_ssl_certificate_not_after_timestamp"),
+        Some(TEST_CERT_NOT_AFTER)
+    );
+    assert_eq!(gauge(&registry, "pg_ssl_certificate_valid"), Some(1.0));
+
+    pool.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_certificate_collector_tolerates_unreadable_pem_file() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let collector = CertificateCollector::with_source(CertificateSource::PemFile(
+        std::env::temp_dir().join("pg_exporter_missing_cert.pem"),
+    ));
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    assert_eq!(
+        gauge(&registry, "pg_ssl_certificate_not_after_timestamp"),
+        Some(0.0)
+    );
+
+    pool.close().await;
+    Ok(())
+}
@@ -1,6 +1,7 @@
 mod certificate;
 mod connection_stats;
 mod server_config;
+mod transport;
 
 #[allow(clippy::duplicate_mod)]
 #[path = "../../common/mod.rs"]
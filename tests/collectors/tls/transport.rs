@@ -0,0 +1,58 @@
+#[allow(clippy::duplicate_mod)]
+#[path = "../../common/mod.rs"]
+mod common;
+
+use anyhow::Result;
+use pg_exporter::collectors::Collector;
+use pg_exporter::collectors::tls::transport::{ConnectionTransportCollector, TRANSPORTS};
+use prometheus::Registry;
+
+#[tokio::test]
+async fn test_connection_transport_collector_name() {
+    let collector = ConnectionTransportCollector::new();
+    assert_eq!(collector.name(), "tls.transport");
+    assert!(!collector.enabled_by_default());
+}
+
+#[tokio::test]
+async fn test_connection_transport_collector_exports_every_transport() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let collector = ConnectionTransportCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let families = registry.gather();
+    let family = families
+        .iter()
+        .find(|m| m.name() == "pg_connections_by_transport")
+        .expect("pg_connections_by_transport metric should exist");
+
+    let mut transports: Vec<&str> = family
+        .get_metric()
+        .iter()
+        .flat_map(|m| {
+            m.get_label()
+                .iter()
+                .map(prometheus::proto::LabelPair::value)
+        })
+        .collect();
+    transports.sort_unstable();
+    assert_eq!(transports, TRANSPORTS.to_vec());
+
+    // The pool's own connection is counted under one of them
+    let total: i64 = family
+        .get_metric()
+        .iter()
+        .map(|m| common::metric_value_to_i64(m.get_gauge().value()))
+        .sum();
+    assert!(
+        total >= 1,
+        "expected at least the test connection, got {total}"
+    );
+
+    pool.close().await;
+    Ok(())
+}
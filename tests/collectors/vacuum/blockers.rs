@@ -159,6 +159,82 @@ async fn test_vacuum_blockers_worst_offender_series_are_bounded() -> Result<()>
     Ok(())
 }
 
+#[tokio::test]
+async fn test_vacuum_blockers_oldest_xmin_age_names_a_long_transaction() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+    let collector = VacuumBlockersCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+
+    // Hold a snapshot open in a separate session so backend_xmin is pinned, then burn a
+    // few xids so its age is strictly positive.
+    let mut snapshot_conn = pool.acquire().await?;
+    sqlx::query("SET application_name = 'pg_exporter_xmin_holder_test'")
+        .execute(&mut *snapshot_conn)
+        .await?;
+    sqlx::query("BEGIN ISOLATION LEVEL REPEATABLE READ")
+        .execute(&mut *snapshot_conn)
+        .await?;
+    sqlx::query("SELECT 1").execute(&mut *snapshot_conn).await?;
+    for _ in 0..3 {
+        sqlx::query("SELECT txid_current()").execute(&pool).await?;
+    }
+
+    collector.collect(&pool).await?;
+
+    let families = registry.gather();
+    let oldest = metric_family(&families, "pg_oldest_xmin_age")
+        .ok_or_else(|| anyhow::anyhow!("missing pg_oldest_xmin_age"))?;
+    assert_eq!(
+        oldest.get_metric().len(),
+        1,
+        "pg_oldest_xmin_age must emit exactly one series while a snapshot is held"
+    );
+    let metric = oldest
+        .get_metric()
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("missing pg_oldest_xmin_age sample"))?;
+    let holder = metric
+        .get_label()
+        .iter()
+        .find(|label| label.name() == "holder")
+        .map(prometheus::proto::LabelPair::value)
+        .unwrap_or_default();
+    assert!(
+        ["backend", "prepared_xact", "replication_slot"].contains(&holder),
+        "unexpected holder label {holder}"
+    );
+    assert!(metric.get_gauge().value() > 0.0);
+
+    // The worst backend is named with its pid, ready for pg_terminate_backend()
+    let backend_identity = metric_family(&families, "pg_xmin_horizon_holder_age_xids")
+        .and_then(|family| {
+            family.get_metric().iter().find(|metric| {
+                metric
+                    .get_label()
+                    .iter()
+                    .any(|label| label.name() == "holder" && label.value() == "backend")
+            })
+        })
+        .and_then(|metric| {
+            metric
+                .get_label()
+                .iter()
+                .find(|label| label.name() == "identity")
+                .map(|label| label.value().to_string())
+        })
+        .ok_or_else(|| anyhow::anyhow!("missing backend holder identity"))?;
+    assert!(
+        backend_identity.starts_with("pid ") || backend_identity.contains(" (pid "),
+        "backend identity should carry the pid: {backend_identity}"
+    );
+
+    sqlx::query("ROLLBACK").execute(&mut *snapshot_conn).await?;
+    drop(snapshot_conn);
+    pool.close().await;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_vacuum_blockers_prepared_xact_type_conversions() -> Result<()> {
     let pool = common::create_test_pool().await?;
@@ -0,0 +1,51 @@
+use super::super::common;
+use anyhow::Result;
+use pg_exporter::collectors::{Collector, vacuum::freeze_blocked::FreezeBlockedCollector};
+use prometheus::Registry;
+
+#[tokio::test]
+async fn test_freeze_blocked_registers_without_error() -> Result<()> {
+    let collector = FreezeBlockedCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_freeze_blocked_collector_name() {
+    let collector = FreezeBlockedCollector::new();
+    assert_eq!(collector.name(), "vacuum_freeze_blocked");
+}
+
+#[tokio::test]
+async fn test_freeze_blocked_exports_nothing_without_waiting_autovacuum() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let waiting: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*)::bigint FROM pg_locks l JOIN pg_stat_activity a ON a.pid = l.pid
+         WHERE a.backend_type = 'autovacuum worker' AND NOT l.granted",
+    )
+    .fetch_one(&pool)
+    .await?;
+
+    let collector = FreezeBlockedCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+    pool.close().await;
+
+    if waiting == 0 {
+        // Vec metrics without series are not gathered at all
+        let families = registry.gather();
+        assert!(
+            families
+                .iter()
+                .all(|f| f.get_metric().is_empty()
+                    || !f.name().starts_with("pg_tables_freeze_blocked")),
+            "no table is blocked, so no series should be exported"
+        );
+    }
+
+    Ok(())
+}
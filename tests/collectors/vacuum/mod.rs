@@ -3,5 +3,6 @@
 pub mod analyze_progress;
 pub mod blockers;
 pub mod create_index_progress;
+pub mod freeze_blocked;
 pub mod progress;
 pub mod stats;
@@ -93,6 +93,8 @@ async fn test_vacuum_progress_collector_metrics_have_database_and_table_labels()
         "pg_vacuum_heap_progress",
         "pg_vacuum_heap_vacuumed",
         "pg_vacuum_index_vacuum_count",
+        "pg_vacuum_phase",
+        "pg_stat_progress_vacuum_percent_complete",
     ];
 
     for metric_name in table_metrics {
@@ -153,6 +155,36 @@ async fn test_vacuum_progress_collector_progress_ratio_is_valid() -> Result<()>
     Ok(())
 }
 
+#[tokio::test]
+async fn test_vacuum_progress_percent_complete_is_a_percentage() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let collector = VacuumProgressCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let metric_families = registry.gather();
+
+    // Unlike pg_vacuum_heap_progress this is 0-100, and only present while it measures progress
+    if let Some(percent_metric) = metric_families
+        .iter()
+        .find(|m| m.name() == "pg_stat_progress_vacuum_percent_complete")
+    {
+        for metric in percent_metric.get_metric() {
+            let value = metric.get_gauge().value();
+            assert!(
+                (0.0..=100.0).contains(&value),
+                "Percent complete should be within 0-100, got {value}"
+            );
+        }
+    }
+
+    pool.close().await;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_vacuum_progress_collector_counts_are_non_negative() -> Result<()> {
     let pool = common::create_test_pool().await?;
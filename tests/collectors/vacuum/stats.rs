@@ -33,6 +33,7 @@ async fn test_vacuum_stats_collector_has_all_metrics_after_collection() -> Resul
         "pg_vacuum_freeze_max_age_xids",
         "pg_vacuum_database_freeze_age_pct_of_max",
         "pg_vacuum_autovacuum_workers",
+        "pg_autovacuum_workers_blocked",
     ];
 
     for metric_name in expected_metrics {
@@ -314,3 +315,25 @@ async fn test_vacuum_stats_collector_preserves_last_good_snapshot_on_query_failu
     broken_pool.close().await;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_vacuum_stats_collector_blocked_workers_not_negative() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let collector = VacuumStatsCollector::new();
+    let registry = Registry::new();
+
+    collector.register_metrics(&registry)?;
+    collector.collect(&pool).await?;
+
+    let blocked = registry
+        .gather()
+        .iter()
+        .find(|m| m.name() == "pg_autovacuum_workers_blocked")
+        .and_then(|m| m.get_metric().first().map(|m| m.get_gauge().value()));
+
+    assert!(blocked.is_some_and(|v| v >= 0.0));
+
+    pool.close().await;
+    Ok(())
+}
@@ -0,0 +1,73 @@
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+#![allow(clippy::indexing_slicing)]
+
+//! Deployments whose DSN names a database other than `postgres`. Runs in its own test
+//! binary because the default database and exclusions are process-wide and set once.
+
+use anyhow::Result;
+use pg_exporter::collectors::{
+    Collector,
+    stat::user_tables::StatUserTablesCollector,
+    util::{get_default_database, set_base_connect_options_from_dsn, set_excluded_databases},
+};
+use prometheus::Registry;
+use secrecy::SecretString;
+use url::Url;
+
+mod common;
+
+#[tokio::test]
+async fn test_dsn_database_reuses_shared_pool() -> Result<()> {
+    let test_db = common::IsolatedTestDatabase::new("default_db").await?;
+    let datname = test_db.database_name().to_string();
+    let pool = test_db.pool();
+
+    sqlx::query("CREATE TABLE default_db_probe (id int)")
+        .execute(pool)
+        .await?;
+
+    // Per-database connections are built from these options and would be refused on
+    // port 1, so the probe table can only be read through the shared pool.
+    let mut dsn = Url::parse(&common::get_test_dsn())?;
+    dsn.set_path(&format!("/{datname}"));
+    dsn.set_port(Some(1)).expect("set DSN port");
+    set_base_connect_options_from_dsn(&SecretString::from(dsn.to_string()))?;
+    assert_eq!(get_default_database(), Some(datname.as_str()));
+
+    let others: Vec<String> =
+        sqlx::query_scalar("SELECT datname::text FROM pg_database WHERE datname <> $1")
+            .bind(&datname)
+            .fetch_all(pool)
+            .await?;
+    set_excluded_databases(others);
+
+    let collector = StatUserTablesCollector::new();
+    let registry = Registry::new();
+    collector.register_metrics(&registry)?;
+    collector.collect(pool).await?;
+
+    let families = registry.gather();
+    let probe = families
+        .iter()
+        .find(|family| family.name() == "pg_stat_user_tables_n_live_tup")
+        .and_then(|family| {
+            family.get_metric().iter().find(|metric| {
+                metric
+                    .get_label()
+                    .iter()
+                    .any(|label| label.name() == "relname" && label.value() == "default_db_probe")
+            })
+        })
+        .expect("probe table should be collected through the shared pool");
+    assert!(
+        probe
+            .get_label()
+            .iter()
+            .any(|label| label.name() == "datname" && label.value() == datname)
+    );
+
+    test_db.cleanup().await?;
+    Ok(())
+}
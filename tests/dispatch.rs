@@ -30,7 +30,10 @@ fn test_handler_happy_path_sets_exclusions_and_returns_action() -> Result<()> {
         listen: _,
         dsn,
         collector_config,
-    } = action;
+    } = action
+    else {
+        panic!("expected Action::Run");
+    };
 
     assert_eq!(port, 9898);
     assert_eq!(
@@ -75,7 +78,10 @@ fn test_handler_reads_statements_top_n() -> Result<()> {
 
     let Action::Run {
         collector_config, ..
-    } = action;
+    } = action
+    else {
+        panic!("expected Action::Run");
+    };
     assert!(collector_config.is_enabled("statements"));
     assert_eq!(collector_config.statements.top_n, 10);
 
@@ -93,7 +99,9 @@ fn test_dsn_unix_socket_with_user() -> Result<()> {
 
     let action = pg_exporter::cli::dispatch::handler(&matches)?;
 
-    let Action::Run { dsn, .. } = action;
+    let Action::Run { dsn, .. } = action else {
+        panic!("expected Action::Run");
+    };
 
     assert_eq!(
         dsn.expose_secret(),
@@ -114,7 +122,9 @@ fn test_dsn_unix_socket_with_host_and_user() -> Result<()> {
 
     let action = pg_exporter::cli::dispatch::handler(&matches)?;
 
-    let Action::Run { dsn, .. } = action;
+    let Action::Run { dsn, .. } = action else {
+        panic!("expected Action::Run");
+    };
 
     assert_eq!(
         dsn.expose_secret(),
@@ -135,7 +145,9 @@ fn test_dsn_ssl_mode_require() -> Result<()> {
 
     let action = pg_exporter::cli::dispatch::handler(&matches)?;
 
-    let Action::Run { dsn, .. } = action;
+    let Action::Run { dsn, .. } = action else {
+        panic!("expected Action::Run");
+    };
 
     assert_eq!(
         dsn.expose_secret(),
@@ -156,7 +168,9 @@ fn test_dsn_ssl_mode_verify_ca() -> Result<()> {
 
     let action = pg_exporter::cli::dispatch::handler(&matches)?;
 
-    let Action::Run { dsn, .. } = action;
+    let Action::Run { dsn, .. } = action else {
+        panic!("expected Action::Run");
+    };
 
     assert_eq!(
         dsn.expose_secret(),
@@ -177,7 +191,9 @@ fn test_dsn_ssl_mode_verify_full() -> Result<()> {
 
     let action = pg_exporter::cli::dispatch::handler(&matches)?;
 
-    let Action::Run { dsn, .. } = action;
+    let Action::Run { dsn, .. } = action else {
+        panic!("expected Action::Run");
+    };
 
     assert_eq!(
         dsn.expose_secret(),
@@ -186,3 +202,56 @@ fn test_dsn_ssl_mode_verify_full() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_handler_healthcheck_uses_port_and_listen() -> Result<()> {
+    temp_env::with_vars(
+        [
+            ("PG_EXPORTER_PORT", None::<&str>),
+            ("PG_EXPORTER_LISTEN", None::<&str>),
+            ("PG_EXPORTER_HEALTHCHECK_URL", None::<&str>),
+            ("PG_EXPORTER_HEALTHCHECK_TIMEOUT_MS", None::<&str>),
+        ],
+        || {
+            let matches = commands::new().get_matches_from(vec![
+                "pg_exporter",
+                "healthcheck",
+                "--port",
+                "9187",
+                "--listen",
+                "::1",
+                "--timeout-ms",
+                "1500",
+            ]);
+
+            let action = pg_exporter::cli::dispatch::handler(&matches)?;
+
+            let Action::Healthcheck { url, timeout } = action else {
+                panic!("expected Action::Healthcheck");
+            };
+
+            assert_eq!(url, "http://[::1]:9187/health");
+            assert_eq!(timeout, std::time::Duration::from_millis(1500));
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn test_handler_healthcheck_explicit_url() -> Result<()> {
+    let matches = commands::new().get_matches_from(vec![
+        "pg_exporter",
+        "healthcheck",
+        "--url",
+        "http://127.0.0.1:9999/health",
+    ]);
+
+    let action = pg_exporter::cli::dispatch::handler(&matches)?;
+
+    let Action::Healthcheck { url, .. } = action else {
+        panic!("expected Action::Healthcheck");
+    };
+
+    assert_eq!(url, "http://127.0.0.1:9999/health");
+    Ok(())
+}
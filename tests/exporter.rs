@@ -243,9 +243,9 @@ async fn test_exporter_returns_200_with_pg_up_zero_when_db_down() -> Result<()>
         pg_exporter::exporter::new(port, None, dsn, collector_config(&["default"])).await
     });
 
-    // Wait for server to start
+    // Startup waits up to the connect timeout (5s default) for version detection
     assert!(
-        common::wait_for_server(port, 50).await,
+        common::wait_for_server(port, 100).await,
         "Server failed to start on port {port} despite DB being down"
     );
 
@@ -324,3 +324,39 @@ async fn test_exporter_sets_application_name_on_database_sessions() -> Result<()
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_preflight_reports_missing_extension() -> Result<()> {
+    use pg_exporter::collectors::preflight;
+
+    // pg_stat_statements is never created in an isolated database
+    let test_db = common::IsolatedTestDatabase::new("preflight").await?;
+
+    let results = preflight::run(
+        &collector_config(&["default", "statements"]),
+        test_db.pool(),
+    )
+    .await;
+
+    let default = results.iter().find(|r| r.collector == "default").unwrap();
+    assert!(default.is_ready(), "{default:?}");
+
+    let statements = results
+        .iter()
+        .find(|r| r.collector == "statements")
+        .unwrap();
+    assert!(!statements.is_ready());
+    assert!(
+        statements
+            .problem
+            .as_deref()
+            .is_some_and(|problem| problem.contains("statements")),
+        "{statements:?}"
+    );
+
+    let table = preflight::format_table(&results);
+    assert!(table.contains("NOT READY"), "{table}");
+
+    test_db.cleanup().await?;
+    Ok(())
+}
@@ -120,8 +120,9 @@ async fn test_health_endpoint_returns_503_when_db_down() -> Result<()> {
         pg_exporter::exporter::new(port, None, dsn, collector_config(&["default"])).await
     });
 
+    // Startup waits up to the connect timeout (5s default) for version detection
     assert!(
-        common::wait_for_server(port, 50).await,
+        common::wait_for_server(port, 100).await,
         "Server failed to start while DB is down"
     );
 
@@ -0,0 +1,322 @@
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+#![allow(clippy::indexing_slicing)]
+//! Label-set stability guard.
+//!
+//! Scrapes with every collector enabled and compares the label names of each exported
+//! metric family with `tests/metric_labels.txt`. A renamed, added or dropped label breaks
+//! dashboards and recording rules, so such a change has to show up in review as an edit
+//! of that file. Metrics that the test database does not produce are not checked.
+//!
+//! After an intentional change, regenerate the entries with
+//! `PG_EXPORTER_UPDATE_METRIC_LABELS=1 cargo test --test metric_labels`.
+use anyhow::Result;
+use pg_exporter::collectors::{
+    COLLECTOR_NAMES,
+    config::CollectorConfig,
+    registry::CollectorRegistry,
+    util::{
+        ANONYMIZED_LABELS, anonymize_label_value, anonymize_metric_families,
+        get_null_datname_label, set_anonymize_salt, set_base_connect_options_from_dsn,
+        set_excluded_databases, set_excluded_users,
+    },
+};
+use prometheus::proto::LabelPair;
+use std::collections::{BTreeMap, HashSet};
+
+mod common;
+
+const SNAPSHOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/metric_labels.txt");
+
+/// `metric_name label,label,...` per line; metrics without labels have nothing after the name
+fn parse_snapshot(contents: &str) -> BTreeMap<String, String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (name, labels) = line.split_once(' ').unwrap_or((line, ""));
+            (name.to_string(), labels.trim().to_string())
+        })
+        .collect()
+}
+
+fn render_snapshot(entries: &BTreeMap<String, String>) -> String {
+    let mut out = String::from(
+        "# Label names of every exported metric, checked by tests/metric_labels.rs.\n\
+         # Regenerate with: PG_EXPORTER_UPDATE_METRIC_LABELS=1 cargo test --test metric_labels\n",
+    );
+    for (name, labels) in entries {
+        out.push_str(name);
+        if !labels.is_empty() {
+            out.push(' ');
+            out.push_str(labels);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[tokio::test]
+async fn test_metric_label_sets_are_stable() -> Result<()> {
+    let pool = common::create_test_pool().await?;
+
+    let enabled: Vec<String> = COLLECTOR_NAMES.iter().map(ToString::to_string).collect();
+    let registry = CollectorRegistry::new(&CollectorConfig::new(25).with_enabled(&enabled))?;
+    registry
+        .collect_all(&pool)
+        .await
+        .map_err(|e| anyhow::anyhow!("scrape failed: {e}"))?;
+
+    let databases: HashSet<String> =
+        sqlx::query_scalar::<_, String>("SELECT datname::text FROM pg_database")
+            .fetch_all(&pool)
+            .await?
+            .into_iter()
+            .collect();
+    pool.close().await;
+
+    let mut observed: BTreeMap<String, String> = BTreeMap::new();
+    let mut problems: Vec<String> = Vec::new();
+
+    for family in registry.registry().gather() {
+        let name = family.name().to_string();
+
+        for metric in family.get_metric() {
+            let names: Vec<&str> = metric.get_label().iter().map(LabelPair::name).collect();
+
+            // A swapped with_label_values() argument puts e.g. a relation into datname
+            if let Some(datname) = metric.get_label().iter().find(|l| l.name() == "datname")
+                && !databases.contains(datname.value())
+                && datname.value() != get_null_datname_label()
+            {
+                problems.push(format!(
+                    "{name}: datname={:?} is not a database; label values out of declared order?",
+                    datname.value()
+                ));
+            }
+
+            let labels = names.join(",");
+            match observed.get(&name) {
+                Some(previous) if *previous != labels => problems.push(format!(
+                    "{name}: inconsistent label sets within one scrape: {previous:?} vs {labels:?}"
+                )),
+                Some(_) => {}
+                None => {
+                    observed.insert(name.clone(), labels);
+                }
+            }
+        }
+    }
+
+    assert!(
+        observed.len() > 100,
+        "expected a full scrape, got {} metric families",
+        observed.len()
+    );
+
+    let contents = std::fs::read_to_string(SNAPSHOT).unwrap_or_default();
+    let mut snapshot = parse_snapshot(&contents);
+
+    if std::env::var_os("PG_EXPORTER_UPDATE_METRIC_LABELS").is_some() {
+        // Merge so metrics this database cannot produce keep their recorded entry
+        snapshot.extend(observed);
+        std::fs::write(SNAPSHOT, render_snapshot(&snapshot))?;
+        assert!(problems.is_empty(), "{}", problems.join("\n"));
+        return Ok(());
+    }
+
+    for (name, labels) in &observed {
+        match snapshot.get(name) {
+            None => problems.push(format!(
+                "{name}: not in tests/metric_labels.txt (labels {labels:?})"
+            )),
+            Some(expected) if expected != labels => problems.push(format!(
+                "{name}: label set changed from {expected:?} to {labels:?}"
+            )),
+            Some(_) => {}
+        }
+    }
+
+    assert!(
+        problems.is_empty(),
+        "metric label sets drifted (update tests/metric_labels.txt with \
+         PG_EXPORTER_UPDATE_METRIC_LABELS=1 if intentional):\n{}",
+        problems.join("\n")
+    );
+
+    Ok(())
+}
+
+/// Objects created (or, for the exclusions, configured) by
+/// [`test_identifier_labels_are_anonymized`]; unique enough that no label carries one of
+/// these names by accident.
+const FIXTURE_SCHEMA: &str = "anon_probe_schema";
+const FIXTURE_EXCLUDED_DATABASE: &str = "anon_probe_excluded_db";
+const FIXTURE_EXCLUDED_USER: &str = "anon_probe_excluded_role";
+const FIXTURE_IDENTIFIERS: [&str; 7] = [
+    FIXTURE_SCHEMA,
+    "anon_probe_table",
+    "anon_probe_index",
+    "anon_probe_seq",
+    "anon_probe_fn",
+    FIXTURE_EXCLUDED_DATABASE,
+    FIXTURE_EXCLUDED_USER,
+];
+
+const SALT: &str = "test-salt";
+
+fn carries_fixture(value: &str) -> bool {
+    FIXTURE_IDENTIFIERS.iter().any(|name| value.contains(name))
+}
+
+async fn execute(pool: &sqlx::PgPool, sql: &str) -> Result<()> {
+    sqlx::raw_sql(sqlx::AssertSqlSafe(sql))
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// `pg_exporter_config_info` hashes its exclusion lists per entry while collecting, not at
+/// exposition time, so check them before [`anonymize_metric_families`] runs.
+fn assert_exclusions_hashed(families: &[prometheus::proto::MetricFamily]) {
+    let config_info = families
+        .iter()
+        .find(|family| family.name() == "pg_exporter_config_info")
+        .and_then(|family| family.get_metric().first())
+        .expect("pg_exporter_config_info should be exported");
+    for (label, name) in [
+        ("exclude_databases", FIXTURE_EXCLUDED_DATABASE),
+        ("exclude_users", FIXTURE_EXCLUDED_USER),
+    ] {
+        let value = config_info
+            .get_label()
+            .iter()
+            .find(|pair| pair.name() == label)
+            .map(LabelPair::value);
+        assert_eq!(
+            value,
+            Some(anonymize_label_value(name, SALT).as_str()),
+            "{label} should list the hashed entry"
+        );
+    }
+}
+
+/// Every label that carries the name of a schema, table, index, sequence or function must
+/// be anonymized, so a new collector cannot leak tenant names under a label name that
+/// `--anonymize-labels` does not know. Labels that merely contain a name, such as the
+/// statement text in `query_short` or the exclusion lists of `pg_exporter_config_info`,
+/// count as carriers too.
+#[tokio::test]
+async fn test_identifier_labels_are_anonymized() -> Result<()> {
+    // Per-database collectors reach the isolated database with the test DSN's options
+    set_base_connect_options_from_dsn(&common::get_test_dsn_secret())?;
+    set_anonymize_salt(SALT.to_string());
+    set_excluded_databases(vec![FIXTURE_EXCLUDED_DATABASE.to_string()]);
+    set_excluded_users(vec![FIXTURE_EXCLUDED_USER.to_string()]);
+
+    // pg_stat_statements puts fixture names into query text; without it only the
+    // catalog-backed labels are checked
+    let (test_db, with_statements) =
+        match common::create_pg_statements_test_database("anon_labels").await? {
+            Some(test_db) => (test_db, true),
+            None => (
+                common::IsolatedTestDatabase::new("anon_labels").await?,
+                false,
+            ),
+        };
+    let pool = test_db.pool();
+    execute(
+        pool,
+        &format!(
+            "CREATE SCHEMA {FIXTURE_SCHEMA};
+             CREATE SEQUENCE {FIXTURE_SCHEMA}.anon_probe_seq;
+             CREATE TABLE {FIXTURE_SCHEMA}.anon_probe_table (
+                 id bigint PRIMARY KEY DEFAULT nextval('{FIXTURE_SCHEMA}.anon_probe_seq')
+             );
+             CREATE INDEX anon_probe_index ON {FIXTURE_SCHEMA}.anon_probe_table (id);
+             INSERT INTO {FIXTURE_SCHEMA}.anon_probe_table SELECT FROM generate_series(1, 10);
+             CREATE FUNCTION {FIXTURE_SCHEMA}.anon_probe_fn() RETURNS bigint
+                 LANGUAGE sql AS 'SELECT count(*) FROM {FIXTURE_SCHEMA}.anon_probe_table';
+             ANALYZE {FIXTURE_SCHEMA}.anon_probe_table;"
+        ),
+    )
+    .await?;
+    sqlx::query(sqlx::AssertSqlSafe(format!(
+        "SELECT count(*) FROM {FIXTURE_SCHEMA}.anon_probe_table WHERE id > $1"
+    )))
+    .bind(0_i64)
+    .execute(pool)
+    .await?;
+
+    // Rank every statement (pg_stat_statements.max defaults to 5000) so other tests'
+    // statements cannot crowd the fixture's out of the top-N
+    let enabled: Vec<String> = COLLECTOR_NAMES.iter().map(ToString::to_string).collect();
+    let registry = CollectorRegistry::new(&CollectorConfig::new(5000).with_enabled(&enabled))?;
+    let scrape = registry.collect_all(pool).await;
+    test_db.cleanup().await?;
+    scrape.map_err(|e| anyhow::anyhow!("scrape failed: {e}"))?;
+
+    let mut families = registry.registry().gather();
+    let mut carriers: BTreeMap<String, String> = BTreeMap::new();
+    for family in &families {
+        for label in family
+            .get_metric()
+            .iter()
+            .flat_map(prometheus::proto::Metric::get_label)
+        {
+            if carries_fixture(label.value()) {
+                carriers.insert(label.name().to_string(), family.name().to_string());
+            }
+        }
+    }
+
+    // Without the fixture showing up the check below would pass vacuously
+    let mut expected = vec!["schemaname", "relname"];
+    if with_statements {
+        expected.push("query_short");
+    }
+    for expected in expected {
+        assert!(
+            carriers.contains_key(expected),
+            "no {expected} label carried a fixture name; carriers: {carriers:?}"
+        );
+    }
+
+    assert_exclusions_hashed(&families);
+
+    let leaking: Vec<String> = carriers
+        .iter()
+        .filter(|(label, _)| !ANONYMIZED_LABELS.contains(&label.as_str()))
+        .map(|(label, family)| format!("{family}: {label}"))
+        .collect();
+    assert!(
+        leaking.is_empty(),
+        "labels carry object names but are missing from ANONYMIZED_LABELS:\n{}",
+        leaking.join("\n")
+    );
+
+    anonymize_metric_families(&mut families, SALT);
+    let remaining: Vec<String> = families
+        .iter()
+        .flat_map(|family| {
+            family.get_metric().iter().flat_map(move |metric| {
+                metric
+                    .get_label()
+                    .iter()
+                    .filter(|label| carries_fixture(label.value()))
+                    .map(move |label| {
+                        format!("{}: {}={:?}", family.name(), label.name(), label.value())
+                    })
+            })
+        })
+        .collect();
+    assert!(
+        remaining.is_empty(),
+        "object names left after anonymization:\n{}",
+        remaining.join("\n")
+    );
+
+    Ok(())
+}
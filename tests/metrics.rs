@@ -125,3 +125,55 @@ async fn test_metrics_endpoint_performance() -> Result<()> {
 
     Ok(())
 }
+
+fn sample_value(body: &str, series: &str) -> Option<f64> {
+    body.lines()
+        .find_map(|line| line.strip_prefix(series)?.trim().parse().ok())
+}
+
+#[tokio::test]
+async fn test_metrics_endpoint_breaks_down_series_per_collector() -> Result<()> {
+    let port = common::get_available_port();
+    let dsn = common::get_test_dsn_secret();
+
+    let handle = tokio::spawn(async move {
+        pg_exporter::exporter::new(port, None, dsn, collector_config(&["default", "exporter"]))
+            .await
+    });
+
+    assert!(common::wait_for_server(port, 50).await);
+
+    // Counts describe the previous scrape, like pg_exporter_metrics_total
+    let client = reqwest::Client::new();
+    let url = format!("{}/metrics", common::get_test_url(port));
+    client.get(&url).send().await?.text().await?;
+    let body = client.get(&url).send().await?.text().await?;
+
+    handle.abort();
+
+    let default = sample_value(
+        &body,
+        "pg_exporter_collector_metrics{collector=\"default\"}",
+    )
+    .expect("default collector series count");
+    let exporter = sample_value(
+        &body,
+        "pg_exporter_collector_metrics{collector=\"exporter\"}",
+    )
+    .expect("exporter collector series count");
+    let total =
+        sample_value(&body, "pg_exporter_metrics_total").expect("pg_exporter_metrics_total");
+
+    assert!(default > 0.0, "default collector exports series: {default}");
+    assert!(
+        exporter > 0.0,
+        "exporter collector exports series: {exporter}"
+    );
+    // pg_up, build info and the other core metrics belong to no collector
+    assert!(
+        default + exporter < total,
+        "{default} + {exporter} should stay below {total}"
+    );
+
+    Ok(())
+}